@@ -0,0 +1,28 @@
+//! Criterion benchmarks of a single `step` for every engine across grid sizes
+//!
+//! Run with `cargo bench --bench engines`; pass a filter such as `ultimate/`
+//! to bench one engine. The bench profile keeps debug info and symbols, so
+//! the binary under `target/release/deps` can be profiled directly.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use game_of_life::benchmark::fixtures::{self, BENCH_SIZES};
+
+fn step(c: &mut Criterion) {
+    for fixture in fixtures::engine_fixtures() {
+        let mut group = c.benchmark_group(fixture.name);
+        for size in BENCH_SIZES {
+            let soup = fixtures::soup(size, size);
+            group.throughput(Throughput::Elements((size * size) as u64));
+            group.bench_with_input(BenchmarkId::new("step", size), &soup, |b, soup| {
+                // Each sample continues from where the last left off; large
+                // soups stay active for far longer than a bench runs
+                let mut engine = fixture.build(soup);
+                b.iter(|| fixtures::run_generations(&mut *engine, 1));
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
@@ -0,0 +1,249 @@
+//! Counting the objects left behind once a pattern has settled
+//!
+//! The live cells are split into objects, joining any two cells at most two
+//! apart since those share a neighbor and can interact. Each object is run on its
+//! own until it recurs, and the result is recorded under its canonical apgcode:
+//! the shortest (then alphabetically first) Wechsler encoding over every phase
+//! and orientation, prefixed with `xs<population>` for still lifes,
+//! `xp<period>` for oscillators and `xq<period>` for spaceships.
+
+use super::spaceship::{find_recurrence, Recurrence};
+use crate::grid::components::components;
+use crate::grid::{Grid, StandardGrid, Transform};
+use crate::patterns::to_wechsler;
+use std::collections::{BTreeMap, HashMap};
+
+/// Longest period `census` looks for when classifying an object
+pub const DEFAULT_MAX_PERIOD: usize = 30;
+
+/// Apgcodes and names of the objects most common in Conway's Life ash
+const COMMON_OBJECTS: &[(&str, &str)] = &[
+    ("xs4_33", "block"),
+    ("xs6_696", "beehive"),
+    ("xs7_2596", "loaf"),
+    ("xs5_253", "boat"),
+    ("xs4_252", "tub"),
+    ("xs8_6996", "pond"),
+    ("xs6_356", "ship"),
+    ("xs6_25a4", "barge"),
+    ("xs7_25ac", "long boat"),
+    ("xs7_178c", "eater 1"),
+    ("xs6_39c", "snake"),
+    ("xs6_bd", "aircraft carrier"),
+    ("xp2_7", "blinker"),
+    ("xp2_7e", "toad"),
+    ("xp2_318c", "beacon"),
+    ("xp3_co9nas0san9oczgoldlo0oldlogz1047210127401", "pulsar"),
+    ("xp15_4r4z4r4", "pentadecathlon"),
+    ("xq4_153", "glider"),
+    ("xq4_6frc", "lightweight spaceship"),
+    ("xq4_27dee6", "middleweight spaceship"),
+    ("xq4_27deee6", "heavyweight spaceship"),
+];
+
+/// Names for apgcodes, so census results can be reported as "block" rather than "xs4_33"
+#[derive(Debug, Clone, Default)]
+pub struct ObjectDatabase {
+    names: HashMap<String, String>,
+}
+
+impl ObjectDatabase {
+    /// Create a database with no entries
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Create a database of the common still lifes, oscillators and spaceships
+    pub fn common() -> Self {
+        let mut database = Self::new();
+        for &(code, name) in COMMON_OBJECTS {
+            database.register(code, name);
+        }
+        database
+    }
+    
+    /// Add or rename an object
+    pub fn register(&mut self, code: impl Into<String>, name: impl Into<String>) {
+        self.names.insert(code.into(), name.into());
+    }
+    
+    /// Name of the object with this apgcode, if known
+    pub fn name(&self, code: &str) -> Option<&str> {
+        self.names.get(code).map(String::as_str)
+    }
+    
+    /// Apgcode registered under this name, if any
+    pub fn code(&self, name: &str) -> Option<&str> {
+        self.names.iter().find(|(_, known)| known.as_str() == name).map(|(code, _)| code.as_str())
+    }
+    
+    /// Number of known objects
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+    
+    /// Whether the database has no entries
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Tally of the objects found by `census`, keyed by apgcode
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CensusReport {
+    /// Number of objects seen for each apgcode
+    pub counts: BTreeMap<String, usize>,
+    /// Objects that did not recur within the period limit
+    pub unrecognized: usize,
+}
+
+impl CensusReport {
+    /// Number of objects with this apgcode
+    pub fn count(&self, code: &str) -> usize {
+        self.counts.get(code).copied().unwrap_or(0)
+    }
+    
+    /// Number of objects with this name in `database`
+    pub fn count_named(&self, database: &ObjectDatabase, name: &str) -> usize {
+        database.code(name).map_or(0, |code| self.count(code))
+    }
+    
+    /// Total number of objects, recognized or not
+    pub fn total(&self) -> usize {
+        self.counts.values().sum::<usize>() + self.unrecognized
+    }
+    
+    /// Whether no objects were found
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+    
+    /// Add the counts from another census, e.g. of a different soup
+    pub fn merge(&mut self, other: &CensusReport) {
+        for (code, &count) in &other.counts {
+            *self.counts.entry(code.clone()).or_insert(0) += count;
+        }
+        self.unrecognized += other.unrecognized;
+    }
+    
+    /// Objects by descending count, labelled with their names from `database` where known
+    pub fn named(&self, database: &ObjectDatabase) -> Vec<(String, usize)> {
+        let mut entries: Vec<_> = self
+            .counts
+            .iter()
+            .map(|(code, &count)| (database.name(code).unwrap_or(code).to_string(), count))
+            .collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        entries
+    }
+}
+
+/// Split the live cells of `grid` into objects and count them by apgcode
+pub fn census(grid: &dyn Grid) -> CensusReport {
+    census_with_period(grid, DEFAULT_MAX_PERIOD)
+}
+
+/// As `census`, classifying objects with periods up to `max_period`
+pub fn census_with_period(grid: &dyn Grid, max_period: usize) -> CensusReport {
+    let mut report = CensusReport::default();
+    for object in objects(grid) {
+        match object_apgcode(&object, max_period) {
+            Some(code) => *report.counts.entry(code).or_insert(0) += 1,
+            None => report.unrecognized += 1,
+        }
+    }
+    report
+}
+
+/// Canonical apgcode of a single object, or `None` if it does not recur within `max_period`
+pub(crate) fn object_apgcode(object: &dyn Grid, max_period: usize) -> Option<String> {
+    let recurrence = find_recurrence(object, max_period)?;
+    Some(canonical_apgcode(&recurrence, object.count_live_cells()))
+}
+
+/// Canonical apgcode of an object that recurs as described
+pub(crate) fn canonical_apgcode(recurrence: &Recurrence, population: usize) -> String {
+    let prefix = match recurrence {
+        Recurrence { period: 1, dx: 0, dy: 0, .. } => format!("xs{}", population),
+        Recurrence { period, dx: 0, dy: 0, .. } => format!("xp{}", period),
+        Recurrence { period, .. } => format!("xq{}", period),
+    };
+    let wechsler = recurrence
+        .phases
+        .iter()
+        .flat_map(|phase| Transform::ALL.map(|transform| to_wechsler(&transform.apply(phase))))
+        .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+        .unwrap_or_default();
+    format!("{}_{}", prefix, wechsler)
+}
+
+/// Split the live cells into groups of cells at most two apart, each cropped to its bounding box
+pub(crate) fn objects(grid: &dyn Grid) -> Vec<StandardGrid> {
+    components(grid, 2).iter().map(|cells| object_grid(cells)).collect()
+}
+
+/// Crop a group of (row, col) cells to a grid just large enough to hold them
+pub(crate) fn object_grid(cells: &[(usize, usize)]) -> StandardGrid {
+    let top = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let left = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+    let bottom = cells.iter().map(|&(row, _)| row).max().map_or(0, |row| row + 1);
+    let right = cells.iter().map(|&(_, col)| col).max().map_or(0, |col| col + 1);
+    let mut object = StandardGrid::new(right - left, bottom - top);
+    for &(row, col) in cells {
+        object.set_cell(row - top, col - left, true);
+    }
+    object
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::PasteMode;
+    use crate::patterns;
+    
+    #[test]
+    fn test_canonical_codes_of_library_patterns() {
+        let database = ObjectDatabase::common();
+        let expected = [
+            (patterns::block(), "block"),
+            (patterns::blinker(), "blinker"),
+            (patterns::glider(), "glider"),
+            (patterns::lwss(), "lightweight spaceship"),
+            (patterns::mwss(), "middleweight spaceship"),
+            (patterns::hwss(), "heavyweight spaceship"),
+            (patterns::pulsar(), "pulsar"),
+            (patterns::pentadecathlon(), "pentadecathlon"),
+            (patterns::eater(), "eater 1"),
+        ];
+        for (pattern, name) in expected {
+            let report = census(&pattern.grid);
+            assert_eq!(report.count_named(&database, name), 1, "{}: {:?}", name, report.counts);
+        }
+        
+        let beehive = StandardGrid::from_string_pattern(&[".##.", "#..#", ".##."], '#', '.').unwrap();
+        assert_eq!(census(&beehive.rotate90()).count("xs6_696"), 1);
+    }
+    
+    #[test]
+    fn test_census_of_mixed_ash() {
+        let mut ash = StandardGrid::new(40, 20);
+        ash.paste(&patterns::block().grid, 1, 1, PasteMode::Copy);
+        ash.paste(&patterns::block().grid, 1, 30, PasteMode::Copy);
+        ash.paste(&patterns::blinker().grid, 10, 5, PasteMode::Copy);
+        ash.paste(&patterns::glider().grid.rotate180(), 12, 20, PasteMode::Copy);
+        ash.paste(&patterns::r_pentomino().grid, 15, 34, PasteMode::Copy);
+        
+        let report = census_with_period(&ash, 8);
+        assert_eq!(report.count("xs4_33"), 2);
+        assert_eq!(report.count("xp2_7"), 1);
+        assert_eq!(report.count("xq4_153"), 1);
+        assert_eq!(report.unrecognized, 1);
+        assert_eq!(report.total(), 5);
+        
+        let mut twice = report.clone();
+        twice.merge(&report);
+        assert_eq!(twice.count("xs4_33"), 4);
+        assert_eq!(twice.named(&ObjectDatabase::common())[0], ("block".to_string(), 4));
+        assert!(census(&StandardGrid::new(8, 8)).is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+//! Damage spreading: how a single flipped cell propagates
+
+use crate::engines::{auto_from_grid_ultimate_engine, GameOfLifeEngine};
+use crate::error::GameOfLifeError;
+use crate::grid::{Grid, Rect};
+
+/// Difference between the twin simulations at one generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DamageSample {
+    /// Generations since the perturbation
+    pub generation: usize,
+    /// Number of cells whose state differs between the twins (the Hamming distance)
+    pub distance: usize,
+    /// Smallest rectangle containing every differing cell, if any differ
+    pub extent: Option<Rect>,
+}
+
+/// Runs two engines in lockstep, one of them with a single cell flipped, and records where they differ
+///
+/// Damage that dies out means the pattern absorbs small perturbations;
+/// damage that keeps growing is the signature of chaotic dynamics.
+pub struct DamageSpreading {
+    original: Box<dyn GameOfLifeEngine>,
+    perturbed: Box<dyn GameOfLifeEngine>,
+    history: Vec<DamageSample>,
+}
+
+impl DamageSpreading {
+    /// Start from `original`'s current state, loading it into `perturbed` with cell (row, col) flipped
+    ///
+    /// The perturbed engine is switched to the original's rule, so any two
+    /// engines of the same size can be paired. Fails if the sizes differ, the
+    /// cell is out of bounds, or the perturbed engine cannot run the rule.
+    pub fn new(original: Box<dyn GameOfLifeEngine>, mut perturbed: Box<dyn GameOfLifeEngine>, row: usize, col: usize) -> Result<Self, GameOfLifeError> {
+        if (original.width(), original.height()) != (perturbed.width(), perturbed.height()) {
+            return Err(GameOfLifeError::dimensions((original.width(), original.height()), (perturbed.width(), perturbed.height())));
+        }
+        if row >= original.height() || col >= original.width() {
+            return Err(GameOfLifeError::invalid(format!(
+                "Cell ({}, {}) is outside the {}x{} grid",
+                row,
+                col,
+                original.width(),
+                original.height()
+            )));
+        }
+        if let Some(rule) = original.rule() {
+            perturbed.set_rule(rule)?;
+        }
+        
+        let mut snapshot = original.snapshot();
+        snapshot.grid.set_cell(row, col, !snapshot.grid.get_cell(row, col));
+        perturbed.restore(&snapshot)?;
+        let mut spreading = Self { original, perturbed, history: Vec::new() };
+        spreading.record();
+        Ok(spreading)
+    }
+    
+    /// Twin fast engines loaded from `grid`, with cell (row, col) flipped in one of them
+    pub fn from_grid(grid: &dyn Grid, row: usize, col: usize) -> Result<Self, GameOfLifeError> {
+        Self::new(auto_from_grid_ultimate_engine(grid), auto_from_grid_ultimate_engine(grid), row, col)
+    }
+    
+    /// Advance both engines one generation and record the damage
+    pub fn step(&mut self) -> DamageSample {
+        self.original.step();
+        self.perturbed.step();
+        self.record()
+    }
+    
+    /// Advance both engines `generations` times, returning the full damage history
+    pub fn run(&mut self, generations: usize) -> &[DamageSample] {
+        for _ in 0..generations {
+            self.step();
+        }
+        &self.history
+    }
+    
+    /// Damage at every generation so far, starting with the single flipped cell
+    pub fn history(&self) -> &[DamageSample] {
+        &self.history
+    }
+    
+    /// Current Hamming distance between the twins
+    pub fn distance(&self) -> usize {
+        self.history.last().map_or(0, |sample| sample.distance)
+    }
+    
+    /// First generation at which the twins became identical again, if they have
+    pub fn healed_at(&self) -> Option<usize> {
+        self.history.iter().find(|sample| sample.distance == 0).map(|sample| sample.generation)
+    }
+    
+    /// Largest Hamming distance seen so far
+    pub fn peak_distance(&self) -> usize {
+        self.history.iter().map(|sample| sample.distance).max().unwrap_or(0)
+    }
+    
+    /// The unperturbed engine
+    pub fn original(&self) -> &dyn GameOfLifeEngine {
+        self.original.as_ref()
+    }
+    
+    /// The engine that started with the flipped cell
+    pub fn perturbed(&self) -> &dyn GameOfLifeEngine {
+        self.perturbed.as_ref()
+    }
+    
+    fn record(&mut self) -> DamageSample {
+        let damage = self.original.snapshot().grid.xor(&self.perturbed.snapshot().grid);
+        let sample = DamageSample {
+            generation: self.history.len(),
+            distance: damage.count_live_cells(),
+            extent: damage.bounding_box(),
+        };
+        self.history.push(sample);
+        sample
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NaiveEngine;
+    use crate::grid::{Anchor, StandardGrid};
+    use crate::patterns;
+    
+    #[test]
+    fn test_isolated_damage_heals() {
+        // A lone cell far from the block dies at once
+        let grid = patterns::block().grid.embedded_in(16, 16, Anchor::Center);
+        let mut spreading = DamageSpreading::from_grid(&grid, 2, 2).unwrap();
+        assert_eq!(spreading.distance(), 1);
+        assert_eq!(spreading.history()[0].extent, Some(Rect::new(2, 2, 1, 1)));
+        spreading.run(3);
+        assert_eq!(spreading.healed_at(), Some(1));
+        assert_eq!(spreading.peak_distance(), 1);
+        
+        // A block with a cell missing grows straight back
+        let mut spreading = DamageSpreading::from_grid(&grid, 7, 7).unwrap();
+        spreading.run(5);
+        assert_eq!(spreading.healed_at(), Some(1));
+        assert_eq!(spreading.distance(), 0);
+        assert!(DamageSpreading::from_grid(&grid, 16, 0).is_err());
+    }
+    
+    #[test]
+    fn test_damage_spreads_in_soup() {
+        let soup = StandardGrid::random(48, 48, 0.4, 9).unwrap();
+        let original = Box::new(NaiveEngine::from_grid(&soup));
+        let mut spreading = DamageSpreading::new(original, Box::new(NaiveEngine::new(48, 48)), 24, 24).unwrap();
+        let history = spreading.run(60).to_vec();
+        assert_eq!(history.len(), 61);
+        assert!(spreading.peak_distance() > 10, "{:?}", history);
+        
+        // Fast twins see exactly the same damage
+        let mut fast = DamageSpreading::from_grid(&soup, 24, 24).unwrap();
+        assert_eq!(fast.run(60), &history[..]);
+        assert!(DamageSpreading::new(Box::new(NaiveEngine::new(4, 4)), Box::new(NaiveEngine::new(5, 4)), 0, 0).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! Growth of population and extent over a run
+
+use crate::engines::GameOfLifeEngine;
+use std::fmt::{Display, Formatter};
+
+/// Fewest samples needed before `GrowthTracker::classify` will commit to an answer
+pub const MIN_SAMPLES: usize = 8;
+
+/// How a pattern's population grows in the long run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GrowthClass {
+    /// Population stays bounded: still lifes, oscillators, spaceships, or dying out
+    Bounded,
+    /// Population grows linearly, as from a gun or puffer
+    Linear,
+    /// Population grows quadratically, as from a breeder
+    Quadratic,
+    /// Too few samples to tell
+    Insufficient,
+}
+
+impl Display for GrowthClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrowthClass::Bounded => write!(f, "bounded"),
+            GrowthClass::Linear => write!(f, "linear growth"),
+            GrowthClass::Quadratic => write!(f, "quadratic growth"),
+            GrowthClass::Insufficient => write!(f, "insufficient data"),
+        }
+    }
+}
+
+/// Least-squares polynomial fit of a series against generation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fit {
+    /// Coefficients from the constant term up, in generations
+    pub coefficients: Vec<f64>,
+    /// Fraction of the variance the fit explains; 1 when the series is constant
+    pub r_squared: f64,
+}
+
+impl Fit {
+    /// Value of the fitted polynomial at `generation`
+    pub fn predict(&self, generation: f64) -> f64 {
+        self.coefficients.iter().rev().fold(0.0, |acc, &coefficient| acc * generation + coefficient)
+    }
+}
+
+/// Population and bounding-box samples with linear and quadratic fits
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrowthReport {
+    pub class: GrowthClass,
+    /// Linear fit of population over the second half of the run
+    pub population_linear: Fit,
+    /// Quadratic fit of population over the second half of the run
+    pub population_quadratic: Fit,
+    /// Linear fit of the bounding-box diameter over the second half of the run
+    pub diameter_linear: Fit,
+}
+
+/// One sample: generation, population and the longer side of the bounding box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrowthSample {
+    pub generation: usize,
+    pub population: usize,
+    pub diameter: usize,
+}
+
+/// Records population and bounding-box diameter over a run and classifies the growth
+///
+/// Only the second half of the samples is fitted so start-up transients, such
+/// as a gun assembling itself, do not dominate.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GrowthTracker {
+    samples: Vec<GrowthSample>,
+}
+
+impl GrowthTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Record one sample
+    pub fn record(&mut self, generation: usize, population: usize, diameter: usize) {
+        self.samples.push(GrowthSample { generation, population, diameter });
+    }
+    
+    /// Record the engine's current generation, population and bounding box
+    pub fn sample(&mut self, engine: &dyn GameOfLifeEngine) {
+        let diameter = engine.bounding_box().map_or(0, |rect| rect.width.max(rect.height));
+        self.record(engine.generation(), engine.count_live_cells(), diameter);
+    }
+    
+    /// Sample the engine now and every `interval` generations while running `generations` steps
+    pub fn run(&mut self, engine: &mut dyn GameOfLifeEngine, generations: usize, interval: usize) {
+        let interval = interval.max(1);
+        self.sample(engine);
+        let mut remaining = generations;
+        while remaining > 0 {
+            let steps = remaining.min(interval);
+            engine.run_steps(steps);
+            remaining -= steps;
+            self.sample(engine);
+        }
+    }
+    
+    /// Samples recorded so far, in order
+    pub fn samples(&self) -> &[GrowthSample] {
+        &self.samples
+    }
+    
+    /// Fit the second half of the samples and classify the growth
+    ///
+    /// Population is bounded if the linear fit rises by less than a tenth of
+    /// the mean population (plus a few cells) over that span, and quadratic if
+    /// the quadratic term accounts for a sizeable part of the rise.
+    pub fn report(&self) -> GrowthReport {
+        let tail = &self.samples[self.samples.len() / 2..];
+        let generations: Vec<f64> = tail.iter().map(|sample| sample.generation as f64).collect();
+        let populations: Vec<f64> = tail.iter().map(|sample| sample.population as f64).collect();
+        let diameters: Vec<f64> = tail.iter().map(|sample| sample.diameter as f64).collect();
+        let population_linear = fit(&generations, &populations, 1);
+        let population_quadratic = fit(&generations, &populations, 2);
+        let diameter_linear = fit(&generations, &diameters, 1);
+        
+        let class = if self.samples.len() < MIN_SAMPLES {
+            GrowthClass::Insufficient
+        } else {
+            let span = generations[generations.len() - 1] - generations[0];
+            let mean = populations.iter().sum::<f64>() / populations.len() as f64;
+            let rise = population_linear.coefficients[1] * span;
+            let curvature = population_quadratic.coefficients[2] * span * span;
+            if rise <= 0.1 * mean + 4.0 {
+                GrowthClass::Bounded
+            } else if curvature > 0.15 * rise {
+                GrowthClass::Quadratic
+            } else {
+                GrowthClass::Linear
+            }
+        };
+        GrowthReport { class, population_linear, population_quadratic, diameter_linear }
+    }
+    
+    /// Classify the growth seen so far
+    pub fn classify(&self) -> GrowthClass {
+        self.report().class
+    }
+}
+
+/// Least-squares fit of a polynomial of the given degree, solved by Gaussian elimination
+fn fit(xs: &[f64], ys: &[f64], degree: usize) -> Fit {
+    let terms = degree + 1;
+    // Shift generations to start at zero so the normal equations stay well conditioned
+    let origin = xs.first().copied().unwrap_or(0.0);
+    let mut matrix = vec![vec![0.0; terms + 1]; terms];
+    for (&x, &y) in xs.iter().zip(ys) {
+        let powers: Vec<f64> = (0..terms).map(|power| (x - origin).powi(power as i32)).collect();
+        for row in 0..terms {
+            for col in 0..terms {
+                matrix[row][col] += powers[row] * powers[col];
+            }
+            matrix[row][terms] += powers[row] * y;
+        }
+    }
+    
+    let mut shifted = vec![0.0; terms];
+    for pivot in 0..terms {
+        let best = (pivot..terms).max_by(|&a, &b| matrix[a][pivot].abs().total_cmp(&matrix[b][pivot].abs())).unwrap();
+        matrix.swap(pivot, best);
+        if matrix[pivot][pivot].abs() < 1e-12 {
+            continue;
+        }
+        let pivot_row = matrix[pivot].clone();
+        for (index, row) in matrix.iter_mut().enumerate() {
+            if index != pivot {
+                let factor = row[pivot] / pivot_row[pivot];
+                for (value, &subtrahend) in row.iter_mut().zip(&pivot_row).skip(pivot) {
+                    *value -= factor * subtrahend;
+                }
+            }
+        }
+    }
+    for (index, row) in matrix.iter().enumerate() {
+        if row[index].abs() >= 1e-12 {
+            shifted[index] = row[terms] / row[index];
+        }
+    }
+    
+    // Expand the shifted polynomial back into powers of the generation itself
+    let mut coefficients = vec![0.0; terms];
+    for (power, &value) in shifted.iter().enumerate() {
+        for k in 0..=power {
+            let binomial = (0..k).fold(1.0, |acc, i| acc * (power - i) as f64 / (i + 1) as f64);
+            coefficients[power - k] += value * binomial * (-origin).powi(k as i32);
+        }
+    }
+    
+    let mean = ys.iter().sum::<f64>() / ys.len().max(1) as f64;
+    let total: f64 = ys.iter().map(|y| (y - mean).powi(2)).sum();
+    let model = Fit { coefficients, r_squared: 1.0 };
+    let residual: f64 = xs.iter().zip(ys).map(|(&x, &y)| (y - model.predict(x)).powi(2)).sum();
+    let r_squared = if total == 0.0 { 1.0 } else { 1.0 - residual / total };
+    Fit { r_squared, ..model }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NaiveEngine;
+    use crate::grid::{Anchor, Grid, PasteMode, StandardGrid};
+    use crate::patterns;
+    
+    #[test]
+    fn test_fits_recover_polynomials() {
+        let xs: Vec<f64> = (100..120).map(f64::from).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 3.0 + 0.5 * x + 0.25 * x * x).collect();
+        let quadratic = fit(&xs, &ys, 2);
+        for (coefficient, expected) in quadratic.coefficients.iter().zip([3.0, 0.5, 0.25]) {
+            assert!((coefficient - expected).abs() < 1e-6, "{:?}", quadratic.coefficients);
+        }
+        assert!((quadratic.r_squared - 1.0).abs() < 1e-9);
+        assert!(fit(&xs, &ys, 1).r_squared < 1.0);
+    }
+    
+    #[test]
+    fn test_classifies_synthetic_series() {
+        let classify = |population: &dyn Fn(usize) -> usize| {
+            let mut tracker = GrowthTracker::new();
+            for generation in (0..=1000).step_by(50) {
+                tracker.record(generation, population(generation), generation / 10);
+            }
+            tracker.classify()
+        };
+        assert_eq!(classify(&|g| 40 + g % 3), GrowthClass::Bounded);
+        assert_eq!(classify(&|g| 36 + 5 * (g / 30)), GrowthClass::Linear);
+        assert_eq!(classify(&|g| 50 + g * g / 500), GrowthClass::Quadratic);
+        assert_eq!(GrowthTracker::new().classify(), GrowthClass::Insufficient);
+    }
+    
+    #[test]
+    fn test_gun_grows_linearly() {
+        let mut universe = StandardGrid::new(120, 120);
+        universe.paste(&patterns::gosper_glider_gun().grid, 2, 2, PasteMode::Copy);
+        let mut engine = NaiveEngine::from_grid(&universe);
+        let mut tracker = GrowthTracker::new();
+        tracker.run(&mut engine, 300, 15);
+        assert_eq!(tracker.samples().len(), 21);
+        let report = tracker.report();
+        assert_eq!(report.class, GrowthClass::Linear);
+        assert!(report.diameter_linear.coefficients[1] > 0.1);
+        
+        let mut blinker = NaiveEngine::from_grid(&patterns::blinker().grid.embedded_in(8, 8, Anchor::Center));
+        let mut tracker = GrowthTracker::new();
+        tracker.run(&mut blinker, 40, 3);
+        assert_eq!(tracker.classify(), GrowthClass::Bounded);
+    }
+}
@@ -0,0 +1,146 @@
+//! Locating copies of a small pattern inside a larger grid
+
+use crate::grid::{BitGrid, Grid, StandardGrid, Transform};
+
+/// Find every placement of `needle`, in any orientation, within `haystack`
+///
+/// A placement `(row, col, transform)` means the needle with `transform`
+/// applied matches the haystack cell for cell, dead cells included, in the
+/// window whose top-left corner is (row, col). Orientations that look the same
+/// are reported once, under the first transform in `Transform::ALL`.
+/// Placements are ordered by transform, then row-major. Only this phase of the
+/// needle is matched; search once per phase to find an oscillator or ship in
+/// any phase.
+pub fn find_pattern(haystack: &dyn Grid, needle: &dyn Grid) -> Vec<(usize, usize, Transform)> {
+    search(haystack, needle, false)
+}
+
+/// As `find_pattern`, but also require the ring of cells around the window to be dead
+///
+/// Parts of the ring beyond the haystack's edge count as dead. This is the
+/// usual way to count objects, e.g. gliders emitted by a gun, without matching
+/// pieces of something larger.
+pub fn find_isolated_pattern(haystack: &dyn Grid, needle: &dyn Grid) -> Vec<(usize, usize, Transform)> {
+    search(haystack, needle, true)
+}
+
+fn search(haystack: &dyn Grid, needle: &dyn Grid, isolated: bool) -> Vec<(usize, usize, Transform)> {
+    let bits = BitGrid::from_grid(haystack);
+    let mut orientations: Vec<(Transform, StandardGrid)> = Vec::new();
+    for transform in Transform::ALL {
+        let image = transform.apply(needle);
+        if orientations.iter().all(|(_, seen)| *seen != image) {
+            orientations.push((transform, image));
+        }
+    }
+    
+    let mut found = Vec::new();
+    for (transform, image) in orientations {
+        let (width, height) = (image.width(), image.height());
+        if width == 0 || height == 0 || width > bits.width() || height > bits.height() {
+            continue;
+        }
+        let packed = pack_rows(&image);
+        for row in 0..=bits.height() - height {
+            for col in 0..=bits.width() - width {
+                if matches_at(&bits, &packed, width, row, col) && (!isolated || ring_is_dead(&bits, row, col, width, height)) {
+                    found.push((row, col, transform));
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Each row of a pattern as chunks of up to 64 cells, first cell in the highest bit of the chunk
+fn pack_rows(pattern: &StandardGrid) -> Vec<Vec<u64>> {
+    (0..pattern.height())
+        .map(|row| {
+            (0..pattern.width())
+                .step_by(64)
+                .map(|start| (start..(start + 64).min(pattern.width())).fold(0u64, |acc, col| acc << 1 | pattern.get_cell(row, col) as u64))
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether the window at (row, col) equals the packed pattern, compared up to 64 columns at a time
+fn matches_at(bits: &BitGrid, packed: &[Vec<u64>], width: usize, row: usize, col: usize) -> bool {
+    packed.iter().enumerate().all(|(r, chunks)| {
+        let words = bits.row_words(row + r);
+        chunks.iter().enumerate().all(|(i, &expected)| {
+            let start = i * 64;
+            window(words, col + start, (width - start).min(64)) == expected
+        })
+    })
+}
+
+/// The `len` cells starting at column `col` of a packed row, first cell in the highest bit
+fn window(words: &[u64], col: usize, len: usize) -> u64 {
+    let (index, offset) = (col / 64, col % 64);
+    let high = words[index] as u128;
+    let low = words.get(index + 1).copied().unwrap_or(0) as u128;
+    let combined = (high << 64 | low) << offset;
+    (combined >> (128 - len)) as u64
+}
+
+/// Whether the cells bordering the window are all dead
+fn ring_is_dead(bits: &BitGrid, row: usize, col: usize, width: usize, height: usize) -> bool {
+    let rows = row.saturating_sub(1)..(row + height + 1).min(bits.height());
+    let cols = col.saturating_sub(1)..(col + width + 1).min(bits.width());
+    rows.clone().all(|r| {
+        cols.clone().all(|c| {
+            let inside = (row..row + height).contains(&r) && (col..col + width).contains(&c);
+            inside || !bits.get_cell(r, c)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{GameOfLifeEngine, NaiveEngine};
+    use crate::grid::{Anchor, PasteMode, StandardGrid};
+    use crate::patterns;
+    
+    #[test]
+    fn test_finds_all_orientations() {
+        let glider = patterns::glider().grid;
+        let mut haystack = StandardGrid::new(100, 12);
+        haystack.paste(&glider, 1, 2, PasteMode::Copy);
+        haystack.paste(&Transform::Rotate90.apply(&glider), 6, 70, PasteMode::Copy);
+        haystack.paste(&Transform::FlipHorizontal.apply(&glider), 2, 40, PasteMode::Copy);
+        
+        let found = find_pattern(&haystack, &glider);
+        assert_eq!(found, vec![
+            (1, 2, Transform::Identity),
+            (6, 70, Transform::Rotate90),
+            (2, 40, Transform::FlipHorizontal),
+        ]);
+        
+        // A block looks the same every way round, so it is reported once
+        haystack.paste(&patterns::block().grid, 9, 20, PasteMode::Copy);
+        assert_eq!(find_pattern(&haystack, &patterns::block().grid), vec![(9, 20, Transform::Identity)]);
+    }
+    
+    #[test]
+    fn test_counts_gliders_from_gun() {
+        let gun = patterns::gosper_glider_gun().grid;
+        let mut universe = StandardGrid::new(120, 120);
+        universe.paste(&gun, 2, 2, PasteMode::Copy);
+        let mut engine = NaiveEngine::from_grid(&universe);
+        engine.run_steps(240);
+        
+        // Every glider is in one of the two shapes a glider takes as it moves
+        let glider = patterns::glider().grid;
+        let mut stepped = NaiveEngine::from_grid(&glider.embedded_in(5, 5, Anchor::Center));
+        stepped.step();
+        let other_shape = stepped.get_grid().trimmed();
+        let gliders = find_isolated_pattern(engine.get_grid(), &glider).len()
+            + find_isolated_pattern(engine.get_grid(), &other_shape).len();
+        
+        // The gun fires every 30 generations and gliders take a while to separate
+        assert!((6..=8).contains(&gliders), "found {} gliders", gliders);
+        assert!(find_pattern(&StandardGrid::new(2, 2), &patterns::glider().grid).is_empty());
+    }
+}
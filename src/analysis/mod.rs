@@ -0,0 +1,21 @@
+//! Classifying patterns and the objects they contain
+
+pub mod census;
+pub mod damage;
+pub mod growth;
+pub mod matching;
+pub mod objects;
+pub mod report;
+pub mod spaceship;
+pub mod stability;
+pub mod streams;
+
+pub use census::{census, census_with_period, CensusReport, ObjectDatabase};
+pub use damage::{DamageSample, DamageSpreading};
+pub use growth::{Fit, GrowthClass, GrowthReport, GrowthSample, GrowthTracker};
+pub use matching::{find_isolated_pattern, find_pattern};
+pub use objects::{classify_object, classify_objects, ClassifiedObject, ObjectKind};
+pub use report::{analyze_pattern, PatternAnalysis};
+pub use spaceship::{detect_spaceship, ShipDirection, Spaceship};
+pub use stability::{classify_engine, classify_state, StabilityTracker, StateClass};
+pub use streams::{GliderCrossing, GliderDirection, GliderStream, GliderStreamDetector, MeasurementLine};
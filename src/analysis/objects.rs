@@ -0,0 +1,144 @@
+//! Classifying objects by behavior and symmetry, independently of orientation
+
+use super::census::{canonical_apgcode, objects, DEFAULT_MAX_PERIOD};
+use super::spaceship::{find_recurrence, Spaceship};
+use crate::grid::{Grid, StandardGrid, Symmetry, Transform};
+use crate::patterns::to_wechsler;
+use std::fmt::{Display, Formatter};
+
+/// How an object behaves over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectKind {
+    StillLife,
+    Oscillator { period: usize },
+    /// A moving object; the displacement is in the orientation it was found in
+    Spaceship(Spaceship),
+}
+
+impl Display for ObjectKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjectKind::StillLife => write!(f, "still life"),
+            ObjectKind::Oscillator { period } => write!(f, "period {} oscillator", period),
+            ObjectKind::Spaceship(ship) => write!(f, "{}", ship),
+        }
+    }
+}
+
+/// An object with its symmetry and a canonical form shared by all its phases and orientations
+///
+/// Two objects are the same up to rotation, reflection and phase exactly when
+/// their `apgcode`s (equivalently, their `canonical_phase`s) are equal, which
+/// makes either a good key for deduplicating search results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassifiedObject {
+    pub kind: ObjectKind,
+    /// Symmetry of the most symmetric phase, whatever the orientation of its axes
+    pub symmetry: Symmetry,
+    /// The most symmetric phase, rotated or reflected into a canonical orientation
+    pub canonical_phase: StandardGrid,
+    /// Canonical apgcode, as used by `census`
+    pub apgcode: String,
+}
+
+impl Display for ClassifiedObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} ({})", self.symmetry, self.kind, self.apgcode)
+    }
+}
+
+/// Classify the live cells of `grid` as one object, following it for at most `max_period` generations
+///
+/// The canonical phase is the phase with the largest symmetry group; ties,
+/// and the choice of orientation, go to the image with the shortest and then
+/// alphabetically first Wechsler code. Returns `None` if the object is empty,
+/// dies out or does not recur in time.
+pub fn classify_object(grid: &dyn Grid, max_period: usize) -> Option<ClassifiedObject> {
+    let recurrence = find_recurrence(grid, max_period)?;
+    let kind = if recurrence.dx != 0 || recurrence.dy != 0 {
+        ObjectKind::Spaceship(Spaceship { period: recurrence.period, dx: recurrence.dx, dy: recurrence.dy })
+    } else if recurrence.period == 1 {
+        ObjectKind::StillLife
+    } else {
+        ObjectKind::Oscillator { period: recurrence.period }
+    };
+    
+    let symmetries: Vec<Symmetry> = recurrence.phases.iter().map(|phase| phase.symmetries().classify_any_orientation()).collect();
+    let symmetry = symmetries.iter().copied().max_by_key(|symmetry| symmetry.order()).unwrap_or_default();
+    let (canonical_phase, _) = recurrence
+        .phases
+        .iter()
+        .zip(&symmetries)
+        .filter(|(_, phase_symmetry)| phase_symmetry.order() == symmetry.order())
+        .flat_map(|(phase, _)| Transform::ALL.map(|transform| transform.apply(phase)))
+        .map(|image| {
+            let code = to_wechsler(&image);
+            (image, code)
+        })
+        .min_by(|(a, a_code), (b, b_code)| {
+            a_code.len().cmp(&b_code.len()).then_with(|| a_code.cmp(b_code)).then_with(|| a.width().cmp(&b.width()))
+        })?;
+    let apgcode = canonical_apgcode(&recurrence, grid.count_live_cells());
+    Some(ClassifiedObject { kind, symmetry, canonical_phase, apgcode })
+}
+
+/// Split the live cells into objects, as `census` does, and classify each one
+///
+/// Objects that do not recur within `DEFAULT_MAX_PERIOD` generations are left out.
+pub fn classify_objects(grid: &dyn Grid) -> Vec<ClassifiedObject> {
+    objects(grid).iter().filter_map(|object| classify_object(object, DEFAULT_MAX_PERIOD)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::PasteMode;
+    use crate::patterns;
+    
+    #[test]
+    fn test_symmetry_of_common_objects() {
+        let expected = [
+            (patterns::block().grid, Symmetry::D8),
+            (patterns::blinker().grid, Symmetry::D4),
+            (StandardGrid::from_string_pattern(&["##.", "#.#", ".#."], '#', '.').unwrap(), Symmetry::D2),
+            (patterns::pentadecathlon().grid, Symmetry::D4),
+            (patterns::pulsar().grid, Symmetry::D8),
+            // Gliders and ships are only symmetric up to a glide, which does not count
+            (patterns::glider().grid, Symmetry::C1),
+            (patterns::lwss().grid, Symmetry::C1),
+            (patterns::eater().grid, Symmetry::C1),
+        ];
+        for (grid, symmetry) in expected {
+            let object = classify_object(&grid, 30).unwrap();
+            assert_eq!(object.symmetry, symmetry, "{}", object);
+        }
+        
+        let glider = classify_object(&patterns::glider().grid, 30).unwrap();
+        assert_eq!(glider.to_string(), "C1 c/4 diagonal spaceship (period 4) (xq4_153)");
+        assert!(classify_object(&StandardGrid::new(3, 3), 30).is_none());
+    }
+    
+    #[test]
+    fn test_canonical_phase_ignores_orientation_and_phase() {
+        let lwss = patterns::lwss().grid;
+        let reference = classify_object(&lwss, 30).unwrap();
+        for transform in Transform::ALL {
+            let object = classify_object(&transform.apply(&lwss), 30).unwrap();
+            assert_eq!((&object.canonical_phase, &object.apgcode), (&reference.canonical_phase, &reference.apgcode));
+            assert_eq!(object.symmetry, reference.symmetry);
+        }
+        
+        // Two gliders in different phases and orientations are one object, the blinker another
+        let mut grid = StandardGrid::new(30, 10);
+        grid.paste(&patterns::glider().grid, 1, 1, PasteMode::Copy);
+        grid.paste(&Transform::Rotate90.apply(&reference.canonical_phase), 1, 24, PasteMode::Copy);
+        grid.paste(&Transform::Rotate270.apply(&classify_object(&patterns::glider().grid, 30).unwrap().canonical_phase), 5, 10, PasteMode::Copy);
+        grid.paste(&patterns::blinker().grid, 7, 18, PasteMode::Copy);
+        let found = classify_objects(&grid);
+        assert_eq!(found.len(), 4);
+        let mut codes: Vec<&str> = found.iter().map(|object| object.apgcode.as_str()).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes, ["xp2_7", "xq4_153", "xq4_6frc"]);
+    }
+}
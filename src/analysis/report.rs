@@ -0,0 +1,102 @@
+//! One report combining stabilization, census and growth for a whole run
+
+use super::census::{census, CensusReport};
+use super::growth::{GrowthClass, GrowthReport, GrowthTracker};
+use crate::engines::{GameOfLifeEngine, StabilizationReport};
+use std::collections::HashMap;
+
+/// Roughly how many growth samples a run that never stabilizes is split into
+pub const GROWTH_SAMPLES: usize = 1024;
+
+/// Everything `analyze_pattern` learned from running a pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternAnalysis {
+    /// When the run entered its final cycle and how long that cycle is
+    pub stabilization: StabilizationReport,
+    /// Live cells in the final state
+    pub final_population: usize,
+    /// Objects in the final state, by apgcode
+    pub census: CensusReport,
+    /// Population and extent over the run
+    pub growth: GrowthReport,
+}
+
+impl PatternAnalysis {
+    /// Growth class of the run, `Bounded` for any run that settled into a cycle
+    ///
+    /// Patterns that stabilize early leave too few samples to fit, but a
+    /// repeating state cannot be growing.
+    pub fn growth_class(&self) -> GrowthClass {
+        if self.stabilization.is_stable() {
+            GrowthClass::Bounded
+        } else {
+            self.growth.class
+        }
+    }
+}
+
+/// Step `engine` until a state repeats or `max_generations` pass, then census what is left
+///
+/// Growth is sampled about `GROWTH_SAMPLES` times over the budget. The engine
+/// is left in the final state.
+pub fn analyze_pattern(engine: &mut dyn GameOfLifeEngine, max_generations: usize) -> PatternAnalysis {
+    let interval = (max_generations / GROWTH_SAMPLES).max(1);
+    let mut growth = GrowthTracker::new();
+    growth.sample(engine);
+    let mut seen = HashMap::new();
+    seen.insert(engine.state_hash(), 0);
+    
+    let mut stabilization = StabilizationReport { steps_run: max_generations, stabilized_at: None, period: None };
+    for step in 1..=max_generations {
+        engine.step();
+        if step % interval == 0 {
+            growth.sample(engine);
+        }
+        let hash = engine.state_hash();
+        if let Some(&first) = seen.get(&hash) {
+            stabilization = StabilizationReport { steps_run: step, stabilized_at: Some(first), period: Some(step - first) };
+            break;
+        }
+        seen.insert(hash, step);
+    }
+    
+    PatternAnalysis {
+        stabilization,
+        final_population: engine.count_live_cells(),
+        census: census(&engine.snapshot().grid),
+        growth: growth.report(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::ObjectDatabase;
+    use crate::engines::NaiveEngine;
+    use crate::grid::{Anchor, StandardGrid};
+    use crate::patterns;
+    
+    #[test]
+    fn test_analyze_settling_pattern() {
+        let blinker = patterns::blinker().grid.embedded_in(12, 12, Anchor::Center);
+        let mut engine = NaiveEngine::from_grid(&blinker);
+        let analysis = analyze_pattern(&mut engine, 100);
+        assert_eq!(analysis.stabilization, StabilizationReport { steps_run: 2, stabilized_at: Some(0), period: Some(2) });
+        assert_eq!(analysis.final_population, 3);
+        assert_eq!(analysis.census.count_named(&ObjectDatabase::common(), "blinker"), 1);
+        assert_eq!(analysis.growth.class, GrowthClass::Insufficient);
+        assert_eq!(analysis.growth_class(), GrowthClass::Bounded);
+        
+        // The R-pentomino is still going long after a short budget runs out
+        let r_pentomino = patterns::r_pentomino().grid.embedded_in(64, 64, Anchor::Center);
+        let mut engine = NaiveEngine::from_grid(&r_pentomino);
+        let analysis = analyze_pattern(&mut engine, 50);
+        assert!(!analysis.stabilization.is_stable());
+        assert_eq!((analysis.stabilization.steps_run, engine.generation()), (50, 50));
+        assert_eq!(analysis.final_population, engine.count_live_cells());
+        assert_ne!(analysis.growth_class(), GrowthClass::Insufficient);
+        
+        let mut engine = NaiveEngine::from_grid(&StandardGrid::new(8, 8));
+        assert!(analyze_pattern(&mut engine, 10).census.is_empty());
+    }
+}
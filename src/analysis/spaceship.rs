@@ -0,0 +1,173 @@
+//! Patterns that repeat under translation
+
+use crate::engines::{GameOfLifeEngine, NaiveEngine};
+use crate::grid::{Grid, PasteMode, StandardGrid};
+use std::fmt::{Display, Formatter};
+
+/// Direction class of a spaceship's travel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShipDirection {
+    /// Along a row or column
+    Orthogonal,
+    /// Along a 45 degree diagonal
+    Diagonal,
+    /// Any other slope, such as a knightship's (2, 1)
+    Oblique,
+}
+
+impl Display for ShipDirection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShipDirection::Orthogonal => write!(f, "orthogonal"),
+            ShipDirection::Diagonal => write!(f, "diagonal"),
+            ShipDirection::Oblique => write!(f, "oblique"),
+        }
+    }
+}
+
+/// A pattern that reappears displaced by (`dx`, `dy`) every `period` generations
+///
+/// `dx` counts columns to the right and `dy` rows downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Spaceship {
+    pub period: usize,
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Spaceship {
+    /// Whether the ship travels orthogonally, diagonally or obliquely
+    pub fn direction(&self) -> ShipDirection {
+        if self.dx == 0 || self.dy == 0 {
+            ShipDirection::Orthogonal
+        } else if self.dx.abs() == self.dy.abs() {
+            ShipDirection::Diagonal
+        } else {
+            ShipDirection::Oblique
+        }
+    }
+    
+    /// Average displacement per generation as (columns, rows)
+    pub fn velocity(&self) -> (f64, f64) {
+        (self.dx as f64 / self.period as f64, self.dy as f64 / self.period as f64)
+    }
+    
+    /// Speed in the usual notation: "c/4", "2c/5", or "(2,1)c/6" for oblique ships
+    pub fn speed(&self) -> String {
+        let (dx, dy) = (self.dx.unsigned_abs(), self.dy.unsigned_abs());
+        if self.direction() == ShipDirection::Oblique {
+            return format!("({},{})c/{}", dx.max(dy), dx.min(dy), self.period);
+        }
+        let distance = dx.max(dy);
+        let divisor = gcd(distance, self.period);
+        let (distance, period) = (distance / divisor, self.period / divisor);
+        match (distance, period) {
+            (1, 1) => "c".to_string(),
+            (1, _) => format!("c/{}", period),
+            (_, 1) => format!("{}c", distance),
+            _ => format!("{}c/{}", distance, period),
+        }
+    }
+}
+
+impl Display for Spaceship {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} spaceship (period {})", self.speed(), self.direction(), self.period)
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// How a pattern reappears: after `period` generations, shifted by (`dx`, `dy`)
+pub(crate) struct Recurrence {
+    pub period: usize,
+    pub dx: isize,
+    pub dy: isize,
+    /// The trimmed pattern in each generation of one period, starting with the original
+    pub phases: Vec<StandardGrid>,
+}
+
+/// Run the live cells of `grid` in empty space until they reappear, for at most `max_period` generations
+///
+/// Returns `None` if the pattern is empty, dies out or does not recur in time.
+pub(crate) fn find_recurrence(grid: &dyn Grid, max_period: usize) -> Option<Recurrence> {
+    let pattern = grid.trimmed();
+    if pattern.count_live_cells() == 0 {
+        return None;
+    }
+    
+    // Nothing moves faster than c, so the pattern cannot reach the edges in time
+    let margin = max_period + 2;
+    let mut space = StandardGrid::new(pattern.width() + 2 * margin, pattern.height() + 2 * margin);
+    space.paste(&pattern, margin, margin, PasteMode::Copy);
+    let mut engine = NaiveEngine::from_grid(&space);
+    let mut phases = vec![pattern.clone()];
+    
+    for period in 1..=max_period {
+        engine.step();
+        let current = engine.get_grid();
+        let bounds = current.bounding_box()?;
+        let phase = current.crop(bounds);
+        if phase != pattern {
+            phases.push(phase);
+            continue;
+        }
+        let (dx, dy) = (bounds.col as isize - margin as isize, bounds.row as isize - margin as isize);
+        return Some(Recurrence { period, dx, dy, phases });
+    }
+    None
+}
+
+/// Check whether the live cells of `grid` form a spaceship of period at most `max_period`
+///
+/// The pattern is run under Conway's rule in an empty space with room to travel,
+/// so the grid's own edges do not matter. Oscillators, still lifes and patterns
+/// that do not recur in time return `None`.
+pub fn detect_spaceship(grid: &dyn Grid, max_period: usize) -> Option<Spaceship> {
+    let Recurrence { period, dx, dy, .. } = find_recurrence(grid, max_period)?;
+    (dx != 0 || dy != 0).then_some(Spaceship { period, dx, dy })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns;
+    
+    #[test]
+    fn test_detect_standard_ships() {
+        let glider = detect_spaceship(&patterns::glider().grid, 10).unwrap();
+        assert_eq!(glider, Spaceship { period: 4, dx: 1, dy: 1 });
+        assert_eq!(glider.direction(), ShipDirection::Diagonal);
+        assert_eq!(glider.speed(), "c/4");
+        assert_eq!(glider.velocity(), (0.25, 0.25));
+        assert_eq!(glider.to_string(), "c/4 diagonal spaceship (period 4)");
+        
+        for ship in [patterns::lwss(), patterns::mwss(), patterns::hwss()] {
+            let found = detect_spaceship(&ship.grid, 10).unwrap();
+            assert_eq!((found.period, found.dx.abs(), found.dy), (4, 2, 0));
+            assert_eq!(found.to_string(), "c/2 orthogonal spaceship (period 4)");
+        }
+    }
+    
+    #[test]
+    fn test_non_ships_are_rejected() {
+        assert_eq!(detect_spaceship(&patterns::block().grid, 10), None);
+        assert_eq!(detect_spaceship(&patterns::blinker().grid, 10), None);
+        assert_eq!(detect_spaceship(&patterns::r_pentomino().grid, 10), None);
+        assert_eq!(detect_spaceship(&StandardGrid::new(5, 5), 10), None);
+        // Too short a search to see the glider come back
+        assert_eq!(detect_spaceship(&patterns::glider().grid, 3), None);
+    }
+    
+    #[test]
+    fn test_speed_notation() {
+        let ship = |period, dx, dy| Spaceship { period, dx, dy };
+        assert_eq!(ship(5, 0, -2).speed(), "2c/5");
+        assert_eq!(ship(6, 2, 1).speed(), "(2,1)c/6");
+        assert_eq!(ship(6, -2, -2).speed(), "c/3");
+        assert_eq!(ship(1, 1, 0).speed(), "c");
+        assert_eq!(ship(6, 1, -2).direction(), ShipDirection::Oblique);
+    }
+}
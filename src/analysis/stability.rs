@@ -0,0 +1,154 @@
+//! Empty, still life, oscillator or still evolving
+
+use crate::engines::{GameOfLifeEngine, NaiveEngine};
+use crate::grid::Grid;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// What a pattern settled into within a step budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StateClass {
+    /// Every cell died
+    Empty,
+    /// A non-empty state that maps to itself
+    StillLife,
+    /// A cycle of states with the given period (at least 2)
+    Oscillator { period: usize },
+    /// No state repeated within the budget
+    Evolving,
+}
+
+impl StateClass {
+    /// Whether the pattern stopped changing or entered a cycle
+    pub fn is_settled(&self) -> bool {
+        *self != StateClass::Evolving
+    }
+    
+    /// Cycle length: 1 for empty grids and still lifes, `None` while evolving
+    pub fn period(&self) -> Option<usize> {
+        match self {
+            StateClass::Empty | StateClass::StillLife => Some(1),
+            StateClass::Oscillator { period } => Some(*period),
+            StateClass::Evolving => None,
+        }
+    }
+}
+
+impl Display for StateClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateClass::Empty => write!(f, "empty"),
+            StateClass::StillLife => write!(f, "still life"),
+            StateClass::Oscillator { period } => write!(f, "period {} oscillator", period),
+            StateClass::Evolving => write!(f, "still evolving"),
+        }
+    }
+}
+
+/// Incremental stabilization check for loops that step an engine themselves
+///
+/// Call `observe` on the initial state and after every step; it returns the
+/// class as soon as the grid empties or a previously seen state recurs.
+#[derive(Debug, Clone, Default)]
+pub struct StabilityTracker {
+    seen: HashMap<u64, usize>,
+    observed: usize,
+}
+
+impl StabilityTracker {
+    /// Create a tracker that has seen no states
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Record the engine's current state and classify it if it has settled
+    pub fn observe(&mut self, engine: &dyn GameOfLifeEngine) -> Option<StateClass> {
+        let index = self.observed;
+        self.observed += 1;
+        if engine.count_live_cells() == 0 {
+            return Some(StateClass::Empty);
+        }
+        
+        let hash = engine.state_hash();
+        match self.seen.get(&hash) {
+            Some(&first) if index - first == 1 => Some(StateClass::StillLife),
+            Some(&first) => Some(StateClass::Oscillator { period: index - first }),
+            None => {
+                self.seen.insert(hash, index);
+                None
+            }
+        }
+    }
+    
+    /// Number of states observed so far
+    pub fn observed(&self) -> usize {
+        self.observed
+    }
+    
+    /// Forget every observed state
+    pub fn reset(&mut self) {
+        self.seen.clear();
+        self.observed = 0;
+    }
+}
+
+/// Step an engine until it settles or `max_steps` is reached
+///
+/// The engine is left in the state where the classification was made.
+pub fn classify_engine(engine: &mut dyn GameOfLifeEngine, max_steps: usize) -> StateClass {
+    let mut tracker = StabilityTracker::new();
+    if let Some(class) = tracker.observe(engine) {
+        return class;
+    }
+    for _ in 0..max_steps {
+        engine.step();
+        if let Some(class) = tracker.observe(engine) {
+            return class;
+        }
+    }
+    StateClass::Evolving
+}
+
+/// Classify a grid under Conway's rule, within `steps` generations
+pub fn classify_state(grid: &dyn Grid, steps: usize) -> StateClass {
+    classify_engine(&mut NaiveEngine::from_grid(grid), steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Anchor, StandardGrid};
+    use crate::patterns;
+    
+    fn in_space(pattern: &StandardGrid, size: usize) -> StandardGrid {
+        pattern.embedded_in(size, size, Anchor::Center)
+    }
+    
+    #[test]
+    fn test_classify_known_patterns() {
+        assert_eq!(classify_state(&StandardGrid::new(8, 8), 10), StateClass::Empty);
+        assert_eq!(classify_state(&in_space(&patterns::block().grid, 8), 10), StateClass::StillLife);
+        assert_eq!(classify_state(&in_space(&patterns::blinker().grid, 8), 10), StateClass::Oscillator { period: 2 });
+        assert_eq!(classify_state(&in_space(&patterns::pulsar().grid, 20), 10), StateClass::Oscillator { period: 3 });
+        assert_eq!(classify_state(&in_space(&patterns::pentadecathlon().grid, 20), 10), StateClass::Evolving);
+        assert_eq!(classify_state(&in_space(&patterns::pentadecathlon().grid, 20), 20), StateClass::Oscillator { period: 15 });
+        
+        // A lone pair of cells dies after one generation
+        let pair = StandardGrid::from_string_pattern(&["##"], '#', '.').unwrap();
+        assert_eq!(classify_state(&in_space(&pair, 6), 5), StateClass::Empty);
+    }
+    
+    #[test]
+    fn test_classify_engine_stops_at_settled_state() {
+        // The R-pentomino takes over a thousand generations to settle
+        let mut engine = NaiveEngine::from_grid(&in_space(&patterns::r_pentomino().grid, 40));
+        assert_eq!(classify_engine(&mut engine, 5), StateClass::Evolving);
+        assert_eq!(engine.generation(), 5);
+        
+        let mut engine = NaiveEngine::from_grid(&in_space(&patterns::blinker().grid, 8));
+        let class = classify_engine(&mut engine, 100);
+        assert_eq!(class.period(), Some(2));
+        assert!(class.is_settled());
+        assert_eq!(engine.generation(), 2);
+    }
+}
@@ -0,0 +1,250 @@
+//! Counting gliders as they cross a measurement line
+
+use super::matching::find_isolated_pattern;
+use super::spaceship::find_recurrence;
+use crate::engines::{GameOfLifeEngine, NaiveEngine};
+use crate::grid::{Anchor, Grid, StandardGrid, Transform};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Line that gliders are counted crossing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeasurementLine {
+    /// The horizontal line through this row
+    Row(usize),
+    /// The vertical line through this column
+    Column(usize),
+}
+
+/// Diagonal a glider travels along
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GliderDirection {
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl GliderDirection {
+    fn from_displacement(dx: isize, dy: isize) -> Self {
+        match (dx > 0, dy > 0) {
+            (true, false) => GliderDirection::NorthEast,
+            (false, false) => GliderDirection::NorthWest,
+            (true, true) => GliderDirection::SouthEast,
+            (false, true) => GliderDirection::SouthWest,
+        }
+    }
+    
+    /// Columns and rows moved per period, each +1 or -1
+    pub fn displacement(self) -> (isize, isize) {
+        match self {
+            GliderDirection::NorthEast => (1, -1),
+            GliderDirection::NorthWest => (-1, -1),
+            GliderDirection::SouthEast => (1, 1),
+            GliderDirection::SouthWest => (-1, 1),
+        }
+    }
+}
+
+impl Display for GliderDirection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GliderDirection::NorthEast => write!(f, "north-east"),
+            GliderDirection::NorthWest => write!(f, "north-west"),
+            GliderDirection::SouthEast => write!(f, "south-east"),
+            GliderDirection::SouthWest => write!(f, "south-west"),
+        }
+    }
+}
+
+/// One glider crossing the measurement line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GliderCrossing {
+    /// Generation at which the glider's center reached the line
+    pub generation: usize,
+    /// Column (for a row line) or row (for a column line) where it crossed
+    pub position: usize,
+    pub direction: GliderDirection,
+}
+
+/// Gliders that crossed the line at the same place travelling the same way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GliderStream {
+    pub direction: GliderDirection,
+    /// Column (for a row line) or row (for a column line) where the stream crosses
+    pub position: usize,
+    /// Generation of each crossing, in order
+    pub generations: Vec<usize>,
+}
+
+impl GliderStream {
+    /// Number of gliders seen
+    pub fn count(&self) -> usize {
+        self.generations.len()
+    }
+    
+    /// Emission period, if at least two gliders were seen and all were evenly spaced
+    pub fn period(&self) -> Option<usize> {
+        let mut gaps = self.generations.windows(2).map(|pair| pair[1] - pair[0]);
+        let first = gaps.next()?;
+        gaps.all(|gap| gap == first).then_some(first)
+    }
+}
+
+/// The two shapes a glider takes as it moves, each in its standard orientation
+pub(crate) fn glider_shapes() -> [StandardGrid; 2] {
+    let glider = crate::patterns::glider().grid;
+    let mut engine = NaiveEngine::from_grid(&glider.embedded_in(5, 5, Anchor::Center));
+    engine.step();
+    let stepped = engine.get_grid().trimmed();
+    [glider, stepped]
+}
+
+/// Centre of a glider seen in one generation
+#[derive(Debug, Clone, Copy)]
+struct Sighting {
+    row: usize,
+    col: usize,
+    direction: GliderDirection,
+}
+
+/// Watches a simulation generation by generation and records gliders crossing a line
+///
+/// Only isolated gliders are recognized, so place the line far enough from a
+/// gun or reaction for the gliders to have separated from it. Gliders are
+/// tracked from one generation to the next, so `observe` must see every
+/// generation.
+#[derive(Debug, Clone)]
+pub struct GliderStreamDetector {
+    line: MeasurementLine,
+    /// The two glider shapes, with the direction each orientation travels in
+    shapes: Vec<(StandardGrid, [GliderDirection; 8])>,
+    previous: Vec<Sighting>,
+    crossings: Vec<GliderCrossing>,
+}
+
+impl GliderStreamDetector {
+    /// Count gliders crossing `line`
+    pub fn new(line: MeasurementLine) -> Self {
+        let shapes = glider_shapes()
+            .into_iter()
+            .map(|shape| {
+                let directions = Transform::ALL.map(|transform| {
+                    let recurrence = find_recurrence(&transform.apply(&shape), 4).expect("a glider recurs after 4 generations");
+                    GliderDirection::from_displacement(recurrence.dx, recurrence.dy)
+                });
+                (shape, directions)
+            })
+            .collect();
+        Self { line, shapes, previous: Vec::new(), crossings: Vec::new() }
+    }
+    
+    /// The line being measured
+    pub fn line(&self) -> MeasurementLine {
+        self.line
+    }
+    
+    /// Look for gliders in `grid` and record any that crossed the line since the previous generation
+    pub fn observe(&mut self, generation: usize, grid: &dyn Grid) {
+        let mut sightings = Vec::new();
+        for (shape, directions) in &self.shapes {
+            for (row, col, transform) in find_isolated_pattern(grid, shape) {
+                let index = Transform::ALL.iter().position(|&t| t == transform).unwrap();
+                sightings.push(Sighting { row: row + 1, col: col + 1, direction: directions[index] });
+            }
+        }
+        
+        for sighting in &sightings {
+            let previous = self.previous.iter().find(|earlier| {
+                earlier.direction == sighting.direction
+                    && earlier.row.abs_diff(sighting.row) <= 1
+                    && earlier.col.abs_diff(sighting.col) <= 1
+            });
+            if let Some(previous) = previous {
+                if let Some(position) = self.crossed(previous, sighting) {
+                    self.crossings.push(GliderCrossing { generation, position, direction: sighting.direction });
+                }
+            }
+        }
+        self.previous = sightings;
+    }
+    
+    /// Observe the engine's current state, then step and observe `generations` more times
+    ///
+    /// Each generation is read through a snapshot, so this works with engines
+    /// such as `UltimateEngine` that do not expose a grid.
+    pub fn run(&mut self, engine: &mut dyn GameOfLifeEngine, generations: usize) {
+        self.observe(engine.generation(), &engine.snapshot().grid);
+        for _ in 0..generations {
+            engine.step();
+            self.observe(engine.generation(), &engine.snapshot().grid);
+        }
+    }
+    
+    /// Where a glider moving from `from` to `to` crossed the line, if it did
+    fn crossed(&self, from: &Sighting, to: &Sighting) -> Option<usize> {
+        let (dx, dy) = to.direction.displacement();
+        let (before, after, line, position) = match self.line {
+            MeasurementLine::Row(line) => (from.row, to.row, line, to.col),
+            MeasurementLine::Column(line) => (from.col, to.col, line, to.row),
+        };
+        let forward = match self.line {
+            MeasurementLine::Row(_) => dy > 0,
+            MeasurementLine::Column(_) => dx > 0,
+        };
+        let crossed = if forward { before < line && after >= line } else { before > line && after <= line };
+        crossed.then_some(position)
+    }
+    
+    /// Every crossing recorded so far, in order
+    pub fn crossings(&self) -> &[GliderCrossing] {
+        &self.crossings
+    }
+    
+    /// Crossings grouped into streams by direction and crossing position
+    pub fn streams(&self) -> Vec<GliderStream> {
+        let mut streams: BTreeMap<(GliderDirection, usize), Vec<usize>> = BTreeMap::new();
+        for crossing in &self.crossings {
+            streams.entry((crossing.direction, crossing.position)).or_default().push(crossing.generation);
+        }
+        streams
+            .into_iter()
+            .map(|((direction, position), generations)| GliderStream { direction, position, generations })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::PasteMode;
+    use crate::patterns;
+    
+    #[test]
+    fn test_single_glider_crosses_once() {
+        let mut universe = StandardGrid::new(40, 40);
+        universe.paste(&patterns::glider().grid.rotate180(), 30, 30, PasteMode::Copy);
+        let mut engine = NaiveEngine::from_grid(&universe);
+        
+        let mut detector = GliderStreamDetector::new(MeasurementLine::Column(20));
+        detector.run(&mut engine, 80);
+        let crossings = detector.crossings();
+        assert_eq!(crossings.len(), 1);
+        assert_eq!(crossings[0].direction, GliderDirection::NorthWest);
+        assert!(detector.streams()[0].period().is_none());
+    }
+    
+    #[test]
+    fn test_gosper_gun_stream_period() {
+        let mut universe = StandardGrid::new(64, 64);
+        universe.paste(&patterns::gosper_glider_gun().grid, 2, 2, PasteMode::Copy);
+        let mut engine = NaiveEngine::from_grid(&universe);
+        
+        let mut detector = GliderStreamDetector::new(MeasurementLine::Row(30));
+        detector.run(&mut engine, 240);
+        let streams = detector.streams();
+        assert_eq!(streams.len(), 1, "{:?}", streams);
+        assert!(streams[0].count() >= 3);
+        assert_eq!(streams[0].period(), Some(30));
+    }
+}
@@ -0,0 +1,190 @@
+//! Saved benchmark runs and regression checks against them
+
+use super::metrics::BenchmarkResult;
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// A named set of benchmark timings to compare later runs against
+///
+/// With the `serde` feature a baseline can be saved to and loaded from JSON.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Baseline {
+    pub name: String,
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// Timing of one benchmark configuration in a baseline
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaselineEntry {
+    /// The configuration's `engine_name`, which the suite makes unique per configuration
+    pub configuration: String,
+    pub steps: usize,
+    pub median: Duration,
+    pub std_dev: Duration,
+}
+
+/// How a configuration's median time moved relative to the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+/// One configuration's timing against the baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigurationChange {
+    pub configuration: String,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change in median time; 0.1 means 10% slower
+    pub change: f64,
+    pub kind: ChangeKind,
+}
+
+impl Display for ConfigurationChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {:?} -> {:?} ({:+.1}%)", self.configuration, self.baseline, self.current, self.change * 100.0)
+    }
+}
+
+/// Comparison of a run against a baseline
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RegressionReport {
+    /// Configurations present in both, in the order of the new run
+    pub changes: Vec<ConfigurationChange>,
+    /// Configurations in the baseline that the new run did not include
+    pub missing: Vec<String>,
+    /// Configurations in the new run that the baseline does not have
+    pub added: Vec<String>,
+}
+
+impl RegressionReport {
+    /// Configurations that got slower by more than the threshold
+    pub fn regressions(&self) -> impl Iterator<Item = &ConfigurationChange> {
+        self.changes.iter().filter(|change| change.kind == ChangeKind::Regressed)
+    }
+    
+    /// Configurations that got faster by more than the threshold
+    pub fn improvements(&self) -> impl Iterator<Item = &ConfigurationChange> {
+        self.changes.iter().filter(|change| change.kind == ChangeKind::Improved)
+    }
+    
+    /// Whether any configuration regressed
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+}
+
+impl Baseline {
+    /// Record a run's median timings under `name`
+    pub fn from_results(name: impl Into<String>, results: &[BenchmarkResult]) -> Self {
+        Self {
+            name: name.into(),
+            entries: results
+                .iter()
+                .map(|result| BaselineEntry {
+                    configuration: result.engine_name.clone(),
+                    steps: result.steps,
+                    median: result.timing.median,
+                    std_dev: result.timing.std_dev,
+                })
+                .collect(),
+        }
+    }
+    
+    /// Look up a configuration's entry
+    pub fn entry(&self, configuration: &str) -> Option<&BaselineEntry> {
+        self.entries.iter().find(|entry| entry.configuration == configuration)
+    }
+    
+    /// Compare a new run's median times with the baseline
+    ///
+    /// A configuration regressed if its median time grew by more than
+    /// `threshold` (0.05 for 5%) and improved if it shrank by more than that.
+    pub fn compare(&self, results: &[BenchmarkResult], threshold: f64) -> Result<RegressionReport, GameOfLifeError> {
+        if threshold.is_nan() || threshold < 0.0 {
+            return Err(GameOfLifeError::invalid(format!("Regression threshold must be non-negative, got {}", threshold)));
+        }
+        
+        let mut report = RegressionReport::default();
+        for result in results {
+            let Some(entry) = self.entry(&result.engine_name) else {
+                report.added.push(result.engine_name.clone());
+                continue;
+            };
+            let (baseline, current) = (entry.median, result.timing.median);
+            let change = current.as_secs_f64() / baseline.as_secs_f64().max(f64::EPSILON) - 1.0;
+            let kind = if change > threshold {
+                ChangeKind::Regressed
+            } else if change < -threshold {
+                ChangeKind::Improved
+            } else {
+                ChangeKind::Unchanged
+            };
+            report.changes.push(ConfigurationChange { configuration: result.engine_name.clone(), baseline, current, change, kind });
+        }
+        report.missing = self
+            .entries
+            .iter()
+            .filter(|entry| results.iter().all(|result| result.engine_name != entry.configuration))
+            .map(|entry| entry.configuration.clone())
+            .collect();
+        Ok(report)
+    }
+    
+    /// Write the baseline to a JSON file
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), GameOfLifeError> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+    
+    /// Read a baseline written by `save`
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, GameOfLifeError> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn result(configuration: &str, millis: u64) -> BenchmarkResult {
+        BenchmarkResult::from_samples(configuration.to_string(), 10, 100, 5, &[Duration::from_millis(millis)])
+    }
+    
+    #[test]
+    fn test_flags_regressions_beyond_threshold() {
+        let baseline = Baseline::from_results("main", &[result("a", 100), result("b", 100), result("c", 100), result("gone", 1)]);
+        let run = [result("a", 120), result("b", 103), result("c", 80), result("new", 1)];
+        let report = baseline.compare(&run, 0.05).unwrap();
+        
+        let kinds: Vec<ChangeKind> = report.changes.iter().map(|change| change.kind).collect();
+        assert_eq!(kinds, [ChangeKind::Regressed, ChangeKind::Unchanged, ChangeKind::Improved]);
+        assert!(report.has_regressions());
+        assert_eq!(report.regressions().next().unwrap().to_string(), "a: 100ms -> 120ms (+20.0%)");
+        assert_eq!((report.missing, report.added), (vec!["gone".to_string()], vec!["new".to_string()]));
+        assert!(baseline.compare(&run, -1.0).is_err());
+    }
+    
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_baseline_round_trips_through_json() {
+        let path = std::env::temp_dir().join(format!("gol_baseline_{}.json", std::process::id()));
+        let baseline = Baseline::from_results("main", &[result("a", 100), result("b", 7)]);
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+        
+        std::fs::write(&path, "{\"name\": \"main\", \"entries\": [").unwrap();
+        assert!(matches!(Baseline::load(&path), Err(GameOfLifeError::Json(_))));
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(Baseline::load(&path), Err(GameOfLifeError::Io(_))));
+    }
+}
@@ -0,0 +1,118 @@
+//! Locating where one engine overtakes another
+
+use std::fmt::{Display, Formatter};
+
+/// Workload parameter searched for a crossover
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrossoverAxis {
+    /// Side length of square grids between `min` and `max`, filled with soup at `density`
+    GridSize { min: usize, max: usize, density: f64 },
+    /// Soup density between `min` and `max` on a fixed grid size
+    Density { min: f64, max: f64, width: usize, height: usize },
+}
+
+impl CrossoverAxis {
+    /// Whether probes must land on whole numbers
+    fn is_integral(&self) -> bool {
+        matches!(self, CrossoverAxis::GridSize { .. })
+    }
+    
+    /// The range searched
+    fn bounds(&self) -> (f64, f64) {
+        match *self {
+            CrossoverAxis::GridSize { min, max, .. } => (min as f64, max as f64),
+            CrossoverAxis::Density { min, max, .. } => (min, max),
+        }
+    }
+    
+    /// Width, height and density of the workload at `value`
+    pub(crate) fn workload(&self, value: f64) -> (usize, usize, f64) {
+        match *self {
+            CrossoverAxis::GridSize { density, .. } => (value as usize, value as usize, density),
+            CrossoverAxis::Density { width, height, .. } => (width, height, value),
+        }
+    }
+}
+
+/// Relative speed of the two engines at one point on the axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrossoverProbe {
+    pub value: f64,
+    /// Throughput of the first engine divided by that of the second
+    pub speedup: f64,
+}
+
+/// Point at which the faster of two engines changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crossover {
+    /// Grid side length or density where the engines are about equally fast
+    pub value: f64,
+    /// Whether the first engine is the faster one below `value`
+    pub first_faster_below: bool,
+    /// Every probe, in the order they were measured
+    pub probes: Vec<CrossoverProbe>,
+}
+
+impl Display for Crossover {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (below, above) = if self.first_faster_below { ("first", "second") } else { ("second", "first") };
+        write!(f, "{} engine faster below {}, {} engine faster above", below, self.value, above)
+    }
+}
+
+/// Bisect the axis for the point where `measure`'s speedup crosses 1
+///
+/// Stops when the bracket is one unit wide for grid sizes, or 0.01 for
+/// densities. Returns `None` if the same engine is faster at both ends. The
+/// speedup is assumed to cross 1 only once; with several crossings, one of
+/// them is found.
+pub(crate) fn bisect(axis: &CrossoverAxis, mut measure: impl FnMut(f64) -> f64) -> Option<Crossover> {
+    let (mut low, mut high) = axis.bounds();
+    let tolerance = if axis.is_integral() { 1.0 } else { 0.01 };
+    let mut probes = Vec::new();
+    let mut probe = |value: f64, probes: &mut Vec<CrossoverProbe>| {
+        let speedup = measure(value);
+        probes.push(CrossoverProbe { value, speedup });
+        speedup > 1.0
+    };
+    
+    let first_faster_below = probe(low, &mut probes);
+    if probe(high, &mut probes) == first_faster_below {
+        return None;
+    }
+    while high - low > tolerance {
+        let mut middle = (low + high) / 2.0;
+        if axis.is_integral() {
+            middle = middle.floor();
+        }
+        if probe(middle, &mut probes) == first_faster_below {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Some(Crossover { value: if axis.is_integral() { high } else { (low + high) / 2.0 }, first_faster_below, probes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_bisect_finds_crossing() {
+        // The second engine pays a fixed overhead but less per cell
+        let axis = CrossoverAxis::GridSize { min: 8, max: 1024, density: 0.5 };
+        let speedup = |side: f64| (200.0 + side * side * 0.01) / (side * side * 0.04 + 1.0);
+        let crossover = bisect(&axis, speedup).unwrap();
+        assert_eq!(crossover.value, 82.0);
+        assert!(crossover.first_faster_below);
+        assert!(crossover.probes.len() <= 14);
+        assert_eq!(crossover.to_string(), "first engine faster below 82, second engine faster above");
+        
+        let density = CrossoverAxis::Density { min: 0.0, max: 1.0, width: 64, height: 64 };
+        let crossover = bisect(&density, |d| 2.0 - 4.0 * d).unwrap();
+        assert!((crossover.value - 0.25).abs() < 0.01);
+        assert!(crossover.first_faster_below);
+        assert!(bisect(&density, |_| 2.0).is_none());
+    }
+}
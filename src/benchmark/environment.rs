@@ -0,0 +1,106 @@
+//! Description of the machine a benchmark ran on
+
+use std::fmt::{Display, Formatter};
+use std::thread::available_parallelism;
+
+/// Machine details recorded with deterministic benchmark results
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchmarkEnvironment {
+    /// Processor model, where the platform reports one
+    pub cpu_model: Option<String>,
+    /// cpufreq scaling governor of the core the benchmark ran on, e.g. "performance"
+    pub frequency_governor: Option<String>,
+    /// Logical cores available to the process
+    pub logical_cores: usize,
+    pub os: &'static str,
+    pub arch: &'static str,
+    /// Worker threads the benchmark ran on
+    pub threads: usize,
+    /// Core the benchmark thread was pinned to, if pinning succeeded
+    pub pinned_core: Option<usize>,
+}
+
+impl BenchmarkEnvironment {
+    /// Describe this machine for a run on `threads` threads, pinned to `pinned_core` if any
+    pub fn detect(threads: usize, pinned_core: Option<usize>) -> Self {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+        let governor_path = format!("/sys/devices/system/cpu/cpu{}/cpufreq/scaling_governor", pinned_core.unwrap_or(0));
+        Self {
+            cpu_model: cpu_model(&cpuinfo),
+            frequency_governor: std::fs::read_to_string(governor_path).ok().map(|governor| governor.trim().to_string()),
+            logical_cores: available_parallelism().map_or(1, usize::from),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            threads,
+            pinned_core,
+        }
+    }
+}
+
+impl Display for BenchmarkEnvironment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} logical cores, {} governor) on {}/{}, {} thread{}",
+            self.cpu_model.as_deref().unwrap_or("unknown CPU"),
+            self.logical_cores,
+            self.frequency_governor.as_deref().unwrap_or("unknown"),
+            self.os,
+            self.arch,
+            self.threads,
+            if self.threads == 1 { "" } else { "s" }
+        )?;
+        match self.pinned_core {
+            Some(core) => write!(f, " pinned to core {}", core),
+            None => write!(f, ", unpinned"),
+        }
+    }
+}
+
+/// Processor model from the contents of `/proc/cpuinfo`
+fn cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        matches!(key.trim(), "model name" | "cpu model" | "Model").then(|| value.trim().to_string()).filter(|value| !value.is_empty())
+    })
+}
+
+/// Pin the calling thread to the first core it may run on, returning that core
+#[cfg(feature = "affinity")]
+pub(crate) fn pin_current_thread() -> Option<usize> {
+    let core = core_affinity::get_core_ids()?.into_iter().next()?;
+    core_affinity::set_for_current(core).then_some(core.id)
+}
+
+/// Pinning needs the `affinity` feature
+#[cfg(not(feature = "affinity"))]
+pub(crate) fn pin_current_thread() -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_environment_description() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Example CPU @ 3.00GHz\n\nprocessor\t: 1\n";
+        assert_eq!(cpu_model(cpuinfo).as_deref(), Some("Example CPU @ 3.00GHz"));
+        assert_eq!(cpu_model("processor : 0\n"), None);
+        
+        let environment = BenchmarkEnvironment {
+            cpu_model: Some("Example CPU".to_string()),
+            frequency_governor: None,
+            logical_cores: 8,
+            os: "linux",
+            arch: "x86_64",
+            threads: 1,
+            pinned_core: Some(2),
+        };
+        assert_eq!(environment.to_string(), "Example CPU (8 logical cores, unknown governor) on linux/x86_64, 1 thread pinned to core 2");
+        
+        let detected = BenchmarkEnvironment::detect(1, None);
+        assert!(detected.logical_cores >= 1);
+        assert_eq!(detected.os, std::env::consts::OS);
+    }
+}
@@ -0,0 +1,78 @@
+//! Engines and starting states shared by the Criterion benches in `benches/`
+
+use super::suite::SOUP_SEED;
+use crate::engines::generations::GenerationsEngine;
+use crate::engines::lookup::LookupEngine;
+use crate::engines::ltl::LtlEngine;
+use crate::engines::naive::NaiveEngine;
+use crate::engines::ultimate::UltimateEngine;
+use crate::engines::GameOfLifeEngine;
+use crate::grid::StandardGrid;
+use crate::rules::{GenerationsRule, LtlRule, MapRule, Rule};
+
+/// Side lengths of the square grids every engine is benchmarked on
+pub const BENCH_SIZES: [usize; 3] = [64, 256, 1024];
+
+/// Density of the random soups engines start from
+pub const SOUP_DENSITY: f64 = 0.5;
+
+/// An engine benchmarked by name
+#[derive(Debug, Clone, Copy)]
+pub struct EngineFixture {
+    pub name: &'static str,
+    build: fn(&StandardGrid) -> Box<dyn GameOfLifeEngine>,
+}
+
+impl EngineFixture {
+    /// A fresh engine starting from `grid`
+    pub fn build(&self, grid: &StandardGrid) -> Box<dyn GameOfLifeEngine> {
+        (self.build)(grid)
+    }
+}
+
+/// Every engine the benches cover, each with the rule it is usually run with
+pub fn engine_fixtures() -> Vec<EngineFixture> {
+    vec![
+        EngineFixture { name: "naive", build: |grid| Box::new(NaiveEngine::from_grid(grid)) },
+        EngineFixture {
+            name: "lookup",
+            build: |grid| Box::new(LookupEngine::from_grid(grid, MapRule::try_from(Rule::conway()).expect("Conway's Life is isotropic"))),
+        },
+        EngineFixture { name: "ultimate", build: |grid| Box::new(UltimateEngine::<4>::from_grid(grid)) },
+        EngineFixture { name: "generations", build: |grid| Box::new(GenerationsEngine::from_grid(grid, GenerationsRule::brians_brain())) },
+        EngineFixture { name: "ltl", build: |grid| Box::new(LtlEngine::from_grid(grid, LtlRule::bosco())) },
+    ]
+}
+
+/// The random soup every engine starts from, the same on every run
+pub fn soup(width: usize, height: usize) -> StandardGrid {
+    StandardGrid::random(width, height, SOUP_DENSITY, SOUP_SEED).expect("SOUP_DENSITY is a valid density")
+}
+
+/// Step `engine` `generations` times
+///
+/// Never inlined, so profiles and flamegraphs of the benches show the
+/// generations being stepped under one named frame.
+#[inline(never)]
+pub fn run_generations(engine: &mut dyn GameOfLifeEngine, generations: usize) {
+    for _ in 0..generations {
+        engine.step();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_fixtures_start_from_soup() {
+        let grid = soup(64, 64);
+        assert_eq!(grid, soup(64, 64));
+        for fixture in engine_fixtures() {
+            let mut engine = fixture.build(&grid);
+            assert_eq!((engine.width(), engine.height()), (64, 64), "{}", fixture.name);
+            run_generations(&mut *engine, 2);
+            assert_eq!(engine.generation(), 2, "{}", fixture.name);
+        }
+    }
+}
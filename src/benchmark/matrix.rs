@@ -0,0 +1,115 @@
+//! Results of several engines over the same benchmark matrix
+
+use super::metrics::BenchmarkResult;
+use std::fmt::{Display, Formatter};
+
+/// Every engine's result for every configuration, with speedups against one baseline engine
+///
+/// Displays as a table of speedups with a row per configuration, a column per
+/// engine and a final row of geometric means.
+#[derive(Debug, Clone)]
+pub struct ComparisonMatrix {
+    /// Engine names, in the order the engines were given
+    pub engines: Vec<String>,
+    /// Configuration labels: pattern, grid size and step count
+    pub configurations: Vec<String>,
+    /// `results[engine][configuration]`
+    pub results: Vec<Vec<BenchmarkResult>>,
+    /// Index of the engine speedups are measured against
+    pub baseline: usize,
+}
+
+impl ComparisonMatrix {
+    /// One engine's result for one configuration
+    pub fn result(&self, engine: usize, configuration: usize) -> Option<&BenchmarkResult> {
+        self.results.get(engine)?.get(configuration)
+    }
+    
+    /// How many times faster `engine` ran `configuration` than the baseline engine did
+    pub fn speedup(&self, engine: usize, configuration: usize) -> Option<f64> {
+        let baseline = self.result(self.baseline, configuration)?;
+        Some(self.result(engine, configuration)?.speedup_vs(baseline))
+    }
+    
+    /// Geometric mean of an engine's speedups over every configuration
+    pub fn mean_speedup(&self, engine: usize) -> Option<f64> {
+        let speedups: Vec<f64> = (0..self.configurations.len()).filter_map(|configuration| self.speedup(engine, configuration)).collect();
+        if speedups.is_empty() {
+            return None;
+        }
+        Some((speedups.iter().map(|speedup| speedup.ln()).sum::<f64>() / speedups.len() as f64).exp())
+    }
+    
+    /// Measure speedups against another engine instead
+    ///
+    /// Returns `None` if there is no engine at `baseline`.
+    pub fn with_baseline(mut self, baseline: usize) -> Option<Self> {
+        (baseline < self.engines.len()).then(|| {
+            self.baseline = baseline;
+            self
+        })
+    }
+}
+
+impl Display for ComparisonMatrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label_width = self.configurations.iter().map(String::len).chain(["configuration".len()]).max().unwrap_or(0);
+        let widths: Vec<usize> = self.engines.iter().map(|engine| engine.len().max(8)).collect();
+        
+        write!(f, "{:<label_width$}", "configuration")?;
+        for (engine, width) in self.engines.iter().zip(&widths) {
+            write!(f, "  {:>width$}", engine)?;
+        }
+        writeln!(f)?;
+        
+        let row = |f: &mut Formatter<'_>, label: &str, speedup: &dyn Fn(usize) -> Option<f64>| {
+            write!(f, "{:<label_width$}", label)?;
+            for (engine, width) in widths.iter().enumerate() {
+                match speedup(engine) {
+                    Some(speedup) => write!(f, "  {:>width$}", format!("{:.2}x", speedup), width = width)?,
+                    None => write!(f, "  {:>width$}", "-", width = width)?,
+                }
+            }
+            writeln!(f)
+        };
+        for (index, configuration) in self.configurations.iter().enumerate() {
+            row(f, configuration, &|engine| self.speedup(engine, index))?;
+        }
+        row(f, "geometric mean", &|engine| self.mean_speedup(engine))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    
+    fn result(millis: u64) -> BenchmarkResult {
+        BenchmarkResult::from_samples(String::new(), 10, 100, 5, &[Duration::from_millis(millis)])
+    }
+    
+    #[test]
+    fn test_speedups_against_baseline() {
+        let matrix = ComparisonMatrix {
+            engines: vec!["Slow".to_string(), "Fast".to_string()],
+            configurations: vec!["a".to_string(), "b".to_string()],
+            results: vec![vec![result(40), result(90)], vec![result(10), result(10)]],
+            baseline: 0,
+        };
+        assert_eq!(matrix.speedup(0, 1), Some(1.0));
+        assert_eq!(matrix.speedup(1, 0), Some(4.0));
+        assert!((matrix.mean_speedup(1).unwrap() - 6.0).abs() < 1e-9);
+        assert_eq!(matrix.speedup(2, 0), None);
+        
+        let table = matrix.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("configuration") && lines[0].ends_with("Fast"));
+        assert!(lines[1].starts_with("a ") && lines[1].ends_with("4.00x"));
+        assert!(lines[3].starts_with("geometric mean") && lines[3].ends_with("6.00x"));
+        
+        let rebased = matrix.with_baseline(1).unwrap();
+        assert_eq!(rebased.speedup(0, 0), Some(0.25));
+        assert!(rebased.with_baseline(2).is_none());
+    }
+}
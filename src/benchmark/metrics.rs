@@ -1,5 +1,7 @@
 //! Performance metrics and result types for benchmarking
 
+use super::environment::BenchmarkEnvironment;
+use crate::stats::Distribution;
 use std::time::Duration;
 
 /// Result of a benchmark run
@@ -7,13 +9,103 @@ use std::time::Duration;
 pub struct BenchmarkResult {
     pub engine_name: String,
     pub steps: usize,
+    /// Median time of the timed iterations
     pub duration: Duration,
     pub total_cells: usize,
     pub live_cells: usize,
+    /// Throughput at the median time
     pub cells_per_second: f64,
+    /// Spread of the timed iterations
+    pub timing: TimingStats,
+    /// Cell-buffer memory and, when the `perf` feature could count them, hardware events per timed iteration
+    pub metrics: Option<PerformanceMetrics>,
+    /// Machine the run was made on, recorded by deterministic runs
+    pub environment: Option<BenchmarkEnvironment>,
+}
+
+/// Hardware events counted while an engine was stepping
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HardwareCounters {
+    pub instructions: u64,
+    pub cache_misses: u64,
+    pub branch_misses: u64,
+}
+
+/// Summary of repeated timings of one configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingStats {
+    /// Number of timed iterations, not counting warmup
+    pub iterations: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    /// Sample standard deviation; zero for a single iteration
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl TimingStats {
+    /// Summarize a set of timings
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let distribution = Distribution::from_samples(samples.iter().map(Duration::as_secs_f64).collect());
+        let seconds = |value: Option<f64>| Duration::from_secs_f64(value.unwrap_or(0.0));
+        Self {
+            iterations: samples.len(),
+            mean: seconds(Some(distribution.mean)),
+            median: seconds(distribution.median()),
+            std_dev: seconds(Some(distribution.std_dev)),
+            min: seconds(distribution.min()),
+            max: seconds(distribution.max()),
+        }
+    }
+    
+    /// Standard deviation relative to the mean; above a few percent the timings are noisy
+    pub fn coefficient_of_variation(&self) -> f64 {
+        self.std_dev.as_secs_f64() / self.mean.as_secs_f64().max(f64::EPSILON)
+    }
 }
 
 impl BenchmarkResult {
+    /// Build a result from the timings of repeated runs, using the median for throughput
+    pub fn from_samples(engine_name: String, steps: usize, total_cells: usize, live_cells: usize, samples: &[Duration]) -> Self {
+        let timing = TimingStats::from_samples(samples);
+        Self {
+            engine_name,
+            steps,
+            duration: timing.median,
+            total_cells,
+            live_cells,
+            cells_per_second: (total_cells as f64 * steps as f64) / timing.median.as_secs_f64(),
+            timing,
+            metrics: None,
+            environment: None,
+        }
+    }
+    
+    /// Record the engine's cell-buffer memory, keeping any hardware counters already measured
+    pub fn with_memory(mut self, memory_bytes: usize) -> Self {
+        let measured = PerformanceMetrics::from_memory(memory_bytes, self.total_cells);
+        self.metrics = Some(match self.metrics {
+            Some(metrics) => PerformanceMetrics {
+                memory_usage_bytes: measured.memory_usage_bytes,
+                memory_per_cell_bytes: measured.memory_per_cell_bytes,
+                ..metrics
+            },
+            None => measured,
+        });
+        self
+    }
+    
+    /// Bytes the engine allocated for its cell buffers, when measured
+    pub fn memory_bytes(&self) -> Option<usize> {
+        self.metrics.as_ref().map(|metrics| metrics.memory_usage_bytes)
+    }
+    
+    /// Cell-buffer memory per cell, when measured
+    pub fn bytes_per_cell(&self) -> Option<f64> {
+        self.metrics.as_ref().map(|metrics| metrics.memory_per_cell_bytes)
+    }
+    
     /// Get the average time per step
     pub fn time_per_step(&self) -> Duration {
         self.duration / self.steps as u32
@@ -30,12 +122,26 @@ impl BenchmarkResult {
     }
 }
 
+/// Throughput of one grid size at one thread count, relative to the fewest threads measured
+#[derive(Debug, Clone)]
+pub struct ScalingPoint {
+    pub threads: usize,
+    pub width: usize,
+    pub height: usize,
+    pub result: BenchmarkResult,
+    /// Throughput relative to the smallest thread count at the same grid size
+    pub speedup: f64,
+    /// Speedup divided by the increase in threads; 1.0 is perfect scaling
+    pub efficiency: f64,
+}
+
 /// Detailed performance metrics
 #[derive(Debug, Clone)]
 pub struct PerformanceMetrics {
     pub memory_usage_bytes: usize,
     pub memory_per_cell_bytes: f64,
     pub cache_misses: Option<u64>,
+    pub branch_misses: Option<u64>,
     pub instructions_per_cell: Option<f64>,
 }
 
@@ -44,11 +150,20 @@ impl PerformanceMetrics {
     pub fn from_memory(total_memory: usize, total_cells: usize) -> Self {
         Self {
             memory_usage_bytes: total_memory,
-            memory_per_cell_bytes: total_memory as f64 / total_cells as f64,
+            memory_per_cell_bytes: total_memory as f64 / total_cells.max(1) as f64,
             cache_misses: None,
+            branch_misses: None,
             instructions_per_cell: None,
         }
     }
+    
+    /// Fill in the hardware counters of a run that made `cell_updates` cell updates
+    pub fn with_counters(mut self, counters: &HardwareCounters, cell_updates: u64) -> Self {
+        self.cache_misses = Some(counters.cache_misses);
+        self.branch_misses = Some(counters.branch_misses);
+        self.instructions_per_cell = Some(counters.instructions as f64 / cell_updates.max(1) as f64);
+        self
+    }
 }
 
 /// Comparison between two benchmark results
@@ -57,13 +172,17 @@ pub struct BenchmarkComparison {
     pub baseline: BenchmarkResult,
     pub optimized: BenchmarkResult,
     pub speedup: f64,
-    pub memory_improvement: f64,
+    /// Baseline bytes per cell divided by optimized bytes per cell, when both were measured
+    pub memory_improvement: Option<f64>,
 }
 
 impl BenchmarkComparison {
     pub fn new(baseline: BenchmarkResult, optimized: BenchmarkResult) -> Self {
         let speedup = optimized.speedup_vs(&baseline);
-        let memory_improvement = baseline.total_cells as f64 / optimized.total_cells as f64;
+        let memory_improvement = baseline
+            .bytes_per_cell()
+            .zip(optimized.bytes_per_cell())
+            .map(|(base, opt)| base / opt.max(f64::EPSILON));
         
         Self {
             baseline,
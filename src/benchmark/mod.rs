@@ -1,30 +1,214 @@
 //! Benchmarking framework for Game of Life engines
 
+pub mod baseline;
+pub mod crossover;
+pub mod environment;
+pub mod fixtures;
+pub mod matrix;
+#[cfg(all(feature = "perf", target_os = "linux"))]
+mod perf;
+pub mod roofline;
 pub mod suite;
 pub mod metrics;
 
-pub use suite::BenchmarkSuite;
-pub use metrics::{BenchmarkResult, PerformanceMetrics};
+pub use baseline::{Baseline, BaselineEntry, ChangeKind, ConfigurationChange, RegressionReport};
+pub use crossover::{Crossover, CrossoverAxis, CrossoverProbe};
+pub use environment::BenchmarkEnvironment;
+pub use matrix::ComparisonMatrix;
+pub use roofline::{BandwidthCalibration, RooflinePoint};
+pub use suite::{BenchmarkProgress, BenchmarkSuite, BenchmarkSuiteBuilder, TestPattern, ThreadedEngineFactory};
+pub use metrics::{BenchmarkResult, HardwareCounters, PerformanceMetrics, ScalingPoint, TimingStats};
 
 use crate::engines::GameOfLifeEngine;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Run a simple benchmark on an engine
 pub fn benchmark_engine(engine: &mut dyn GameOfLifeEngine, steps: usize) -> BenchmarkResult {
-    let start = Instant::now();
-    engine.run_steps(steps);
-    let duration = start.elapsed();
+    benchmark_engine_repeated(engine, steps, 0, 1)
+}
+
+/// Benchmark an engine `iterations` times after `warmup` untimed runs
+///
+/// Every run starts from the engine's current state, so the runs are
+/// identical, and the engine is left where the last run finished. The result
+/// reports the median time along with the spread of all timed runs. With the
+/// `perf` feature on Linux, hardware events are counted during the timed runs
+/// where the kernel allows it.
+pub fn benchmark_engine_repeated(engine: &mut dyn GameOfLifeEngine, steps: usize, warmup: usize, iterations: usize) -> BenchmarkResult {
+    let initial = engine.snapshot();
+    let measurement = time_iterations(warmup, iterations, |probe| {
+        engine.restore(&initial).expect("an engine can restore its own snapshot");
+        probe.start();
+        engine.run_steps(steps);
+        probe.stop();
+    });
+    
+    let total_cells = engine.width() * engine.height();
+    measurement.into_result(engine.benchmark_info().name, steps, total_cells, engine.count_live_cells(), Some(engine.memory_usage()))
+}
+
+/// Times the measured part of a benchmark iteration, counting hardware events in it where possible
+pub(crate) struct Probe {
+    elapsed: Duration,
+    started: Option<Instant>,
+    #[cfg(all(feature = "perf", target_os = "linux"))]
+    counters: Option<perf::CounterGroup>,
+}
+
+impl Probe {
+    #[cfg_attr(not(all(feature = "perf", target_os = "linux")), allow(unused_variables))]
+    fn new(count_events: bool) -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            started: None,
+            #[cfg(all(feature = "perf", target_os = "linux"))]
+            counters: count_events.then(|| perf::CounterGroup::open().ok()).flatten(),
+        }
+    }
     
-    let grid = engine.get_grid();
-    let total_cells = grid.total_cells();
-    let live_cells = grid.count_live_cells();
+    /// Start the clock and any counters
+    pub(crate) fn start(&mut self) {
+        #[cfg(all(feature = "perf", target_os = "linux"))]
+        if let Some(counters) = &self.counters {
+            counters.enable();
+        }
+        self.started = Some(Instant::now());
+    }
     
-    BenchmarkResult {
-        engine_name: engine.benchmark_info().name,
-        steps,
-        duration,
-        total_cells,
-        live_cells,
-        cells_per_second: (total_cells as f64 * steps as f64) / duration.as_secs_f64(),
-    }
-}
\ No newline at end of file
+    /// Stop the clock and any counters
+    pub(crate) fn stop(&mut self) {
+        if let Some(started) = self.started.take() {
+            self.elapsed += started.elapsed();
+        }
+        #[cfg(all(feature = "perf", target_os = "linux"))]
+        if let Some(counters) = &self.counters {
+            counters.disable();
+        }
+    }
+    
+    /// Hardware events counted so far, if counters could be opened
+    fn counters(&self) -> Option<HardwareCounters> {
+        #[cfg(all(feature = "perf", target_os = "linux"))]
+        if let Some(counters) = &self.counters {
+            return counters.read().ok();
+        }
+        None
+    }
+}
+
+/// Timings of the timed iterations and their average hardware event counts
+pub(crate) struct Measurement {
+    pub samples: Vec<Duration>,
+    pub counters: Option<HardwareCounters>,
+}
+
+impl Measurement {
+    /// Summarize the measurement as a benchmark result
+    ///
+    /// Hardware counters are reported in the result's `PerformanceMetrics`,
+    /// so they are kept only when the engine's memory was measured too.
+    pub(crate) fn into_result(
+        self,
+        engine_name: String,
+        steps: usize,
+        total_cells: usize,
+        live_cells: usize,
+        memory_bytes: Option<usize>,
+    ) -> BenchmarkResult {
+        let metrics = memory_bytes.map(|bytes| {
+            let metrics = PerformanceMetrics::from_memory(bytes, total_cells);
+            match &self.counters {
+                Some(counters) => metrics.with_counters(counters, (total_cells * steps) as u64),
+                None => metrics,
+            }
+        });
+        BenchmarkResult {
+            metrics,
+            ..BenchmarkResult::from_samples(engine_name, steps, total_cells, live_cells, &self.samples)
+        }
+    }
+}
+
+/// Call `run` `warmup + iterations` times, timing whatever it brackets with the probe's `start` and `stop`
+///
+/// Only the last `iterations` runs are kept, and only they count hardware events.
+pub(crate) fn time_iterations(warmup: usize, iterations: usize, mut run: impl FnMut(&mut Probe)) -> Measurement {
+    for _ in 0..warmup {
+        run(&mut Probe::new(false));
+    }
+    let iterations = iterations.max(1);
+    let mut probe = Probe::new(true);
+    let samples = (0..iterations)
+        .map(|_| {
+            probe.elapsed = Duration::ZERO;
+            run(&mut probe);
+            probe.elapsed
+        })
+        .collect();
+    let counters = probe.counters().map(|total| HardwareCounters {
+        instructions: total.instructions / iterations as u64,
+        cache_misses: total.cache_misses / iterations as u64,
+        branch_misses: total.branch_misses / iterations as u64,
+    });
+    Measurement { samples, counters }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::metrics::BenchmarkComparison;
+    use crate::engines::ultimate::UltimateEngine;
+    use crate::engines::NaiveEngine;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_repeated_runs_start_from_the_same_state() {
+        let soup = StandardGrid::random(32, 32, 0.4, 3).unwrap();
+        let mut engine = NaiveEngine::from_grid(&soup);
+        let result = benchmark_engine_repeated(&mut engine, 20, 2, 4);
+        assert_eq!(result.timing.iterations, 4);
+        assert_eq!(result.duration, result.timing.median);
+        assert!(result.timing.min <= result.timing.mean && result.timing.mean <= result.timing.max);
+        
+        let mut single = NaiveEngine::from_grid(&soup);
+        single.run_steps(20);
+        assert_eq!(engine.state_hash(), single.state_hash());
+        assert_eq!(benchmark_engine(&mut NaiveEngine::from_grid(&soup), 20).live_cells, result.live_cells);
+    }
+    
+    #[test]
+    fn test_counters_fill_performance_metrics() {
+        let counters = HardwareCounters { instructions: 5_000, cache_misses: 30, branch_misses: 7 };
+        let metrics = PerformanceMetrics::from_memory(1024, 100).with_counters(&counters, 1_000);
+        assert_eq!((metrics.cache_misses, metrics.branch_misses), (Some(30), Some(7)));
+        assert_eq!(metrics.instructions_per_cell, Some(5.0));
+        
+        // Counters are only available with the `perf` feature, and only where the kernel allows
+        let result = benchmark_engine(&mut NaiveEngine::from_grid(&StandardGrid::random(64, 64, 0.4, 1).unwrap()), 10);
+        let metrics = result.metrics.expect("benchmark_engine measures memory");
+        assert_eq!(metrics.memory_usage_bytes, 2 * 64 * 64);
+        if cfg!(not(feature = "perf")) {
+            assert_eq!((metrics.cache_misses, metrics.branch_misses, metrics.instructions_per_cell), (None, None, None));
+        } else if let Some(instructions_per_cell) = metrics.instructions_per_cell {
+            assert!(instructions_per_cell > 0.0);
+            assert!(metrics.cache_misses.is_some() && metrics.branch_misses.is_some());
+        }
+    }
+    
+    #[test]
+    fn test_memory_is_measured_per_engine() {
+        let soup = StandardGrid::random(128, 64, 0.4, 5).unwrap();
+        let naive = benchmark_engine(&mut NaiveEngine::from_grid(&soup), 5);
+        assert_eq!(naive.memory_bytes(), Some(2 * 128 * 64));
+        assert_eq!(naive.bytes_per_cell(), Some(2.0));
+        
+        // Bit-packed buffers take under a byte per cell, padding included
+        let packed = UltimateEngine::<4>::new(128, 64).memory_usage();
+        let optimized = BenchmarkResult { engine_name: "Ultimate".to_string(), ..naive.clone() }.with_memory(packed);
+        let comparison = BenchmarkComparison::new(naive, optimized);
+        assert!(comparison.memory_improvement.unwrap() > 2.0, "{:?}", comparison.memory_improvement);
+        
+        let unmeasured = BenchmarkResult::from_samples("x".to_string(), 1, 100, 0, &[Duration::from_millis(1)]);
+        assert_eq!(BenchmarkComparison::new(unmeasured.clone(), unmeasured).memory_improvement, None);
+    }
+}
@@ -0,0 +1,104 @@
+//! Hardware performance counters through Linux's `perf_event_open`
+
+use super::metrics::HardwareCounters;
+use perf_event_open_sys as sys;
+use sys::bindings::{perf_event_attr, perf_hw_id, PERF_FLAG_FD_CLOEXEC};
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::fd::{AsRawFd, FromRawFd};
+
+/// Events counted, in the order `CounterGroup` stores them
+const EVENTS: [perf_hw_id; 3] = [
+    sys::bindings::perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS,
+    sys::bindings::perf_hw_id_PERF_COUNT_HW_CACHE_MISSES,
+    sys::bindings::perf_hw_id_PERF_COUNT_HW_BRANCH_MISSES,
+];
+
+/// User-space event counters on every thread of this process
+///
+/// Counters are opened on each thread that exists when the group is opened,
+/// including idle worker pools, and inherited by threads those spawn later.
+/// Work done by other threads of the process, such as a concurrently running
+/// test, is counted too.
+pub(crate) struct CounterGroup {
+    counters: Vec<[File; 3]>,
+}
+
+impl CounterGroup {
+    /// Open disabled counters, failing if the kernel or hardware does not provide them
+    pub(crate) fn open() -> io::Result<Self> {
+        let mut counters = Vec::new();
+        let mut first_error = None;
+        for entry in std::fs::read_dir("/proc/self/task")? {
+            let Ok(tid) = entry?.file_name().to_string_lossy().parse::<i32>() else {
+                continue;
+            };
+            // A thread may exit before its counters are opened
+            match EVENTS.iter().map(|&event| open_counter(tid, event)).collect::<io::Result<Vec<File>>>() {
+                Ok(files) => counters.push(files.try_into().expect("one file per event")),
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        match (counters.is_empty(), first_error) {
+            (true, Some(error)) => Err(error),
+            _ => Ok(Self { counters }),
+        }
+    }
+    
+    /// Start counting
+    pub(crate) fn enable(&self) {
+        self.ioctl(sys::ioctls::ENABLE);
+    }
+    
+    /// Stop counting, keeping the totals so far
+    pub(crate) fn disable(&self) {
+        self.ioctl(sys::ioctls::DISABLE);
+    }
+    
+    /// Totals over every thread since the group was opened
+    pub(crate) fn read(&self) -> io::Result<HardwareCounters> {
+        let mut totals = [0u64; 3];
+        for files in &self.counters {
+            for (total, file) in totals.iter_mut().zip(files) {
+                let mut bytes = [0u8; 8];
+                (&*file).read_exact(&mut bytes)?;
+                *total += u64::from_ne_bytes(bytes);
+            }
+        }
+        let [instructions, cache_misses, branch_misses] = totals;
+        Ok(HardwareCounters { instructions, cache_misses, branch_misses })
+    }
+    
+    fn ioctl(&self, request: unsafe fn(i32, u32) -> i32) {
+        for file in self.counters.iter().flatten() {
+            // SAFETY: the descriptor is an open perf event owned by `file`
+            unsafe {
+                request(file.as_raw_fd(), 0);
+            }
+        }
+    }
+}
+
+/// Open a disabled user-space counter for `event` on thread `tid`
+fn open_counter(tid: i32, event: perf_hw_id) -> io::Result<File> {
+    let mut attrs = perf_event_attr {
+        type_: sys::bindings::perf_type_id_PERF_TYPE_HARDWARE,
+        size: std::mem::size_of::<perf_event_attr>() as u32,
+        config: event as u64,
+        ..Default::default()
+    };
+    attrs.set_disabled(1);
+    attrs.set_inherit(1);
+    attrs.set_exclude_kernel(1);
+    attrs.set_exclude_hv(1);
+    
+    // SAFETY: `attrs` is a fully initialized attribute struct that outlives the call
+    let fd = unsafe { sys::perf_event_open(&mut attrs, tid, -1, -1, PERF_FLAG_FD_CLOEXEC as _) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: the kernel just returned this descriptor and nothing else owns it
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
@@ -0,0 +1,133 @@
+//! Achieved memory bandwidth of engines against a STREAM-style calibration
+
+use super::metrics::BenchmarkResult;
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Default length of each calibration array: 4 Mi doubles, 32 MiB, larger than most last-level caches
+pub const DEFAULT_CALIBRATION_LEN: usize = 1 << 22;
+
+/// Doubles handed to each rayon task by the triad kernel
+const CHUNK: usize = 1 << 14;
+
+/// Sustainable memory bandwidth measured with the STREAM triad kernel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandwidthCalibration {
+    /// Best bandwidth seen over all runs
+    pub bytes_per_second: f64,
+    /// Length of each of the three arrays, in doubles
+    pub array_len: usize,
+    pub iterations: usize,
+}
+
+impl BandwidthCalibration {
+    /// Run the triad `a = b + s * c` over arrays of `array_len` doubles `iterations` times on every thread
+    ///
+    /// Each run moves three arrays' worth of bytes, as STREAM counts it; the
+    /// fastest run is kept. Arrays much larger than the last-level cache are
+    /// needed for the result to reflect main memory rather than cache.
+    pub fn measure(array_len: usize, iterations: usize) -> Self {
+        let array_len = array_len.max(1);
+        let iterations = iterations.max(1);
+        let mut a = vec![0.0f64; array_len];
+        let b = vec![1.0f64; array_len];
+        let c = vec![2.0f64; array_len];
+        let scalar = black_box(3.0);
+        
+        let bytes = (3 * array_len * size_of::<f64>()) as f64;
+        let mut bytes_per_second: f64 = 0.0;
+        for _ in 0..iterations {
+            let start = Instant::now();
+            a.par_chunks_mut(CHUNK).zip(b.par_chunks(CHUNK)).zip(c.par_chunks(CHUNK)).for_each(|((a, b), c)| {
+                for ((a, b), c) in a.iter_mut().zip(b).zip(c) {
+                    *a = b + scalar * c;
+                }
+            });
+            black_box(&a);
+            bytes_per_second = bytes_per_second.max(bytes / start.elapsed().as_secs_f64().max(f64::EPSILON));
+        }
+        Self { bytes_per_second, array_len, iterations }
+    }
+    
+    /// Calibrate with `DEFAULT_CALIBRATION_LEN` arrays and ten runs
+    pub fn measure_default() -> Self {
+        Self::measure(DEFAULT_CALIBRATION_LEN, 10)
+    }
+    
+    /// Bandwidth in gigabytes (10^9 bytes) per second
+    pub fn gb_per_second(&self) -> f64 {
+        self.bytes_per_second / 1e9
+    }
+}
+
+/// Bandwidth one benchmark configuration achieved, relative to the calibrated peak
+#[derive(Debug, Clone)]
+pub struct RooflinePoint {
+    pub result: BenchmarkResult,
+    /// Bytes read and written per generation: the engine's cell buffers, one read and one written
+    pub bytes_per_generation: usize,
+    pub achieved_bytes_per_second: f64,
+    /// Achieved bandwidth divided by the calibrated peak
+    pub fraction_of_peak: f64,
+}
+
+impl RooflinePoint {
+    /// Place a result on the roofline, if its engine memory was measured
+    ///
+    /// An engine is assumed to touch each of its cell buffers once per
+    /// generation, reading the current one and writing the next. Fractions
+    /// above 1 mean the working set fits in cache.
+    pub fn new(result: BenchmarkResult, peak: &BandwidthCalibration) -> Option<Self> {
+        let bytes_per_generation = result.memory_bytes()?;
+        let achieved_bytes_per_second =
+            (bytes_per_generation as f64 * result.steps as f64) / result.duration.as_secs_f64().max(f64::EPSILON);
+        Some(Self {
+            result,
+            bytes_per_generation,
+            achieved_bytes_per_second,
+            fraction_of_peak: achieved_bytes_per_second / peak.bytes_per_second.max(f64::EPSILON),
+        })
+    }
+    
+    /// Achieved bandwidth in gigabytes (10^9 bytes) per second
+    pub fn gb_per_second(&self) -> f64 {
+        self.achieved_bytes_per_second / 1e9
+    }
+}
+
+impl Display for RooflinePoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {:.2} GB/s, {:.1}% of peak",
+            self.result.engine_name,
+            self.gb_per_second(),
+            self.fraction_of_peak * 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    
+    #[test]
+    fn test_roofline_fraction() {
+        let peak = BandwidthCalibration::measure(1 << 16, 3);
+        assert!(peak.bytes_per_second > 0.0 && peak.bytes_per_second.is_finite());
+        assert_eq!((peak.array_len, peak.iterations), (1 << 16, 3));
+        
+        let peak = BandwidthCalibration { bytes_per_second: 10e9, ..peak };
+        let result = BenchmarkResult::from_samples("Naive".to_string(), 100, 1_000_000, 0, &[Duration::from_millis(40)]);
+        assert!(RooflinePoint::new(result.clone(), &peak).is_none());
+        
+        // 2 MB per generation for 100 generations in 40 ms is 5 GB/s
+        let point = RooflinePoint::new(result.with_memory(2_000_000), &peak).unwrap();
+        assert!((point.gb_per_second() - 5.0).abs() < 1e-9);
+        assert!((point.fraction_of_peak - 0.5).abs() < 1e-9);
+        assert_eq!(point.to_string(), "Naive: 5.00 GB/s, 50.0% of peak");
+    }
+}
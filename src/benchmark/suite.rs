@@ -1,15 +1,63 @@
 //! Benchmark suite for comparing Game of Life engines
 
-use crate::engines::GameOfLifeEngine;
-use crate::grid::{Grid, StandardGrid};
-use super::metrics::{BenchmarkResult, BenchmarkComparison};
-use std::time::Instant;
+use crate::engines::{CancellationToken, GameOfLifeEngine, NaiveEngine};
+use crate::error::GameOfLifeError;
+use crate::grid::{Anchor, Grid, StandardGrid};
+use crate::formats::Pattern;
+use crate::patterns;
+use super::crossover::{bisect, Crossover, CrossoverAxis};
+use super::environment::{pin_current_thread, BenchmarkEnvironment};
+use super::matrix::ComparisonMatrix;
+use super::metrics::{BenchmarkResult, BenchmarkComparison, ScalingPoint};
+use super::roofline::{BandwidthCalibration, RooflinePoint};
+use super::time_iterations;
+use rayon::ThreadPoolBuilder;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread::available_parallelism;
+use std::time::{Duration, Instant};
+
+/// Builds an engine for a grid size, to run on the given number of threads
+pub type ThreadedEngineFactory = dyn Fn(usize, usize, usize) -> Box<dyn GameOfLifeEngine> + Sync;
+
+/// One engine's name and results, or the error that stopped its run
+type BenchmarkRun = Result<(String, Vec<BenchmarkResult>), GameOfLifeError>;
+
+/// Seed of every random soup the suite generates, so runs see identical workloads
+pub const SOUP_SEED: u64 = 0x5EED;
 
 /// A comprehensive benchmark suite for Game of Life engines
+#[derive(Debug, Clone)]
 pub struct BenchmarkSuite {
     test_patterns: Vec<TestPattern>,
     grid_sizes: Vec<(usize, usize)>,
     step_counts: Vec<usize>,
+    warmup: usize,
+    iterations: usize,
+    verify: bool,
+    cancel: CancellationToken,
+    max_concurrency: usize,
+}
+
+/// How far a suite run has got, reported after each configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkProgress {
+    /// Configurations finished so far
+    pub completed: usize,
+    /// Configurations in the whole run
+    pub total: usize,
+    /// Name of the configuration that just finished
+    pub configuration: String,
+    /// Time since the run started
+    pub elapsed: Duration,
+}
+
+impl BenchmarkProgress {
+    /// Fraction of the run completed, from 0 to 1
+    pub fn fraction(&self) -> f64 {
+        self.completed as f64 / self.total.max(1) as f64
+    }
 }
 
 /// A test pattern for benchmarking
@@ -27,6 +75,11 @@ impl BenchmarkSuite {
             test_patterns: Self::default_patterns(),
             grid_sizes: vec![(50, 50), (100, 100), (200, 200), (500, 500)],
             step_counts: vec![10, 50, 100, 500],
+            warmup: 1,
+            iterations: 5,
+            verify: false,
+            cancel: CancellationToken::new(),
+            max_concurrency: 1,
         }
     }
     
@@ -36,44 +89,213 @@ impl BenchmarkSuite {
             test_patterns: vec![Self::blinker_pattern()],
             grid_sizes: vec![(10, 10), (50, 50)],
             step_counts: vec![10, 100],
+            warmup: 0,
+            iterations: 3,
+            verify: false,
+            cancel: CancellationToken::new(),
+            max_concurrency: 1,
+        }
+    }
+    
+    /// Start building a suite with custom patterns, grid sizes and step counts
+    pub fn builder() -> BenchmarkSuiteBuilder {
+        BenchmarkSuiteBuilder::default()
+    }
+    
+    /// Patterns each configuration is run on
+    pub fn patterns(&self) -> &[TestPattern] {
+        &self.test_patterns
+    }
+    
+    /// Grid sizes each pattern is embedded in
+    pub fn grid_sizes(&self) -> &[(usize, usize)] {
+        &self.grid_sizes
+    }
+    
+    /// Numbers of generations each configuration is run for
+    pub fn step_counts(&self) -> &[usize] {
+        &self.step_counts
+    }
+    
+    /// Untimed runs of each configuration before timing starts
+    pub fn warmup(&self) -> usize {
+        self.warmup
+    }
+    
+    /// Timed runs of each configuration
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+    
+    /// Whether each configuration's final state is checked against a reference engine
+    pub fn verifies(&self) -> bool {
+        self.verify
+    }
+    
+    /// Most engines `compare_all_concurrent` benchmarks at the same time
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+    
+    /// Replace the test patterns
+    pub fn set_patterns(&mut self, patterns: Vec<TestPattern>) {
+        self.test_patterns = patterns;
+    }
+    
+    /// Add a test pattern to the existing ones
+    pub fn add_pattern(&mut self, pattern: TestPattern) {
+        self.test_patterns.push(pattern);
+    }
+    
+    /// Add every `.rle` and `.cells` file in a directory as a test pattern
+    ///
+    /// Returns the number of patterns added. Nothing is added if any file
+    /// fails to load.
+    pub fn add_pattern_dir(&mut self, dir: impl AsRef<Path>) -> Result<usize, GameOfLifeError> {
+        let patterns = TestPattern::load_dir(dir)?;
+        let count = patterns.len();
+        self.test_patterns.extend(patterns);
+        Ok(count)
+    }
+    
+    /// Replace the grid sizes, rejecting an empty list or a size with no cells
+    pub fn set_grid_sizes(&mut self, grid_sizes: Vec<(usize, usize)>) -> Result<(), GameOfLifeError> {
+        if grid_sizes.is_empty() {
+            return Err(GameOfLifeError::invalid("Benchmark needs at least one grid size"));
+        }
+        check_grid_sizes(&grid_sizes)?;
+        self.grid_sizes = grid_sizes;
+        Ok(())
+    }
+    
+    /// Replace the step counts, rejecting an empty list or a zero count
+    pub fn set_step_counts(&mut self, step_counts: Vec<usize>) -> Result<(), GameOfLifeError> {
+        if step_counts.is_empty() {
+            return Err(GameOfLifeError::invalid("Benchmark needs at least one step count"));
         }
+        check_step_counts(&step_counts)?;
+        self.step_counts = step_counts;
+        Ok(())
+    }
+    
+    /// Change the number of untimed runs of each configuration
+    pub fn set_warmup(&mut self, warmup: usize) {
+        self.warmup = warmup;
+    }
+    
+    /// Change the number of timed runs of each configuration, at least one
+    pub fn set_iterations(&mut self, iterations: usize) {
+        self.iterations = iterations.max(1);
+    }
+    
+    /// Turn checking final states against a reference engine on or off
+    pub fn set_verify(&mut self, verify: bool) {
+        self.verify = verify;
+    }
+    
+    /// Stop runs early once `cancel` is triggered
+    pub fn set_cancellation(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
+    }
+    
+    /// Change how many engines `compare_all_concurrent` benchmarks at once, at least one
+    pub fn set_max_concurrency(&mut self, max_concurrency: usize) {
+        self.max_concurrency = max_concurrency.max(1);
+    }
+    
+    /// Number of configurations `benchmark_engine` runs
+    pub fn configuration_count(&self) -> usize {
+        self.grid_sizes.len() * self.step_counts.len() * self.test_patterns.len()
     }
     
     /// Run a comprehensive benchmark on an engine
-    pub fn benchmark_engine(&self, engine: &mut dyn GameOfLifeEngine) -> Vec<BenchmarkResult> {
+    ///
+    /// Each configuration is run `warmup` times untimed, then `iterations`
+    /// times timed, starting from the same grid every time. With verification
+    /// on, the run fails as soon as a configuration ends in a different state
+    /// than the reference engine.
+    pub fn benchmark_engine(&self, engine: &mut dyn GameOfLifeEngine) -> Result<Vec<BenchmarkResult>, GameOfLifeError> {
+        self.benchmark_engine_with_progress(engine, |_| {})
+    }
+    
+    /// As `benchmark_engine`, calling `progress` after each configuration
+    ///
+    /// The cancellation token is checked between configurations; once it is
+    /// triggered, the results finished so far are returned.
+    pub fn benchmark_engine_with_progress(
+        &self,
+        engine: &mut dyn GameOfLifeEngine,
+        mut progress: impl FnMut(BenchmarkProgress),
+    ) -> Result<Vec<BenchmarkResult>, GameOfLifeError> {
         let mut results = Vec::new();
+        let total = self.configuration_count();
+        let start = Instant::now();
         
         for &(width, height) in &self.grid_sizes {
             for &steps in &self.step_counts {
                 for pattern in &self.test_patterns {
+                    if self.cancel.is_cancelled() {
+                        return Ok(results);
+                    }
                     if let Ok(grid) = self.create_test_grid(pattern, width, height) {
-                        engine.set_grid(&grid);
+                        let measurement = time_iterations(self.warmup, self.iterations, |probe| {
+                            engine.set_grid(&grid);
+                            probe.start();
+                            engine.run_steps(steps);
+                            probe.stop();
+                        });
                         
-                        let start = Instant::now();
-                        engine.run_steps(steps);
-                        let duration = start.elapsed();
+                        let name = format!("{}-{}-{}x{}-{}", 
+                            engine.benchmark_info().name,
+                            pattern.name,
+                            width, height,
+                            steps);
+                        self.verify_run(&*engine, &grid, steps, &name)?;
                         
-                        let final_grid = engine.get_grid();
-                        let result = BenchmarkResult {
-                            engine_name: format!("{}-{}-{}x{}-{}", 
-                                engine.benchmark_info().name,
-                                pattern.name,
-                                width, height,
-                                steps),
+                        let result = measurement.into_result(
+                            name,
                             steps,
-                            duration,
-                            total_cells: final_grid.total_cells(),
-                            live_cells: final_grid.count_live_cells(),
-                            cells_per_second: (final_grid.total_cells() as f64 * steps as f64) / duration.as_secs_f64(),
-                        };
+                            engine.width() * engine.height(),
+                            engine.count_live_cells(),
+                            Some(engine.memory_usage()),
+                        );
                         
+                        progress(BenchmarkProgress {
+                            completed: results.len() + 1,
+                            total,
+                            configuration: result.engine_name.clone(),
+                            elapsed: start.elapsed(),
+                        });
                         results.push(result);
                     }
                 }
             }
         }
         
-        results
+        Ok(results)
+    }
+    
+    /// Check that `engine` reached the same state as a reference engine run `steps` generations from `initial`
+    ///
+    /// The reference is a `NaiveEngine` with the engine's rule and topology, so
+    /// only engines with a Life-like rule can be verified. Does nothing when
+    /// verification is off.
+    fn verify_run(&self, engine: &dyn GameOfLifeEngine, initial: &dyn Grid, steps: usize, configuration: &str) -> Result<(), GameOfLifeError> {
+        if !self.verify {
+            return Ok(());
+        }
+        let rule = engine.rule().ok_or_else(|| {
+            GameOfLifeError::unsupported("Benchmark verification", format!("the {} engine's rule", engine.benchmark_info().name))
+        })?;
+        let mut reference = NaiveEngine::from_grid(initial);
+        reference.set_rule(rule)?;
+        reference.set_topology(engine.topology())?;
+        reference.set_boundary(engine.boundary())?;
+        reference.run_steps(steps);
+        if reference.state_hash() != engine.state_hash() {
+            return Err(GameOfLifeError::Diverged { configuration: configuration.to_string(), generations: steps });
+        }
+        Ok(())
     }
     
     /// Compare two engines across all benchmarks
@@ -81,50 +303,230 @@ impl BenchmarkSuite {
         &self,
         baseline: &mut dyn GameOfLifeEngine,
         optimized: &mut dyn GameOfLifeEngine,
-    ) -> Vec<BenchmarkComparison> {
-        let baseline_results = self.benchmark_engine(baseline);
-        let optimized_results = self.benchmark_engine(optimized);
+    ) -> Result<Vec<BenchmarkComparison>, GameOfLifeError> {
+        let baseline_results = self.benchmark_engine(baseline)?;
+        let optimized_results = self.benchmark_engine(optimized)?;
         
-        baseline_results
+        Ok(baseline_results
             .into_iter()
             .zip(optimized_results.into_iter())
             .map(|(base, opt)| BenchmarkComparison::new(base, opt))
-            .collect()
+            .collect())
     }
     
-    /// Create a test grid from a pattern, scaling it to fit the target size
-    fn create_test_grid(&self, pattern: &TestPattern, width: usize, height: usize) -> Result<StandardGrid, String> {
-        let mut grid = StandardGrid::new(width, height);
-
-        let pattern_height = pattern.pattern.len();
-        if pattern_height == 0 {
-            return Ok(grid);
+    /// Run every engine over the same matrix and tabulate speedups against `engines[baseline]`
+    pub fn compare_all(&self, engines: &mut [&mut dyn GameOfLifeEngine], baseline: usize) -> Result<ComparisonMatrix, GameOfLifeError> {
+        if baseline >= engines.len() {
+            return Err(GameOfLifeError::invalid(format!("Baseline engine {} is out of range for {} engines", baseline, engines.len())));
         }
         
-        let pattern_width = pattern.pattern[0].len();
-        if pattern_width == 0 {
-            return Ok(grid);
+        let mut names = Vec::new();
+        let mut results = Vec::new();
+        for engine in engines.iter_mut() {
+            names.push(engine.benchmark_info().name);
+            results.push(self.benchmark_engine(&mut **engine)?);
+        }
+        Ok(Self::matrix(names, results, baseline))
+    }
+    
+    /// As `compare_all`, benchmarking up to `max_concurrency` engines at once
+    ///
+    /// Each engine comes from `engine_factories[i](width, height, threads)`
+    /// and runs inside a rayon pool of `threads` threads, where the available
+    /// cores are split evenly between the engines running at the same time.
+    /// Factories for engines with a pool of their own should size it to
+    /// `threads` too, as `UltimateEngine::with_threads` does, so the machine
+    /// is not oversubscribed.
+    pub fn compare_all_concurrent(&self, engine_factories: &[&ThreadedEngineFactory], baseline: usize) -> Result<ComparisonMatrix, GameOfLifeError> {
+        if baseline >= engine_factories.len() {
+            return Err(GameOfLifeError::invalid(format!("Baseline engine {} is out of range for {} engines", baseline, engine_factories.len())));
         }
         
-        let start_row = (height.saturating_sub(pattern_height)) / 2;
-        let start_col = (width.saturating_sub(pattern_width)) / 2;
+        let workers = self.max_concurrency.min(engine_factories.len());
+        let threads = (available_parallelism().map_or(1, usize::from) / workers).max(1);
+        let (width, height) = self.grid_sizes.first().copied().unwrap_or((1, 1));
+        let next = AtomicUsize::new(0);
+        let slots: Vec<Mutex<Option<BenchmarkRun>>> = engine_factories.iter().map(|_| Mutex::new(None)).collect();
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(factory) = engine_factories.get(index) else {
+                            break;
+                        };
+                        let run = ThreadPoolBuilder::new()
+                            .num_threads(threads)
+                            .build()
+                            .map_err(|error| GameOfLifeError::invalid(format!("Cannot start {} threads: {}", threads, error)))
+                            .and_then(|pool| {
+                                pool.install(|| {
+                                    let mut engine = factory(width, height, threads);
+                                    Ok((engine.benchmark_info().name, self.benchmark_engine(&mut *engine)?))
+                                })
+                            });
+                        *slots[index].lock().unwrap_or_else(PoisonError::into_inner) = Some(run);
+                    }
+                });
+            }
+        });
         
-        for (row_offset, line) in pattern.pattern.iter().enumerate() {
-            for (col_offset, ch) in line.chars().enumerate() {
-                let grid_row = start_row + row_offset;
-                let grid_col = start_col + col_offset;
-                
-                if grid_row < height && grid_col < width {
-                    let alive = match ch {
-                        '#' | '█' | '*' | 'O' => true,
-                        _ => false,
-                    };
-                    grid.set_cell(grid_row, grid_col, alive);
+        let mut names = Vec::new();
+        let mut results = Vec::new();
+        for slot in slots {
+            let (name, runs) = slot.into_inner().unwrap_or_else(PoisonError::into_inner).expect("every engine is benchmarked")?;
+            names.push(name);
+            results.push(runs);
+        }
+        Ok(Self::matrix(names, results, baseline))
+    }
+    
+    /// Tabulate per-engine results, whose names are "<engine>-<configuration>"
+    fn matrix(engines: Vec<String>, results: Vec<Vec<BenchmarkResult>>, baseline: usize) -> ComparisonMatrix {
+        let prefix = format!("{}-", engines[0]);
+        let configurations = results[0]
+            .iter()
+            .map(|result| result.engine_name.strip_prefix(&prefix).unwrap_or(&result.engine_name).to_string())
+            .collect();
+        ComparisonMatrix { engines, configurations, results, baseline }
+    }
+    
+    /// Benchmark a fresh engine from `engine_factory` in deterministic mode
+    ///
+    /// Every configuration runs on a single rayon worker thread, pinned to one
+    /// core when the `affinity` feature is on, so engines that parallelize on
+    /// rayon's global pool run sequentially. Engines with a pool of their own
+    /// should be built without it, e.g. with `UltimateEngine::sequential`.
+    /// Soups use `SOUP_SEED`, and every result records the machine it ran on.
+    pub fn benchmark_deterministic(
+        &self,
+        engine_factory: impl Fn(usize, usize) -> Box<dyn GameOfLifeEngine> + Sync,
+    ) -> Result<Vec<BenchmarkResult>, GameOfLifeError> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .map_err(|error| GameOfLifeError::invalid(format!("Cannot start a benchmark thread: {}", error)))?;
+        pool.install(|| {
+            let environment = BenchmarkEnvironment::detect(1, pin_current_thread());
+            let (width, height) = self.grid_sizes.first().copied().unwrap_or((1, 1));
+            let mut engine = engine_factory(width, height);
+            Ok(self
+                .benchmark_engine(&mut *engine)?
+                .into_iter()
+                .map(|result| BenchmarkResult { environment: Some(environment.clone()), ..result })
+                .collect())
+        })
+    }
+    
+    /// Powers of two from 1 up to the number of available cores, plus the core count itself
+    pub fn default_thread_counts() -> Vec<usize> {
+        let cores = available_parallelism().map_or(1, usize::from);
+        let mut counts: Vec<usize> = std::iter::successors(Some(1), |&n| Some(n * 2)).take_while(|&n| n < cores).collect();
+        counts.push(cores);
+        counts
+    }
+    
+    /// Measure how throughput scales with the number of threads
+    ///
+    /// For each grid size, a seeded random soup filling the grid is run for the
+    /// largest step count on an engine from `engine_factory(width, height,
+    /// threads)`. The factory should build an engine that uses that many
+    /// threads; each measurement also runs inside a rayon pool of that size, so
+    /// engines that parallelize on rayon's global pool are limited too.
+    pub fn scaling_sweep(
+        &self,
+        engine_factory: impl Fn(usize, usize, usize) -> Box<dyn GameOfLifeEngine> + Sync,
+        thread_counts: &[usize],
+    ) -> Result<Vec<ScalingPoint>, GameOfLifeError> {
+        let steps = self.step_counts.iter().copied().max().unwrap_or(1);
+        let mut points = Vec::new();
+        for &(width, height) in &self.grid_sizes {
+            let soup = StandardGrid::random(width, height, 0.5, SOUP_SEED)?;
+            let mut baseline: Option<(usize, f64)> = None;
+            for &threads in thread_counts {
+                if self.cancel.is_cancelled() {
+                    return Ok(points);
                 }
+                let pool = ThreadPoolBuilder::new()
+                    .num_threads(threads.max(1))
+                    .build()
+                    .map_err(|error| GameOfLifeError::invalid(format!("Cannot start {} threads: {}", threads, error)))?;
+                let result = pool.install(|| {
+                    let mut engine = engine_factory(width, height, threads);
+                    let measurement = time_iterations(self.warmup, self.iterations, |probe| {
+                        engine.set_grid(&soup);
+                        probe.start();
+                        engine.run_steps(steps);
+                        probe.stop();
+                    });
+                    let name = format!("{}-{}x{}-{}t", engine.benchmark_info().name, width, height, threads);
+                    self.verify_run(&*engine, &soup, steps, &name)?;
+                    let memory_bytes = Some(engine.memory_usage());
+                    Ok::<_, GameOfLifeError>(measurement.into_result(name, steps, width * height, engine.count_live_cells(), memory_bytes))
+                })?;
+                
+                let (base_threads, base_throughput) = *baseline.get_or_insert((threads, result.cells_per_second));
+                let speedup = result.cells_per_second / base_throughput;
+                let efficiency = speedup * base_threads as f64 / threads as f64;
+                points.push(ScalingPoint { threads, width, height, result, speedup, efficiency });
             }
         }
+        Ok(points)
+    }
+    
+    /// Run every configuration and place each on the roofline set by `peak`
+    ///
+    /// Calibrate `peak` once with `BandwidthCalibration::measure` and reuse it
+    /// across engines so their fractions are comparable.
+    pub fn roofline(&self, engine: &mut dyn GameOfLifeEngine, peak: &BandwidthCalibration) -> Result<Vec<RooflinePoint>, GameOfLifeError> {
+        Ok(self.benchmark_engine(engine)?
+            .into_iter()
+            .filter_map(|result| RooflinePoint::new(result, peak))
+            .collect())
+    }
+    
+    /// Find where along `axis` one engine overtakes the other
+    ///
+    /// Each probe runs a seeded soup for the smallest step count on a fresh
+    /// engine from each factory and compares median throughputs, bisecting
+    /// until the crossover is pinned down. Returns `None` if the same engine is
+    /// faster across the whole range.
+    pub fn find_crossover(
+        &self,
+        engine_a: impl Fn(usize, usize) -> Box<dyn GameOfLifeEngine>,
+        engine_b: impl Fn(usize, usize) -> Box<dyn GameOfLifeEngine>,
+        axis: CrossoverAxis,
+    ) -> Result<Option<Crossover>, GameOfLifeError> {
+        let steps = self.step_counts.iter().copied().min().unwrap_or(1);
+        let throughput = |factory: &dyn Fn(usize, usize) -> Box<dyn GameOfLifeEngine>, soup: &StandardGrid| {
+            let mut engine = factory(soup.width(), soup.height());
+            let measurement = time_iterations(self.warmup, self.iterations, |probe| {
+                engine.set_grid(soup);
+                probe.start();
+                engine.run_steps(steps);
+                probe.stop();
+            });
+            measurement.into_result(String::new(), steps, soup.total_cells(), 0, None).cells_per_second
+        };
+        crossover_by(axis, |soup| throughput(&engine_a, soup) / throughput(&engine_b, soup))
+    }
+    
+    /// Create a test grid with the pattern centered in it
+    fn create_test_grid(&self, pattern: &TestPattern, width: usize, height: usize) -> Result<StandardGrid, GameOfLifeError> {
+        let pattern_height = pattern.pattern.len();
+        let pattern_width = pattern.pattern.first().map_or(0, |line| line.chars().count());
+        if pattern_height == 0 || pattern_width == 0 {
+            return Ok(StandardGrid::new(width, height));
+        }
         
-        Ok(grid)
+        let mut cells = StandardGrid::new(pattern_width, pattern_height);
+        for (row, line) in pattern.pattern.iter().enumerate() {
+            for (col, ch) in line.chars().take(pattern_width).enumerate() {
+                cells.set_cell(row, col, matches!(ch, '#' | '█' | '*' | 'O'));
+            }
+        }
+        
+        Ok(cells.embedded_in(width, height, Anchor::Center))
     }
     
     /// Get default test patterns
@@ -139,64 +541,214 @@ impl BenchmarkSuite {
     
     /// Blinker pattern (period-2 oscillator)
     fn blinker_pattern() -> TestPattern {
-        TestPattern {
-            name: "blinker".to_string(),
-            description: "Simple period-2 oscillator".to_string(),
-            pattern: vec![
-                "...".to_string(),
-                "###".to_string(),
-                "...".to_string(),
-            ],
-        }
+        TestPattern::from_grid("blinker", "Simple period-2 oscillator", &patterns::blinker().grid)
     }
     
     /// Block pattern (still life)
     fn block_pattern() -> TestPattern {
-        TestPattern {
-            name: "block".to_string(),
-            description: "Simple still life".to_string(),
-            pattern: vec![
-                "....".to_string(),
-                ".##.".to_string(),
-                ".##.".to_string(),
-                "....".to_string(),
-            ],
-        }
+        TestPattern::from_grid("block", "Simple still life", &patterns::block().grid)
     }
     
     /// Glider pattern (moving spaceship)
     fn glider_pattern() -> TestPattern {
-        TestPattern {
-            name: "glider".to_string(),
-            description: "Simple moving spaceship".to_string(),
-            pattern: vec![
-                ".....".to_string(),
-                "..#..".to_string(),
-                "...#.".to_string(),
-                ".###.".to_string(),
-                ".....".to_string(),
-            ],
-        }
+        TestPattern::from_grid("glider", "Simple moving spaceship", &patterns::glider().grid)
     }
     
-    /// Random pattern for stress testing
+    /// Seeded 50x50 random soup for stress testing
     fn random_pattern() -> TestPattern {
-        TestPattern {
-            name: "random".to_string(),
-            description: "Random pattern for stress testing".to_string(),
-            pattern: vec![
-                "##.#.##.#".to_string(),
-                ".#..#..#.".to_string(),
-                "#.#.#.#.#".to_string(),
-                "..#...#..".to_string(),
-                "#.#.#.#.#".to_string(),
-                ".#..#..#.".to_string(),
-                "##.#.##.#".to_string(),
-            ],
+        let soup = StandardGrid::random(50, 50, 0.5, SOUP_SEED).expect("0.5 is a valid density");
+        TestPattern::from_grid("random", "Seeded random soup for stress testing", &soup)
+    }
+}
+
+impl TestPattern {
+    /// Describe a grid as '#'/'.' rows
+    pub fn from_grid(name: &str, description: &str, grid: &dyn Grid) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            pattern: (0..grid.height())
+                .map(|row| (0..grid.width()).map(|col| if grid.get_cell(row, col) { '#' } else { '.' }).collect())
+                .collect(),
+        }
+    }
+    
+    /// Load a pattern file in any detectable format
+    ///
+    /// The pattern is named after the name recorded in the file, or the file
+    /// name if there is none, and described by its first comment line.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GameOfLifeError> {
+        let path = path.as_ref();
+        let pattern = Pattern::load(path)?;
+        let stem = path.file_stem().map_or_else(|| path.display().to_string(), |stem| stem.to_string_lossy().into_owned());
+        let name = pattern.meta.name.clone().unwrap_or(stem);
+        let description = pattern.meta.comments.first().cloned().unwrap_or_else(|| format!("Loaded from {}", path.display()));
+        Ok(Self::from_grid(&name, &description, &pattern.grid))
+    }
+    
+    /// Load every `.rle` and `.cells` file in a directory, in file name order
+    ///
+    /// Subdirectories and files with other extensions are skipped.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Self>, GameOfLifeError> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let extension = path.extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase);
+            if path.is_file() && matches!(extension.as_deref(), Some("rle" | "cells")) {
+                paths.push(path);
+            }
         }
+        paths.sort();
+        paths.iter().map(Self::load).collect()
     }
 }
 
+/// Builder for a `BenchmarkSuite` with a custom benchmark matrix
+///
+/// Anything left unset falls back to the defaults of `BenchmarkSuite::new`.
+/// Pattern files and directories are read when the suite is built.
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkSuiteBuilder {
+    patterns: Vec<TestPattern>,
+    pattern_files: Vec<PathBuf>,
+    pattern_dirs: Vec<PathBuf>,
+    grid_sizes: Vec<(usize, usize)>,
+    step_counts: Vec<usize>,
+    warmup: Option<usize>,
+    iterations: Option<usize>,
+    verify: bool,
+    cancel: CancellationToken,
+    max_concurrency: Option<usize>,
+}
+
+impl BenchmarkSuiteBuilder {
+    /// Add a test pattern
+    pub fn pattern(mut self, pattern: TestPattern) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+    
+    /// Add a grid as a test pattern
+    pub fn pattern_grid(self, name: &str, grid: &dyn Grid) -> Self {
+        self.pattern(TestPattern::from_grid(name, name, grid))
+    }
+    
+    /// Add a test pattern loaded from a file in any detectable format
+    pub fn pattern_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.pattern_files.push(path.into());
+        self
+    }
+    
+    /// Add every `.rle` and `.cells` file in a directory as a test pattern
+    pub fn pattern_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.pattern_dirs.push(dir.into());
+        self
+    }
+    
+    /// Add grid sizes to embed each pattern in
+    pub fn grid_sizes(mut self, sizes: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.grid_sizes.extend(sizes);
+        self
+    }
+    
+    /// Add numbers of generations to run each configuration for
+    pub fn steps(mut self, steps: impl IntoIterator<Item = usize>) -> Self {
+        self.step_counts.extend(steps);
+        self
+    }
+    
+    /// Run each configuration this many times untimed before timing it
+    pub fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = Some(warmup);
+        self
+    }
+    
+    /// Time each configuration this many times, at least once
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = Some(iterations.max(1));
+        self
+    }
+    
+    /// Check each configuration's final state against a reference engine
+    pub fn verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+    
+    /// Stop runs early once `cancel` is triggered
+    pub fn cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+    
+    /// Benchmark up to this many engines at once in `compare_all_concurrent`, at least one
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency.max(1));
+        self
+    }
+    
+    /// Load the pattern files and assemble the suite
+    ///
+    /// Fails if a pattern file cannot be read or parsed, or if a grid size or
+    /// step count is zero.
+    pub fn build(self) -> Result<BenchmarkSuite, GameOfLifeError> {
+        check_grid_sizes(&self.grid_sizes)?;
+        check_step_counts(&self.step_counts)?;
+        
+        let defaults = BenchmarkSuite::new();
+        let mut test_patterns = self.patterns;
+        for path in &self.pattern_files {
+            test_patterns.push(TestPattern::load(path)?);
+        }
+        for dir in &self.pattern_dirs {
+            test_patterns.extend(TestPattern::load_dir(dir)?);
+        }
+        Ok(BenchmarkSuite {
+            test_patterns: if test_patterns.is_empty() { defaults.test_patterns } else { test_patterns },
+            grid_sizes: if self.grid_sizes.is_empty() { defaults.grid_sizes } else { self.grid_sizes },
+            step_counts: if self.step_counts.is_empty() { defaults.step_counts } else { self.step_counts },
+            warmup: self.warmup.unwrap_or(defaults.warmup),
+            iterations: self.iterations.unwrap_or(defaults.iterations),
+            verify: self.verify,
+            cancel: self.cancel,
+            max_concurrency: self.max_concurrency.unwrap_or(defaults.max_concurrency),
+        })
+    }
+}
+
+/// Bisect `axis` for a crossover, with `speedup` comparing the engines on each probe's soup
+fn crossover_by(axis: CrossoverAxis, mut speedup: impl FnMut(&StandardGrid) -> f64) -> Result<Option<Crossover>, GameOfLifeError> {
+    match axis {
+        CrossoverAxis::GridSize { min, max, density } if min == 0 || min >= max || !(0.0..=1.0).contains(&density) => {
+            return Err(GameOfLifeError::invalid(format!("Invalid crossover grid sizes {}..{} at density {}", min, max, density)));
+        }
+        CrossoverAxis::Density { min, max, width, height } if width == 0 || height == 0 || !(0.0 <= min && min < max && max <= 1.0) => {
+            return Err(GameOfLifeError::invalid(format!("Invalid crossover densities {}..{} on {}x{}", min, max, width, height)));
+        }
+        _ => {}
+    }
+    
+    Ok(bisect(&axis, |value| {
+        let (width, height, density) = axis.workload(value);
+        let soup = StandardGrid::random(width, height, density, SOUP_SEED).expect("densities are checked above");
+        speedup(&soup)
+    }))
+}
+
+fn check_grid_sizes(grid_sizes: &[(usize, usize)]) -> Result<(), GameOfLifeError> {
+    match grid_sizes.iter().find(|&&(width, height)| width == 0 || height == 0) {
+        Some(&(width, height)) => Err(GameOfLifeError::invalid(format!("Benchmark grid size {}x{} has no cells", width, height))),
+        None => Ok(()),
+    }
+}
+
+fn check_step_counts(step_counts: &[usize]) -> Result<(), GameOfLifeError> {
+    if step_counts.contains(&0) {
+        return Err(GameOfLifeError::invalid("Benchmark step counts must be positive"));
+    }
+    Ok(())
+}
+
 impl Default for BenchmarkSuite {
     fn default() -> Self {
         Self::new()
@@ -206,7 +758,40 @@ impl Default for BenchmarkSuite {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engines::naive::NaiveEngine;
+    use crate::engines::life3d::Life3DEngine;
+    use crate::engines::ultimate::UltimateEngine;
+    use crate::engines::EngineInfo;
+    use crate::rules::BaysRule;
+    
+    /// Engine that silently runs two generations per step
+    struct Faulty(NaiveEngine);
+    
+    impl GameOfLifeEngine for Faulty {
+        fn step(&mut self) {
+            self.0.step();
+            self.0.step();
+        }
+        
+        fn get_grid(&self) -> &dyn Grid {
+            self.0.get_grid()
+        }
+        
+        fn set_grid(&mut self, grid: &dyn Grid) {
+            self.0.set_grid(grid);
+        }
+        
+        fn benchmark_info(&self) -> EngineInfo {
+            EngineInfo { name: "Faulty".to_string(), ..self.0.benchmark_info() }
+        }
+        
+        fn get_cell(&self, row: usize, col: usize) -> bool {
+            self.0.get_cell(row, col)
+        }
+        
+        fn generation(&self) -> usize {
+            self.0.generation() / 2
+        }
+    }
     
     #[test]
     fn test_benchmark_suite_creation() {
@@ -226,17 +811,269 @@ mod tests {
         assert_eq!(grid.height(), 10);
     }
     
+    #[test]
+    fn test_builder() {
+        let dir = std::env::temp_dir().join(format!("gol_suite_builder_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("beehive.cells");
+        std::fs::write(&file, "!Name: Beehive\n.OO.\nO..O\n.OO.\n").unwrap();
+        
+        let suite = BenchmarkSuite::builder()
+            .grid_sizes([(16, 16), (32, 8)])
+            .steps([5])
+            .iterations(2)
+            .pattern_grid("glider", &patterns::glider().grid)
+            .pattern_file(&file)
+            .build()
+            .unwrap();
+        assert_eq!(suite.grid_sizes(), &[(16, 16), (32, 8)]);
+        assert_eq!(suite.step_counts(), &[5]);
+        assert_eq!((suite.warmup(), suite.iterations()), (1, 2));
+        let names: Vec<&str> = suite.patterns().iter().map(|pattern| pattern.name.as_str()).collect();
+        assert_eq!(names, ["glider", "Beehive"]);
+        assert_eq!(suite.patterns()[1].pattern, [".##.", "#..#", ".##."]);
+        
+        let mut engine = NaiveEngine::new(16, 16);
+        assert_eq!(suite.benchmark_engine(&mut engine).unwrap().len(), 4);
+        
+        // Unset parts of the matrix keep their defaults
+        let mut defaults = BenchmarkSuite::builder().steps([1]).build().unwrap();
+        assert_eq!(defaults.grid_sizes(), BenchmarkSuite::new().grid_sizes());
+        defaults.set_grid_sizes(vec![(8, 8)]).unwrap();
+        assert_eq!(defaults.grid_sizes(), &[(8, 8)]);
+        // The setters reject what `build` would
+        assert!(defaults.set_grid_sizes(vec![(8, 0)]).is_err());
+        assert!(defaults.set_grid_sizes(Vec::new()).is_err());
+        assert!(defaults.set_step_counts(vec![4, 0]).is_err());
+        assert_eq!((defaults.grid_sizes(), defaults.step_counts()), (&[(8, 8)][..], &[1][..]));
+        
+        assert!(BenchmarkSuite::builder().pattern_file(dir.join("missing.rle")).build().is_err());
+        assert!(BenchmarkSuite::builder().grid_sizes([(0, 4)]).build().is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[test]
+    fn test_pattern_directory() {
+        let dir = std::env::temp_dir().join(format!("gol_suite_patterns_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("b_glider.rle"), "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n").unwrap();
+        std::fs::write(dir.join("a_block.CELLS"), "!Name: Block\nOO\nOO\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a pattern").unwrap();
+        std::fs::write(dir.join("nested").join("ignored.rle"), "x = 1, y = 1\no!\n").unwrap();
+        
+        let suite = BenchmarkSuite::builder().pattern_dir(&dir).build().unwrap();
+        let names: Vec<&str> = suite.patterns().iter().map(|pattern| pattern.name.as_str()).collect();
+        assert_eq!(names, ["Block", "Glider"]);
+        assert_eq!(suite.patterns()[1].pattern, [".#.", "..#", "###"]);
+        
+        let mut minimal = BenchmarkSuite::minimal();
+        assert_eq!(minimal.add_pattern_dir(&dir).unwrap(), 2);
+        assert_eq!(minimal.patterns().len(), 3);
+        
+        std::fs::write(dir.join("broken.rle"), "x = 2, y = 2\nzz!\n").unwrap();
+        assert!(minimal.add_pattern_dir(&dir).is_err());
+        assert_eq!(minimal.patterns().len(), 3);
+        assert!(TestPattern::load_dir(dir.join("missing")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[test]
+    fn test_compare_all() {
+        let suite = BenchmarkSuite::builder().grid_sizes([(16, 16), (24, 24)]).steps([4]).iterations(1).warmup(0).build().unwrap();
+        let mut naive = NaiveEngine::new(16, 16);
+        let mut ultimate = UltimateEngine::<4>::new(16, 16);
+        let mut life3d = Life3DEngine::new(1, 1, 1, BaysRule::life_5766());
+        let matrix = suite.compare_all(&mut [&mut naive, &mut ultimate, &mut life3d], 0).unwrap();
+        
+        assert_eq!(matrix.engines.len(), 3);
+        assert_eq!(matrix.configurations.len(), 2 * suite.patterns().len());
+        assert_eq!(matrix.configurations[0], "blinker-16x16-4");
+        assert!(matrix.results.iter().all(|row| row.len() == matrix.configurations.len()));
+        assert_eq!(matrix.speedup(0, 3), Some(1.0));
+        assert!(matrix.mean_speedup(1).unwrap() > 0.0);
+        
+        assert!(suite.compare_all(&mut [&mut naive], 1).is_err());
+    }
+    
+    #[test]
+    fn test_compare_all_concurrent() {
+        let suite = BenchmarkSuite::builder().grid_sizes([(32, 32)]).steps([4]).iterations(1).warmup(0).max_concurrency(2).build().unwrap();
+        assert_eq!(suite.max_concurrency(), 2);
+        let naive = |width, height, _threads| Box::new(NaiveEngine::new(width, height)) as Box<dyn GameOfLifeEngine>;
+        let ultimate = |width, height, threads| Box::new(UltimateEngine::<4>::with_threads(width, height, threads)) as Box<dyn GameOfLifeEngine>;
+        let life3d = |_width, _height, _threads| Box::new(Life3DEngine::new(1, 1, 1, BaysRule::life_5766())) as Box<dyn GameOfLifeEngine>;
+        let matrix = suite.compare_all_concurrent(&[&naive, &ultimate, &life3d], 0).unwrap();
+        
+        assert_eq!(matrix.engines[..2], ["Naive".to_string(), UltimateEngine::<4>::new(1, 1).benchmark_info().name]);
+        assert_eq!(matrix.configurations.len(), suite.patterns().len());
+        assert!(matrix.results.iter().all(|row| row.len() == matrix.configurations.len()));
+        // Concurrency changes timing, not what each engine computes
+        let sequential = suite.compare_all(&mut [&mut NaiveEngine::new(32, 32)], 0).unwrap();
+        assert_eq!(
+            matrix.results[1].iter().map(|result| result.live_cells).collect::<Vec<_>>(),
+            sequential.results[0].iter().map(|result| result.live_cells).collect::<Vec<_>>()
+        );
+        assert!(suite.compare_all_concurrent(&[&naive], 1).is_err());
+    }
+    
+    #[test]
+    fn test_scaling_sweep() {
+        let suite = BenchmarkSuite::builder().grid_sizes([(128, 64)]).steps([8]).iterations(1).warmup(0).build().unwrap();
+        let points = suite
+            .scaling_sweep(|width, height, threads| Box::new(UltimateEngine::<4>::with_threads(width, height, threads)), &[1, 2])
+            .unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!((points[0].speedup, points[0].efficiency), (1.0, 1.0));
+        assert_eq!(points[1].threads, 2);
+        assert!(points[1].result.engine_name.ends_with("128x64-2t"));
+        assert!(points.iter().all(|point| point.result.cells_per_second > 0.0));
+        
+        let counts = BenchmarkSuite::default_thread_counts();
+        assert_eq!(counts[0], 1);
+        assert!(counts.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(UltimateEngine::<4>::with_threads(64, 64, 3).threads(), 3);
+    }
+    
+    #[test]
+    fn test_find_crossover_probes_both_ends() {
+        // The first engine wins below 37 cells a side and loses from there on
+        let axis = CrossoverAxis::GridSize { min: 8, max: 64, density: 0.5 };
+        let mut soups = Vec::new();
+        let crossover = crossover_by(axis, |soup| {
+            soups.push((soup.width(), soup.height()));
+            if soup.width() < 37 { 2.0 } else { 0.5 }
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(crossover.value, 37.0);
+        assert!(crossover.first_faster_below);
+        assert_eq!((crossover.probes[0].value, crossover.probes[1].value), (8.0, 64.0));
+        assert_eq!(soups.len(), crossover.probes.len());
+        assert_eq!(soups[..2], [(8, 8), (64, 64)]);
+        
+        // One engine faster across the whole range has no crossover
+        assert_eq!(crossover_by(axis, |_| 2.0).unwrap(), None);
+        
+        // Real timings go through the same search; only their outcome is noisy
+        let suite = BenchmarkSuite::builder().steps([2]).iterations(1).warmup(0).build().unwrap();
+        let naive = |width, height| Box::new(NaiveEngine::new(width, height)) as Box<dyn GameOfLifeEngine>;
+        let small = CrossoverAxis::GridSize { min: 8, max: 10, density: 0.5 };
+        assert!(suite.find_crossover(naive, naive, small).is_ok());
+        let reversed = CrossoverAxis::Density { min: 0.6, max: 0.2, width: 8, height: 8 };
+        assert!(suite.find_crossover(naive, naive, reversed).is_err());
+    }
+    
     #[test]
     fn test_engine_benchmark() {
         let suite = BenchmarkSuite::minimal();
         let mut engine = NaiveEngine::new(50, 50);
         
-        let results = suite.benchmark_engine(&mut engine);
+        let results = suite.benchmark_engine(&mut engine).unwrap();
         assert!(!results.is_empty());
         
         for result in results {
             assert!(result.duration.as_nanos() > 0);
             assert!(result.cells_per_second > 0.0);
+            assert_eq!(result.timing.iterations, 3);
+            assert!(result.timing.min <= result.timing.median && result.timing.median <= result.timing.max);
         }
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_engine_without_grid_benchmark() {
+        let suite = BenchmarkSuite::builder()
+            .grid_sizes([(70, 20)])
+            .steps([6])
+            .iterations(1)
+            .warmup(0)
+            .pattern_grid("glider", &patterns::glider().grid)
+            .verify(true)
+            .build()
+            .unwrap();
+        let mut engine = UltimateEngine::<4>::new(70, 20);
+        let results = suite.benchmark_engine(&mut engine).unwrap();
+        assert_eq!((results[0].total_cells, results[0].live_cells), (70 * 20, 5));
+        assert!(results[0].memory_bytes().is_some());
+    }
+    
+    #[test]
+    fn test_deterministic_benchmark() {
+        let suite = BenchmarkSuite::builder().grid_sizes([(64, 32)]).steps([4]).iterations(1).warmup(0).build().unwrap();
+        let results = suite
+            .benchmark_deterministic(|width, height| Box::new(UltimateEngine::<4>::sequential(width, height)))
+            .unwrap();
+        assert_eq!(results.len(), suite.patterns().len());
+        let environment = results[0].environment.as_ref().unwrap();
+        assert_eq!(environment.threads, 1);
+        assert_eq!(environment.pinned_core.is_some(), cfg!(feature = "affinity"));
+        assert_eq!(UltimateEngine::<4>::sequential(8, 8).threads(), 1);
+        
+        // The same seeded workloads end in the same states on every run
+        let again = suite.benchmark_deterministic(|width, height| Box::new(NaiveEngine::new(width, height))).unwrap();
+        let live: Vec<usize> = results.iter().map(|result| result.live_cells).collect();
+        assert_eq!(live, again.iter().map(|result| result.live_cells).collect::<Vec<_>>());
+    }
+    
+    #[test]
+    fn test_progress_and_cancellation() {
+        let cancel = CancellationToken::new();
+        let suite = BenchmarkSuite::builder()
+            .grid_sizes([(16, 16), (24, 24)])
+            .steps([2, 4])
+            .iterations(1)
+            .warmup(0)
+            .cancellation(cancel.clone())
+            .build()
+            .unwrap();
+        assert_eq!(suite.configuration_count(), 4 * suite.patterns().len());
+        
+        let mut reports = Vec::new();
+        let results = suite.benchmark_engine_with_progress(&mut NaiveEngine::new(16, 16), |progress| reports.push(progress)).unwrap();
+        assert_eq!(reports.len(), results.len());
+        assert_eq!(reports.last().unwrap().fraction(), 1.0);
+        assert_eq!(reports[0].configuration, results[0].engine_name);
+        assert!(reports.windows(2).all(|pair| pair[0].elapsed <= pair[1].elapsed));
+        
+        // Cancelling from the callback stops the run after the current configuration
+        let partial = suite
+            .benchmark_engine_with_progress(&mut NaiveEngine::new(16, 16), |progress| {
+                if progress.completed == 3 {
+                    cancel.cancel();
+                }
+            })
+            .unwrap();
+        assert_eq!(partial.len(), 3);
+        assert!(suite.benchmark_engine(&mut NaiveEngine::new(16, 16)).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_3d_engine_benchmark() {
+        let suite = BenchmarkSuite::minimal();
+        let mut engine = Life3DEngine::new(1, 1, 4, BaysRule::life_5766());
+        
+        let results = suite.benchmark_engine(&mut engine).unwrap();
+        assert_eq!(results[0].total_cells, 10 * 10 * 4);
+    }
+    
+    #[test]
+    fn test_verification_catches_divergence() {
+        let suite = BenchmarkSuite::builder()
+            .grid_sizes([(16, 16)])
+            .steps([4])
+            .iterations(1)
+            .warmup(0)
+            .pattern_grid("glider", &patterns::glider().grid)
+            .verify(true)
+            .build()
+            .unwrap();
+        assert!(suite.verifies() && !BenchmarkSuite::new().verifies());
+        assert_eq!(suite.benchmark_engine(&mut NaiveEngine::new(16, 16)).unwrap().len(), 1);
+        
+        let error = suite.benchmark_engine(&mut Faulty(NaiveEngine::new(16, 16))).unwrap_err();
+        assert!(matches!(&error, GameOfLifeError::Diverged { configuration, generations: 4 } if configuration == "Faulty-glider-16x16-4"), "{}", error);
+        
+        // Without a Life-like rule there is no reference to compare against
+        let mut life3d = Life3DEngine::new(1, 1, 4, BaysRule::life_5766());
+        assert!(matches!(suite.benchmark_engine(&mut life3d), Err(GameOfLifeError::Unsupported { .. })));
+    }
+}
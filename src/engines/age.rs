@@ -0,0 +1,253 @@
+//! Optional per-cell age layer on top of any engine
+
+use crate::engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver};
+use crate::error::GameOfLifeError;
+use crate::grid::{BitGrid, Boundary, Grid, StandardGrid, Topology, ValueGrid};
+use crate::rules::Rule;
+
+/// How long a cell has been in its current state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellAge {
+    /// Alive for this many generations since it was born (0 on the generation of its birth)
+    Alive(u32),
+    /// Dead for this many generations since it died (0 on the generation of its death)
+    Dead(u32),
+}
+
+impl CellAge {
+    /// Generations spent in the current state, alive or dead
+    pub fn generations(self) -> u32 {
+        match self {
+            CellAge::Alive(age) | CellAge::Dead(age) => age,
+        }
+    }
+    
+    /// Whether the cell is alive
+    pub fn is_alive(self) -> bool {
+        matches!(self, CellAge::Alive(_))
+    }
+}
+
+/// Wraps an engine and records how long every cell has been alive or dead
+///
+/// Ages are updated after each generation by comparing against the previous
+/// state, so multi-step runs advance the inner engine one step at a time.
+/// Ages start at 0 whenever the grid is set or a snapshot is restored.
+pub struct AgeTrackingEngine<E: GameOfLifeEngine> {
+    inner: E,
+    previous: BitGrid,
+    ages: ValueGrid<u32>,
+}
+
+impl<E: GameOfLifeEngine> AgeTrackingEngine<E> {
+    /// Start tracking ages of the engine's current state
+    pub fn new(inner: E) -> Self {
+        let mut engine = Self {
+            inner,
+            previous: BitGrid::new(0, 0),
+            ages: ValueGrid::new(0, 0),
+        };
+        engine.reset_ages();
+        engine
+    }
+    
+    /// The wrapped engine
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+    
+    /// Stop tracking and return the wrapped engine
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+    
+    /// Age of the cell at (row, col)
+    pub fn cell_age(&self, row: usize, col: usize) -> CellAge {
+        let age = self.ages.get(row, col);
+        if self.previous.get_cell(row, col) {
+            CellAge::Alive(age)
+        } else {
+            CellAge::Dead(age)
+        }
+    }
+    
+    /// Cells that have not changed state for at least `min_generations`
+    pub fn frozen_cells(&self, min_generations: u32) -> StandardGrid {
+        let mut frozen = StandardGrid::new(self.ages.width(), self.ages.height());
+        for (row, ages) in self.ages.rows().enumerate() {
+            for (col, &age) in ages.iter().enumerate() {
+                if age >= min_generations {
+                    frozen.set_cell(row, col, true);
+                }
+            }
+        }
+        frozen
+    }
+    
+    /// Forget all ages and treat the current state as freshly set
+    ///
+    /// The state is read through a snapshot, so engines such as
+    /// `UltimateEngine` that do not expose a grid can be wrapped too.
+    fn reset_ages(&mut self) {
+        self.previous = BitGrid::from_grid(&self.inner.snapshot().grid);
+        self.ages.reset(self.inner.width(), self.inner.height());
+    }
+    
+    /// Compare the new state against the previous one and age every cell
+    fn update_ages(&mut self) {
+        for row in 0..self.inner.height() {
+            for col in 0..self.inner.width() {
+                let alive = self.inner.get_cell(row, col);
+                let age = self.ages.get_mut(row, col);
+                if alive == self.previous.get_cell(row, col) {
+                    *age = age.saturating_add(1);
+                } else {
+                    *age = 0;
+                    self.previous.set_cell(row, col, alive);
+                }
+            }
+        }
+    }
+}
+
+impl<E: GameOfLifeEngine> GameOfLifeEngine for AgeTrackingEngine<E> {
+    fn step(&mut self) {
+        self.inner.step();
+        self.update_ages();
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        for completed in 0..steps {
+            if cancel.is_cancelled() {
+                return completed;
+            }
+            self.step();
+        }
+        steps
+    }
+    
+    /// The live cells as of the last age update, which always match the inner engine's
+    fn get_grid(&self) -> &dyn Grid {
+        &self.previous
+    }
+    
+    fn set_grid(&mut self, grid: &dyn Grid) {
+        self.inner.set_grid(grid);
+        self.reset_ages();
+    }
+    
+    fn benchmark_info(&self) -> EngineInfo {
+        let info = self.inner.benchmark_info();
+        EngineInfo {
+            name: format!("{} (aged)", info.name),
+            description: format!("{} with per-cell age tracking", info.description),
+            memory_per_cell_bits: info.memory_per_cell_bits + 33.0,
+            ..info
+        }
+    }
+    
+    fn rule(&self) -> Option<Rule> {
+        self.inner.rule()
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        self.inner.set_rule(rule)
+    }
+    
+    fn topology(&self) -> Topology {
+        self.inner.topology()
+    }
+    
+    fn set_topology(&mut self, topology: Topology) -> Result<(), GameOfLifeError> {
+        self.inner.set_topology(topology)
+    }
+    
+    fn boundary(&self) -> Boundary {
+        self.inner.boundary()
+    }
+    
+    fn set_boundary(&mut self, boundary: Boundary) -> Result<(), GameOfLifeError> {
+        self.inner.set_boundary(boundary)
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        self.inner.get_cell(row, col)
+    }
+    
+    fn cell_state(&self, row: usize, col: usize) -> u8 {
+        self.inner.cell_state(row, col)
+    }
+    
+    fn num_states(&self) -> u8 {
+        self.inner.num_states()
+    }
+    
+    fn generation(&self) -> usize {
+        self.inner.generation()
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.inner.on_generation(observer);
+    }
+    
+    fn snapshot(&self) -> EngineSnapshot {
+        self.inner.snapshot()
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        self.inner.restore(snapshot)?;
+        self.reset_ages();
+        Ok(())
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.inner.count_live_cells()
+    }
+    
+    fn state_hash(&self) -> u64 {
+        self.inner.state_hash()
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.inner.memory_usage() + self.previous.memory_bytes() + self.ages.memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{NaiveEngine, UltimateEngine};
+    
+    #[test]
+    fn test_blinker_ages() {
+        let grid = StandardGrid::from_string_pattern(&[".....", ".....", ".###.", ".....", "....."], '#', '.').unwrap();
+        let mut engine = AgeTrackingEngine::new(NaiveEngine::from_grid(&grid));
+        assert_eq!(engine.cell_age(2, 2), CellAge::Alive(0));
+        
+        engine.run_steps(3);
+        assert_eq!(engine.generation(), 3);
+        // The center never changes; the arms flip every generation
+        assert_eq!(engine.cell_age(2, 2), CellAge::Alive(3));
+        assert_eq!(engine.cell_age(1, 2), CellAge::Alive(0));
+        assert_eq!(engine.cell_age(2, 1), CellAge::Dead(0));
+        assert_eq!(engine.cell_age(0, 0), CellAge::Dead(3));
+        
+        let frozen = engine.frozen_cells(2);
+        assert_eq!(frozen.count_live_cells(), 25 - 4);
+        
+        engine.set_grid(&grid);
+        assert_eq!(engine.cell_age(0, 0), CellAge::Dead(0));
+    }
+    
+    #[test]
+    fn test_ages_on_ultimate_engine() {
+        let grid = StandardGrid::random(70, 20, 0.4, 3).unwrap();
+        let mut aged = AgeTrackingEngine::new(UltimateEngine::<4>::from_grid(&grid));
+        let mut reference = AgeTrackingEngine::new(NaiveEngine::from_grid(&grid));
+        aged.run_steps(4);
+        reference.run_steps(4);
+        assert_eq!(aged.frozen_cells(1), reference.frozen_cells(1));
+        assert_eq!(aged.get_grid().count_live_cells(), aged.count_live_cells());
+        assert_eq!(BitGrid::from_grid(aged.get_grid()), BitGrid::from_grid(&aged.snapshot().grid));
+    }
+}
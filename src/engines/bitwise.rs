@@ -0,0 +1,62 @@
+//! Word-level bit-sliced stepping of rows in the `BitGrid` layout
+
+/// Neighbors to the west, the cell itself and neighbors to the east for every bit of word `i`
+fn shifted(row: &[u64], i: usize) -> (u64, u64, u64) {
+    let center = row[i];
+    let previous = if i > 0 { row[i - 1] } else { 0 };
+    let next = row.get(i + 1).copied().unwrap_or(0);
+    ((center >> 1) | (previous << 63), center, (center << 1) | (next >> 63))
+}
+
+/// Compute one packed output row from the rows above, at and below it
+///
+/// Rows use the `BitGrid` layout; missing rows and cells past the edges are dead.
+/// `birth` and `survival` are Moore neighbor-count masks.
+pub(crate) fn step_packed_row(above: Option<&[u64]>, row: &[u64], below: Option<&[u64]>, out: &mut [u64], width: usize, birth: u32, survival: u32) {
+    for (i, out_word) in out.iter_mut().enumerate() {
+        let mut neighbors = [0u64; 8];
+        let (west, center, east) = shifted(row, i);
+        neighbors[0] = west;
+        neighbors[1] = east;
+        if let Some(above) = above {
+            let (w, c, e) = shifted(above, i);
+            neighbors[2..5].copy_from_slice(&[w, c, e]);
+        }
+        if let Some(below) = below {
+            let (w, c, e) = shifted(below, i);
+            neighbors[5..8].copy_from_slice(&[w, c, e]);
+        }
+        
+        // Bit-sliced ripple-carry count: planes[k] holds bit k of each cell's neighbor count
+        let mut planes = [0u64; 4];
+        for &neighbor in &neighbors {
+            let mut carry = neighbor;
+            for plane in planes.iter_mut() {
+                let sum = *plane ^ carry;
+                carry &= *plane;
+                *plane = sum;
+            }
+        }
+        
+        let (mut born, mut kept) = (0u64, 0u64);
+        for count in 0..=8u32 {
+            let matches = planes
+                .iter()
+                .enumerate()
+                .fold(!0u64, |acc, (bit, &plane)| acc & if count >> bit & 1 == 1 { plane } else { !plane });
+            if birth >> count & 1 == 1 {
+                born |= matches;
+            }
+            if survival >> count & 1 == 1 {
+                kept |= matches;
+            }
+        }
+        *out_word = (center & kept) | (!center & born);
+    }
+    
+    if !width.is_multiple_of(64) {
+        if let Some(last) = out.last_mut() {
+            *last &= !0u64 << (64 - width % 64);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::engines::{load_buffers, GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::{Grid, StandardGrid};
+use crate::rules::Rule;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Outer-totalistic rule given as a closure from (alive, live neighbors) to the next state
+pub type RuleCallback = Arc<dyn Fn(bool, u8) -> bool + Send + Sync>;
+
+/// Engine for prototyping rules as closures, using NaiveEngine's parallel row processing
+pub struct CallbackEngine {
+    grid: StandardGrid,
+    next_grid: StandardGrid,
+    rule: RuleCallback,
+    generation: usize,
+    observers: GenerationObservers,
+}
+
+impl CallbackEngine {
+    /// Create a new engine with the specified grid dimensions and rule closure
+    pub fn new(width: usize, height: usize, rule: impl Fn(bool, u8) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            grid: StandardGrid::new(width, height),
+            next_grid: StandardGrid::new(width, height),
+            rule: Arc::new(rule),
+            generation: 0,
+            observers: GenerationObservers::new(),
+        }
+    }
+    
+    /// Create a new engine from an existing grid
+    pub fn from_grid(grid: &dyn Grid, rule: impl Fn(bool, u8) -> bool + Send + Sync + 'static) -> Self {
+        let mut engine = Self::new(grid.width(), grid.height(), rule);
+        engine.set_grid(grid);
+        engine
+    }
+    
+    /// Replace the rule closure
+    pub fn set_callback(&mut self, rule: impl Fn(bool, u8) -> bool + Send + Sync + 'static) {
+        self.rule = Arc::new(rule);
+    }
+    
+    /// Update using a safer approach that collects results first
+    fn update_safe(&mut self) {
+        let width = self.grid.width();
+        let height = self.grid.height();
+        let rule = &self.rule;
+        
+        let new_cells: Vec<bool> = (0..height * width)
+            .into_par_iter()
+            .map(|idx| {
+                let row = idx / width;
+                let col = idx % width;
+                let neighbors = self.grid.count_neighbors(row, col);
+                let current_cell = self.grid.get_cell(row, col);
+                
+                rule(current_cell, neighbors)
+            })
+            .collect();
+        
+        self.next_grid.cells_mut().copy_from_slice(&new_cells);
+        std::mem::swap(&mut self.grid, &mut self.next_grid);
+        self.generation += 1;
+    }
+}
+
+impl Stepped for CallbackEngine {
+    fn next_generation(&mut self) {
+        self.update_safe();
+    }
+    
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
+impl GameOfLifeEngine for CallbackEngine {
+    fn step(&mut self) {
+        self.run_steps(1);
+    }
+    
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
+    }
+    
+    fn get_grid(&self) -> &dyn Grid {
+        &self.grid
+    }
+    
+    fn set_grid(&mut self, grid: &dyn Grid) {
+        load_buffers(&mut self.grid, &mut self.next_grid, grid);
+        self.generation = 0;
+    }
+    
+    fn benchmark_info(&self) -> EngineInfo {
+        EngineInfo {
+            name: "Callback".to_string(),
+            description: "User-supplied rule closure with parallel row processing".to_string(),
+            memory_per_cell_bits: 8.0,
+            supports_parallel: true,
+            supports_simd: false,
+            min_grid_size: Some((1, 1)),
+            max_grid_size: None,
+        }
+    }
+    
+    /// Tabulate the closure over all neighbor counts; any closure of (alive, count) is Life-like
+    fn rule(&self) -> Option<Rule> {
+        let mut birth = 0;
+        let mut survival = 0;
+        for count in 0..=8u8 {
+            birth |= ((self.rule)(false, count) as u32) << count;
+            survival |= ((self.rule)(true, count) as u32) << count;
+        }
+        Some(Rule::from_masks(birth, survival))
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        rule.require_moore("Callback engine")?;
+        self.set_callback(move |alive, neighbors| rule.next_state(alive, neighbors));
+        Ok(())
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        self.grid.get_cell(row, col)
+    }
+    
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.grid.width(), self.grid.height())?;
+        load_buffers(&mut self.grid, &mut self.next_grid, &snapshot.grid);
+        self.generation = snapshot.generation;
+        Ok(())
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.grid.memory_bytes() + self.next_grid.memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NaiveEngine;
+    
+    #[test]
+    fn test_closure_matches_naive() {
+        let pattern = [".#....", "..#...", "###...", "......", "....##", "....##"];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut naive = NaiveEngine::from_grid(&grid as &dyn Grid);
+        let mut callback = CallbackEngine::from_grid(&grid, |alive, n| n == 3 || (alive && n == 2));
+        
+        assert_eq!(callback.rule(), Some(Rule::conway()));
+        for _ in 0..8 {
+            naive.step();
+            callback.step();
+            assert_eq!(naive.snapshot(), callback.snapshot());
+        }
+    }
+    
+    #[test]
+    fn test_set_rule() {
+        let mut engine = CallbackEngine::new(4, 4, |_, _| false);
+        engine.set_rule("B36/S23".parse().unwrap()).unwrap();
+        assert_eq!(engine.rule().unwrap().to_string(), "B36/S23");
+        assert!(engine.set_rule("B2/S/V".parse().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+//! Cooperative cancellation for long-running simulations
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared flag that lets another thread stop a running simulation
+///
+/// Engines check the token between generations, so cancellation takes effect
+/// after at most one more step.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a new token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Request cancellation
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+    
+    /// Whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+    
+    /// Clear a previous cancellation so the token can be reused
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancellationToken {
+    fn from(cancelled: Arc<AtomicBool>) -> Self {
+        Self { cancelled }
+    }
+}
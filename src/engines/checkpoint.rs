@@ -0,0 +1,192 @@
+//! Rolling on-disk checkpoints for long-running simulations
+
+use super::GameOfLifeEngine;
+use crate::error::GameOfLifeError;
+use crate::formats::{state, FormatError};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const PREFIX: &str = "checkpoint_";
+const EXTENSION: &str = "golstate";
+
+/// Writes state snapshots every N generations and/or every M seconds
+///
+/// Each checkpoint is written to a temporary file and renamed into place, so a
+/// crash mid-write never leaves a truncated checkpoint behind. Only the newest
+/// `keep` checkpoints are retained, including ones left by a previous run in
+/// the same directory.
+#[derive(Debug)]
+pub struct Checkpointer {
+    dir: PathBuf,
+    every_generations: Option<usize>,
+    every_interval: Option<Duration>,
+    keep: usize,
+    last_generation: Option<usize>,
+    last_time: Instant,
+    written: VecDeque<PathBuf>,
+}
+
+impl Checkpointer {
+    /// Checkpoint into `dir`, creating it if needed; defaults to keeping 3 checkpoints and no schedule
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, FormatError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        let written = Self::existing(&dir)?.into();
+        Ok(Self {
+            dir,
+            every_generations: None,
+            every_interval: None,
+            keep: 3,
+            last_generation: None,
+            last_time: Instant::now(),
+            written,
+        })
+    }
+    
+    /// Checkpoint each time the generation count passes a multiple of `generations`
+    ///
+    /// Fails if `generations` is zero.
+    pub fn every_generations(mut self, generations: usize) -> Result<Self, GameOfLifeError> {
+        if generations == 0 {
+            return Err(GameOfLifeError::invalid("Checkpoint interval must be at least one generation"));
+        }
+        self.every_generations = Some(generations);
+        Ok(self)
+    }
+    
+    /// Checkpoint whenever at least `interval` has elapsed since the last one
+    pub fn every_interval(mut self, interval: Duration) -> Self {
+        self.every_interval = Some(interval);
+        self
+    }
+    
+    /// Retain only the newest `count` checkpoints (at least one)
+    pub fn keep(mut self, count: usize) -> Self {
+        self.keep = count.max(1);
+        self
+    }
+    
+    /// Paths of the retained checkpoints, oldest first
+    pub fn checkpoints(&self) -> impl Iterator<Item = &Path> {
+        self.written.iter().map(PathBuf::as_path)
+    }
+    
+    /// Most recent checkpoint, to resume from after a crash
+    pub fn latest(&self) -> Option<&Path> {
+        self.written.back().map(PathBuf::as_path)
+    }
+    
+    /// Whether the schedule calls for a checkpoint of `engine` now
+    pub fn is_due(&self, engine: &dyn GameOfLifeEngine) -> bool {
+        let last_generation = self.last_generation.unwrap_or(0);
+        let by_generation = self.every_generations.is_some_and(|every| engine.generation() / every > last_generation / every);
+        let by_time = self.every_interval.is_some_and(|every| self.last_time.elapsed() >= every);
+        by_generation || by_time
+    }
+    
+    /// Write a checkpoint if one is due, returning its path
+    pub fn maybe_checkpoint(&mut self, engine: &dyn GameOfLifeEngine) -> Result<Option<PathBuf>, FormatError> {
+        if self.is_due(engine) {
+            self.checkpoint(engine).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+    
+    /// Write a checkpoint now and apply the retention policy
+    pub fn checkpoint(&mut self, engine: &dyn GameOfLifeEngine) -> Result<PathBuf, FormatError> {
+        let path = self.dir.join(format!("{}{:012}.{}", PREFIX, engine.generation(), EXTENSION));
+        let temp = path.with_extension(format!("{}.tmp", EXTENSION));
+        {
+            let mut out = BufWriter::new(File::create(&temp)?);
+            state::write_state(&mut out, &engine.snapshot(), engine.rule())?;
+            out.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        }
+        std::fs::rename(&temp, &path)?;
+        
+        self.last_generation = Some(engine.generation());
+        self.last_time = Instant::now();
+        self.written.retain(|existing| existing != &path);
+        self.written.push_back(path.clone());
+        while self.written.len() > self.keep {
+            if let Some(oldest) = self.written.pop_front() {
+                std::fs::remove_file(oldest)?;
+            }
+        }
+        Ok(path)
+    }
+    
+    /// Step `engine` up to `steps` times, checkpointing whenever the schedule says so
+    pub fn run(&mut self, engine: &mut dyn GameOfLifeEngine, steps: usize) -> Result<(), FormatError> {
+        // A resumed engine should not be checkpointed again before its next boundary
+        self.last_generation.get_or_insert(engine.generation());
+        for _ in 0..steps {
+            engine.step();
+            self.maybe_checkpoint(engine)?;
+        }
+        Ok(())
+    }
+    
+    /// Checkpoints already in `dir`, oldest generation first
+    fn existing(dir: &Path) -> Result<Vec<PathBuf>, FormatError> {
+        let mut found = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_checkpoint = path.extension().is_some_and(|ext| ext == EXTENSION)
+                && path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with(PREFIX));
+            if is_checkpoint {
+                found.push(path);
+            }
+        }
+        // Zero-padded generations sort correctly by name
+        found.sort();
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NaiveEngine;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_rolling_checkpoints() {
+        let dir = std::env::temp_dir().join(format!("gol_checkpoints_{}", std::process::id()));
+        let mut engine = NaiveEngine::from_grid(&StandardGrid::random(32, 32, 0.35, 4).unwrap());
+        let mut checkpointer = Checkpointer::new(&dir).unwrap().every_generations(10).unwrap().keep(2);
+        
+        checkpointer.run(&mut engine, 35).unwrap();
+        let names: Vec<String> = checkpointer
+            .checkpoints()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["checkpoint_000000000020.golstate", "checkpoint_000000000030.golstate"]);
+        let on_disk = std::fs::read_dir(&dir).unwrap().count();
+        
+        // A new checkpointer adopts the existing files and resumes from the latest
+        let resumed = Checkpointer::new(&dir).unwrap();
+        let mut restored = NaiveEngine::new(1, 1);
+        restored.load_state(resumed.latest().unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        assert_eq!(on_disk, 2);
+        assert_eq!(restored.generation(), 30);
+    }
+    
+    #[test]
+    fn test_schedule() {
+        let dir = std::env::temp_dir().join(format!("gol_checkpoint_schedule_{}", std::process::id()));
+        let engine = NaiveEngine::new(4, 4);
+        let unscheduled = Checkpointer::new(&dir).unwrap();
+        let timed = Checkpointer::new(&dir).unwrap().every_interval(Duration::ZERO);
+        let due = (unscheduled.is_due(&engine), timed.is_due(&engine));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(due, (false, true));
+        assert!(Checkpointer::new(&dir).unwrap().every_generations(0).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,304 @@
+//! Elementary (one-dimensional) cellular automata with Wolfram rule numbers
+
+use crate::engines::ultimate::{shl, shr};
+use crate::engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::{BitGrid, Grid};
+use crate::rules::{ElementaryRule, Rule};
+use std::simd::{LaneCount, Simd, SupportedLaneCount};
+
+/// Bit-packed SIMD engine for elementary cellular automata
+///
+/// Each generation is a single row of cells. The engine keeps the last
+/// `height` generations as a scrolling image whose bottom row is the current
+/// generation, so it can be rendered like any two-dimensional engine. Cells
+/// beyond either end of the row are always dead.
+pub struct ElementaryAutomaton<const N: usize = 4>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    row: Vec<u64>,      // current generation with one padding word on each side
+    next_row: Vec<u64>,
+    columns: usize,     // includes padding and SIMD alignment
+    boundary_masks: Vec<u64>,
+    image: BitGrid,
+    rule: ElementaryRule,
+    generation: usize,
+    observers: GenerationObservers,
+}
+
+impl<const N: usize> ElementaryAutomaton<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Create an automaton `width` cells wide that keeps `height` generations of history
+    pub fn new(width: usize, height: usize, rule: ElementaryRule) -> Self {
+        let columns = width.div_ceil(64).div_ceil(N) * N + 2;
+        
+        let mut boundary_masks = vec![0u64; columns];
+        for (col, mask) in boundary_masks.iter_mut().enumerate().skip(1).take(columns - 2) {
+            let global_x = (col - 1) * 64;
+            if global_x + 64 <= width {
+                *mask = !0;
+            } else if global_x < width {
+                *mask = !0u64 << (64 - (width - global_x));
+            }
+        }
+        
+        Self {
+            row: vec![0; columns],
+            next_row: vec![0; columns],
+            columns,
+            boundary_masks,
+            image: BitGrid::new(width, height),
+            rule,
+            generation: 0,
+            observers: GenerationObservers::new(),
+        }
+    }
+    
+    /// Create an automaton whose history starts as `grid`; its bottom row is the current generation
+    pub fn from_grid(grid: &dyn Grid, rule: ElementaryRule) -> Self {
+        let mut engine = Self::new(grid.width(), grid.height(), rule);
+        engine.set_grid(grid);
+        engine
+    }
+    
+    /// Get the Wolfram rule
+    pub fn elementary_rule(&self) -> ElementaryRule {
+        self.rule
+    }
+    
+    /// Replace the Wolfram rule
+    pub fn set_elementary_rule(&mut self, rule: ElementaryRule) {
+        self.rule = rule;
+    }
+    
+    /// Clear the automaton and start from a single live cell in the middle of the row
+    pub fn seed_center(&mut self) {
+        let width = self.image.width();
+        let mut grid = BitGrid::new(width, self.image.height());
+        if width > 0 && grid.height() > 0 {
+            grid.set_cell(grid.height() - 1, width / 2, true);
+        }
+        self.set_grid(&grid);
+    }
+    
+    /// Apply the rule to a SIMD chunk given each cell's left and right neighbors
+    #[inline(always)]
+    fn apply_rule(left: Simd<u64, N>, center: Simd<u64, N>, right: Simd<u64, N>, rule: ElementaryRule) -> Simd<u64, N> {
+        let mut result = Simd::splat(0);
+        for pattern in 0..8u8 {
+            if rule.number() & (1 << pattern) == 0 {
+                continue;
+            }
+            let l = if pattern & 4 != 0 { left } else { !left };
+            let c = if pattern & 2 != 0 { center } else { !center };
+            let r = if pattern & 1 != 0 { right } else { !right };
+            result |= l & c & r;
+        }
+        result
+    }
+    
+    /// Compute the next row and scroll it into the image
+    fn update(&mut self) {
+        let row = &self.row;
+        let rule = self.rule;
+        
+        for x in (1..self.columns - 1).step_by(N) {
+            let center = Simd::<u64, N>::from_slice(&row[x..x + N]);
+            
+            // Same cross-word fix-ups as the 2D engine's west and east neighbors
+            let mut left = shr(center);
+            let mut right = shl(center);
+            left[0] |= (row[x - 1] & 1) << 63;
+            right[N - 1] |= (row[x + N] & (1 << 63)) >> 63;
+            
+            let mut result = Self::apply_rule(left, center, right, rule);
+            for lane in 0..N {
+                result[lane] &= self.boundary_masks[x + lane];
+            }
+            self.next_row[x..x + N].copy_from_slice(result.as_array());
+        }
+        
+        std::mem::swap(&mut self.row, &mut self.next_row);
+        self.scroll_in_current_row();
+        self.generation += 1;
+    }
+    
+    /// Shift the image up one row and write the current generation at the bottom
+    fn scroll_in_current_row(&mut self) {
+        let height = self.image.height();
+        if height == 0 {
+            return;
+        }
+        let words_per_row = self.image.words_per_row();
+        self.image.words_mut().rotate_left(words_per_row);
+        self.image
+            .row_words_mut(height - 1)
+            .copy_from_slice(&self.row[1..1 + words_per_row]);
+    }
+    
+    /// Load the current generation from the bottom row of the image
+    fn load_current_row(&mut self) {
+        self.row.fill(0);
+        let height = self.image.height();
+        if height > 0 {
+            let words = self.image.row_words(height - 1);
+            self.row[1..1 + words.len()].copy_from_slice(words);
+        }
+    }
+}
+
+impl<const N: usize> Stepped for ElementaryAutomaton<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn next_generation(&mut self) {
+        self.update();
+    }
+    
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
+impl<const N: usize> GameOfLifeEngine for ElementaryAutomaton<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn step(&mut self) {
+        self.run_steps(1);
+    }
+    
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
+    }
+    
+    fn get_grid(&self) -> &dyn Grid {
+        &self.image
+    }
+    
+    fn set_grid(&mut self, grid: &dyn Grid) {
+        if self.image.width() != grid.width() || self.image.height() != grid.height() {
+            *self = Self::new(grid.width(), grid.height(), self.rule);
+        }
+        self.image = BitGrid::from_grid(grid);
+        self.load_current_row();
+        self.generation = 0;
+    }
+    
+    fn benchmark_info(&self) -> EngineInfo {
+        EngineInfo {
+            name: "Elementary".to_string(),
+            description: format!("1D Wolfram rule {} on bit-packed rows, SIMD ({}x u64)", self.rule, N),
+            memory_per_cell_bits: 1.0,
+            supports_parallel: false,
+            supports_simd: true,
+            min_grid_size: Some((1, 1)),
+            max_grid_size: None,
+        }
+    }
+    
+    fn rule(&self) -> Option<Rule> {
+        None
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        Err(GameOfLifeError::unsupported("Elementary engine", format!("Life-like rule {}; it runs Wolfram rules", rule)))
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        self.image.get_cell(row, col)
+    }
+    
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    fn snapshot(&self) -> EngineSnapshot {
+        EngineSnapshot {
+            generation: self.generation,
+            grid: self.image.clone(),
+            cell_states: None,
+        }
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.image.width(), self.image.height())?;
+        self.image = snapshot.grid.clone();
+        self.load_current_row();
+        self.generation = snapshot.generation;
+        Ok(())
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.image.count_live_cells()
+    }
+    
+    fn memory_usage(&self) -> usize {
+        (self.row.capacity() + self.next_row.capacity() + self.boundary_masks.capacity()) * size_of::<u64>()
+            + self.image.memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Straightforward scalar reference for one generation
+    fn reference_step(cells: &[bool], rule: ElementaryRule) -> Vec<bool> {
+        (0..cells.len())
+            .map(|i| {
+                let left = i > 0 && cells[i - 1];
+                let right = i + 1 < cells.len() && cells[i + 1];
+                rule.next_state(left, cells[i], right)
+            })
+            .collect()
+    }
+    
+    #[test]
+    fn test_rule_90_triangle() {
+        let mut engine = ElementaryAutomaton::<4>::new(9, 4, ElementaryRule::new(90));
+        engine.seed_center();
+        engine.run_steps(3);
+        
+        let rows: Vec<String> = (0..4)
+            .map(|row| (0..9).map(|col| if engine.get_cell(row, col) { '#' } else { '.' }).collect())
+            .collect();
+        assert_eq!(rows, ["....#....", "...#.#...", "..#...#..", ".#.#.#.#."]);
+        assert_eq!(engine.generation(), 3);
+    }
+    
+    #[test]
+    fn test_matches_reference_across_words() {
+        // 150 cells span three words, exercising the cross-word and boundary fix-ups
+        let width = 150;
+        for number in [30u8, 45, 73, 110, 255] {
+            let rule = ElementaryRule::new(number);
+            let mut engine = ElementaryAutomaton::<2>::new(width, 1, rule);
+            let mut cells: Vec<bool> = (0..width).map(|i| (i * 7 + i / 3) % 5 == 0).collect();
+            
+            let mut grid = BitGrid::new(width, 1);
+            for (col, &alive) in cells.iter().enumerate() {
+                grid.set_cell(0, col, alive);
+            }
+            engine.set_grid(&grid);
+            
+            for _ in 0..20 {
+                engine.step();
+                cells = reference_step(&cells, rule);
+                let row: Vec<bool> = (0..width).map(|col| engine.get_cell(0, col)).collect();
+                assert_eq!(row, cells, "rule {}", number);
+            }
+        }
+    }
+}
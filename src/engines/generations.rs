@@ -0,0 +1,284 @@
+use crate::engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::hash::StateHasher;
+use crate::grid::{BitGrid, Grid, StateGrid, ValueGrid};
+use crate::rules::{GenerationsRule, Rule};
+use rayon::prelude::*;
+
+/// Multi-state engine for Generations rules such as Brian's Brain and Star Wars
+///
+/// Cells are stored one byte each; only state 1 counts as a live neighbor.
+pub struct GenerationsEngine {
+    cells: ValueGrid<u8>,
+    next_cells: ValueGrid<u8>,
+    rule: GenerationsRule,
+    generation: usize,
+    observers: GenerationObservers,
+}
+
+impl GenerationsEngine {
+    /// Create a new engine with the specified grid dimensions and rule
+    pub fn new(width: usize, height: usize, rule: GenerationsRule) -> Self {
+        Self {
+            cells: ValueGrid::new(width, height),
+            next_cells: ValueGrid::new(width, height),
+            rule,
+            generation: 0,
+            observers: GenerationObservers::new(),
+        }
+    }
+    
+    /// Create a new engine from an existing grid; live cells start in state 1
+    pub fn from_grid(grid: &dyn Grid, rule: GenerationsRule) -> Self {
+        let mut engine = Self::new(grid.width(), grid.height(), rule);
+        engine.set_grid(grid);
+        engine
+    }
+    
+    /// Get the Generations rule
+    pub fn generations_rule(&self) -> GenerationsRule {
+        self.rule
+    }
+    
+    /// Replace the Generations rule
+    pub fn set_generations_rule(&mut self, rule: GenerationsRule) {
+        self.rule = rule;
+    }
+    
+    /// Set the state of a cell directly
+    pub fn set_cell_state(&mut self, row: usize, col: usize, state: u8) {
+        assert!(state < self.rule.states(), "State {} out of range for {}", state, self.rule);
+        self.cells.set(row, col, state);
+    }
+    
+    /// Get the per-cell states
+    pub fn states(&self) -> &ValueGrid<u8> {
+        &self.cells
+    }
+    
+    /// Compute one generation in parallel, one row per task
+    fn update(&mut self) {
+        let width = self.cells.width();
+        let rule = self.rule;
+        let offsets = rule.life_rule().neighborhood().offsets();
+        let cells = &self.cells;
+        
+        self.next_cells
+            .cells_mut()
+            .par_chunks_mut(width.max(1))
+            .enumerate()
+            .for_each(|(row, next_row)| {
+                for (col, next) in next_row.iter_mut().enumerate() {
+                    let live = cells.count_neighbors_matching(row, col, offsets, &|state| state == 1);
+                    *next = rule.next_state(cells.get(row, col), live);
+                }
+            });
+        
+        std::mem::swap(&mut self.cells, &mut self.next_cells);
+        self.generation += 1;
+    }
+}
+
+impl Stepped for GenerationsEngine {
+    fn next_generation(&mut self) {
+        self.update();
+    }
+    
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
+impl GameOfLifeEngine for GenerationsEngine {
+    fn step(&mut self) {
+        self.run_steps(1);
+    }
+    
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
+    }
+    
+    fn get_grid(&self) -> &dyn Grid {
+        panic!("GenerationsEngine doesn't support direct grid access - use get_cell or cell_state instead")
+    }
+    
+    fn set_grid(&mut self, grid: &dyn Grid) {
+        if self.cells.width() != grid.width() || self.cells.height() != grid.height() {
+            self.next_cells.reset(grid.width(), grid.height());
+        }
+        self.cells = ValueGrid::from_fn(grid.width(), grid.height(), |row, col| grid.get_cell(row, col) as u8);
+        self.generation = 0;
+    }
+    
+    fn benchmark_info(&self) -> EngineInfo {
+        EngineInfo {
+            name: "Generations".to_string(),
+            description: format!("Multi-state Generations rule {} with parallel row processing", self.rule),
+            memory_per_cell_bits: 8.0,
+            supports_parallel: true,
+            supports_simd: false,
+            min_grid_size: Some((1, 1)),
+            max_grid_size: None,
+        }
+    }
+    
+    fn rule(&self) -> Option<Rule> {
+        (self.rule.states() == 2).then(|| self.rule.life_rule())
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        self.rule = GenerationsRule::new(rule, self.rule.states())?;
+        Ok(())
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        self.cell_state(row, col) == 1
+    }
+    
+    fn cell_state(&self, row: usize, col: usize) -> u8 {
+        self.cells.get(row, col)
+    }
+    
+    fn num_states(&self) -> u8 {
+        self.rule.states()
+    }
+    
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    fn snapshot(&self) -> EngineSnapshot {
+        let mut grid = BitGrid::new(self.cells.width(), self.cells.height());
+        for (row, states) in self.cells.rows().enumerate() {
+            for (col, &state) in states.iter().enumerate() {
+                if state == 1 {
+                    grid.set_cell(row, col, true);
+                }
+            }
+        }
+        EngineSnapshot {
+            generation: self.generation,
+            grid,
+            cell_states: Some(self.cells.cells().to_vec()),
+        }
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.cells.width(), self.cells.height())?;
+        
+        match &snapshot.cell_states {
+            Some(states) => {
+                if let Some(&bad) = states.iter().find(|&&state| state >= self.rule.states()) {
+                    return Err(GameOfLifeError::invalid(format!("Snapshot state {} out of range for {}", bad, self.rule)));
+                }
+                self.cells.cells_mut().copy_from_slice(states);
+            }
+            None => {
+                let grid = &snapshot.grid;
+                self.cells = ValueGrid::from_fn(grid.width(), grid.height(), |row, col| grid.get_cell(row, col) as u8);
+            }
+        }
+        self.generation = snapshot.generation;
+        Ok(())
+    }
+    
+    fn width(&self) -> usize {
+        self.cells.width()
+    }
+    
+    fn height(&self) -> usize {
+        self.cells.height()
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.cells.count_matching(&|state| state == 1)
+    }
+    
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new(self.cells.width(), self.cells.height());
+        for row in self.cells.rows() {
+            hasher.write_cells(row.iter().map(|&state| state == 1));
+        }
+        // Decay states matter for cycle detection, but leave two-state hashes
+        // identical to other engines
+        if self.cells.cells().iter().any(|&state| state > 1) {
+            for chunk in self.cells.cells().chunks(8) {
+                let mut word = [0u8; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                hasher.write_word(u64::from_le_bytes(word));
+            }
+        }
+        hasher.finish()
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.cells.memory_bytes() + self.next_cells.memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_brians_brain_decay() {
+        let pattern = [
+            "....",
+            ".##.",
+            "....",
+            "....",
+        ];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut engine = GenerationsEngine::from_grid(&grid, GenerationsRule::brians_brain());
+        
+        engine.step();
+        // Live cells never survive in Brian's Brain; they start dying
+        assert_eq!(engine.cell_state(1, 1), 2);
+        assert_eq!(engine.cell_state(1, 2), 2);
+        // Cells touching both live cells are born
+        assert_eq!(engine.cell_state(0, 1), 1);
+        assert_eq!(engine.cell_state(2, 2), 1);
+        assert_eq!(engine.count_live_cells(), 4);
+        
+        engine.step();
+        assert_eq!(engine.cell_state(1, 1), 0);
+    }
+    
+    #[test]
+    fn test_two_state_matches_naive() {
+        use crate::engines::NaiveEngine;
+        
+        let pattern = [".....", ".###.", ".....", ".###.", "....."];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut naive = NaiveEngine::from_grid(&grid as &dyn Grid);
+        let mut generations = GenerationsEngine::from_grid(&grid, Rule::conway().into());
+        
+        for _ in 0..4 {
+            naive.step();
+            generations.step();
+            assert_eq!(naive.state_hash(), generations.state_hash());
+        }
+    }
+    
+    #[test]
+    fn test_snapshot_keeps_decay_states() {
+        let mut engine = GenerationsEngine::new(5, 5, GenerationsRule::star_wars());
+        engine.set_cell_state(2, 2, 1);
+        engine.set_cell_state(2, 3, 3);
+        let snapshot = engine.snapshot();
+        
+        engine.run_steps(3);
+        engine.restore(&snapshot).unwrap();
+        assert_eq!(engine.cell_state(2, 3), 3);
+        assert_eq!(engine.generation(), 0);
+    }
+}
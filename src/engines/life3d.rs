@@ -0,0 +1,258 @@
+//! Experimental three-dimensional Life engine
+
+use crate::engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::{BitGrid, Grid, Grid3, StandardGrid3};
+use crate::rules::{BaysRule, Rule};
+use rayon::prelude::*;
+
+/// Naive 3D Life engine counting all 26 neighbors of each cell
+///
+/// Through `GameOfLifeEngine` the volume is seen as its layers stacked
+/// vertically, so a `width x height x depth` engine reports a height of
+/// `height * depth`. `set_grid` with a grid of any other height resizes each
+/// layer to that grid and places it in the middle layer, which lets 2D
+/// patterns and the benchmark suite seed 3D runs.
+pub struct Life3DEngine {
+    grid: StandardGrid3,
+    next_grid: StandardGrid3,
+    rule: BaysRule,
+    generation: usize,
+    observers: GenerationObservers,
+}
+
+impl Life3DEngine {
+    /// Create a new engine with the specified volume dimensions and rule
+    pub fn new(width: usize, height: usize, depth: usize, rule: BaysRule) -> Self {
+        Self {
+            grid: StandardGrid3::new(width, height, depth),
+            next_grid: StandardGrid3::new(width, height, depth),
+            rule,
+            generation: 0,
+            observers: GenerationObservers::new(),
+        }
+    }
+    
+    /// Create a new engine from an existing volume
+    pub fn from_volume(volume: &dyn Grid3, rule: BaysRule) -> Self {
+        let mut engine = Self::new(volume.width(), volume.height(), volume.depth(), rule);
+        engine.set_volume(volume);
+        engine
+    }
+    
+    /// Get the 3D rule
+    pub fn bays_rule(&self) -> BaysRule {
+        self.rule
+    }
+    
+    /// Replace the 3D rule
+    pub fn set_bays_rule(&mut self, rule: BaysRule) {
+        self.rule = rule;
+    }
+    
+    /// Number of layers in the volume
+    pub fn depth(&self) -> usize {
+        Grid3::depth(&self.grid)
+    }
+    
+    /// Height of each layer
+    pub fn layer_height(&self) -> usize {
+        Grid3::height(&self.grid)
+    }
+    
+    /// Get the volume
+    pub fn volume(&self) -> &StandardGrid3 {
+        &self.grid
+    }
+    
+    /// Replace the volume, resizing if needed, and reset the generation counter
+    pub fn set_volume(&mut self, volume: &dyn Grid3) {
+        self.grid = StandardGrid3::new(volume.width(), volume.height(), volume.depth());
+        self.next_grid = StandardGrid3::new(volume.width(), volume.height(), volume.depth());
+        for layer in 0..volume.depth() {
+            for row in 0..volume.height() {
+                for col in 0..volume.width() {
+                    Grid3::set_cell(&mut self.grid, layer, row, col, volume.get_cell(layer, row, col));
+                }
+            }
+        }
+        self.generation = 0;
+    }
+    
+    /// Get the state of a cell in the volume
+    pub fn get_cell3(&self, layer: usize, row: usize, col: usize) -> bool {
+        Grid3::get_cell(&self.grid, layer, row, col)
+    }
+    
+    /// Compute one generation in parallel, one layer row per task
+    fn update(&mut self) {
+        let width = Grid3::width(&self.grid);
+        let height = Grid3::height(&self.grid);
+        let rule = self.rule;
+        let grid = &self.grid;
+        
+        self.next_grid
+            .cells_mut()
+            .par_chunks_mut(width.max(1))
+            .enumerate()
+            .for_each(|(index, next_row)| {
+                let layer = index / height;
+                let row = index % height;
+                for (col, next) in next_row.iter_mut().enumerate() {
+                    let neighbors = Grid3::count_neighbors(grid, layer, row, col);
+                    *next = rule.next_state(Grid3::get_cell(grid, layer, row, col), neighbors);
+                }
+            });
+        
+        std::mem::swap(&mut self.grid, &mut self.next_grid);
+        self.generation += 1;
+    }
+}
+
+impl Stepped for Life3DEngine {
+    fn next_generation(&mut self) {
+        self.update();
+    }
+    
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
+impl GameOfLifeEngine for Life3DEngine {
+    fn step(&mut self) {
+        self.run_steps(1);
+    }
+    
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
+    }
+    
+    fn get_grid(&self) -> &dyn Grid {
+        &self.grid
+    }
+    
+    fn set_grid(&mut self, grid: &dyn Grid) {
+        let depth = self.depth();
+        let stacked = grid.width() == Grid::width(&self.grid) && grid.height() == Grid::height(&self.grid);
+        if !stacked {
+            self.grid = StandardGrid3::new(grid.width(), grid.height(), depth);
+            self.next_grid = StandardGrid3::new(grid.width(), grid.height(), depth);
+        }
+        Grid3::clear(&mut self.grid);
+        
+        // Stacked layers copy straight across; anything else becomes the middle layer
+        let offset = if stacked { 0 } else { depth / 2 * grid.height() };
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                Grid::set_cell(&mut self.grid, offset + row, col, grid.get_cell(row, col));
+            }
+        }
+        self.generation = 0;
+    }
+    
+    fn benchmark_info(&self) -> EngineInfo {
+        EngineInfo {
+            name: "Life3D".to_string(),
+            description: format!("Naive 3D Life ({}) with 26-neighbor counting and parallel rows", self.rule),
+            memory_per_cell_bits: 8.0,
+            supports_parallel: true,
+            supports_simd: false,
+            min_grid_size: Some((1, 1)),
+            max_grid_size: None,
+        }
+    }
+    
+    fn rule(&self) -> Option<Rule> {
+        None
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        Err(GameOfLifeError::unsupported("Life3D engine", format!("2D rule {}; it runs 3D rules", rule)))
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        Grid::get_cell(&self.grid, row, col)
+    }
+    
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    fn snapshot(&self) -> EngineSnapshot {
+        EngineSnapshot {
+            generation: self.generation,
+            grid: BitGrid::from_grid(&self.grid),
+            cell_states: None,
+        }
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(Grid::width(&self.grid), Grid::height(&self.grid))?;
+        
+        for row in 0..snapshot.height() {
+            for col in 0..snapshot.width() {
+                Grid::set_cell(&mut self.grid, row, col, snapshot.grid.get_cell(row, col));
+            }
+        }
+        self.generation = snapshot.generation;
+        Ok(())
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        Grid::count_live_cells(&self.grid)
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.grid.memory_bytes() + self.next_grid.memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_5766_block_is_still() {
+        // A 2x2x2 cube: every live cell has 7 live neighbors, every dead cell at most 4
+        let mut volume = StandardGrid3::new(4, 4, 4);
+        for layer in 1..3 {
+            for row in 1..3 {
+                for col in 1..3 {
+                    Grid3::set_cell(&mut volume, layer, row, col, true);
+                }
+            }
+        }
+        let mut engine = Life3DEngine::from_volume(&volume, BaysRule::life_5766());
+        engine.run_steps(3);
+        assert_eq!(engine.volume(), &volume);
+        assert_eq!(engine.count_live_cells(), 8);
+    }
+    
+    #[test]
+    fn test_set_grid_seeds_middle_layer() {
+        let pattern = ["##", "##"];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut engine = Life3DEngine::new(1, 1, 3, BaysRule::life_4555());
+        engine.set_grid(&grid);
+        
+        assert_eq!(engine.layer_height(), 2);
+        assert_eq!(engine.height(), 6);
+        assert!(engine.get_cell3(1, 0, 0));
+        assert!(!engine.get_cell3(0, 0, 0));
+        assert_eq!(engine.count_live_cells(), 4);
+        
+        // 4555: each square cell has 3 neighbors and dies; cells above and below have 4
+        engine.step();
+        assert_eq!(engine.count_live_cells(), 0);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::engines::{load_buffers, GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::{Grid, StandardGrid};
+use crate::rules::{MapRule, Rule};
+use rayon::prelude::*;
+
+/// Scalar engine for arbitrary 3x3 rules (MAP strings and isotropic non-totalistic rules)
+///
+/// Each cell's 3x3 neighborhood is packed into a 9-bit index into the rule's
+/// 512-entry lookup table.
+pub struct LookupEngine {
+    grid: StandardGrid,
+    next_grid: StandardGrid,
+    rule: MapRule,
+    generation: usize,
+    observers: GenerationObservers,
+}
+
+impl LookupEngine {
+    /// Create a new lookup engine with the specified grid dimensions and rule
+    pub fn new(width: usize, height: usize, rule: MapRule) -> Self {
+        Self {
+            grid: StandardGrid::new(width, height),
+            next_grid: StandardGrid::new(width, height),
+            rule,
+            generation: 0,
+            observers: GenerationObservers::new(),
+        }
+    }
+    
+    /// Create a new lookup engine from an existing grid
+    pub fn from_grid(grid: &dyn Grid, rule: MapRule) -> Self {
+        let mut engine = Self::new(grid.width(), grid.height(), rule);
+        engine.set_grid(grid);
+        engine
+    }
+    
+    /// Get the lookup table rule
+    pub fn map_rule(&self) -> MapRule {
+        self.rule
+    }
+    
+    /// Replace the lookup table rule
+    pub fn set_map_rule(&mut self, rule: MapRule) {
+        self.rule = rule;
+    }
+    
+    /// Compute one generation in parallel, one row per task
+    fn update(&mut self) {
+        let width = self.grid.width();
+        let height = self.grid.height();
+        let rule = self.rule;
+        let cells = self.grid.cells();
+        
+        let alive = |row: isize, col: isize| -> u16 {
+            if row < 0 || col < 0 || row >= height as isize || col >= width as isize {
+                0
+            } else {
+                cells[row as usize * width + col as usize] as u16
+            }
+        };
+        
+        self.next_grid
+            .cells_mut()
+            .par_chunks_mut(width.max(1))
+            .enumerate()
+            .for_each(|(row, next_row)| {
+                let r = row as isize;
+                for (col, next) in next_row.iter_mut().enumerate() {
+                    let c = col as isize;
+                    let mut index = 0;
+                    for dr in -1..=1 {
+                        for dc in -1..=1 {
+                            index = (index << 1) | alive(r + dr, c + dc);
+                        }
+                    }
+                    *next = rule.next_state(index);
+                }
+            });
+        
+        std::mem::swap(&mut self.grid, &mut self.next_grid);
+        self.generation += 1;
+    }
+}
+
+impl Stepped for LookupEngine {
+    fn next_generation(&mut self) {
+        self.update();
+    }
+    
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
+impl GameOfLifeEngine for LookupEngine {
+    fn step(&mut self) {
+        self.run_steps(1);
+    }
+    
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
+    }
+    
+    fn get_grid(&self) -> &dyn Grid {
+        &self.grid
+    }
+    
+    fn set_grid(&mut self, grid: &dyn Grid) {
+        load_buffers(&mut self.grid, &mut self.next_grid, grid);
+        self.generation = 0;
+    }
+    
+    fn benchmark_info(&self) -> EngineInfo {
+        EngineInfo {
+            name: "Lookup".to_string(),
+            description: "512-entry neighborhood lookup for MAP and isotropic non-totalistic rules".to_string(),
+            memory_per_cell_bits: 8.0,
+            supports_parallel: true,
+            supports_simd: false,
+            min_grid_size: Some((1, 1)),
+            max_grid_size: None,
+        }
+    }
+    
+    fn rule(&self) -> Option<Rule> {
+        self.rule.as_life_rule()
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        self.rule = MapRule::try_from(rule)?;
+        Ok(())
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        self.grid.get_cell(row, col)
+    }
+    
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.grid.width(), self.grid.height())?;
+        load_buffers(&mut self.grid, &mut self.next_grid, &snapshot.grid);
+        self.generation = snapshot.generation;
+        Ok(())
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.grid.memory_bytes() + self.next_grid.memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NaiveEngine;
+    
+    #[test]
+    fn test_conway_table_matches_naive() {
+        let pattern = [".#....", "..#...", "###...", "......", "....##", "....##"];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut naive = NaiveEngine::from_grid(&grid as &dyn Grid);
+        let mut lookup = LookupEngine::from_grid(&grid, MapRule::try_from(Rule::conway()).unwrap());
+        
+        for _ in 0..10 {
+            naive.step();
+            lookup.step();
+            assert_eq!(naive.snapshot(), lookup.snapshot());
+        }
+    }
+    
+    #[test]
+    fn test_non_totalistic_step() {
+        // Under B2-a/S the pair N+S of the center (2i) gives birth, adjacent pairs (2a) do not
+        let pattern = [
+            ".#.",
+            "...",
+            ".#.",
+        ];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut engine = LookupEngine::from_grid(&grid, "B2-a/S".parse().unwrap());
+        engine.step();
+        assert!(engine.get_cell(1, 1));
+        assert!(!engine.get_cell(0, 1));
+        assert_eq!(engine.rule(), None);
+    }
+    
+    #[test]
+    fn test_von_neumann_table_matches_naive() {
+        let pattern = ["#.#...", ".##...", "..#.#.", "....##", "#.....", "##...#"];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let rule: Rule = "B1/S012/V".parse().unwrap();
+        let mut naive = NaiveEngine::from_grid(&grid as &dyn Grid);
+        naive.set_rule(rule).unwrap();
+        let mut lookup = LookupEngine::from_grid(&grid, MapRule::try_from(rule).unwrap());
+        
+        for _ in 0..6 {
+            naive.step();
+            lookup.step();
+            assert_eq!(naive.snapshot(), lookup.snapshot());
+        }
+        assert!(lookup.set_rule("B3/S23/M2".parse().unwrap()).is_err());
+    }
+}
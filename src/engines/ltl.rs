@@ -0,0 +1,340 @@
+use crate::engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::hash::StateHasher;
+use crate::grid::{BitGrid, Grid};
+use crate::rules::{LtlNeighborhood, LtlRule, Rule};
+use rayon::prelude::*;
+
+/// Larger than Life engine for neighborhoods of radius R
+///
+/// Neighborhood counts come from prefix sums: each row is prefix-summed once,
+/// then Moore boxes are summed with a vertical prefix pass, so the cost per
+/// cell is independent of the radius. Von Neumann diamonds use the row
+/// prefix sums directly at O(R) per cell.
+pub struct LtlEngine {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+    next_cells: Vec<u8>,
+    row_prefix: Vec<u32>,
+    column_prefix: Vec<u32>,
+    rule: LtlRule,
+    generation: usize,
+    observers: GenerationObservers,
+}
+
+impl LtlEngine {
+    /// Create a new engine with the specified grid dimensions and rule
+    pub fn new(width: usize, height: usize, rule: LtlRule) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height],
+            next_cells: vec![0; width * height],
+            row_prefix: vec![0; (width + 1) * height],
+            column_prefix: vec![0; width * (height + 1)],
+            rule,
+            generation: 0,
+            observers: GenerationObservers::new(),
+        }
+    }
+    
+    /// Create a new engine from an existing grid; live cells start in state 1
+    pub fn from_grid(grid: &dyn Grid, rule: LtlRule) -> Self {
+        let mut engine = Self::new(grid.width(), grid.height(), rule);
+        engine.set_grid(grid);
+        engine
+    }
+    
+    /// Get the Larger than Life rule
+    pub fn ltl_rule(&self) -> &LtlRule {
+        &self.rule
+    }
+    
+    /// Replace the Larger than Life rule
+    pub fn set_ltl_rule(&mut self, rule: LtlRule) {
+        self.rule = rule;
+    }
+    
+    /// Compute one generation
+    fn update(&mut self) {
+        let width = self.width;
+        let height = self.height;
+        let range = self.rule.range;
+        let rule = &self.rule;
+        let cells = &self.cells;
+        
+        // Prefix sums of live cells along each row
+        self.row_prefix
+            .par_chunks_mut(width + 1)
+            .zip(cells.par_chunks(width.max(1)))
+            .for_each(|(prefix, row)| {
+                prefix[0] = 0;
+                for (x, &state) in row.iter().enumerate() {
+                    prefix[x + 1] = prefix[x] + (state == 1) as u32;
+                }
+            });
+        
+        let row_prefix = &self.row_prefix;
+        let row_sum = |y: usize, from: usize, to: usize| -> u32 {
+            // Live cells in row y over columns from..to (exclusive)
+            row_prefix[y * (width + 1) + to] - row_prefix[y * (width + 1) + from]
+        };
+        
+        if rule.neighborhood == LtlNeighborhood::Moore {
+            // Horizontal box sums, accumulated down each column
+            for y in 0..height {
+                for x in 0..width {
+                    let boxed = row_sum(y, x.saturating_sub(range), (x + range + 1).min(width));
+                    self.column_prefix[(y + 1) * width + x] = self.column_prefix[y * width + x] + boxed;
+                }
+            }
+        }
+        
+        let column_prefix = &self.column_prefix;
+        self.next_cells
+            .par_chunks_mut(width.max(1))
+            .enumerate()
+            .for_each(|(y, next_row)| {
+                for (x, next) in next_row.iter_mut().enumerate() {
+                    let mut count = match rule.neighborhood {
+                        LtlNeighborhood::Moore => {
+                            let top = y.saturating_sub(range);
+                            let bottom = (y + range + 1).min(height);
+                            column_prefix[bottom * width + x] - column_prefix[top * width + x]
+                        }
+                        LtlNeighborhood::VonNeumann => {
+                            let mut sum = 0;
+                            for ny in y.saturating_sub(range)..(y + range + 1).min(height) {
+                                let reach = range - ny.abs_diff(y);
+                                sum += row_sum(ny, x.saturating_sub(reach), (x + reach + 1).min(width));
+                            }
+                            sum
+                        }
+                    };
+                    
+                    let state = cells[y * width + x];
+                    if state == 1 && !rule.include_center {
+                        count -= 1;
+                    }
+                    *next = rule.next_state(state, count);
+                }
+            });
+        
+        std::mem::swap(&mut self.cells, &mut self.next_cells);
+        self.generation += 1;
+    }
+}
+
+impl Stepped for LtlEngine {
+    fn next_generation(&mut self) {
+        self.update();
+    }
+    
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
+impl GameOfLifeEngine for LtlEngine {
+    fn step(&mut self) {
+        self.run_steps(1);
+    }
+    
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
+    }
+    
+    fn get_grid(&self) -> &dyn Grid {
+        panic!("LtlEngine doesn't support direct grid access - use get_cell or cell_state instead")
+    }
+    
+    fn set_grid(&mut self, grid: &dyn Grid) {
+        if self.width != grid.width() || self.height != grid.height() {
+            *self = Self {
+                observers: std::mem::take(&mut self.observers),
+                ..Self::new(grid.width(), grid.height(), self.rule.clone())
+            };
+        }
+        
+        for row in 0..self.height {
+            for col in 0..self.width {
+                self.cells[row * self.width + col] = grid.get_cell(row, col) as u8;
+            }
+        }
+        self.generation = 0;
+    }
+    
+    fn benchmark_info(&self) -> EngineInfo {
+        EngineInfo {
+            name: "LargerThanLife".to_string(),
+            description: format!("Larger than Life rule {} using prefix-sum neighborhood counts", self.rule),
+            memory_per_cell_bits: 8.0,
+            supports_parallel: true,
+            supports_simd: false,
+            min_grid_size: Some((1, 1)),
+            max_grid_size: None,
+        }
+    }
+    
+    fn rule(&self) -> Option<Rule> {
+        self.rule.as_life_rule()
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        Err(GameOfLifeError::unsupported("LargerThanLife engine", format!("switching from its LtlRule to {}", rule)))
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        self.cell_state(row, col) == 1
+    }
+    
+    fn cell_state(&self, row: usize, col: usize) -> u8 {
+        assert!(row < self.height && col < self.width, "Cell coordinates out of bounds");
+        self.cells[row * self.width + col]
+    }
+    
+    fn num_states(&self) -> u8 {
+        self.rule.states
+    }
+    
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    fn snapshot(&self) -> EngineSnapshot {
+        let mut grid = BitGrid::new(self.width, self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.cells[row * self.width + col] == 1 {
+                    grid.set_cell(row, col, true);
+                }
+            }
+        }
+        EngineSnapshot {
+            generation: self.generation,
+            grid,
+            cell_states: (self.rule.states > 2).then(|| self.cells.clone()),
+        }
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.width, self.height)?;
+        
+        match &snapshot.cell_states {
+            Some(states) => {
+                if let Some(&bad) = states.iter().find(|&&state| state >= self.rule.states) {
+                    return Err(GameOfLifeError::invalid(format!("Snapshot state {} out of range for {}", bad, self.rule)));
+                }
+                self.cells.copy_from_slice(states);
+            }
+            None => {
+                for row in 0..self.height {
+                    for col in 0..self.width {
+                        self.cells[row * self.width + col] = snapshot.grid.get_cell(row, col) as u8;
+                    }
+                }
+            }
+        }
+        self.generation = snapshot.generation;
+        Ok(())
+    }
+    
+    fn width(&self) -> usize {
+        self.width
+    }
+    
+    fn height(&self) -> usize {
+        self.height
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.cells.iter().filter(|&&state| state == 1).count()
+    }
+    
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new(self.width, self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            hasher.write_cells(row.iter().map(|&state| state == 1));
+        }
+        if self.cells.iter().any(|&state| state > 1) {
+            for chunk in self.cells.chunks(8) {
+                let mut word = [0u8; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                hasher.write_word(u64::from_le_bytes(word));
+            }
+        }
+        hasher.finish()
+    }
+    
+    fn memory_usage(&self) -> usize {
+        (self.cells.capacity() + self.next_cells.capacity()) * size_of::<u8>()
+            + (self.row_prefix.capacity() + self.column_prefix.capacity()) * size_of::<u32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NaiveEngine;
+    use crate::grid::StandardGrid;
+    
+    fn soup(width: usize, height: usize) -> StandardGrid {
+        StandardGrid::random(width, height, 0.4, 0x9E37_79B9_7F4A_7C15).unwrap()
+    }
+    
+    #[test]
+    fn test_range_one_matches_life() {
+        let grid = soup(30, 20);
+        let mut naive = NaiveEngine::from_grid(&grid as &dyn Grid);
+        let mut ltl = LtlEngine::from_grid(&grid, "R1,C0,M0,S2..3,B3..3,NM".parse().unwrap());
+        assert_eq!(ltl.rule(), Some(Rule::conway()));
+        
+        for _ in 0..8 {
+            naive.step();
+            ltl.step();
+            assert_eq!(naive.state_hash(), ltl.state_hash());
+        }
+    }
+    
+    #[test]
+    fn test_von_neumann_counts() {
+        let grid = soup(12, 9);
+        let rule: LtlRule = "R2,C0,M1,S3..6,B3..4,NN".parse().unwrap();
+        let mut engine = LtlEngine::from_grid(&grid, rule.clone());
+        engine.step();
+        
+        // Brute-force diamond counts
+        for row in 0..9 {
+            for col in 0..12 {
+                let mut count = 0;
+                for r in 0..9usize {
+                    for c in 0..12usize {
+                        if r.abs_diff(row) + c.abs_diff(col) <= 2 && grid.get_cell(r, c) {
+                            count += 1;
+                        }
+                    }
+                }
+                let expected = rule.next_state(grid.get_cell(row, col) as u8, count);
+                assert_eq!(engine.cell_state(row, col), expected, "Mismatch at ({}, {})", row, col);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_bosco_runs() {
+        let grid = soup(64, 64);
+        let mut engine = LtlEngine::from_grid(&grid, LtlRule::bosco());
+        engine.run_steps(5);
+        assert_eq!(engine.generation(), 5);
+        assert_eq!(engine.ltl_rule().neighborhood_size(), 121);
+    }
+}
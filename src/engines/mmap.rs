@@ -0,0 +1,255 @@
+//! Streaming engine for memory-mapped boards larger than RAM
+
+use crate::engines::bitwise::step_packed_row;
+use crate::engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::{BitGrid, Grid, MmapGrid};
+use crate::rules::Rule;
+use rayon::prelude::*;
+use std::io;
+use std::path::Path;
+
+/// Rows computed per parallel task; each band reads only its own rows plus one above and below
+const BAND_ROWS: usize = 256;
+
+/// Engine that steps a memory-mapped field into a second memory-mapped field
+///
+/// Generations are computed band by band with word-level bit-sliced
+/// arithmetic, so the working set is a few bands of each file rather than
+/// the whole board. Cells beyond the edges are dead.
+pub struct MmapEngine {
+    current: MmapGrid,
+    next: MmapGrid,
+    rule: Rule,
+    generation: usize,
+    observers: GenerationObservers,
+}
+
+impl MmapEngine {
+    /// Create an engine from the field to simulate and a scratch field of the same size
+    pub fn new(current: MmapGrid, scratch: MmapGrid) -> Result<Self, GameOfLifeError> {
+        if current.width() != scratch.width() || current.height() != scratch.height() {
+            return Err(GameOfLifeError::dimensions(
+                (current.width(), current.height()),
+                (scratch.width(), scratch.height()),
+            ));
+        }
+        Ok(Self {
+            current,
+            next: scratch,
+            rule: Rule::conway(),
+            generation: 0,
+            observers: GenerationObservers::new(),
+        })
+    }
+    
+    /// Create an empty `width` x `height` board backed by two files in `dir`
+    pub fn create(dir: impl AsRef<Path>, width: usize, height: usize) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        let current = MmapGrid::create(dir.join("field-a.bits"), width, height)?;
+        let scratch = MmapGrid::create(dir.join("field-b.bits"), width, height)?;
+        Ok(Self::new(current, scratch).expect("Freshly created fields have equal sizes"))
+    }
+    
+    /// The memory-mapped field holding the current generation
+    pub fn field(&self) -> &MmapGrid {
+        &self.current
+    }
+    
+    /// The current field, mutably, for editing cells in place
+    pub fn field_mut(&mut self) -> &mut MmapGrid {
+        &mut self.current
+    }
+    
+    /// Write the current generation back to its file
+    pub fn flush(&self) -> io::Result<()> {
+        self.current.flush()
+    }
+    
+    /// Copy a grid into the mapped field, resizing both backing files if needed
+    ///
+    /// If either file cannot be resized, both are put back at their old size
+    /// and the error is returned.
+    pub fn try_set_grid(&mut self, grid: &dyn Grid) -> Result<(), GameOfLifeError> {
+        let (width, height) = (self.current.width(), self.current.height());
+        if width != grid.width() || height != grid.height() {
+            let resized = self.current.resize(grid.width(), grid.height()).and_then(|()| self.next.resize(grid.width(), grid.height()));
+            if let Err(err) = resized {
+                // Best effort: the old cells are gone, but the fields must match
+                let _ = self.current.resize(width, height).and_then(|()| self.next.resize(width, height));
+                self.generation = 0;
+                return Err(err.into());
+            }
+        } else {
+            self.current.clear();
+        }
+        
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                if grid.get_cell(row, col) {
+                    self.current.set_cell(row, col, true);
+                }
+            }
+        }
+        self.generation = 0;
+        Ok(())
+    }
+    
+    /// Compute one generation into the scratch field and swap
+    fn update(&mut self) {
+        let words_per_row = self.current.words_per_row();
+        let width = self.current.width();
+        let (birth, survival) = (self.rule.birth_mask(), self.rule.survival_mask());
+        let source = self.current.words();
+        
+        if words_per_row > 0 {
+            self.next
+                .words_mut()
+                .par_chunks_mut(words_per_row * BAND_ROWS)
+                .enumerate()
+                .for_each(|(band, out)| {
+                    for (offset, out_row) in out.chunks_mut(words_per_row).enumerate() {
+                        let row = band * BAND_ROWS + offset;
+                        let row_at = |r: usize| &source[r * words_per_row..(r + 1) * words_per_row];
+                        let above = row.checked_sub(1).map(row_at);
+                        let below = (row + 1 < source.len() / words_per_row).then(|| row_at(row + 1));
+                        step_packed_row(above, row_at(row), below, out_row, width, birth, survival);
+                    }
+                });
+        }
+        
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+    }
+}
+
+impl Stepped for MmapEngine {
+    fn next_generation(&mut self) {
+        self.update();
+    }
+    
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
+impl GameOfLifeEngine for MmapEngine {
+    fn step(&mut self) {
+        self.run_steps(1);
+    }
+    
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
+    }
+    
+    fn get_grid(&self) -> &dyn Grid {
+        &self.current
+    }
+    
+    /// Copy a grid into the mapped field, resizing both backing files if needed
+    ///
+    /// Panics if a backing file cannot be resized; use `try_set_grid` to handle that.
+    fn set_grid(&mut self, grid: &dyn Grid) {
+        if let Err(err) = self.try_set_grid(grid) {
+            panic!("Failed to resize memory-mapped fields: {}", err);
+        }
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        self.current.get_cell(row, col)
+    }
+    
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    fn rule(&self) -> Option<Rule> {
+        Some(self.rule)
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        rule.require_moore("Memory-mapped engine")?;
+        self.rule = rule;
+        Ok(())
+    }
+    
+    fn snapshot(&self) -> EngineSnapshot {
+        EngineSnapshot {
+            generation: self.generation,
+            grid: BitGrid::from_words(self.current.width(), self.current.height(), self.current.words().to_vec())
+                .expect("Mapped field keeps the BitGrid layout"),
+            cell_states: None,
+        }
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.current.width(), self.current.height())?;
+        self.current.words_mut().copy_from_slice(snapshot.grid.words());
+        self.generation = snapshot.generation;
+        Ok(())
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.current.count_live_cells()
+    }
+    
+    fn state_hash(&self) -> u64 {
+        self.current.state_hash()
+    }
+    
+    fn benchmark_info(&self) -> EngineInfo {
+        EngineInfo {
+            name: "Mmap".to_string(),
+            description: "Bit-sliced band-by-band stepping of memory-mapped fields".to_string(),
+            memory_per_cell_bits: 2.0,
+            supports_parallel: true,
+            supports_simd: false,
+            min_grid_size: Some((1, 1)),
+            max_grid_size: None,
+        }
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.current.memory_bytes() + self.next.memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NaiveEngine;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_matches_naive_engine() {
+        let dir = std::env::temp_dir().join(format!("gol_mmap_engine_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        
+        let soup = StandardGrid::random(130, 300, 0.35, 12).unwrap();
+        for rule in ["B3/S23", "B36/S23", "B2/S"] {
+            let mut mmap = MmapEngine::create(&dir, 1, 1).unwrap();
+            mmap.try_set_grid(&soup).unwrap();
+            mmap.set_rule(rule.parse().unwrap()).unwrap();
+            let mut naive = NaiveEngine::from_grid(&soup);
+            naive.set_rule(rule.parse().unwrap()).unwrap();
+            
+            for _ in 0..12 {
+                mmap.step();
+                naive.step();
+                assert_eq!(mmap.state_hash(), naive.state_hash(), "{} diverged", rule);
+            }
+        }
+        
+        let mut mmap = MmapEngine::create(&dir, 10, 10).unwrap();
+        assert!(mmap.set_rule("B1/S012/V".parse().unwrap()).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,11 +1,49 @@
+pub mod age;
+pub(crate) mod bitwise;
+pub mod callback;
+pub mod cancel;
+pub mod checkpoint;
+pub mod elementary;
+pub mod generations;
+pub mod life3d;
+pub mod lookup;
+pub mod ltl;
+#[cfg(feature = "mmap")]
+pub mod mmap;
 pub mod naive;
+pub mod observer;
+pub mod registry;
+pub mod snapshot;
+pub mod stochastic;
 pub mod ultimate;
 
+pub use age::{AgeTrackingEngine, CellAge};
+pub use callback::{CallbackEngine, RuleCallback};
+pub use cancel::CancellationToken;
+pub use checkpoint::Checkpointer;
+pub use elementary::ElementaryAutomaton;
+pub use generations::GenerationsEngine;
+pub use life3d::Life3DEngine;
+pub use lookup::LookupEngine;
+pub use ltl::LtlEngine;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapEngine;
 pub use naive::NaiveEngine;
-pub use ultimate::{UltimateEngine, create_optimal_engine, auto_new_ultimate_engine, auto_from_grid_ultimate_engine, safe_auto_new_ultimate_engine};
+pub use observer::{GenerationEvent, GenerationObserver, GenerationObservers};
+pub use registry::{EngineBuilder, EngineKind};
+pub use snapshot::EngineSnapshot;
+pub use stochastic::StochasticEngine;
+pub use ultimate::{UltimateEngine, FrameReader, ReadGuard, create_optimal_engine, auto_new_ultimate_engine, auto_from_grid_ultimate_engine, safe_auto_new_ultimate_engine, ultimate_engine_from_grid, ULTIMATE_SIMD_WIDTHS};
 
-use crate::grid::Grid;
-use std::time::Duration;
+use crate::error::GameOfLifeError;
+use crate::formats::{self, FormatError, RuleHeaderError};
+use crate::grid::{BitGrid, Boundary, Grid, Rect, StandardGrid, Topology};
+use crate::grid::hash::StateHasher;
+use crate::rules::{Rule, RuleString};
+use crate::stats::StepStats;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 /// Information about a Game of Life engine's performance characteristics
 #[derive(Debug, Clone)]
@@ -19,8 +57,98 @@ pub struct EngineInfo {
     pub max_grid_size: Option<(usize, usize)>,
 }
 
+/// Outcome of running an engine until its state repeats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StabilizationReport {
+    /// Number of steps actually executed
+    pub steps_run: usize,
+    /// Generation (relative to the start of the run) at which the repeating cycle was entered
+    pub stabilized_at: Option<usize>,
+    /// Length of the detected cycle (1 for still lifes and empty grids)
+    pub period: Option<usize>,
+}
+
+impl StabilizationReport {
+    /// Whether a repeating state was found within the step budget
+    pub fn is_stable(&self) -> bool {
+        self.period.is_some()
+    }
+
+    /// Whether the final state is a still life (period 1)
+    pub fn is_still_life(&self) -> bool {
+        self.period == Some(1)
+    }
+
+    /// Whether the final state is an oscillator with period greater than 1
+    pub fn is_oscillator(&self) -> bool {
+        matches!(self.period, Some(p) if p > 1)
+    }
+}
+
+/// Upcast helper that lets default trait methods hand `self` to `&dyn GameOfLifeEngine` callbacks
+pub trait AsDynEngine {
+    /// View this engine as a trait object
+    fn as_dyn_engine(&self) -> &dyn GameOfLifeEngine;
+}
+
+impl<T: GameOfLifeEngine> AsDynEngine for T {
+    fn as_dyn_engine(&self) -> &dyn GameOfLifeEngine {
+        self
+    }
+}
+
+/// Generation loop shared by the built-in engines
+///
+/// Implementors supply a single update and their observers; `advance` adds
+/// cancellation checks and observer events around it.
+pub(crate) trait Stepped: GameOfLifeEngine {
+    /// Compute the next generation and bump the generation count
+    fn next_generation(&mut self);
+    
+    /// Observers registered through `on_generation`
+    fn observers_mut(&mut self) -> &mut GenerationObservers;
+    
+    /// Advance up to `steps` generations, notifying observers and honoring cancellation
+    ///
+    /// Returns the number of generations completed.
+    fn advance(&mut self, steps: usize, cancel: Option<&CancellationToken>) -> usize {
+        let start = Instant::now();
+        for completed in 0..steps {
+            if cancel.is_some_and(|token| token.is_cancelled()) {
+                return completed;
+            }
+            
+            self.next_generation();
+            
+            if !self.observers_mut().is_empty() {
+                let event = GenerationEvent {
+                    generation: self.generation(),
+                    population: self.count_live_cells(),
+                    elapsed: start.elapsed(),
+                };
+                self.observers_mut().notify(&event);
+            }
+        }
+        steps
+    }
+}
+
+/// Copy `grid` into a double-buffered engine, resizing both buffers if the size changed
+pub(crate) fn load_buffers(current: &mut StandardGrid, next: &mut StandardGrid, grid: &dyn Grid) {
+    if current.width() != grid.width() || current.height() != grid.height() {
+        *current = StandardGrid::new(grid.width(), grid.height());
+        *next = StandardGrid::new(grid.width(), grid.height());
+    }
+    
+    for row in 0..grid.height() {
+        for col in 0..grid.width() {
+            current.set_cell(row, col, grid.get_cell(row, col));
+        }
+    }
+}
+
 /// Trait for Game of Life engine implementations
-pub trait GameOfLifeEngine {
+pub trait GameOfLifeEngine: AsDynEngine {
     /// Advance the simulation by one step
     fn step(&mut self);
     
@@ -33,9 +161,142 @@ pub trait GameOfLifeEngine {
     /// Get information about this engine
     fn benchmark_info(&self) -> EngineInfo;
     
+    /// Get the Life-like rule this engine simulates, if its rule is Life-like
+    fn rule(&self) -> Option<Rule> {
+        Some(Rule::conway())
+    }
+    
+    /// Change the Life-like rule used for subsequent steps
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        if rule.is_conway() {
+            Ok(())
+        } else {
+            Err(GameOfLifeError::unsupported(format!("{} engine", self.benchmark_info().name), format!("rule {}", rule)))
+        }
+    }
+    
+    /// Get the neighborhood topology this engine simulates
+    fn topology(&self) -> Topology {
+        Topology::Square
+    }
+    
+    /// Change the neighborhood topology used for subsequent steps
+    fn set_topology(&mut self, topology: Topology) -> Result<(), GameOfLifeError> {
+        if topology == Topology::Square {
+            Ok(())
+        } else {
+            Err(GameOfLifeError::unsupported(format!("{} engine", self.benchmark_info().name), format!("the {} topology", topology)))
+        }
+    }
+    
+    /// Get how this engine reads neighbors beyond the grid's edges
+    fn boundary(&self) -> Boundary {
+        Boundary::Bounded
+    }
+    
+    /// Change how neighbors beyond the grid's edges are read in subsequent steps
+    fn set_boundary(&mut self, boundary: Boundary) -> Result<(), GameOfLifeError> {
+        if boundary == Boundary::Bounded {
+            Ok(())
+        } else {
+            Err(GameOfLifeError::unsupported(format!("{} engine", self.benchmark_info().name), format!("the {} boundary", boundary)))
+        }
+    }
+    
     /// Get the value of a specific cell
     fn get_cell(&self, row: usize, col: usize) -> bool;
     
+    /// Get the state of a cell: 0 is dead, 1 is alive, higher values are decay states
+    fn cell_state(&self, row: usize, col: usize) -> u8 {
+        self.get_cell(row, col) as u8
+    }
+    
+    /// Number of distinct cell states this engine simulates
+    fn num_states(&self) -> u8 {
+        2
+    }
+    
+    /// Number of generations simulated since the grid was last set
+    ///
+    /// Engines that do not count generations report 0.
+    fn generation(&self) -> usize {
+        0
+    }
+    
+    /// Register an observer invoked after every generation with population and timing
+    ///
+    /// Engines that do not report generations drop the observer.
+    fn on_generation(&mut self, _observer: GenerationObserver) {}
+    
+    /// Capture the full simulation state, including the generation count
+    ///
+    /// The default reads every cell through `get_cell`.
+    fn snapshot(&self) -> EngineSnapshot {
+        let mut grid = BitGrid::new(self.width(), self.height());
+        for row in 0..self.height() {
+            for col in 0..self.width() {
+                if self.get_cell(row, col) {
+                    grid.set_cell(row, col, true);
+                }
+            }
+        }
+        EngineSnapshot { generation: self.generation(), grid, cell_states: None }
+    }
+    
+    /// Restore a state previously captured with `snapshot`
+    ///
+    /// The default loads the cells through `set_grid`, which restarts the
+    /// generation count, so it rejects snapshots taken after generation 0.
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.width(), self.height())?;
+        if snapshot.generation != 0 {
+            return Err(GameOfLifeError::unsupported(
+                format!("{} engine", self.benchmark_info().name),
+                format!("restoring generation {}", snapshot.generation),
+            ));
+        }
+        self.set_grid(&snapshot.grid);
+        Ok(())
+    }
+    
+    /// Write the grid, generation and rule to a binary snapshot file
+    fn save_state(&self, path: &Path) -> Result<(), FormatError> {
+        formats::state::save(path, &self.snapshot(), self.rule())
+    }
+    
+    /// Replace the grid, generation and rule with those saved by `save_state`
+    ///
+    /// The grid is resized to the snapshot's dimensions. Nothing changes if the
+    /// engine cannot run the saved rule or rejects the saved state.
+    fn load_state(&mut self, path: &Path) -> Result<(), FormatError> {
+        let (snapshot, rule) = formats::state::load(path)?;
+        let (previous, previous_rule) = (self.snapshot(), self.rule());
+        if let Some(rule) = rule {
+            if self.rule() != Some(rule) {
+                self.set_rule(rule).map_err(|reason| RuleHeaderError::Unsupported {
+                    rule: Box::new(RuleString::Life(rule)),
+                    engine: self.benchmark_info().name,
+                    reason,
+                })?;
+            }
+        }
+        if self.width() != snapshot.width() || self.height() != snapshot.height() {
+            self.set_grid(&snapshot.grid);
+        }
+        if let Err(reason) = self.restore(&snapshot) {
+            // Put back the rule and state this load replaced
+            if let Some(rule) = previous_rule {
+                self.set_rule(rule).map_err(FormatError::rejected)?;
+            }
+            if self.width() != previous.width() || self.height() != previous.height() {
+                self.set_grid(&previous.grid);
+            }
+            self.restore(&previous).map_err(FormatError::rejected)?;
+            return Err(FormatError::rejected(reason));
+        }
+        Ok(())
+    }
+    
     /// Get the width of the grid
     fn width(&self) -> usize {
         self.get_grid().width()
@@ -46,6 +307,15 @@ pub trait GameOfLifeEngine {
         self.get_grid().height()
     }
     
+    /// Bytes allocated for the cell buffers the engine steps between
+    ///
+    /// The default estimates this from `memory_per_cell_bits` in
+    /// `benchmark_info`; the built-in engines report what they actually hold.
+    fn memory_usage(&self) -> usize {
+        let bits = self.benchmark_info().memory_per_cell_bits * (self.width() * self.height()) as f64;
+        (bits / 8.0).ceil() as usize
+    }
+    
     /// Count the number of live cells
     fn count_live_cells(&self) -> usize {
         let grid = self.get_grid();
@@ -60,6 +330,18 @@ pub trait GameOfLifeEngine {
         count
     }
     
+    /// Smallest rectangle containing every live cell, or `None` if the grid is empty
+    fn bounding_box(&self) -> Option<Rect> {
+        self.snapshot().grid.bounding_box()
+    }
+    
+    /// Advance one step and report how many cells were born and died
+    fn step_with_stats(&mut self) -> StepStats {
+        let before = self.snapshot().grid;
+        self.step();
+        StepStats::between(self.generation(), &before, &self.snapshot().grid)
+    }
+    
     /// Run multiple steps efficiently
     fn run_steps(&mut self, steps: usize) {
         for _ in 0..steps {
@@ -67,10 +349,134 @@ pub trait GameOfLifeEngine {
         }
     }
     
+    /// Run up to `steps` steps, stopping early once `cancel` is triggered
+    ///
+    /// Returns the number of steps actually completed.
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        for completed in 0..steps {
+            if cancel.is_cancelled() {
+                return completed;
+            }
+            self.step();
+        }
+        steps
+    }
+    
     /// Run steps with timing information
     fn run_steps_timed(&mut self, steps: usize) -> Duration {
-        let start = std::time::Instant::now();
+        let start = Instant::now();
         self.run_steps(steps);
         start.elapsed()
     }
-}
\ No newline at end of file
+    
+    /// Step until `pred` returns true or `max_steps` is reached
+    ///
+    /// The predicate is checked on the initial state and after every step.
+    /// Returns the number of steps taken when the predicate was satisfied,
+    /// or `None` if the step budget ran out first.
+    fn run_until(
+        &mut self,
+        pred: &mut dyn FnMut(&dyn GameOfLifeEngine) -> bool,
+        max_steps: usize,
+    ) -> Option<usize> {
+        if pred(self.as_dyn_engine()) {
+            return Some(0);
+        }
+        for step in 1..=max_steps {
+            self.step();
+            if pred(self.as_dyn_engine()) {
+                return Some(step);
+            }
+        }
+        None
+    }
+    
+    /// Hash of the current cell contents, equal across engines holding the same state
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new(self.width(), self.height());
+        for row in 0..self.height() {
+            hasher.write_cells((0..self.width()).map(|col| self.get_cell(row, col)));
+        }
+        hasher.finish()
+    }
+    
+    /// Step until a previously seen state recurs or `max_steps` is reached
+    ///
+    /// Detects still lifes (period 1) and oscillator cycles by hashing each
+    /// generation, so the engine is left in the first repeated state.
+    fn run_until_stable(&mut self, max_steps: usize) -> StabilizationReport {
+        let mut seen = HashMap::new();
+        seen.insert(self.state_hash(), 0);
+        
+        for step in 1..=max_steps {
+            self.step();
+            let hash = self.state_hash();
+            if let Some(&first) = seen.get(&hash) {
+                return StabilizationReport {
+                    steps_run: step,
+                    stabilized_at: Some(first),
+                    period: Some(step - first),
+                };
+            }
+            seen.insert(hash, step);
+        }
+        
+        StabilizationReport {
+            steps_run: max_steps,
+            stabilized_at: None,
+            period: None,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Anchor, StandardGrid};
+    use crate::patterns;
+    
+    /// Engine implementing only the required methods, never changing its grid
+    struct Frozen(StandardGrid);
+    
+    impl GameOfLifeEngine for Frozen {
+        fn step(&mut self) {}
+        
+        fn get_grid(&self) -> &dyn Grid {
+            &self.0
+        }
+        
+        fn set_grid(&mut self, grid: &dyn Grid) {
+            self.0 = StandardGrid::from_grid(grid);
+        }
+        
+        fn benchmark_info(&self) -> EngineInfo {
+            EngineInfo { name: "Frozen".to_string(), ..NaiveEngine::new(1, 1).benchmark_info() }
+        }
+        
+        fn get_cell(&self, row: usize, col: usize) -> bool {
+            self.0.get_cell(row, col)
+        }
+    }
+    
+    #[test]
+    fn test_default_generation_hooks() {
+        let mut engine = Frozen(StandardGrid::new(4, 4));
+        engine.on_generation(Box::new(|_| panic!("Frozen does not report generations")));
+        engine.run_steps(3);
+        assert_eq!(engine.generation(), 0);
+    }
+    
+    #[test]
+    fn test_default_snapshot_restore() {
+        let glider = patterns::glider().grid.embedded_in(6, 5, Anchor::Center);
+        let mut engine = Frozen(glider.clone());
+        let snapshot = engine.snapshot();
+        assert_eq!((snapshot.generation, snapshot.grid.count_live_cells()), (0, 5));
+        
+        engine.set_grid(&StandardGrid::new(6, 5));
+        engine.restore(&snapshot).unwrap();
+        assert_eq!(engine.0, glider);
+        
+        assert!(engine.restore(&EngineSnapshot { generation: 3, ..snapshot.clone() }).is_err());
+        assert!(Frozen(StandardGrid::new(2, 2)).restore(&snapshot).is_err());
+    }
+}
@@ -1,11 +1,18 @@
-use crate::engines::{GameOfLifeEngine, EngineInfo};
-use crate::grid::{Grid, StandardGrid};
+use crate::engines::{load_buffers, GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::{Boundary, Grid, StandardGrid, Topology};
+use crate::rules::Rule;
 use rayon::prelude::*;
 
 /// Naive Game of Life engine using basic cell-by-cell simulation
 pub struct NaiveEngine {
     grid: StandardGrid,
     next_grid: StandardGrid,
+    generation: usize,
+    observers: GenerationObservers,
+    rule: Rule,
+    topology: Topology,
+    boundary: Boundary,
 }
 
 impl NaiveEngine {
@@ -14,6 +21,11 @@ impl NaiveEngine {
         Self {
             grid: StandardGrid::new(width, height),
             next_grid: StandardGrid::new(width, height),
+            generation: 0,
+            observers: GenerationObservers::new(),
+            rule: Rule::conway(),
+            topology: Topology::Square,
+            boundary: Boundary::Bounded,
         }
     }
     
@@ -32,6 +44,11 @@ impl NaiveEngine {
         Self {
             grid: new_grid,
             next_grid: StandardGrid::new(width, height),
+            generation: 0,
+            observers: GenerationObservers::new(),
+            rule: Rule::conway(),
+            topology: Topology::Square,
+            boundary: Boundary::Bounded,
         }
     }
     
@@ -39,19 +56,23 @@ impl NaiveEngine {
     fn update_safe(&mut self) {
         let width = self.grid.width();
         let height = self.grid.height();
+        let rule = self.rule;
+        let topology = self.topology;
+        let boundary = self.boundary;
         
         let new_cells: Vec<bool> = (0..height * width)
             .into_par_iter()
             .map(|idx| {
                 let row = idx / width;
                 let col = idx % width;
-                let neighbors = self.grid.count_neighbors(row, col);
+                let offsets = match topology {
+                    Topology::Square => rule.neighborhood().offsets(),
+                    Topology::Hex => topology.neighbor_offsets(row),
+                };
+                let neighbors = self.grid.count_neighbors_across(row, col, offsets, boundary);
                 let current_cell = self.grid.get_cell(row, col);
                 
-                match (current_cell, neighbors) {
-                    (true, 2) | (true, 3) | (false, 3) => true,
-                    _ => false,
-                }
+                rule.next_state(current_cell, neighbors)
             })
             .collect();
         
@@ -62,6 +83,7 @@ impl NaiveEngine {
         }
         
         std::mem::swap(&mut self.grid, &mut self.next_grid);
+        self.generation += 1;
     }
     
     /// Get the width of the grid
@@ -85,9 +107,27 @@ impl NaiveEngine {
     }
 }
 
+impl Stepped for NaiveEngine {
+    fn next_generation(&mut self) {
+        self.update_safe();
+    }
+    
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
 impl GameOfLifeEngine for NaiveEngine {
     fn step(&mut self) {
-        self.update_safe();
+        self.run_steps(1);
+    }
+    
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+    
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
     }
     
     fn get_grid(&self) -> &dyn Grid {
@@ -95,24 +135,62 @@ impl GameOfLifeEngine for NaiveEngine {
     }
     
     fn set_grid(&mut self, grid: &dyn Grid) {
-        if self.grid.width() != grid.width() || self.grid.height() != grid.height() {
-            self.grid = StandardGrid::new(grid.width(), grid.height());
-            self.next_grid = StandardGrid::new(grid.width(), grid.height());
-        } else {
-            self.grid.clear();
-        }
-        
-        for row in 0..grid.height() {
-            for col in 0..grid.width() {
-                self.grid.set_cell(row, col, grid.get_cell(row, col));
-            }
-        }
+        load_buffers(&mut self.grid, &mut self.next_grid, grid);
+        self.generation = 0;
     }
     
     fn get_cell(&self, row: usize, col: usize) -> bool {
         self.grid.get_cell(row, col)
     }
     
+    fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    fn rule(&self) -> Option<Rule> {
+        Some(self.rule)
+    }
+    
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        if self.topology == Topology::Hex {
+            rule.require_moore("Hex topology")?;
+        }
+        self.rule = rule;
+        Ok(())
+    }
+    
+    fn topology(&self) -> Topology {
+        self.topology
+    }
+    
+    fn set_topology(&mut self, topology: Topology) -> Result<(), GameOfLifeError> {
+        if topology == Topology::Hex {
+            self.rule.require_moore("Hex topology")?;
+        }
+        self.topology = topology;
+        Ok(())
+    }
+    
+    fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+    
+    fn set_boundary(&mut self, boundary: Boundary) -> Result<(), GameOfLifeError> {
+        self.boundary = boundary;
+        Ok(())
+    }
+    
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.grid.width(), self.grid.height())?;
+        load_buffers(&mut self.grid, &mut self.next_grid, &snapshot.grid);
+        self.generation = snapshot.generation;
+        Ok(())
+    }
+    
     fn benchmark_info(&self) -> EngineInfo {
         EngineInfo {
             name: "Naive".to_string(),
@@ -124,11 +202,16 @@ impl GameOfLifeEngine for NaiveEngine {
             max_grid_size: None,
         }
     }
+    
+    fn memory_usage(&self) -> usize {
+        self.grid.memory_bytes() + self.next_grid.memory_bytes()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rules::NeighborhoodKind;
     use crate::grid::StandardGrid;
     
     #[test]
@@ -189,4 +272,185 @@ mod tests {
         engine.step();
         assert_eq!(engine.get_grid().count_live_cells(), initial_count);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_run_until_stable() {
+        let blinker = StandardGrid::from_string_pattern(&[".....", ".....", ".###.", ".....", "....."], '#', '.').unwrap();
+        let mut engine = NaiveEngine::from_grid(&blinker as &dyn Grid);
+        let report = engine.run_until_stable(10);
+        assert_eq!(report.period, Some(2));
+        assert_eq!(report.stabilized_at, Some(0));
+        assert!(report.is_oscillator());
+        
+        // A pre-block settles into a block after one generation
+        let pre_block = StandardGrid::from_string_pattern(&["....", ".##.", ".#..", "...."], '#', '.').unwrap();
+        let mut engine = NaiveEngine::from_grid(&pre_block as &dyn Grid);
+        let report = engine.run_until_stable(10);
+        assert!(report.is_still_life());
+        assert_eq!(report.stabilized_at, Some(1));
+        assert_eq!(report.steps_run, 2);
+    }
+    
+    #[test]
+    fn test_run_until() {
+        let glider = StandardGrid::from_string_pattern(&[".#...", "..#..", "###..", ".....", "....."], '#', '.').unwrap();
+        let mut engine = NaiveEngine::from_grid(&glider as &dyn Grid);
+        
+        // The glider reaches the bottom-right corner and decays into a block
+        let steps = engine.run_until(&mut |e| e.get_cell(4, 4), 100);
+        assert!(steps.is_some());
+        assert!(engine.get_cell(4, 4));
+        
+        let mut engine = NaiveEngine::from_grid(&glider as &dyn Grid);
+        assert_eq!(engine.run_until(&mut |e| e.count_live_cells() == 5, 10), Some(0));
+        assert_eq!(engine.run_until(&mut |e| e.count_live_cells() > 100, 3), None);
+    }
+    
+    #[test]
+    fn test_generation_observer() {
+        use std::sync::{Arc, Mutex};
+        
+        let grid = StandardGrid::from_string_pattern(&["...", "###", "..."], '#', '.').unwrap();
+        let mut engine = NaiveEngine::from_grid(&grid as &dyn Grid);
+        
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        engine.on_generation(Box::new(move |event| sink.lock().unwrap().push(*event)));
+        
+        engine.run_steps(3);
+        engine.step();
+        
+        let events = events.lock().unwrap();
+        let generations: Vec<usize> = events.iter().map(|e| e.generation).collect();
+        assert_eq!(generations, vec![1, 2, 3, 4]);
+        assert!(events.iter().all(|e| e.population == 3));
+        assert_eq!(engine.generation(), 4);
+    }
+    
+    #[test]
+    fn test_snapshot_restore() {
+        let grid = StandardGrid::from_string_pattern(&[".#...", "..#..", "###..", ".....", "....."], '#', '.').unwrap();
+        let mut engine = NaiveEngine::from_grid(&grid as &dyn Grid);
+        engine.run_steps(2);
+        let snapshot = engine.snapshot();
+        assert_eq!(snapshot.generation, 2);
+        
+        engine.run_steps(5);
+        engine.restore(&snapshot).unwrap();
+        assert_eq!(engine.generation(), 2);
+        assert_eq!(engine.snapshot(), snapshot);
+        
+        let mut small = NaiveEngine::new(3, 3);
+        assert!(small.restore(&snapshot).is_err());
+    }
+    
+    #[test]
+    fn test_run_steps_cancellable() {
+        let mut engine = NaiveEngine::new(8, 8);
+        let cancel = CancellationToken::new();
+        
+        let token = cancel.clone();
+        engine.on_generation(Box::new(move |event| {
+            if event.generation == 3 {
+                token.cancel();
+            }
+        }));
+        
+        assert_eq!(engine.run_steps_cancellable(100, &cancel), 3);
+        assert_eq!(engine.generation(), 3);
+        
+        cancel.reset();
+        assert_eq!(engine.run_steps_cancellable(2, &cancel), 2);
+    }
+    
+    #[test]
+    fn test_custom_rule() {
+        // Under HighLife (B36/S23) a dead cell with six neighbors is born
+        let pattern = [
+            "###",
+            "#.#",
+            "#..",
+        ];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        
+        let mut conway = NaiveEngine::from_grid(&grid as &dyn Grid);
+        conway.step();
+        assert!(!conway.get_cell(1, 1));
+        
+        let mut highlife = NaiveEngine::from_grid(&grid as &dyn Grid);
+        highlife.set_rule("B36/S23".parse().unwrap()).unwrap();
+        highlife.step();
+        assert!(highlife.get_cell(1, 1));
+        assert_eq!(highlife.rule().unwrap().to_string(), "B36/S23");
+    }
+    
+    #[test]
+    fn test_hex_topology() {
+        // Row 1 is odd, so the cell at (1, 1) touches (0, 1), (0, 2), (2, 1) and (2, 2)
+        // but not (0, 0) or (2, 0)
+        let pattern = [
+            "#.#",
+            "...",
+            "#.#",
+        ];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        
+        let mut engine = NaiveEngine::from_grid(&grid as &dyn Grid);
+        engine.set_rule("B2/S34".parse().unwrap()).unwrap();
+        engine.set_topology(Topology::Hex).unwrap();
+        assert_eq!(engine.topology(), Topology::Hex);
+        engine.step();
+        assert!(engine.get_cell(1, 1));
+        assert!(!engine.get_cell(0, 0));
+    }
+    
+    #[test]
+    fn test_boundaries() {
+        // On an 8x8 torus a glider moves one cell diagonally every 4 generations and comes back after 32
+        let mut universe = StandardGrid::new(8, 8);
+        universe.paste(&crate::patterns::glider().grid, 0, 0, crate::grid::PasteMode::Copy);
+        let mut torus = NaiveEngine::from_grid(&universe);
+        torus.set_boundary(Boundary::Torus).unwrap();
+        torus.run_steps(32);
+        assert_eq!(torus.boundary(), Boundary::Torus);
+        assert_eq!(torus.count_live_cells(), 5);
+        assert!(crate::grid::grids_equal(torus.get_grid(), &universe));
+        
+        // A block in the corner sees its own reflection: each cell has 8 live neighbors and dies
+        let block = StandardGrid::from_string_pattern(&["##..", "##..", "....", "...."], '#', '.').unwrap();
+        let mut mirror = NaiveEngine::from_grid(&block);
+        mirror.set_boundary(Boundary::Mirror).unwrap();
+        mirror.step();
+        assert!(!mirror.get_cell(0, 0));
+        let mut bounded = NaiveEngine::from_grid(&block);
+        bounded.step();
+        assert!(bounded.get_cell(0, 0));
+    }
+    
+    #[test]
+    fn test_neighborhood_kinds() {
+        // The center has 4 diagonal neighbors, 0 orthogonal ones and 8 within radius 2
+        let pattern = [
+            "#...#",
+            ".#.#.",
+            ".....",
+            ".#.#.",
+            "#...#",
+        ];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        assert_eq!(grid.count_neighbors_with(2, 2, NeighborhoodKind::Moore), 4);
+        assert_eq!(grid.count_neighbors_with(2, 2, NeighborhoodKind::VonNeumann), 0);
+        assert_eq!(grid.count_neighbors_with(2, 2, NeighborhoodKind::MooreRadius2), 8);
+        
+        let mut von_neumann = NaiveEngine::from_grid(&grid as &dyn Grid);
+        von_neumann.set_rule("B0/S/V".parse().unwrap()).unwrap();
+        von_neumann.step();
+        assert!(von_neumann.get_cell(2, 2));
+        
+        let mut radius_2 = NaiveEngine::from_grid(&grid as &dyn Grid);
+        radius_2.set_rule("B8/S/M2".parse().unwrap()).unwrap();
+        radius_2.step();
+        assert!(radius_2.get_cell(2, 2));
+        assert!(radius_2.set_topology(Topology::Hex).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+//! Per-generation observer hooks for engines
+
+use std::time::Duration;
+
+/// Information passed to observers after each generation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationEvent {
+    /// Generation number the engine just reached
+    pub generation: usize,
+    /// Number of live cells in that generation
+    pub population: usize,
+    /// Time elapsed since the current run started
+    pub elapsed: Duration,
+}
+
+/// Boxed observer callback
+pub type GenerationObserver = Box<dyn FnMut(&GenerationEvent) + Send>;
+
+/// Collection of observers registered on an engine
+#[derive(Default)]
+pub struct GenerationObservers {
+    observers: Vec<GenerationObserver>,
+}
+
+impl GenerationObservers {
+    /// Create an empty observer list
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Register a new observer
+    pub fn push(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+    
+    /// Whether no observers are registered (engines skip event construction entirely)
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+    
+    /// Invoke every observer with the given event
+    pub fn notify(&mut self, event: &GenerationEvent) {
+        for observer in &mut self.observers {
+            observer(event);
+        }
+    }
+}
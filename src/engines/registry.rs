@@ -0,0 +1,221 @@
+//! Life engines that can be chosen by name, e.g. from the command line
+
+use super::generations::GenerationsEngine;
+use super::lookup::LookupEngine;
+use super::ltl::LtlEngine;
+use super::naive::NaiveEngine;
+use super::ultimate::ultimate_engine_from_grid;
+use super::GameOfLifeEngine;
+use crate::error::GameOfLifeError;
+use crate::grid::{Boundary, Grid, Topology};
+use crate::rules::RuleString;
+use std::fmt;
+use std::str::FromStr;
+
+/// SIMD width `UltimateEngine` is built with unless another is asked for
+const DEFAULT_SIMD_WIDTH: usize = 4;
+
+/// A Life engine selectable by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EngineKind {
+    /// `NaiveEngine`: one byte per cell, every topology and boundary
+    Naive,
+    /// `UltimateEngine`: bit-packed SIMD, square topology and bounded edges only
+    Ultimate,
+    /// `UltimateEngine` where it supports the topology, boundary and rule, `NaiveEngine` otherwise
+    #[default]
+    Auto,
+}
+
+impl EngineKind {
+    /// Every kind, in the order they are listed in help text
+    pub const ALL: [EngineKind; 3] = [EngineKind::Naive, EngineKind::Ultimate, EngineKind::Auto];
+}
+
+impl FromStr for EngineKind {
+    type Err = GameOfLifeError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "naive" => Ok(EngineKind::Naive),
+            "ultimate" | "simd" => Ok(EngineKind::Ultimate),
+            "auto" => Ok(EngineKind::Auto),
+            other => Err(GameOfLifeError::parse(s, s, format!("Unknown engine '{}', expected 'naive', 'ultimate' or 'auto'", other))),
+        }
+    }
+}
+
+impl fmt::Display for EngineKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineKind::Naive => write!(f, "naive"),
+            EngineKind::Ultimate => write!(f, "ultimate"),
+            EngineKind::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// Builds an engine of a given kind with a topology, boundary, rule and performance settings
+///
+/// Life-like rules run on the engine the kind names, with `Auto` falling back
+/// to `NaiveEngine` for anything `UltimateEngine` cannot run. Other rule
+/// families need an engine of their own, which only `Auto` will pick.
+#[derive(Debug, Clone, Default)]
+pub struct EngineBuilder {
+    kind: EngineKind,
+    topology: Topology,
+    boundary: Boundary,
+    rule: Option<RuleString>,
+    threads: Option<usize>,
+    simd_width: Option<usize>,
+}
+
+impl EngineBuilder {
+    /// Build engines of `kind` running Conway's Life on a bounded square grid
+    pub fn new(kind: EngineKind) -> Self {
+        Self { kind, ..Self::default() }
+    }
+    
+    /// Neighborhood shape the engine steps with
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+    
+    /// What lies beyond the edges of the grid
+    pub fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+    
+    /// Run `rule` instead of Conway's Life
+    pub fn rule(mut self, rule: RuleString) -> Self {
+        self.rule = Some(rule);
+        self
+    }
+    
+    /// Step `UltimateEngine` on this many worker threads instead of every core
+    ///
+    /// Other engines parallelize on rayon's global pool, which is sized when
+    /// the program starts.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+    
+    /// Build `UltimateEngine` with this many u64 SIMD lanes, one of `ULTIMATE_SIMD_WIDTHS`
+    ///
+    /// `NaiveEngine` has no lanes, so asking for it by kind with a SIMD width
+    /// fails. `Auto` ignores the width whenever it picks another engine.
+    pub fn simd_width(mut self, simd_width: usize) -> Self {
+        self.simd_width = Some(simd_width);
+        self
+    }
+    
+    /// A new engine starting from `grid`
+    ///
+    /// Fails if the chosen engine does not support the topology, boundary or
+    /// rule, or the SIMD width is not one `UltimateEngine` can be built with.
+    pub fn build(&self, grid: &dyn Grid) -> Result<Box<dyn GameOfLifeEngine>, GameOfLifeError> {
+        if self.kind == EngineKind::Naive && self.simd_width.is_some() {
+            return Err(GameOfLifeError::unsupported("naive engine", "a SIMD width"));
+        }
+        let mut engine: Box<dyn GameOfLifeEngine> = match (self.kind, &self.rule) {
+            (_, None | Some(RuleString::Life(_))) => {
+                let simd_capable = self.topology == Topology::Square && self.boundary == Boundary::Bounded;
+                let mut engine = match self.kind {
+                    EngineKind::Naive => Box::new(NaiveEngine::from_grid(grid)),
+                    EngineKind::Ultimate => self.ultimate(grid)?,
+                    EngineKind::Auto if simd_capable => self.ultimate(grid)?,
+                    EngineKind::Auto => Box::new(NaiveEngine::from_grid(grid)),
+                };
+                if let Some(rule) = self.rule.as_ref().and_then(RuleString::life_rule) {
+                    if let Err(error) = engine.set_rule(rule) {
+                        if self.kind != EngineKind::Auto {
+                            return Err(error);
+                        }
+                        engine = Box::new(NaiveEngine::from_grid(grid));
+                        engine.set_rule(rule)?;
+                    }
+                }
+                engine
+            }
+            (EngineKind::Auto, Some(RuleString::Generations(rule))) => Box::new(GenerationsEngine::from_grid(grid, *rule)),
+            (EngineKind::Auto, Some(RuleString::LargerThanLife(rule))) => Box::new(LtlEngine::from_grid(grid, rule.clone())),
+            (EngineKind::Auto, Some(RuleString::Isotropic(rule))) => Box::new(LookupEngine::from_grid(grid, *rule)),
+            (kind, Some(rule)) => return Err(GameOfLifeError::unsupported(format!("{} engine", kind), format!("rule {}", rule))),
+        };
+        engine.set_topology(self.topology)?;
+        engine.set_boundary(self.boundary)?;
+        Ok(engine)
+    }
+    
+    fn ultimate(&self, grid: &dyn Grid) -> Result<Box<dyn GameOfLifeEngine>, GameOfLifeError> {
+        ultimate_engine_from_grid(grid, self.simd_width.unwrap_or(DEFAULT_SIMD_WIDTH), self.threads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::ULTIMATE_SIMD_WIDTHS;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_engine_kinds() {
+        for kind in EngineKind::ALL {
+            assert_eq!(kind.to_string().parse::<EngineKind>().unwrap(), kind);
+        }
+        assert!("hashlife".parse::<EngineKind>().is_err());
+        
+        let grid = StandardGrid::random(32, 32, 0.5, 7).unwrap();
+        let mut naive = EngineBuilder::new(EngineKind::Naive).build(&grid).unwrap();
+        let mut ultimate = EngineBuilder::new(EngineKind::Ultimate).build(&grid).unwrap();
+        assert_eq!(ultimate.benchmark_info().name, "Ultimate");
+        naive.step();
+        ultimate.step();
+        assert_eq!(naive.state_hash(), ultimate.state_hash());
+        
+        let hex = EngineBuilder::new(EngineKind::Auto).topology(Topology::Hex).build(&grid).unwrap();
+        assert_eq!((hex.benchmark_info().name.as_str(), hex.topology()), ("Naive", Topology::Hex));
+        assert!(EngineBuilder::new(EngineKind::Ultimate).topology(Topology::Hex).build(&grid).is_err());
+        
+        let torus = EngineBuilder::new(EngineKind::Auto).boundary(Boundary::Torus).build(&grid).unwrap();
+        assert_eq!((torus.benchmark_info().name.as_str(), torus.boundary()), ("Naive", Boundary::Torus));
+        assert!(EngineBuilder::new(EngineKind::Ultimate).boundary(Boundary::Mirror).build(&grid).is_err());
+    }
+    
+    #[test]
+    fn test_engine_for_rule() {
+        let grid = StandardGrid::random(32, 32, 0.5, 7).unwrap();
+        let highlife = RuleString::parse("B36/S23").unwrap();
+        let engine = EngineBuilder::new(EngineKind::Ultimate).rule(highlife.clone()).build(&grid).unwrap();
+        assert_eq!(engine.rule(), highlife.life_rule());
+        
+        // Von Neumann rules are beyond UltimateEngine, so auto falls back to NaiveEngine
+        let von_neumann = RuleString::parse("B2/S34V").unwrap();
+        assert!(EngineBuilder::new(EngineKind::Ultimate).rule(von_neumann.clone()).build(&grid).is_err());
+        let engine = EngineBuilder::new(EngineKind::Auto).rule(von_neumann.clone()).build(&grid).unwrap();
+        assert_eq!((engine.benchmark_info().name.as_str(), engine.rule()), ("Naive", von_neumann.life_rule()));
+        
+        let brians_brain = RuleString::parse("/2/3").unwrap();
+        assert_eq!(EngineBuilder::new(EngineKind::Auto).rule(brians_brain.clone()).build(&grid).unwrap().num_states(), 3);
+        assert!(EngineBuilder::new(EngineKind::Naive).rule(brians_brain).build(&grid).is_err());
+    }
+    
+    #[test]
+    fn test_threads_and_simd_width() {
+        let grid = StandardGrid::random(100, 40, 0.5, 7).unwrap();
+        let mut reference = NaiveEngine::from_grid(&grid);
+        reference.run_steps(5);
+        for simd_width in ULTIMATE_SIMD_WIDTHS {
+            let mut engine = EngineBuilder::new(EngineKind::Ultimate).threads(2).simd_width(simd_width).build(&grid).unwrap();
+            engine.run_steps(5);
+            assert_eq!(engine.state_hash(), reference.state_hash(), "{} lanes", simd_width);
+        }
+        assert!(EngineBuilder::new(EngineKind::Auto).simd_width(3).build(&grid).is_err());
+        assert!(EngineBuilder::new(EngineKind::Naive).simd_width(4).build(&grid).is_err());
+        let fallback = EngineBuilder::new(EngineKind::Auto).simd_width(4).boundary(Boundary::Torus).build(&grid).unwrap();
+        assert_eq!(fallback.benchmark_info().name, "Naive");
+    }
+}
@@ -0,0 +1,55 @@
+//! Saved engine state for cheap save/restore
+
+use crate::error::GameOfLifeError;
+use crate::grid::{BitGrid, Grid};
+
+/// Complete simulation state captured from an engine
+///
+/// Cells are stored bit-packed so snapshots are cheap to take from
+/// `UltimateEngine` and can be restored into any engine of the same size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EngineSnapshot {
+    pub generation: usize,
+    pub grid: BitGrid,
+    /// Per-cell states, row-major, for engines with more than two states
+    pub cell_states: Option<Vec<u8>>,
+}
+
+impl EngineSnapshot {
+    /// Width of the captured grid
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+    
+    /// Height of the captured grid
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+    
+    /// Check that the snapshot can be restored into a grid of the given size
+    pub(crate) fn check_dimensions(&self, width: usize, height: usize) -> Result<(), GameOfLifeError> {
+        if self.width() != width || self.height() != height {
+            return Err(GameOfLifeError::dimensions((width, height), (self.width(), self.height())));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::engines::{GameOfLifeEngine, NaiveEngine};
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_snapshot_serde_round_trip() {
+        let mut engine = NaiveEngine::from_grid(&StandardGrid::random(40, 30, 0.4, 5).unwrap());
+        engine.run_steps(7);
+        let json = serde_json::to_string(&engine.snapshot()).unwrap();
+        
+        let mut restored = NaiveEngine::new(40, 30);
+        restored.restore(&serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(restored.generation(), 7);
+        assert_eq!(restored.state_hash(), engine.state_hash());
+    }
+}
@@ -1,9 +1,15 @@
-use crate::engines::{GameOfLifeEngine, EngineInfo};
-use crate::grid::Grid;
+use crate::engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationObserver, GenerationObservers, Stepped};
+use crate::error::GameOfLifeError;
+use crate::grid::{BitGrid, Grid, Rect};
+use crate::grid::bit::packed_bounding_box;
+use crate::grid::hash::StateHasher;
+use crate::rules::Rule;
+use crate::stats::StepStats;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::fmt::{Display, Formatter};
 use std::mem::swap;
 use std::simd::{LaneCount, Simd, SupportedLaneCount};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::thread::available_parallelism;
 
 /// Ultimate Game of Life engine with configurable SIMD width
@@ -12,7 +18,7 @@ where
     LaneCount<N>: SupportedLaneCount,
 {
     pool: Option<ThreadPool>,
-    field: Vec<u64>,
+    field: Arc<RwLock<Frame>>, // front buffer, shared with FrameReaders
     new_field: Vec<u64>,
     height: usize,        // includes padding (+2)
     columns: usize,       // includes padding and SIMD alignment
@@ -20,6 +26,31 @@ where
     actual_height: usize, // user-visible height
     boundary_masks: Vec<u64>,
     boundary_x_start: usize,
+    observers: GenerationObservers,
+    rule: Rule,
+}
+
+/// Completed generation held in the front buffer
+struct Frame {
+    cells: Vec<u64>,
+    generation: usize,
+}
+
+/// Lock the front buffer for reading, ignoring poisoning from a panicked reader
+fn read_frame(field: &RwLock<Frame>) -> RwLockReadGuard<'_, Frame> {
+    field.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Lock the front buffer for writing, ignoring poisoning from a panicked reader
+fn write_frame(field: &RwLock<Frame>) -> RwLockWriteGuard<'_, Frame> {
+    field.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Word index and bit mask of a cell in a padded field with the given row stride
+fn bit_position(columns: usize, x: usize, y: usize) -> (usize, u64) {
+    let column = x / 64 + 1;  // +1 for padding
+    let bit = 0x8000_0000_0000_0000 >> (x % 64);  // MSB first (reference style)
+    ((y + 1) * columns + column, bit)
 }
 
 /// Helper function for ceiling division
@@ -38,17 +69,27 @@ where
 {
     /// Create a new ultimate engine with the specified grid dimensions
     pub fn new(width: usize, height: usize) -> Self {
+        // Use available parallelism with fallback
+        let threads = available_parallelism()
+            .map(|n| n.into())
+            .unwrap_or(2);
+        Self::with_threads(width, height, threads)
+    }
+
+    /// Create an engine that steps on the calling thread, with no worker pool
+    pub fn sequential(width: usize, height: usize) -> Self {
+        Self { pool: None, ..Self::with_threads(width, height, 1) }
+    }
+
+    /// Create an engine whose stepping loop runs on exactly `threads` worker threads
+    pub fn with_threads(width: usize, height: usize, threads: usize) -> Self {
         // Create thread pool only for native platforms, not WebAssembly
         let pool = if cfg!(target_arch = "wasm32") {
             // WebAssembly: No thread pool needed, we'll run everything sequentially
             None
         } else {
-            // Native platforms: use available parallelism with fallback
-            let threads = available_parallelism()
-                .map(|n| n.into())
-                .unwrap_or(2);
             ThreadPoolBuilder::new()
-                .num_threads(threads)
+                .num_threads(threads.max(1))
                 .build()
                 .ok()
         };
@@ -74,7 +115,10 @@ where
         
         Self {
             pool,
-            field: vec![0; columns * padded_height],
+            field: Arc::new(RwLock::new(Frame {
+                cells: vec![0; columns * padded_height],
+                generation: 0,
+            })),
             new_field: vec![0; columns * padded_height],
             height: padded_height,
             columns,
@@ -82,6 +126,8 @@ where
             actual_height: height,
             boundary_masks,
             boundary_x_start,
+            observers: GenerationObservers::new(),
+            rule: Rule::conway(),
         }
     }
 
@@ -91,9 +137,8 @@ where
             return;
         }
         
-        let column = x / 64 + 1;  // +1 for padding
-        let bit = 0x8000_0000_0000_0000 >> (x % 64);  // MSB first (reference style)
-        self.field[(y + 1) * self.columns + column] |= bit;
+        let (idx, bit) = bit_position(self.columns, x, y);
+        write_frame(&self.field).cells[idx] |= bit;
     }
 
     /// Get a cell from the grid (using 1-based indexing due to padding)
@@ -102,22 +147,33 @@ where
             return false;
         }
         
-        let column = x / 64 + 1;  // +1 for padding
-        let bit = 0x8000_0000_0000_0000 >> (x % 64);  // MSB first (reference style)
-        (self.field[(y + 1) * self.columns + column] & bit) != 0
+        let (idx, bit) = bit_position(self.columns, x, y);
+        (read_frame(&self.field).cells[idx] & bit) != 0
     }
 
     /// Count live cells in the grid
     pub fn count_live_cells(&self) -> usize {
-        let mut count = 0;
-        for y in 0..self.actual_height {
-            for x in 0..self.actual_width {
-                if self.get(x, y) {
-                    count += 1;
-                }
-            }
+        self.population()
+    }
+
+    /// Number of worker threads the stepping loop uses
+    pub fn threads(&self) -> usize {
+        self.pool.as_ref().map_or(1, |pool| pool.current_num_threads())
+    }
+
+    /// Count live cells with a popcount over the packed field (padding is always zero)
+    fn population(&self) -> usize {
+        read_frame(&self.field).cells.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Get a handle that other threads can use to read the latest completed generation
+    pub fn reader(&self) -> FrameReader {
+        FrameReader {
+            field: Arc::clone(&self.field),
+            columns: self.columns,
+            width: self.actual_width,
+            height: self.actual_height,
         }
-        count
     }
 
     /// Reference implementation's optimized full/half adder algorithm
@@ -152,6 +208,58 @@ where
         center
     }
 
+    /// Full/half adder neighbor count followed by an arbitrary B/S rule
+    #[inline(always)]
+    fn sub_step_rule(center: Simd<u64, N>, nbs: &[Simd<u64, N>; 8], rule: Rule) -> Simd<u64, N> {
+        // Same adder network as sub_step
+        let ta0 = nbs[0] ^ nbs[1];
+        let a8 = ta0 ^ nbs[2];
+        let b0 = (nbs[0] & nbs[1]) | (ta0 & nbs[2]);
+
+        let ta3 = nbs[3] ^ nbs[4];
+        let a9 = ta3 ^ nbs[5];
+        let b1 = (nbs[3] & nbs[4]) | (ta3 & nbs[5]);
+
+        let aa = nbs[6] ^ nbs[7];
+        let b2 = nbs[6] & nbs[7];
+
+        let ta8 = a8 ^ a9;
+        let ab = ta8 ^ aa;
+        let b3 = (a8 & a9) | (ta8 & aa);
+
+        let tb0 = b0 ^ b1;
+        let b4 = tb0 ^ b2;
+        let c0 = (b0 & b1) | (tb0 & b2);
+
+        // Neighbor count = ab + 2 * (b3 + b4) + 4 * c0, as 4 bit planes
+        let carry = b3 & b4;
+        let bits = [ab, b3 ^ b4, c0 ^ carry, c0 & carry];
+
+        let mut born = Simd::splat(0);
+        let mut survives = Simd::splat(0);
+        for count in 0..=8u8 {
+            let birth = rule.births_on(count);
+            let survival = rule.survives_on(count);
+            if !birth && !survival {
+                continue;
+            }
+
+            let mut matches = Simd::splat(!0);
+            for (plane, &bit) in bits.iter().enumerate() {
+                matches &= if count & (1 << plane) != 0 { bit } else { !bit };
+            }
+
+            if birth {
+                born |= matches;
+            }
+            if survival {
+                survives |= matches;
+            }
+        }
+
+        (born & !center) | (survives & center)
+    }
+
     /// Get SIMD chunk from field
     #[inline(always)]
     fn get_simd(field: &[u64], i: usize) -> Simd<u64, N> {
@@ -160,141 +268,160 @@ where
 
     /// Step the simulation for the specified number of steps
     pub fn step_batch(&mut self, steps: u32) {
-        for _ in 0..steps {
-            let columns = self.columns;
-            let boundary_x_start = self.boundary_x_start;
-            let boundary_masks = &self.boundary_masks;
-
-            if let Some(ref pool) = self.pool {
-                // Use thread pool for parallel processing
-                let threads = pool.current_num_threads();
-                let simulation_rows = self.height - 2;
-                let chunk_size = (simulation_rows + threads - 1) / threads;
-
-                pool.scope(|scope| {
-                    for (i, target) in self.new_field
-                        [self.columns..self.columns * self.height - self.columns]
-                        .chunks_mut(chunk_size * self.columns)
-                        .enumerate()
-                    {
-                        let field = &self.field;
-                        let boundary_masks = boundary_masks;
-                        scope.spawn(move |_| {
-                        for yl in 0..(target.len() / columns) {
-                            let y = yl + i * chunk_size + 1;
-                            
-                            // Prefetch next row for better cache performance (x86_64 only)
-                            #[cfg(target_arch = "x86_64")]
-                            if yl + 1 < target.len() / columns {
-                                let next_row_start = (y + 1) * columns;
-                                unsafe {
-                                    std::arch::x86_64::_mm_prefetch(
-                                        field.as_ptr().add(next_row_start) as *const i8,
-                                        std::arch::x86_64::_MM_HINT_T0
-                                    );
-                                }
+        self.advance(steps as usize, None);
+    }
+
+    /// Compute the next generation into the back buffer and publish it
+    fn update(&mut self) {
+        let columns = self.columns;
+        let boundary_x_start = self.boundary_x_start;
+        let boundary_masks = &self.boundary_masks;
+        let rule = self.rule;
+        let conway = rule.is_conway();
+
+        // Readers may hold the front buffer concurrently while we fill the back buffer
+        let frame = read_frame(&self.field);
+        let field: &[u64] = &frame.cells;
+
+        if let Some(ref pool) = self.pool {
+            // Use thread pool for parallel processing
+            let threads = pool.current_num_threads();
+            let simulation_rows = self.height - 2;
+            let chunk_size = (simulation_rows + threads - 1) / threads;
+
+            pool.scope(|scope| {
+                for (i, target) in self.new_field
+                    [self.columns..self.columns * self.height - self.columns]
+                    .chunks_mut(chunk_size * self.columns)
+                    .enumerate()
+                {
+                    let boundary_masks = boundary_masks;
+                    scope.spawn(move |_| {
+                    for yl in 0..(target.len() / columns) {
+                        let y = yl + i * chunk_size + 1;
+                        
+                        // Prefetch next row for better cache performance (x86_64 only)
+                        #[cfg(target_arch = "x86_64")]
+                        if yl + 1 < target.len() / columns {
+                            let next_row_start = (y + 1) * columns;
+                            unsafe {
+                                std::arch::x86_64::_mm_prefetch(
+                                    field.as_ptr().add(next_row_start) as *const i8,
+                                    std::arch::x86_64::_MM_HINT_T0
+                                );
                             }
+                        }
+                        
+                        // Process columns in chunks for better cache locality
+                        for x in (1..columns - 1).step_by(N) {
+                            let i = y * columns + x;
+
+                            let center = Self::get_simd(field, i);
+
+                            let mut nbs = [
+                                shr(Self::get_simd(field, i - columns)),
+                                Self::get_simd(field, i - columns),
+                                shl(Self::get_simd(field, i - columns)),
+                                shr(Self::get_simd(field, i)),
+                                shl(Self::get_simd(field, i)),
+                                shr(Self::get_simd(field, i + columns)),
+                                Self::get_simd(field, i + columns),
+                                shl(Self::get_simd(field, i + columns)),
+                            ];
+
+                            // fix bits in neighbouring columns
+                            nbs[0][0] |= (field[i - columns - 1] & 1) << 63;
+                            nbs[2][N - 1] |= (field[i - columns + N] & (1 << 63)) >> 63;
+                            nbs[3][0] |= (field[i - 1] & 0x1) << 63;
+                            nbs[4][N - 1] |= (field[i + N] & (1 << 63)) >> 63;
+                            nbs[5][0] |= (field[i + columns - 1] & 1) << 63;
+                            nbs[7][N - 1] |= (field[i + columns + N] & (1 << 63)) >> 63;
+
+                            let mut result = if conway {
+                                Self::sub_step(center, &nbs)
+                            } else {
+                                Self::sub_step_rule(center, &nbs, rule)
+                            };
                             
-                            // Process columns in chunks for better cache locality
-                            for x in (1..columns - 1).step_by(N) {
-                                let i = y * columns + x;
-
-                                let center = Self::get_simd(field, i);
-
-                                let mut nbs = [
-                                    shr(Self::get_simd(field, i - columns)),
-                                    Self::get_simd(field, i - columns),
-                                    shl(Self::get_simd(field, i - columns)),
-                                    shr(Self::get_simd(field, i)),
-                                    shl(Self::get_simd(field, i)),
-                                    shr(Self::get_simd(field, i + columns)),
-                                    Self::get_simd(field, i + columns),
-                                    shl(Self::get_simd(field, i + columns)),
-                                ];
-
-                                // fix bits in neighbouring columns
-                                nbs[0][0] |= (field[i - columns - 1] & 1) << 63;
-                                nbs[2][N - 1] |= (field[i - columns + N] & (1 << 63)) >> 63;
-                                nbs[3][0] |= (field[i - 1] & 0x1) << 63;
-                                nbs[4][N - 1] |= (field[i + N] & (1 << 63)) >> 63;
-                                nbs[5][0] |= (field[i + columns - 1] & 1) << 63;
-                                nbs[7][N - 1] |= (field[i + columns + N] & (1 << 63)) >> 63;
-
-                                let mut result = Self::sub_step(center, &nbs);
-                                
-                                // Optimized boundary masking using pre-computed masks
-                                // Only apply masking if we're at or beyond the boundary region
-                                if x >= boundary_x_start {
-                                    for lane in 0..N {
-                                        let col_idx = x + lane;
-                                        if col_idx < boundary_masks.len() {
-                                            result[lane] &= boundary_masks[col_idx];
-                                        }
+                            // Optimized boundary masking using pre-computed masks
+                            // Only apply masking if this chunk reaches the boundary region
+                            if x + N > boundary_x_start {
+                                for lane in 0..N {
+                                    let col_idx = x + lane;
+                                    if col_idx < boundary_masks.len() {
+                                        result[lane] &= boundary_masks[col_idx];
                                     }
                                 }
-                                
-                                target[yl * columns + x..yl * columns + x + N]
-                                    .copy_from_slice(result.as_array());
                             }
+                            
+                            target[yl * columns + x..yl * columns + x + N]
+                                .copy_from_slice(result.as_array());
                         }
-                        });
                     }
-                });
-            } else {
-                // Sequential processing for WebAssembly (no thread pool)
-                for target in self.new_field
-                    [self.columns..self.columns * self.height - self.columns]
-                    .chunks_mut(self.columns)
-                    .enumerate()
-                {
-                    let (i, target_row) = target;
-                    let y = i + 1;
-                    let field = &self.field;
+                    });
+                }
+            });
+        } else {
+            // Sequential processing without a thread pool (WebAssembly or `sequential`)
+            for target in self.new_field
+                [self.columns..self.columns * self.height - self.columns]
+                .chunks_mut(self.columns)
+                .enumerate()
+            {
+                let (i, target_row) = target;
+                let y = i + 1;
+                
+                // Process columns in chunks for better cache locality
+                for x in (1..columns - 1).step_by(N) {
+                    let i = y * columns + x;
+
+                    let center = Self::get_simd(field, i);
+
+                    let mut nbs = [
+                        shr(Self::get_simd(field, i - columns)),
+                        Self::get_simd(field, i - columns),
+                        shl(Self::get_simd(field, i - columns)),
+                        shr(Self::get_simd(field, i)),
+                        shl(Self::get_simd(field, i)),
+                        shr(Self::get_simd(field, i + columns)),
+                        Self::get_simd(field, i + columns),
+                        shl(Self::get_simd(field, i + columns)),
+                    ];
+
+                    // fix bits in neighbouring columns
+                    nbs[0][0] |= (field[i - columns - 1] & 1) << 63;
+                    nbs[2][N - 1] |= (field[i - columns + N] & (1 << 63)) >> 63;
+                    nbs[3][0] |= (field[i - 1] & 0x1) << 63;
+                    nbs[4][N - 1] |= (field[i + N] & (1 << 63)) >> 63;
+                    nbs[5][0] |= (field[i + columns - 1] & 1) << 63;
+                    nbs[7][N - 1] |= (field[i + columns + N] & (1 << 63)) >> 63;
+
+                    let mut result = if conway {
+                        Self::sub_step(center, &nbs)
+                    } else {
+                        Self::sub_step_rule(center, &nbs, rule)
+                    };
                     
-                    // Process columns in chunks for better cache locality
-                    for x in (1..columns - 1).step_by(N) {
-                        let i = y * columns + x;
-
-                        let center = Self::get_simd(field, i);
-
-                        let mut nbs = [
-                            shr(Self::get_simd(field, i - columns)),
-                            Self::get_simd(field, i - columns),
-                            shl(Self::get_simd(field, i - columns)),
-                            shr(Self::get_simd(field, i)),
-                            shl(Self::get_simd(field, i)),
-                            shr(Self::get_simd(field, i + columns)),
-                            Self::get_simd(field, i + columns),
-                            shl(Self::get_simd(field, i + columns)),
-                        ];
-
-                        // fix bits in neighbouring columns
-                        nbs[0][0] |= (field[i - columns - 1] & 1) << 63;
-                        nbs[2][N - 1] |= (field[i - columns + N] & (1 << 63)) >> 63;
-                        nbs[3][0] |= (field[i - 1] & 0x1) << 63;
-                        nbs[4][N - 1] |= (field[i + N] & (1 << 63)) >> 63;
-                        nbs[5][0] |= (field[i + columns - 1] & 1) << 63;
-                        nbs[7][N - 1] |= (field[i + columns + N] & (1 << 63)) >> 63;
-
-                        let mut result = Self::sub_step(center, &nbs);
-                        
-                        // Optimized boundary masking using pre-computed masks
-                        // Only apply masking if we're at or beyond the boundary region
-                        if x >= boundary_x_start {
-                            for lane in 0..N {
-                                let col_idx = x + lane;
-                                if col_idx < boundary_masks.len() {
-                                    result[lane] &= boundary_masks[col_idx];
-                                }
+                    // Optimized boundary masking using pre-computed masks
+                    // Only apply masking if this chunk reaches the boundary region
+                    if x + N > boundary_x_start {
+                        for lane in 0..N {
+                            let col_idx = x + lane;
+                            if col_idx < boundary_masks.len() {
+                                result[lane] &= boundary_masks[col_idx];
                             }
                         }
-                        
-                        target_row[x..x + N].copy_from_slice(result.as_array());
                     }
+                    
+                    target_row[x..x + N].copy_from_slice(result.as_array());
                 }
             }
-            swap(&mut self.field, &mut self.new_field);
         }
+        drop(frame);
+
+        let mut frame = write_frame(&self.field);
+        swap(&mut frame.cells, &mut self.new_field);
+        frame.generation += 1;
     }
 
     /// Get columns for debugging
@@ -307,10 +434,15 @@ where
         self.height
     }
 
+    /// Bytes allocated for the front and back buffers and the boundary masks
+    pub fn memory_usage(&self) -> usize {
+        (read_frame(&self.field).cells.capacity() + self.new_field.capacity() + self.boundary_masks.capacity()) * size_of::<u64>()
+    }
+
     /// Get performance statistics
     pub fn performance_stats(&self) -> PerformanceStats {
         PerformanceStats {
-            memory_usage_bytes: (self.field.len() + self.new_field.len()) * 8,
+            memory_usage_bytes: self.memory_usage(),
             bits_per_cell: 1.0,
             simd_enabled: true,
             simd_width: N,
@@ -329,6 +461,69 @@ pub struct PerformanceStats {
     pub parallel_columns: usize,
 }
 
+/// Cloneable handle for reading an engine's latest completed generation from another thread
+#[derive(Clone)]
+pub struct FrameReader {
+    field: Arc<RwLock<Frame>>,
+    columns: usize,
+    width: usize,
+    height: usize,
+}
+
+impl FrameReader {
+    /// Lock the latest completed generation for reading
+    ///
+    /// The engine keeps computing the next generation into its back buffer while
+    /// guards are held; it only waits for readers when swapping the buffers.
+    pub fn read(&self) -> ReadGuard<'_> {
+        ReadGuard {
+            frame: read_frame(&self.field),
+            columns: self.columns,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// Read access to one completed generation of an `UltimateEngine`
+pub struct ReadGuard<'a> {
+    frame: RwLockReadGuard<'a, Frame>,
+    columns: usize,
+    width: usize,
+    height: usize,
+}
+
+impl ReadGuard<'_> {
+    /// Generation held by this guard
+    pub fn generation(&self) -> usize {
+        self.frame.generation
+    }
+
+    /// Get grid width
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get grid height
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the value of a cell (out-of-range cells are dead)
+    pub fn get_cell(&self, row: usize, col: usize) -> bool {
+        if col >= self.width || row >= self.height {
+            return false;
+        }
+        let (idx, bit) = bit_position(self.columns, col, row);
+        (self.frame.cells[idx] & bit) != 0
+    }
+
+    /// Count live cells in this generation
+    pub fn count_live_cells(&self) -> usize {
+        self.frame.cells.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
 /// SIMD shift left with cross-lane handling (reference implementation)
 #[inline(always)]
 pub fn shl<const N: usize>(v: Simd<u64, N>) -> Simd<u64, N>
@@ -357,6 +552,19 @@ where
     (v >> Simd::splat(1)) | neighbouring_bits
 }
 
+impl<const N: usize> Stepped for UltimateEngine<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn next_generation(&mut self) {
+        self.update();
+    }
+
+    fn observers_mut(&mut self) -> &mut GenerationObservers {
+        &mut self.observers
+    }
+}
+
 impl<const N: usize> GameOfLifeEngine for UltimateEngine<N>
 where
     LaneCount<N>: SupportedLaneCount,
@@ -365,21 +573,40 @@ where
         self.step_batch(1);
     }
 
+    fn step_with_stats(&mut self) -> StepStats {
+        self.step_batch(1);
+        // After the buffer swap the back buffer still holds the previous generation
+        let frame = read_frame(&self.field);
+        StepStats::from_words(frame.generation, &self.new_field, &frame.cells)
+    }
+
+    fn bounding_box(&self) -> Option<Rect> {
+        // Scan the packed field in place rather than copying it into a snapshot
+        let frame = read_frame(&self.field);
+        packed_bounding_box(self.actual_height, |y| {
+            let row_start = (y + 1) * self.columns + 1;
+            &frame.cells[row_start..row_start + self.boundary_x_start]
+        })
+    }
+
     fn get_grid(&self) -> &dyn Grid {
         panic!("UltimateEngine doesn't support direct grid access - use get_cell instead")
     }
 
     fn set_grid(&mut self, grid: &dyn Grid) {
-        self.field.fill(0);
+        let mut frame = write_frame(&self.field);
+        frame.cells.fill(0);
         self.new_field.fill(0);
 
         for row in 0..grid.height().min(self.actual_height) {
             for col in 0..grid.width().min(self.actual_width) {
                 if grid.get_cell(row, col) {
-                    self.set(col, row);
+                    let (idx, bit) = bit_position(self.columns, col, row);
+                    frame.cells[idx] |= bit;
                 }
             }
         }
+        frame.generation = 0;
     }
 
     fn benchmark_info(&self) -> EngineInfo {
@@ -401,6 +628,53 @@ where
         self.get(col, row)
     }
 
+    fn generation(&self) -> usize {
+        read_frame(&self.field).generation
+    }
+
+    fn on_generation(&mut self, observer: GenerationObserver) {
+        self.observers.push(observer);
+    }
+
+    fn rule(&self) -> Option<Rule> {
+        Some(self.rule)
+    }
+
+    fn set_rule(&mut self, rule: Rule) -> Result<(), GameOfLifeError> {
+        rule.require_moore("Ultimate engine")?;
+        self.rule = rule;
+        Ok(())
+    }
+
+    fn snapshot(&self) -> EngineSnapshot {
+        // Copy the packed rows straight out of the field, skipping padding
+        let frame = read_frame(&self.field);
+        let mut grid = BitGrid::new(self.actual_width, self.actual_height);
+        for y in 0..self.actual_height {
+            let row_start = (y + 1) * self.columns + 1;
+            grid.row_words_mut(y)
+                .copy_from_slice(&frame.cells[row_start..row_start + self.boundary_x_start]);
+        }
+        EngineSnapshot {
+            generation: frame.generation,
+            grid,
+            cell_states: None,
+        }
+    }
+
+    fn restore(&mut self, snapshot: &EngineSnapshot) -> Result<(), GameOfLifeError> {
+        snapshot.check_dimensions(self.actual_width, self.actual_height)?;
+
+        let mut frame = write_frame(&self.field);
+        for y in 0..self.actual_height {
+            let row_start = (y + 1) * self.columns + 1;
+            frame.cells[row_start..row_start + self.boundary_x_start]
+                .copy_from_slice(snapshot.grid.row_words(y));
+        }
+        frame.generation = snapshot.generation;
+        Ok(())
+    }
+
     fn width(&self) -> usize {
         self.actual_width
     }
@@ -410,19 +684,32 @@ where
     }
 
     fn count_live_cells(&self) -> usize {
-        let mut count = 0;
-        for row in 0..self.actual_height {
-            for col in 0..self.actual_width {
-                if self.get(col, row) {
-                    count += 1;
-                }
+        self.population()
+    }
+
+    fn run_steps(&mut self, steps: usize) {
+        self.advance(steps, None);
+    }
+
+    fn run_steps_cancellable(&mut self, steps: usize, cancel: &CancellationToken) -> usize {
+        self.advance(steps, Some(cancel))
+    }
+
+    fn state_hash(&self) -> u64 {
+        // Rows are already packed MSB-first with zeroed tail bits, so hash the words directly
+        let frame = read_frame(&self.field);
+        let mut hasher = StateHasher::new(self.actual_width, self.actual_height);
+        for y in 1..=self.actual_height {
+            let row_start = y * self.columns + 1;
+            for &word in &frame.cells[row_start..row_start + self.boundary_x_start] {
+                hasher.write_word(word);
             }
         }
-        count
+        hasher.finish()
     }
 
-    fn run_steps(&mut self, steps: usize) {
-        self.step_batch(steps as u32);
+    fn memory_usage(&self) -> usize {
+        UltimateEngine::memory_usage(self)
     }
 }
 
@@ -431,11 +718,14 @@ where
     LaneCount<N>: SupportedLaneCount,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // One read guard for the whole frame instead of one per cell
+        let cells = &read_frame(&self.field).cells;
         let mut frame = String::new();
 
         for y in 0..self.actual_height {
             for x in 0..self.actual_width {
-                if self.get(x, y) {
+                let (idx, bit) = bit_position(self.columns, x, y);
+                if cells[idx] & bit != 0 {
                     frame.push('█');
                 } else {
                     frame.push('.');
@@ -494,6 +784,40 @@ pub fn auto_from_grid_ultimate_engine(grid: &dyn Grid) -> Box<dyn GameOfLifeEngi
     }
 }
 
+/// SIMD widths, in u64 lanes, that `ultimate_engine_from_grid` can build engines for
+pub const ULTIMATE_SIMD_WIDTHS: [usize; 5] = [1, 2, 4, 8, 16];
+
+/// Create an UltimateEngine from a grid with a SIMD width chosen at runtime
+///
+/// The engine runs on `threads` worker threads, or on every available core
+/// when `threads` is `None`. Fails unless `simd_width` is one of
+/// `ULTIMATE_SIMD_WIDTHS`.
+pub fn ultimate_engine_from_grid(grid: &dyn Grid, simd_width: usize, threads: Option<usize>) -> Result<Box<dyn GameOfLifeEngine>, GameOfLifeError> {
+    fn build<const N: usize>(grid: &dyn Grid, threads: Option<usize>) -> Box<dyn GameOfLifeEngine>
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        let mut engine = match threads {
+            Some(threads) => UltimateEngine::<N>::with_threads(grid.width(), grid.height(), threads),
+            None => UltimateEngine::<N>::new(grid.width(), grid.height()),
+        };
+        engine.set_grid(grid);
+        Box::new(engine)
+    }
+
+    match simd_width {
+        1 => Ok(build::<1>(grid, threads)),
+        2 => Ok(build::<2>(grid, threads)),
+        4 => Ok(build::<4>(grid, threads)),
+        8 => Ok(build::<8>(grid, threads)),
+        16 => Ok(build::<16>(grid, threads)),
+        other => Err(GameOfLifeError::invalid(format!(
+            "Unsupported SIMD width {}, expected one of {:?}",
+            other, ULTIMATE_SIMD_WIDTHS
+        ))),
+    }
+}
+
 /// Create an UltimateEngine with automatic SIMD width detection and runtime error handling
 pub fn safe_auto_new_ultimate_engine(width: usize, height: usize) -> Box<dyn GameOfLifeEngine> {
     if simd_supported() {
@@ -0,0 +1,124 @@
+//! Crate-wide error type
+
+use crate::formats::{FormatError, RuleHeaderError};
+use crate::grid::GridError;
+use crate::rules::RuleStringError;
+use thiserror::Error;
+
+/// Error returned by grid constructors, rule parsers and engines
+#[derive(Debug, Error)]
+pub enum GameOfLifeError {
+    /// Text could not be parsed; `line` and `column` are 1-based
+    #[error("{message} (line {line}, column {column})")]
+    Parse { line: usize, column: usize, message: String },
+    /// Two grids, or a grid and a snapshot, do not have the same size
+    #[error("Expected a {expected_width}x{expected_height} grid, found {width}x{height}")]
+    DimensionMismatch { expected_width: usize, expected_height: usize, width: usize, height: usize },
+    /// The engine or rule cannot provide the requested capability
+    #[error("{component} does not support {capability}")]
+    Unsupported { component: String, capability: String },
+    /// An argument is outside the accepted range
+    #[error("{0}")]
+    InvalidArgument(String),
+    /// An engine's state after a run differs from a reference engine's
+    #[error("{configuration} diverged from the reference engine after {generations} generations")]
+    Diverged { configuration: String, generations: usize },
+    /// A checked grid access failed
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    /// A rulestring could not be parsed or converted
+    #[error(transparent)]
+    RuleString(#[from] RuleStringError),
+    /// A pattern file's rule header could not be applied
+    ///
+    /// Boxed, like `Format`, because it can itself wrap a `GameOfLifeError`.
+    #[error(transparent)]
+    RuleHeader(Box<RuleHeaderError>),
+    /// Reading or writing a pattern or state file failed
+    #[error(transparent)]
+    Format(Box<FormatError>),
+    /// An I/O operation failed
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A JSON document, such as a saved benchmark baseline, is malformed or has the wrong shape
+    #[cfg(feature = "serde")]
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl From<RuleHeaderError> for GameOfLifeError {
+    fn from(err: RuleHeaderError) -> Self {
+        GameOfLifeError::RuleHeader(Box::new(err))
+    }
+}
+
+impl From<FormatError> for GameOfLifeError {
+    fn from(err: FormatError) -> Self {
+        GameOfLifeError::Format(Box::new(err))
+    }
+}
+
+impl GameOfLifeError {
+    /// Parse error for `fragment`, located by its position within `input`
+    ///
+    /// Falls back to the start of `input` when `fragment` is not a slice of it.
+    pub(crate) fn parse(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + fragment.len() <= input.len())
+            .unwrap_or(0);
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        GameOfLifeError::Parse { line, column, message: message.into() }
+    }
+    
+    /// Dimension mismatch between the expected and actual sizes
+    pub(crate) fn dimensions(expected: (usize, usize), found: (usize, usize)) -> Self {
+        GameOfLifeError::DimensionMismatch {
+            expected_width: expected.0,
+            expected_height: expected.1,
+            width: found.0,
+            height: found.1,
+        }
+    }
+    
+    /// Shorthand for an unsupported capability of a named engine or rule
+    pub(crate) fn unsupported(component: impl Into<String>, capability: impl Into<String>) -> Self {
+        GameOfLifeError::Unsupported { component: component.into(), capability: capability.into() }
+    }
+    
+    /// Shorthand for an out-of-range argument
+    pub(crate) fn invalid(message: impl Into<String>) -> Self {
+        GameOfLifeError::InvalidArgument(message.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_locates_fragment() {
+        let input = "x = 3\nB3/S2q";
+        let err = GameOfLifeError::parse(input, &input[11..], "Invalid neighbor count 'q'");
+        assert!(matches!(err, GameOfLifeError::Parse { line: 2, column: 6, .. }));
+        assert_eq!(err.to_string(), "Invalid neighbor count 'q' (line 2, column 6)");
+        
+        // Fragments from another string point at the start
+        let err = GameOfLifeError::parse(input, "q", "oops");
+        assert!(matches!(err, GameOfLifeError::Parse { line: 1, column: 1, .. }));
+    }
+    
+    #[test]
+    fn test_wrapped_errors_keep_their_messages() {
+        let grid = GridError::OutOfBounds { row: 5, col: 0, width: 2, height: 2 };
+        assert_eq!(GameOfLifeError::from(grid.clone()).to_string(), grid.to_string());
+        assert_eq!(
+            GameOfLifeError::dimensions((4, 3), (3, 4)).to_string(),
+            "Expected a 4x3 grid, found 3x4"
+        );
+    }
+}
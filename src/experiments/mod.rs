@@ -0,0 +1,5 @@
+//! Experiments that run many seeded simulations and summarize the outcomes
+
+pub mod monte_carlo;
+
+pub use monte_carlo::{MonteCarlo, ParameterValue, Sweep, SweepPoint};
@@ -0,0 +1,224 @@
+//! Monte Carlo runs: sweep one parameter over many seeded random trials
+
+use crate::engines::{GameOfLifeEngine, NaiveEngine};
+use crate::error::GameOfLifeError;
+use crate::grid::StandardGrid;
+use crate::rules::Rule;
+use crate::stats::Distribution;
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/// Parameter to vary, with the values to try
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sweep {
+    /// Probability that each cell starts alive
+    Density(Vec<f64>),
+    /// Life-like rule to run under
+    Rule(Vec<Rule>),
+    /// Grid width and height
+    GridSize(Vec<(usize, usize)>),
+}
+
+impl Sweep {
+    /// The values in the sweep, in order
+    pub fn values(&self) -> Vec<ParameterValue> {
+        match self {
+            Sweep::Density(values) => values.iter().map(|&density| ParameterValue::Density(density)).collect(),
+            Sweep::Rule(values) => values.iter().map(|&rule| ParameterValue::Rule(rule)).collect(),
+            Sweep::GridSize(values) => values.iter().map(|&(width, height)| ParameterValue::GridSize(width, height)).collect(),
+        }
+    }
+}
+
+/// One value of the swept parameter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParameterValue {
+    Density(f64),
+    Rule(Rule),
+    GridSize(usize, usize),
+}
+
+impl Display for ParameterValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterValue::Density(density) => write!(f, "density {}", density),
+            ParameterValue::Rule(rule) => write!(f, "rule {}", rule),
+            ParameterValue::GridSize(width, height) => write!(f, "{}x{}", width, height),
+        }
+    }
+}
+
+/// Outcome of every trial at one value of the swept parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepPoint {
+    pub value: ParameterValue,
+    /// Generations until each trial entered its final cycle, or the generation limit if it never did
+    pub lifespan: Distribution,
+    /// Fraction of cells alive at the end of each trial
+    pub final_density: Distribution,
+    /// Trials still changing when the generation limit ran out
+    pub unstabilized: usize,
+}
+
+/// Runs seeded random trials on worker threads for each value of a parameter sweep
+///
+/// Every trial starts from a random grid and runs until the whole grid
+/// repeats a previous state. Trial `t` at sweep value `i` uses seed
+/// `seed + i * trials + t`, so results do not depend on the number of threads.
+#[derive(Debug, Clone)]
+pub struct MonteCarlo {
+    sweep: Sweep,
+    trials: usize,
+    seed: u64,
+    width: usize,
+    height: usize,
+    density: f64,
+    rule: Rule,
+    max_generations: usize,
+}
+
+impl MonteCarlo {
+    /// Sweep `sweep` with 100 trials per value on 64x64 grids at density 0.375 under Conway's rule
+    pub fn new(sweep: Sweep) -> Self {
+        Self {
+            sweep,
+            trials: 100,
+            seed: 0,
+            width: 64,
+            height: 64,
+            density: 0.375,
+            rule: Rule::conway(),
+            max_generations: 10_000,
+        }
+    }
+    
+    /// Change the number of trials per sweep value
+    pub fn with_trials(mut self, trials: usize) -> Self {
+        self.trials = trials;
+        self
+    }
+    
+    /// Change the seed of the first trial
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+    
+    /// Change the grid size used when the sweep does not vary it
+    pub fn with_grid_size(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+    
+    /// Change the initial density used when the sweep does not vary it
+    pub fn with_density(mut self, density: f64) -> Self {
+        self.density = density;
+        self
+    }
+    
+    /// Change the rule used when the sweep does not vary it
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+    
+    /// Stop trials that are still changing after this many generations
+    pub fn with_max_generations(mut self, max_generations: usize) -> Self {
+        self.max_generations = max_generations;
+        self
+    }
+    
+    /// Run every trial and summarize each sweep value
+    ///
+    /// Fails before running anything if a density is outside 0..=1 or a rule
+    /// is not supported by `NaiveEngine`.
+    pub fn run(&self) -> Result<Vec<SweepPoint>, GameOfLifeError> {
+        let values = self.sweep.values();
+        for value in &values {
+            let (_, _, density, rule) = self.settings(value);
+            if !(0.0..=1.0).contains(&density) {
+                return Err(GameOfLifeError::invalid(format!("Density must be between 0 and 1, got {}", density)));
+            }
+            NaiveEngine::new(1, 1).set_rule(rule)?;
+        }
+        
+        let trials = self.trials;
+        let outcomes: Vec<(usize, f64, bool)> = (0..values.len() * trials)
+            .into_par_iter()
+            .map(|index| self.trial(&values[index / trials], self.seed.wrapping_add(index as u64)))
+            .collect();
+        
+        Ok(values
+            .iter()
+            .zip(outcomes.chunks(trials.max(1)))
+            .map(|(&value, chunk)| SweepPoint {
+                value,
+                lifespan: Distribution::from_samples(chunk.iter().map(|&(lifespan, _, _)| lifespan as f64).collect()),
+                final_density: Distribution::from_samples(chunk.iter().map(|&(_, density, _)| density).collect()),
+                unstabilized: chunk.iter().filter(|&&(_, _, stable)| !stable).count(),
+            })
+            .collect())
+    }
+    
+    /// Width, height, density and rule for one sweep value
+    fn settings(&self, value: &ParameterValue) -> (usize, usize, f64, Rule) {
+        match *value {
+            ParameterValue::Density(density) => (self.width, self.height, density, self.rule),
+            ParameterValue::Rule(rule) => (self.width, self.height, self.density, rule),
+            ParameterValue::GridSize(width, height) => (width, height, self.density, self.rule),
+        }
+    }
+    
+    /// Run one trial, returning its lifespan, final density and whether it stabilized
+    fn trial(&self, value: &ParameterValue, seed: u64) -> (usize, f64, bool) {
+        let (width, height, density, rule) = self.settings(value);
+        let soup = StandardGrid::random(width, height, density, seed).expect("densities are checked before any trial runs");
+        let mut engine = NaiveEngine::from_grid(&soup);
+        engine.set_rule(rule).expect("rules are checked before any trial runs");
+        let report = engine.run_until_stable(self.max_generations);
+        let cells = (width * height).max(1) as f64;
+        let final_density = engine.count_live_cells() as f64 / cells;
+        match report.stabilized_at {
+            Some(generation) => (generation, final_density, true),
+            None => (report.steps_run, final_density, false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_density_sweep_is_deterministic() {
+        let experiment = MonteCarlo::new(Sweep::Density(vec![0.05, 0.375])).with_trials(6).with_grid_size(24, 24).with_seed(7);
+        let points = experiment.run().unwrap();
+        assert_eq!(points, experiment.run().unwrap());
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].lifespan.len(), 6);
+        assert_eq!(points[0].value.to_string(), "density 0.05");
+        
+        // Sparse soups die off almost at once; denser ones churn for a while
+        assert!(points[0].lifespan.mean < points[1].lifespan.mean, "{:?}", points);
+        let (low, high) = points[1].lifespan.confidence_interval();
+        assert!(low <= points[1].lifespan.mean && points[1].lifespan.mean <= high);
+    }
+    
+    #[test]
+    fn test_rule_and_size_sweeps() {
+        // With no births or survivals every trial is empty after one generation
+        let rules = Sweep::Rule(vec!["B/S".parse().unwrap(), Rule::conway()]);
+        let points = MonteCarlo::new(rules).with_trials(4).with_grid_size(16, 16).run().unwrap();
+        assert_eq!(points[0].final_density.max(), Some(0.0));
+        assert_eq!(points[0].lifespan.max(), Some(1.0));
+        assert_eq!(points[0].unstabilized, 0);
+        
+        let sizes = MonteCarlo::new(Sweep::GridSize(vec![(8, 8), (16, 12)])).with_trials(3).with_max_generations(50);
+        let points = sizes.run().unwrap();
+        assert_eq!(points[1].value.to_string(), "16x12");
+        assert!(points.iter().all(|point| point.lifespan.max().unwrap() <= 50.0));
+        
+        assert!(MonteCarlo::new(Sweep::Density(vec![1.5])).run().is_err());
+    }
+}
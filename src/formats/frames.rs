@@ -0,0 +1,157 @@
+//! Animation export: APNG files and numbered PNG frame directories
+//!
+//! Frames are rendered with `png::render`, live cells black on white at an
+//! integer scale. Requires the `image` feature.
+
+use super::png::{check_scale, render};
+use super::FormatError;
+use crate::error::GameOfLifeError;
+use crate::grid::Grid;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest written by `FrameDirectory::finish`
+pub const MANIFEST_NAME: &str = "manifest.csv";
+
+/// Write frames as an animated PNG that loops forever
+///
+/// Every frame is shown for `delay_ms` milliseconds. All frames must have the
+/// same dimensions, and `scale` must not be zero.
+pub fn write_apng<G: Grid>(path: impl AsRef<Path>, frames: &[G], scale: u32, delay_ms: u16) -> Result<(), FormatError> {
+    let Some(first) = frames.first() else {
+        return Err(FormatError::rejected(GameOfLifeError::invalid("APNG needs at least one frame")));
+    };
+    let (width, height) = (first.width(), first.height());
+    if let Some(frame) = frames.iter().find(|frame| frame.width() != width || frame.height() != height) {
+        return Err(FormatError::rejected(GameOfLifeError::dimensions((width, height), (frame.width(), frame.height()))));
+    }
+    check_scale(scale)?;
+    
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = ::png::Encoder::new(file, width as u32 * scale, height as u32 * scale);
+    encoder.set_color(::png::ColorType::Grayscale);
+    encoder.set_depth(::png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0).map_err(std::io::Error::from)?;
+    encoder.set_frame_delay(delay_ms, 1000).map_err(std::io::Error::from)?;
+    
+    let mut writer = encoder.write_header().map_err(std::io::Error::from)?;
+    for frame in frames {
+        writer.write_image_data(render(frame, scale)?.as_raw()).map_err(std::io::Error::from)?;
+    }
+    writer.finish().map_err(std::io::Error::from)?;
+    Ok(())
+}
+
+/// Writes frames one at a time as `frame_000000.png`, `frame_000001.png`, ...
+///
+/// `finish` adds a CSV manifest listing each frame's file and population, so
+/// video editors and scripts can import the sequence without sorting names.
+pub struct FrameDirectory {
+    dir: PathBuf,
+    scale: u32,
+    frames: Vec<(String, usize)>,
+}
+
+impl FrameDirectory {
+    /// Create (if needed) the output directory
+    ///
+    /// Fails if `scale` is zero.
+    pub fn create(dir: impl Into<PathBuf>, scale: u32) -> Result<Self, FormatError> {
+        check_scale(scale)?;
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir, scale, frames: Vec::new() })
+    }
+    
+    /// Number of frames written so far
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+    
+    /// Whether no frames have been written
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+    
+    /// Render and write the next frame, returning its path
+    pub fn push(&mut self, grid: &dyn Grid) -> Result<PathBuf, FormatError> {
+        let name = format!("frame_{:06}.png", self.frames.len());
+        let path = self.dir.join(&name);
+        render(grid, self.scale)?.save_with_format(&path, image::ImageFormat::Png)?;
+        self.frames.push((name, grid.count_live_cells()));
+        Ok(path)
+    }
+    
+    /// Write the manifest and return its path
+    pub fn finish(self) -> Result<PathBuf, FormatError> {
+        let path = self.dir.join(MANIFEST_NAME);
+        let mut out = BufWriter::new(File::create(&path)?);
+        writeln!(out, "frame,file,population")?;
+        for (index, (name, population)) in self.frames.iter().enumerate() {
+            writeln!(out, "{},{},{}", index, name, population)?;
+        }
+        out.flush()?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{GameOfLifeEngine, NaiveEngine};
+    use crate::grid::{Rect, StandardGrid};
+    
+    fn blinker_frames() -> Vec<StandardGrid> {
+        let start = StandardGrid::from_string_pattern(&[".....", ".....", ".###.", ".....", "....."], '#', '.').unwrap();
+        let mut engine = NaiveEngine::from_grid(&start);
+        let mut frames = vec![start];
+        for _ in 0..3 {
+            engine.step();
+            frames.push(engine.get_grid().crop(Rect::new(0, 0, 5, 5)));
+        }
+        frames
+    }
+    
+    #[test]
+    fn test_apng_is_animated() {
+        let path = std::env::temp_dir().join(format!("gol_frames_{}.png", std::process::id()));
+        write_apng(&path, &blinker_frames(), 2, 100).unwrap();
+        let decoder = ::png::Decoder::new(std::io::BufReader::new(File::open(&path).unwrap()));
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (10, 10));
+        assert_eq!(info.animation_control.map(|control| control.num_frames), Some(4));
+        drop(reader);
+        std::fs::remove_file(&path).unwrap();
+        
+        assert!(matches!(
+            write_apng::<StandardGrid>(&path, &[], 1, 100),
+            Err(FormatError::Rejected(GameOfLifeError::InvalidArgument(_)))
+        ));
+        assert!(matches!(
+            write_apng(&path, &[StandardGrid::new(2, 2), StandardGrid::new(3, 2)], 1, 100),
+            Err(FormatError::Rejected(GameOfLifeError::DimensionMismatch { width: 3, .. }))
+        ));
+        assert!(write_apng(&path, &blinker_frames(), 0, 100).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+    
+    #[test]
+    fn test_frame_directory_manifest() {
+        let dir = std::env::temp_dir().join(format!("gol_frame_dir_{}", std::process::id()));
+        let mut frames = FrameDirectory::create(&dir, 3).unwrap();
+        for frame in blinker_frames() {
+            frames.push(&frame).unwrap();
+        }
+        assert_eq!(frames.len(), 4);
+        let manifest = std::fs::read_to_string(frames.finish().unwrap()).unwrap();
+        let last = crate::formats::png::read(dir.join("frame_000003.png"), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        assert_eq!(manifest.lines().next(), Some("frame,file,population"));
+        assert_eq!(manifest.lines().nth(4), Some("3,frame_000003.png,3"));
+        assert_eq!(last.unwrap(), blinker_frames()[3]);
+        assert!(FrameDirectory::create(&dir, 0).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+//! Life 1.06 coordinate-list import and export
+//!
+//! A `#Life 1.06` header followed by one `x y` pair per live cell. Coordinates
+//! may be negative; on import the pattern is shifted so its bounding box
+//! starts at the grid origin.
+
+use super::{FormatError, PatternMeta, MAX_DENSE_CELLS};
+use crate::grid::{Grid, StandardGrid};
+use crate::rules::RuleString;
+
+/// Header line identifying the format
+pub const HEADER: &str = "#Life 1.06";
+
+/// Parse a Life 1.06 coordinate list into a grid just large enough to hold it
+///
+/// Besides the header, `#N` (name), `#O` (author), `#R` (rule) and `#D`/`#C`
+/// (description) lines written by some tools are recorded in the metadata.
+/// Fails with `FormatError::TooLarge` if the cells span more than
+/// `MAX_DENSE_CELLS`.
+pub fn parse(text: &str) -> Result<(StandardGrid, PatternMeta), FormatError> {
+    let mut lines = text.lines().enumerate().map(|(idx, line)| (idx + 1, line.trim()));
+    match lines.next() {
+        Some((_, line)) if line.eq_ignore_ascii_case(HEADER) => {}
+        _ => return Err(FormatError::syntax(1, format!("Expected '{}' header", HEADER))),
+    }
+    
+    let mut meta = PatternMeta::default();
+    let mut cells = Vec::new();
+    for (line_no, line) in lines {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            let (kind, rest) = comment.split_at(comment.chars().next().map_or(0, char::len_utf8));
+            let rest = rest.trim();
+            match kind {
+                "N" => meta.name = Some(rest.to_string()),
+                "O" => meta.author = Some(rest.to_string()),
+                "R" => meta.rule = Some(RuleString::parse(rest).map_err(FormatError::InvalidRule)?),
+                "D" | "C" => meta.comments.push(rest.to_string()),
+                _ => {}
+            }
+            continue;
+        }
+        
+        let mut fields = line.split_whitespace().map(|field| field.parse::<i64>());
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) => cells.push((x, y)),
+            _ => return Err(FormatError::syntax(line_no, format!("Expected 'x y' coordinates, found '{}'", line))),
+        }
+    }
+    
+    let Some(&(first_x, first_y)) = cells.first() else {
+        return Ok((StandardGrid::new(0, 0), meta));
+    };
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (first_x, first_x, first_y, first_y);
+    for &(x, y) in &cells {
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+    }
+    
+    // abs_diff cannot overflow the way max - min can; only the + 1 needs checking
+    let span = |min: i64, max: i64| max.abs_diff(min).saturating_add(1);
+    let (width, height) = (span(min_x, max_x), span(min_y, max_y));
+    if width.checked_mul(height).is_none_or(|cells| cells > MAX_DENSE_CELLS) {
+        return Err(FormatError::TooLarge { width, height });
+    }
+    let (width, height) = (width as usize, height as usize);
+    let mut grid = StandardGrid::new(width, height);
+    grid.set_cells(&mut cells.into_iter().map(|(x, y)| (y.abs_diff(min_y) as usize, x.abs_diff(min_x) as usize, true)));
+    Ok((grid, meta))
+}
+
+/// Write a grid's live cells as a Life 1.06 coordinate list, with x as the column and y as the row
+///
+/// Metadata goes in the `#N`, `#O`, `#R` and `#D` lines that `parse` reads.
+pub fn write(grid: &dyn Grid, meta: &PatternMeta) -> String {
+    let mut out = String::from(HEADER);
+    out.push('\n');
+    if let Some(name) = &meta.name {
+        out.push_str(&format!("#N {}\n", name));
+    }
+    if let Some(author) = &meta.author {
+        out.push_str(&format!("#O {}\n", author));
+    }
+    if let Some(rule) = &meta.rule {
+        out.push_str(&format!("#R {}\n", rule));
+    }
+    for comment in &meta.comments {
+        out.push_str(&format!("#D {}\n", comment));
+    }
+    for row in 0..grid.height() {
+        for (col, alive) in grid.row(row).enumerate() {
+            if alive {
+                out.push_str(&format!("{} {}\n", col, row));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::grids_equal;
+    
+    #[test]
+    fn test_parse_negative_coordinates() {
+        let text = "#Life 1.06\n#N Glider\n#D Travels diagonally\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+        let (grid, meta) = parse(text).unwrap();
+        let expected = StandardGrid::from_string_pattern(&[".#.", "..#", "###"], '#', '.').unwrap();
+        assert!(grids_equal(&grid, &expected));
+        assert_eq!(meta.name.as_deref(), Some("Glider"));
+        assert_eq!(meta.comments, vec!["Travels diagonally".to_string()]);
+    }
+    
+    #[test]
+    fn test_round_trip_and_errors() {
+        let grid = StandardGrid::from_string_pattern(&["#..#", "....", ".##."], '#', '.').unwrap();
+        let text = write(&grid, &PatternMeta::default());
+        assert!(text.starts_with("#Life 1.06\n0 0\n3 0\n"));
+        assert!(grids_equal(&parse(&text).unwrap().0, &grid));
+        
+        let meta = PatternMeta {
+            name: Some("Pond".to_string()),
+            comments: vec!["Still life".to_string()],
+            ..PatternMeta::default()
+        };
+        assert_eq!(parse(&write(&grid, &meta)).unwrap().1, meta);
+        
+        assert_eq!(parse("#Life 1.06\n").unwrap().0.width(), 0);
+        assert!(matches!(parse("0 0\n"), Err(FormatError::Syntax { line: 1, .. })));
+        assert!(matches!(parse("#Life 1.06\n1 2 3\n"), Err(FormatError::Syntax { line: 2, .. })));
+        
+        // Far-apart cells would need an enormous grid, or overflow the span outright
+        let extremes = format!("#Life 1.06\n{} 0\n{} 0\n", i64::MIN, i64::MAX);
+        assert!(matches!(parse(&extremes), Err(FormatError::TooLarge { width: u64::MAX, height: 1 })));
+        assert!(matches!(parse("#Life 1.06\n0 0\n1000000000 1000000000\n"), Err(FormatError::TooLarge { .. })));
+    }
+}
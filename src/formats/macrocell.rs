@@ -0,0 +1,306 @@
+//! Golly macrocell (`.mc`) import
+//!
+//! A macrocell file is a hash-consed quadtree: every line defines one node,
+//! numbered from 1 in file order, and the last node is the root. Lines of
+//! `.`, `*` and `$` are 8x8 leaves; `k nw ne sw se` lines are level-k nodes
+//! whose children are earlier node numbers (0 for empty). Multi-state files
+//! also use `1 a b c d` nodes whose children are cell states, which load as
+//! live when non-zero. Because shared subtrees are stored once, a pattern with
+//! an astronomically large bounding box can still be parsed and queried.
+
+use super::{FormatError, PatternMeta, MAX_DENSE_CELLS};
+use crate::grid::{Grid, StandardGrid};
+use crate::rules::RuleString;
+
+/// Largest supported tree level, so the side length fits in a `u64`
+const MAX_LEVEL: u32 = 62;
+
+/// Contents of one quadtree node
+#[derive(Debug, Clone)]
+enum NodeKind {
+    /// Up to 8x8 cells, bit `row * 8 + col`
+    Cells(u64),
+    /// Child node numbers in nw, ne, sw, se order; 0 is the empty node
+    Branch([usize; 4]),
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    level: u32,
+    kind: NodeKind,
+    population: u64,
+    /// Live-cell bounds relative to the node's corner: (min_row, min_col, max_row, max_col)
+    bounds: Option<(u64, u64, u64, u64)>,
+}
+
+/// A pattern loaded from a macrocell file, kept in its quadtree form
+#[derive(Debug, Clone)]
+pub struct Macrocell {
+    /// Nodes by number; index 0 is a placeholder for the empty node
+    nodes: Vec<Node>,
+    root: usize,
+    /// Generation recorded by `#G`, 0 when absent
+    pub generation: u64,
+    /// Rule (`#R`) and comments (`#C`, `#N`, `#O`) from the file
+    pub meta: PatternMeta,
+}
+
+impl Macrocell {
+    /// Tree level of the root; the pattern occupies a square of side `2^level`
+    pub fn level(&self) -> u32 {
+        self.nodes[self.root].level
+    }
+    
+    /// Side length of the root square
+    pub fn side_length(&self) -> u64 {
+        1u64 << self.level()
+    }
+    
+    /// Number of distinct nodes in the file
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - 1
+    }
+    
+    /// Number of live cells, saturating at `u64::MAX`
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root].population
+    }
+    
+    /// Bounds of the live cells within the root square as (row, col, height, width)
+    pub fn bounding_box(&self) -> Option<(u64, u64, u64, u64)> {
+        self.nodes[self.root]
+            .bounds
+            .map(|(min_row, min_col, max_row, max_col)| (min_row, min_col, max_row - min_row + 1, max_col - min_col + 1))
+    }
+    
+    /// Call `f(row, col)` for every live cell, in root-square coordinates
+    pub fn for_each_live_cell(&self, mut f: impl FnMut(u64, u64)) {
+        self.visit(self.root, 0, 0, &mut f);
+    }
+    
+    fn visit(&self, index: usize, row: u64, col: u64, f: &mut impl FnMut(u64, u64)) {
+        let node = &self.nodes[index];
+        if node.population == 0 {
+            return;
+        }
+        match node.kind {
+            NodeKind::Cells(mut bits) => {
+                while bits != 0 {
+                    let bit = bits.trailing_zeros() as u64;
+                    f(row + bit / 8, col + bit % 8);
+                    bits &= bits - 1;
+                }
+            }
+            NodeKind::Branch(children) => {
+                let half = 1u64 << (node.level - 1);
+                for (quadrant, &child) in children.iter().enumerate() {
+                    let q = quadrant as u64;
+                    self.visit(child, row + (q / 2) * half, col + (q % 2) * half, f);
+                }
+            }
+        }
+    }
+    
+    /// Expand the pattern into a grid cropped to its bounding box
+    ///
+    /// Fails if the bounding box is too large to allocate as a dense grid.
+    pub fn to_grid(&self) -> Result<StandardGrid, FormatError> {
+        let Some((top, left, height, width)) = self.bounding_box() else {
+            return Ok(StandardGrid::new(0, 0));
+        };
+        if height.checked_mul(width).is_none_or(|cells| cells > MAX_DENSE_CELLS) {
+            return Err(FormatError::TooLarge { width, height });
+        }
+        
+        let mut grid = StandardGrid::new(width as usize, height as usize);
+        self.for_each_live_cell(|row, col| grid.set_cell((row - top) as usize, (col - left) as usize, true));
+        Ok(grid)
+    }
+}
+
+/// Parse a macrocell file
+pub fn parse(text: &str) -> Result<Macrocell, FormatError> {
+    let empty = Node { level: 0, kind: NodeKind::Cells(0), population: 0, bounds: None };
+    let mut nodes = vec![empty];
+    let mut generation = 0;
+    let mut meta = PatternMeta::default();
+    
+    for (idx, line) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('[') {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            let (kind, rest) = comment.split_at(comment.chars().next().map_or(0, char::len_utf8));
+            let rest = rest.trim();
+            match kind {
+                "R" => meta.rule = Some(RuleString::parse(rest).map_err(FormatError::InvalidRule)?),
+                "G" => {
+                    generation = rest
+                        .parse()
+                        .map_err(|_| FormatError::syntax(line_no, format!("Invalid generation '{}'", rest)))?;
+                }
+                "N" => meta.name = Some(rest.to_string()),
+                "O" => meta.author = Some(rest.to_string()),
+                "C" => meta.comments.push(rest.to_string()),
+                _ => {}
+            }
+            continue;
+        }
+        
+        let node = if line.starts_with(['.', '*', '$']) {
+            parse_leaf(line_no, line)?
+        } else {
+            parse_branch(line_no, line, &nodes)?
+        };
+        nodes.push(node);
+    }
+    
+    if nodes.len() == 1 {
+        return Err(FormatError::syntax(text.lines().count().max(1), "File defines no nodes"));
+    }
+    let root = nodes.len() - 1;
+    Ok(Macrocell { nodes, root, generation, meta })
+}
+
+/// Parse an 8x8 leaf such as `.**$*..$`
+fn parse_leaf(line_no: usize, line: &str) -> Result<Node, FormatError> {
+    let (mut row, mut col, mut bits) = (0u64, 0u64, 0u64);
+    for (column, ch) in line.chars().enumerate() {
+        match ch {
+            '.' | '*' => {
+                if row >= 8 || col >= 8 {
+                    return Err(FormatError::syntax_at(line_no, column + 1, "Leaf node exceeds 8x8 cells"));
+                }
+                if ch == '*' {
+                    bits |= 1 << (row * 8 + col);
+                }
+                col += 1;
+            }
+            '$' => {
+                row += 1;
+                col = 0;
+            }
+            _ => return Err(FormatError::syntax_at(line_no, column + 1, format!("Unexpected character '{}' in leaf node", ch))),
+        }
+    }
+    Ok(cells_node(3, bits))
+}
+
+/// Parse a `level nw ne sw se` line
+fn parse_branch(line_no: usize, line: &str, nodes: &[Node]) -> Result<Node, FormatError> {
+    let fields = line
+        .split_whitespace()
+        .map(|field| field.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| FormatError::syntax(line_no, format!("Malformed node '{}'", line)))?;
+    let [level, nw, ne, sw, se] = fields[..] else {
+        return Err(FormatError::syntax(line_no, format!("Expected 'level nw ne sw se', found '{}'", line)));
+    };
+    let level = level as u32;
+    if !(1..=MAX_LEVEL).contains(&level) {
+        return Err(FormatError::syntax(line_no, format!("Node level {} is out of range", level)));
+    }
+    let children = [nw, ne, sw, se];
+    
+    // Level 1 children are cell states rather than node numbers
+    if level == 1 {
+        let bits = children
+            .iter()
+            .enumerate()
+            .filter(|&(_, &state)| state != 0)
+            .fold(0u64, |bits, (quadrant, _)| bits | 1 << ((quadrant / 2) * 8 + quadrant % 2));
+        return Ok(cells_node(1, bits));
+    }
+    
+    for &child in &children {
+        match nodes.get(child) {
+            Some(node) if child == 0 || node.level == level - 1 => {}
+            Some(_) => return Err(FormatError::syntax(line_no, format!("Node {} is not at level {}", child, level - 1))),
+            None => return Err(FormatError::syntax(line_no, format!("Node {} is not defined yet", child))),
+        }
+    }
+    
+    let half = 1u64 << (level - 1);
+    let mut population = 0u64;
+    let mut bounds: Option<(u64, u64, u64, u64)> = None;
+    for (quadrant, &child) in children.iter().enumerate() {
+        let node = &nodes[child];
+        population = population.saturating_add(node.population);
+        if let Some((r0, c0, r1, c1)) = node.bounds {
+            let (dr, dc) = ((quadrant as u64 / 2) * half, (quadrant as u64 % 2) * half);
+            let child_bounds = (r0 + dr, c0 + dc, r1 + dr, c1 + dc);
+            bounds = Some(match bounds {
+                None => child_bounds,
+                Some(b) => (b.0.min(child_bounds.0), b.1.min(child_bounds.1), b.2.max(child_bounds.2), b.3.max(child_bounds.3)),
+            });
+        }
+    }
+    Ok(Node { level, kind: NodeKind::Branch(children), population, bounds })
+}
+
+/// Build a leaf-style node from an 8x8 bitmask
+fn cells_node(level: u32, bits: u64) -> Node {
+    let bounds = (bits != 0).then(|| {
+        let live = (0..64u64).filter(|bit| bits >> bit & 1 == 1);
+        live.fold((u64::MAX, u64::MAX, 0, 0), |(r0, c0, r1, c1), bit| {
+            (r0.min(bit / 8), c0.min(bit % 8), r1.max(bit / 8), c1.max(bit % 8))
+        })
+    });
+    Node { level, kind: NodeKind::Cells(bits), population: bits.count_ones() as u64, bounds }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::grids_equal;
+    use crate::rules::Rule;
+    
+    // A glider in the south-east quadrant of a 16x16 root
+    const GLIDER: &str = "[M2] (golly 4.2)
+#R B3/S23
+#G 12
+$$$$$.*$..*$***$
+4 0 0 0 1
+";
+    
+    #[test]
+    fn test_parse_glider() {
+        let pattern = parse(GLIDER).unwrap();
+        assert_eq!(pattern.level(), 4);
+        assert_eq!(pattern.side_length(), 16);
+        assert_eq!(pattern.node_count(), 2);
+        assert_eq!(pattern.population(), 5);
+        assert_eq!(pattern.generation, 12);
+        assert_eq!(pattern.meta.rule, Some(RuleString::Life(Rule::conway())));
+        assert_eq!(pattern.bounding_box(), Some((13, 8, 3, 3)));
+        
+        let expected = StandardGrid::from_string_pattern(&[".#.", "..#", "###"], '#', '.').unwrap();
+        assert!(grids_equal(&pattern.to_grid().unwrap(), &expected));
+    }
+    
+    #[test]
+    fn test_shared_nodes_describe_huge_patterns() {
+        // One live cell per 8x8 leaf, quadrupled through 30 levels of shared nodes
+        let mut text = String::from("[M2]\n*$\n4 1 1 1 1\n");
+        for level in 5..=30 {
+            text.push_str(&format!("{} {} {} {} {}\n", level, level - 3, level - 3, level - 3, level - 3));
+        }
+        let pattern = parse(&text).unwrap();
+        assert_eq!(pattern.level(), 30);
+        assert_eq!(pattern.node_count(), 28);
+        assert_eq!(pattern.population(), 1 << 54);
+        assert_eq!(pattern.bounding_box(), Some((0, 0, (1 << 30) - 7, (1 << 30) - 7)));
+        assert!(matches!(pattern.to_grid(), Err(FormatError::TooLarge { width: 1073741817, height: 1073741817 })));
+    }
+    
+    #[test]
+    fn test_rejects_malformed_nodes() {
+        assert!(matches!(parse("[M2]\n"), Err(FormatError::Syntax { .. })));
+        assert!(matches!(parse("[M2]\n*$\n5 1 0 0 0\n"), Err(FormatError::Syntax { line: 3, .. })));
+        assert!(matches!(parse("[M2]\n4 2 0 0 0\n"), Err(FormatError::Syntax { line: 2, .. })));
+        assert!(matches!(parse("[M2]\n*********$\n"), Err(FormatError::Syntax { line: 2, .. })));
+        assert!(matches!(parse("[M2]\n4 0 0\n"), Err(FormatError::Syntax { line: 2, .. })));
+    }
+}
@@ -0,0 +1,92 @@
+//! Character-matrix patterns: `0`/`1` digits and Unicode block drawings
+//!
+//! Every row must have the same width; there are no comments or headers.
+
+use super::FormatError;
+use crate::grid::{Grid, StandardGrid};
+
+/// Characters read as live cells in a block drawing
+pub const BLOCK_ALIVE: &[char] = &['█', '■', '⬛'];
+
+/// Characters read as dead cells in a block drawing
+pub const BLOCK_DEAD: &[char] = &['·', '⬜', '□', ' '];
+
+/// Parse rows of `1` (alive) and `0` (dead)
+pub fn parse_binary(text: &str) -> Result<StandardGrid, FormatError> {
+    parse_with(text, &['1'], &['0'])
+}
+
+/// Parse rows of block characters such as `█` (alive) and `·` or `⬜` (dead)
+pub fn parse_blocks(text: &str) -> Result<StandardGrid, FormatError> {
+    parse_with(text, BLOCK_ALIVE, BLOCK_DEAD)
+}
+
+/// Write rows of `1` and `0`
+pub fn write_binary(grid: &dyn Grid) -> String {
+    write_with(grid, '1', '0')
+}
+
+/// Write rows of `█` and `·`
+pub fn write_blocks(grid: &dyn Grid) -> String {
+    write_with(grid, '█', '·')
+}
+
+fn write_with(grid: &dyn Grid, alive: char, dead: char) -> String {
+    let mut out = String::new();
+    for row in 0..grid.height() {
+        out.extend(grid.row(row).map(|cell| if cell { alive } else { dead }));
+        out.push('\n');
+    }
+    out
+}
+
+fn parse_with(text: &str, alive: &[char], dead: &[char]) -> Result<StandardGrid, FormatError> {
+    let lines: Vec<&str> = text.trim_end_matches(['\n', '\r']).lines().collect();
+    if lines.is_empty() {
+        return Err(FormatError::syntax(1, "File is empty"));
+    }
+    
+    let mut rows = Vec::with_capacity(lines.len());
+    for (idx, line) in lines.iter().enumerate() {
+        let row = line
+            .chars()
+            .enumerate()
+            .map(|(col, ch)| {
+                if alive.contains(&ch) {
+                    Ok(true)
+                } else if dead.contains(&ch) {
+                    Ok(false)
+                } else {
+                    Err(FormatError::syntax_at(idx + 1, col + 1, format!("Invalid character '{}'", ch)))
+                }
+            })
+            .collect::<Result<Vec<bool>, _>>()?;
+        if let Some(first) = rows.first().map(Vec::len) {
+            if row.len() != first {
+                return Err(FormatError::syntax(idx + 1, format!("Row has length {}, expected {}", row.len(), first)));
+            }
+        } else if row.is_empty() {
+            return Err(FormatError::syntax(1, "Grid width cannot be zero"));
+        }
+        rows.push(row);
+    }
+    StandardGrid::from_cells(rows).map_err(FormatError::rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_binary_and_blocks() {
+        let grid = parse_binary("101\n010\n101\n").unwrap();
+        assert_eq!((grid.width(), grid.height(), grid.count_live_cells()), (3, 3, 5));
+        assert!(matches!(parse_binary("101\n01\n"), Err(FormatError::Syntax { line: 2, .. })));
+        assert!(matches!(parse_binary("101\n0x1\n"), Err(FormatError::Syntax { line: 2, .. })));
+        
+        let blocks = parse_blocks("⬜█⬜\n·██\n").unwrap();
+        assert_eq!((blocks.width(), blocks.height(), blocks.count_live_cells()), (3, 2, 3));
+        assert_eq!(write_blocks(&blocks), "·█·\n·██\n");
+        assert_eq!(parse_binary(&write_binary(&grid)).unwrap(), grid);
+    }
+}
@@ -0,0 +1,312 @@
+//! Pattern file formats and the header handling they share
+
+#[cfg(feature = "image")]
+pub mod frames;
+pub mod life106;
+pub mod macrocell;
+pub mod matrix;
+pub mod pattern;
+pub mod plaintext;
+#[cfg(feature = "image")]
+pub mod png;
+pub mod rle;
+pub mod state;
+pub mod svg;
+pub mod video;
+
+pub use pattern::{Pattern, PatternMeta};
+
+use crate::engines::GameOfLifeEngine;
+use crate::error::GameOfLifeError;
+use crate::grid::{Grid, StandardGrid};
+use crate::rules::{RuleString, RuleStringError};
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+use std::path::Path;
+
+/// Largest bounding box, in cells, that a coordinate-based format is expanded into
+pub(crate) const MAX_DENSE_CELLS: u64 = 1 << 32;
+
+/// Text pattern formats that can be told apart by their content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PatternFormat {
+    /// Run Length Encoded, with an `x = .., y = ..` header
+    Rle,
+    /// `#Life 1.06` coordinate list
+    Life106,
+    /// LifeWiki `.cells` plaintext of `.` and `O`
+    Plaintext,
+    /// Rows of `0` and `1`
+    Binary,
+    /// Rows of Unicode blocks such as `█` and `·`
+    Blocks,
+}
+
+impl PatternFormat {
+    /// Guess the format of a pattern from its content
+    pub fn detect(text: &str) -> Option<PatternFormat> {
+        let first = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+        if first.eq_ignore_ascii_case(life106::HEADER) {
+            return Some(PatternFormat::Life106);
+        }
+        if first.starts_with('!') {
+            return Some(PatternFormat::Plaintext);
+        }
+        
+        // RLE files open with `#` comments or go straight to the header
+        let is_rle_header = |line: &str| line.strip_prefix('x').is_some_and(|rest| rest.trim_start().starts_with('='));
+        if first.starts_with('#') || is_rle_header(first) {
+            let body = text.lines().map(str::trim).find(|line| !line.is_empty() && !line.starts_with('#'));
+            return body.filter(|line| is_rle_header(line)).map(|_| PatternFormat::Rle);
+        }
+        
+        let cells = || text.chars().filter(|ch| !matches!(ch, '\n' | '\r'));
+        if cells().all(|ch| ch == '0' || ch == '1') {
+            Some(PatternFormat::Binary)
+        } else if cells().all(|ch| matrix::BLOCK_ALIVE.contains(&ch) || matrix::BLOCK_DEAD.contains(&ch)) {
+            Some(PatternFormat::Blocks)
+        } else if cells().all(|ch| matches!(ch, '.' | 'O' | 'o' | '*')) {
+            Some(PatternFormat::Plaintext)
+        } else {
+            None
+        }
+    }
+    
+    /// The format conventionally stored under a file's extension, ignoring case
+    ///
+    /// `.rle`, `.cells`, `.lif` and `.life` name their formats; `.txt` is taken
+    /// to hold rows of `0` and `1`.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<PatternFormat> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "rle" => Some(PatternFormat::Rle),
+            "cells" => Some(PatternFormat::Plaintext),
+            "lif" | "life" => Some(PatternFormat::Life106),
+            "txt" => Some(PatternFormat::Binary),
+            _ => None,
+        }
+    }
+    
+    /// Parse a pattern known to be in this format
+    pub fn parse(self, text: &str) -> Result<(StandardGrid, PatternMeta), FormatError> {
+        match self {
+            PatternFormat::Rle => rle::parse(text),
+            PatternFormat::Life106 => life106::parse(text),
+            PatternFormat::Plaintext => plaintext::parse(text),
+            PatternFormat::Binary => Ok((matrix::parse_binary(text)?, PatternMeta::default())),
+            PatternFormat::Blocks => Ok((matrix::parse_blocks(text)?, PatternMeta::default())),
+        }
+    }
+    
+    /// Write a grid in this format, keeping as much of `meta` as the format can hold
+    pub fn write(self, grid: &dyn Grid, meta: &PatternMeta) -> String {
+        match self {
+            PatternFormat::Rle => rle::write(grid, meta),
+            PatternFormat::Life106 => life106::write(grid, meta),
+            PatternFormat::Plaintext => plaintext::write(grid, meta),
+            PatternFormat::Binary => matrix::write_binary(grid),
+            PatternFormat::Blocks => matrix::write_blocks(grid),
+        }
+    }
+}
+
+impl Display for PatternFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PatternFormat::Rle => "RLE",
+            PatternFormat::Life106 => "Life 1.06",
+            PatternFormat::Plaintext => "plaintext",
+            PatternFormat::Binary => "0/1 matrix",
+            PatternFormat::Blocks => "Unicode block",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Detect a pattern's format and parse it
+///
+/// Parse failures are wrapped in `FormatError::InFormat` so the message names
+/// the format the content was taken to be.
+pub fn parse_any(text: &str) -> Result<(StandardGrid, PatternMeta, PatternFormat), FormatError> {
+    let format = PatternFormat::detect(text).ok_or(FormatError::UnrecognizedFormat)?;
+    let (grid, meta) = format
+        .parse(text)
+        .map_err(|error| FormatError::InFormat { format, error: Box::new(error) })?;
+    Ok((grid, meta, format))
+}
+
+/// Error reading or writing a pattern file
+#[derive(Debug, Error)]
+pub enum FormatError {
+    /// The file is malformed; `line` and `column` are 1-based, and `column` is 1 when only the line is known
+    #[error("Line {line}, column {column}: {message}")]
+    Syntax { line: usize, column: usize, message: String },
+    /// The file declares a rule that does not parse
+    #[error("Invalid rule: {0}")]
+    InvalidRule(#[source] RuleStringError),
+    /// The declared rule cannot be applied to the target engine
+    ///
+    /// Boxed because the engine's own error inside would make every `FormatError` large.
+    #[error(transparent)]
+    RuleHeader(Box<RuleHeaderError>),
+    /// Reading or writing the file failed
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Decoding or encoding an image failed
+    #[cfg(feature = "image")]
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+    /// Downloading a pattern failed
+    #[cfg(feature = "net")]
+    #[error("Download failed: {0}")]
+    Network(#[source] Box<ureq::Error>),
+    /// Binary data is truncated, has the wrong magic or is otherwise inconsistent
+    #[error("Corrupt data: {0}")]
+    Corrupt(String),
+    /// Decoded data, or grids passed in to be written, are not valid for the grid, rule or engine involved
+    #[error("Invalid input: {0}")]
+    Rejected(#[source] GameOfLifeError),
+    /// The pattern's bounding box, saturated at `u64::MAX`, is too large to allocate as a grid
+    #[error("Pattern bounding box {width}x{height} is too large for a dense grid")]
+    TooLarge { width: u64, height: u64 },
+    /// The content does not look like any supported format
+    #[error("Unrecognized pattern format")]
+    UnrecognizedFormat,
+    /// Parsing content detected as `format` failed
+    #[error("Invalid {format} pattern: {error}")]
+    InFormat { format: PatternFormat, #[source] error: Box<FormatError> },
+}
+
+impl From<RuleHeaderError> for FormatError {
+    fn from(err: RuleHeaderError) -> Self {
+        FormatError::RuleHeader(Box::new(err))
+    }
+}
+
+impl FormatError {
+    /// Shorthand for a syntax error somewhere on a given line
+    pub(crate) fn syntax(line: usize, message: impl Into<String>) -> Self {
+        Self::syntax_at(line, 1, message)
+    }
+    
+    /// Shorthand for a syntax error at a given line and column
+    pub(crate) fn syntax_at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        FormatError::Syntax { line, column, message: message.into() }
+    }
+    
+    /// Wrap a validation error about decoded data or the grids given to a writer
+    pub(crate) fn rejected(error: GameOfLifeError) -> Self {
+        FormatError::Rejected(error)
+    }
+}
+
+/// Error applying a pattern file's `rule =` header to an engine
+#[derive(Debug, Error)]
+pub enum RuleHeaderError {
+    /// The header is not a valid rulestring
+    #[error("Invalid rule header: {0}")]
+    Invalid(#[from] RuleStringError),
+    /// The rule is valid but the engine cannot simulate it; the rule is boxed to keep the error small
+    #[error("{engine} engine cannot run pattern rule {rule}: {reason}")]
+    Unsupported { rule: Box<RuleString>, engine: String, #[source] reason: GameOfLifeError },
+}
+
+/// Configure an engine from a pattern file's `rule =` header
+///
+/// Life-like rules are applied with `GameOfLifeEngine::set_rule`. Anything the
+/// engine cannot run is reported rather than ignored, so a pattern is never
+/// silently evolved under the wrong rule. Returns the parsed rule on success.
+pub fn apply_rule_header(engine: &mut dyn GameOfLifeEngine, header: &str) -> Result<RuleString, RuleHeaderError> {
+    let rule = RuleString::parse(header)?;
+    let engine_name = engine.benchmark_info().name;
+    
+    match rule.life_rule() {
+        Some(life) => engine.set_rule(life).map_err(|reason| RuleHeaderError::Unsupported {
+            rule: Box::new(rule.clone()),
+            engine: engine_name,
+            reason,
+        })?,
+        None => {
+            return Err(RuleHeaderError::Unsupported {
+                rule: Box::new(rule.clone()),
+                engine: engine_name,
+                reason: GameOfLifeError::invalid("only Life-like rules can be set through the engine trait"),
+            })
+        }
+    }
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{LookupEngine, NaiveEngine};
+    use crate::rules::{MapRule, Rule};
+    
+    #[test]
+    fn test_header_configures_engine() {
+        let mut engine = NaiveEngine::new(8, 8);
+        let rule = apply_rule_header(&mut engine, "B36/S23").unwrap();
+        assert_eq!(rule.to_string(), "B36/S23");
+        assert_eq!(engine.rule().unwrap().to_string(), "B36/S23");
+        
+        // Golly's S/B form of Conway's Life
+        apply_rule_header(&mut engine, "23/3").unwrap();
+        assert_eq!(engine.rule(), Some(Rule::conway()));
+    }
+    
+    #[test]
+    fn test_header_errors() {
+        let mut engine = NaiveEngine::new(8, 8);
+        assert!(matches!(apply_rule_header(&mut engine, "B9/S23"), Err(RuleHeaderError::Invalid(_))));
+        assert!(matches!(apply_rule_header(&mut engine, "B2/S345/C4"), Err(RuleHeaderError::Unsupported { .. })));
+        assert_eq!(engine.rule(), Some(Rule::conway()));
+        
+        // The lookup engine only runs 3x3 neighborhoods
+        let mut lookup = LookupEngine::new(8, 8, MapRule::try_from(Rule::conway()).unwrap());
+        assert!(matches!(apply_rule_header(&mut lookup, "B3/S23/M2"), Err(RuleHeaderError::Unsupported { .. })));
+        
+        // The engine's own error is kept as the source rather than flattened to text
+        let err = apply_rule_header(&mut engine, "B2/S345/C4").unwrap_err();
+        let source = std::error::Error::source(&err).and_then(|source| source.downcast_ref::<GameOfLifeError>());
+        assert!(source.is_some(), "{:?}", err);
+    }
+    
+    #[test]
+    fn test_detects_each_format() {
+        let cases = [
+            ("#N Glider\nx = 3, y = 3\nbo$2bo$3o!\n", PatternFormat::Rle),
+            ("x=3,y=3,rule=B3/S23\nbo$2bo$3o!\n", PatternFormat::Rle),
+            ("#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n", PatternFormat::Life106),
+            ("!Name: Glider\n.O\n..O\nOOO\n", PatternFormat::Plaintext),
+            (".O.\n..O\nOOO\n", PatternFormat::Plaintext),
+            ("010\n001\n111\n", PatternFormat::Binary),
+            ("·█·\n··█\n███\n", PatternFormat::Blocks),
+        ];
+        for (text, expected) in cases {
+            let (grid, _, format) = parse_any(text).unwrap();
+            assert_eq!(format, expected, "{:?}", text);
+            assert_eq!((grid.width(), grid.height(), grid.count_live_cells()), (3, 3, 5), "{:?}", text);
+        }
+    }
+    
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(PatternFormat::from_path("runs/final.rle"), Some(PatternFormat::Rle));
+        assert_eq!(PatternFormat::from_path("Glider.CELLS"), Some(PatternFormat::Plaintext));
+        assert_eq!(PatternFormat::from_path("gun.lif"), Some(PatternFormat::Life106));
+        assert_eq!(PatternFormat::from_path("default.txt"), Some(PatternFormat::Binary));
+        assert_eq!(PatternFormat::from_path("final.mc"), None);
+        assert_eq!(PatternFormat::from_path("final"), None);
+    }
+    
+    #[test]
+    fn test_errors_name_detected_format() {
+        assert!(matches!(parse_any("hello\n"), Err(FormatError::UnrecognizedFormat)));
+        assert!(matches!(parse_any("#C comment only\n"), Err(FormatError::UnrecognizedFormat)));
+        
+        let err = parse_any("x = 2, y = 1\n3o!").unwrap_err();
+        assert!(matches!(&err, FormatError::InFormat { format: PatternFormat::Rle, .. }));
+        assert!(err.to_string().starts_with("Invalid RLE pattern: Line 2, column 2:"), "{}", err);
+    }
+}
@@ -0,0 +1,112 @@
+//! Grids paired with their provenance
+
+use super::{macrocell, parse_any, FormatError, PatternFormat};
+use crate::grid::StandardGrid;
+use crate::rules::RuleString;
+use std::path::Path;
+
+/// Descriptive information carried alongside a pattern
+///
+/// Every text parser fills in what its format records and every text writer
+/// emits what its format can hold, so converting between formats keeps the
+/// name, author and comments wherever possible.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatternMeta {
+    /// Pattern name (`#N` in RLE)
+    pub name: Option<String>,
+    /// Author or discoverer (`#O` in RLE)
+    pub author: Option<String>,
+    /// Free-form comment lines (`#C` in RLE)
+    pub comments: Vec<String>,
+    /// Rule declared by the file, if any
+    pub rule: Option<RuleString>,
+    /// Absolute (x, y) of the pattern's top-left corner (`#CXRLE Pos=`)
+    pub position: Option<(i64, i64)>,
+    /// Generation the pattern was saved at (`#CXRLE Gen=`)
+    pub generation: Option<u64>,
+}
+
+/// A grid together with its metadata
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub grid: StandardGrid,
+    pub meta: PatternMeta,
+}
+
+impl Pattern {
+    /// Wrap a grid with empty metadata
+    pub fn new(grid: StandardGrid) -> Self {
+        Self { grid, meta: PatternMeta::default() }
+    }
+    
+    /// Parse text in any detectable format, or a Golly macrocell file
+    ///
+    /// Macrocell patterns are expanded to a dense grid cropped to their
+    /// bounding box, which fails for patterns too large to allocate.
+    pub fn parse(text: &str) -> Result<Self, FormatError> {
+        if text.trim_start().starts_with("[M2]") {
+            let pattern = macrocell::parse(text)?;
+            let grid = pattern.to_grid()?;
+            return Ok(Self { grid, meta: pattern.meta });
+        }
+        parse_any(text).map(|(grid, meta, _)| Self { grid, meta })
+    }
+    
+    /// Load a pattern file in any detectable format, or a Golly macrocell file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FormatError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+    
+    /// Write the pattern in `format`
+    pub fn to_format(&self, format: PatternFormat) -> String {
+        format.write(&self.grid, &self.meta)
+    }
+    
+    /// Write the pattern to a file in `format`
+    pub fn save(&self, path: impl AsRef<Path>, format: PatternFormat) -> Result<(), FormatError> {
+        std::fs::write(path, self.to_format(format))?;
+        Ok(())
+    }
+}
+
+impl From<(StandardGrid, PatternMeta)> for Pattern {
+    fn from((grid, meta): (StandardGrid, PatternMeta)) -> Self {
+        Self { grid, meta }
+    }
+}
+
+impl From<StandardGrid> for Pattern {
+    fn from(grid: StandardGrid) -> Self {
+        Self::new(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rule;
+    
+    #[test]
+    fn test_metadata_survives_conversion() {
+        let rle = "#N Glider\n#O Richard K. Guy\n#C The smallest spaceship.\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+        let pattern = Pattern::parse(rle).unwrap();
+        assert_eq!(pattern.meta.rule, Some(RuleString::Life(Rule::conway())));
+        
+        let via_life106 = Pattern::parse(&pattern.to_format(PatternFormat::Life106)).unwrap();
+        assert_eq!(via_life106, pattern);
+        
+        // Plaintext has no rule line, but keeps the rest
+        let via_plaintext = Pattern::parse(&pattern.to_format(PatternFormat::Plaintext)).unwrap();
+        assert_eq!(via_plaintext.grid, pattern.grid);
+        assert_eq!(via_plaintext.meta.name.as_deref(), Some("Glider"));
+        assert_eq!(via_plaintext.meta.author.as_deref(), Some("Richard K. Guy"));
+        assert_eq!(via_plaintext.meta.comments, pattern.meta.comments);
+        
+        let back_to_rle = Pattern::parse(&via_life106.to_format(PatternFormat::Rle)).unwrap();
+        assert_eq!(back_to_rle, pattern);
+        
+        let macrocell = Pattern::parse("[M2] (golly 4.2)\n#R B3/S23\n$$$$$.*$..*$***$\n4 0 0 0 1\n").unwrap();
+        assert_eq!(macrocell.grid, pattern.grid);
+        assert_eq!(macrocell.meta.rule, pattern.meta.rule);
+    }
+}
@@ -0,0 +1,102 @@
+//! LifeWiki plaintext (`.cells`) import and export
+//!
+//! `!` lines are comments; `!Name:` and `!Author:` are captured into the
+//! metadata and the rest become description lines. Each remaining line is a
+//! row of `.` (dead) and `O` (alive), with trailing dead cells optional.
+
+use super::{FormatError, PatternMeta};
+use crate::grid::{Grid, StandardGrid};
+
+/// Parse a `.cells` pattern; the grid is as wide as its longest row
+pub fn parse(text: &str) -> Result<(StandardGrid, PatternMeta), FormatError> {
+    let mut meta = PatternMeta::default();
+    let mut rows: Vec<Vec<bool>> = Vec::new();
+    
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if let Some(comment) = line.strip_prefix('!') {
+            // Comments belong to the header; blank comment lines are just spacing
+            if let Some(name) = comment.strip_prefix("Name:") {
+                meta.name = Some(name.trim().to_string());
+            } else if let Some(author) = comment.strip_prefix("Author:") {
+                meta.author = Some(author.trim().to_string());
+            } else if !comment.trim().is_empty() {
+                meta.comments.push(comment.trim().to_string());
+            }
+            continue;
+        }
+        
+        let row = line
+            .chars()
+            .enumerate()
+            .map(|(col, ch)| match ch {
+                'O' | 'o' | '*' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(FormatError::syntax_at(idx + 1, col + 1, format!("Unexpected character '{}'", ch))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(row);
+    }
+    
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut grid = StandardGrid::new(width, rows.len());
+    grid.set_cells(&mut rows.iter().enumerate().flat_map(|(row, cells)| {
+        cells.iter().enumerate().filter(|&(_, &alive)| alive).map(move |(col, _)| (row, col, true))
+    }));
+    Ok((grid, meta))
+}
+
+/// Write a grid as a `.cells` pattern, with a header built from `meta`
+///
+/// Trailing dead cells are trimmed from each row, as LifeWiki files do.
+pub fn write(grid: &dyn Grid, meta: &PatternMeta) -> String {
+    let mut out = String::new();
+    if let Some(name) = &meta.name {
+        out.push_str(&format!("!Name: {}\n", name));
+    }
+    if let Some(author) = &meta.author {
+        out.push_str(&format!("!Author: {}\n", author));
+    }
+    for comment in &meta.comments {
+        out.push_str(&format!("!{}\n", comment));
+    }
+    for row in 0..grid.height() {
+        let line: String = grid.row(row).map(|alive| if alive { 'O' } else { '.' }).collect();
+        out.push_str(line.trim_end_matches('.'));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::grids_equal;
+    
+    #[test]
+    fn test_parse_lifewiki_glider() {
+        let text = "!Name: Glider\n!Author: Richard K. Guy\n!The smallest spaceship.\n!\n.O\n..O\nOOO\n";
+        let (grid, meta) = parse(text).unwrap();
+        let expected = StandardGrid::from_string_pattern(&[".#.", "..#", "###"], '#', '.').unwrap();
+        assert!(grids_equal(&grid, &expected));
+        assert_eq!(meta.name.as_deref(), Some("Glider"));
+        assert_eq!(meta.author.as_deref(), Some("Richard K. Guy"));
+        assert_eq!(meta.comments, vec!["The smallest spaceship.".to_string()]);
+        assert!(matches!(parse("..O\n.X.\n"), Err(FormatError::Syntax { line: 2, .. })));
+    }
+    
+    #[test]
+    fn test_round_trip_keeps_blank_rows() {
+        let grid = StandardGrid::from_string_pattern(&["#..#", "....", ".##."], '#', '.').unwrap();
+        let meta = PatternMeta {
+            name: Some("Test".to_string()),
+            comments: vec!["Two rows apart".to_string()],
+            ..PatternMeta::default()
+        };
+        let text = write(&grid, &meta);
+        assert_eq!(text, "!Name: Test\n!Two rows apart\nO..O\n\n.OO\n");
+        let (parsed, parsed_meta) = parse(&text).unwrap();
+        assert!(grids_equal(&parsed, &grid));
+        assert_eq!(parsed_meta, meta);
+    }
+}
@@ -0,0 +1,78 @@
+//! Black-and-white PNG import and export
+//!
+//! Each cell is a `scale` x `scale` block of pixels, live cells black on
+//! white. Requires the `image` feature.
+
+use super::FormatError;
+use crate::error::GameOfLifeError;
+use crate::grid::{Grid, StandardGrid};
+use image::{GrayImage, Luma};
+use std::path::Path;
+
+/// Pixels darker than this read as live cells
+const THRESHOLD: u8 = 128;
+
+/// Render a grid with each cell as a `scale` x `scale` block, live cells black
+///
+/// Fails if `scale` is zero.
+pub fn render(grid: &dyn Grid, scale: u32) -> Result<GrayImage, FormatError> {
+    check_scale(scale)?;
+    Ok(GrayImage::from_fn(grid.width() as u32 * scale, grid.height() as u32 * scale, |x, y| {
+        if grid.get_cell((y / scale) as usize, (x / scale) as usize) {
+            Luma([0])
+        } else {
+            Luma([255])
+        }
+    }))
+}
+
+/// Write a grid to a PNG file, `scale` pixels per cell side
+pub fn write(grid: &dyn Grid, path: impl AsRef<Path>, scale: u32) -> Result<(), FormatError> {
+    render(grid, scale)?.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+/// Read a PNG written at `scale` pixels per cell side back into a grid
+///
+/// The center pixel of each block decides the cell, so antialiased or
+/// gridlined renders still load. Partial blocks at the right and bottom
+/// edges are ignored. Fails if `scale` is zero.
+pub fn read(path: impl AsRef<Path>, scale: u32) -> Result<StandardGrid, FormatError> {
+    check_scale(scale)?;
+    let image = image::open(path)?.into_luma8();
+    let (width, height) = (image.width() / scale, image.height() / scale);
+    let sampled = GrayImage::from_fn(width, height, |x, y| *image.get_pixel(x * scale + scale / 2, y * scale + scale / 2));
+    Ok(StandardGrid::from_gray_image(&sampled, THRESHOLD))
+}
+
+/// Reject a zero scale, which would render every grid as an empty image
+pub(crate) fn check_scale(scale: u32) -> Result<(), FormatError> {
+    if scale == 0 {
+        return Err(FormatError::rejected(GameOfLifeError::invalid("PNG scale must be at least 1")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_scaled_round_trip() {
+        let glider = StandardGrid::from_string_pattern(&[".#..", "..#.", "###."], '#', '.').unwrap();
+        let image = render(&glider, 3).unwrap();
+        assert_eq!(image.dimensions(), (12, 9));
+        assert_eq!(image.get_pixel(5, 2).0, [0]);
+        assert_eq!(image.get_pixel(6, 2).0, [255]);
+        
+        let path = std::env::temp_dir().join(format!("gol_png_{}.png", std::process::id()));
+        write(&glider, &path, 3).unwrap();
+        let loaded = read(&path, 3);
+        let missing = read(path.with_extension("missing.png"), 1);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), glider);
+        assert!(missing.is_err());
+        assert!(matches!(render(&glider, 0), Err(FormatError::Rejected(GameOfLifeError::InvalidArgument(_)))));
+        assert!(read(&path, 0).is_err());
+    }
+}
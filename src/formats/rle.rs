@@ -0,0 +1,483 @@
+//! Run Length Encoded (RLE) pattern import and export
+//!
+//! Supports the `x = .., y = .., rule = ..` header, `#N`/`#O`/`#C` comment
+//! lines, Golly's `#CXRLE Pos=x,y Gen=n` extension, body lines wrapped at any
+//! point and the `b`/`o`/`$`/`!` tokens with optional run counts. Multi-state
+//! letters (`A`-`X`) load as live cells. `RleReader` decodes from any reader
+//! without holding the file in memory.
+
+use super::{apply_rule_header, FormatError, PatternMeta};
+use crate::engines::GameOfLifeEngine;
+use crate::grid::{Grid, StandardGrid};
+use crate::rules::RuleString;
+use std::io::{BufRead, BufReader, Read};
+
+/// Longest body line `write` produces, as recommended by the format
+const MAX_LINE_LENGTH: usize = 70;
+
+/// Parse an RLE pattern into a grid of exactly the declared `x` by `y` size
+pub fn parse(text: &str) -> Result<(StandardGrid, PatternMeta), FormatError> {
+    let mut reader = RleReader::new(text.as_bytes())?;
+    let mut grid = StandardGrid::new(reader.width(), reader.height());
+    reader.read_into(&mut grid)?;
+    Ok((grid, reader.into_meta()))
+}
+
+/// A horizontal run of live cells decoded from an RLE body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellRun {
+    pub row: usize,
+    pub col: usize,
+    pub len: usize,
+}
+
+/// Incremental RLE decoder over any `io::Read`
+///
+/// The comment lines and header are read up front; the body is then decoded
+/// straight from the reader's buffer, one run of live cells at a time, so
+/// memory use does not depend on the size of the file. Runs are also available
+/// through the `Iterator` implementation, which stops after the first error.
+pub struct RleReader<R: Read> {
+    reader: BufReader<R>,
+    meta: PatternMeta,
+    width: usize,
+    height: usize,
+    line_no: usize,
+    // 1-based column of the byte last read, for locating errors
+    column: usize,
+    row: usize,
+    col: usize,
+    // Run counts may be split across a line wrap, so the pending count outlives each line
+    count: Option<usize>,
+    finished: bool,
+}
+
+impl<R: Read> RleReader<R> {
+    /// Read the comments and header, leaving the reader at the start of the body
+    pub fn new(reader: R) -> Result<Self, FormatError> {
+        let mut reader = BufReader::new(reader);
+        let mut meta = PatternMeta::default();
+        let mut line_no = 0;
+        let mut buffer = String::new();
+        
+        // Comment lines come first, then the header
+        let (width, height) = loop {
+            buffer.clear();
+            if reader.read_line(&mut buffer)? == 0 {
+                return Err(FormatError::syntax(line_no.max(1), "Missing 'x = .., y = ..' header"));
+            }
+            line_no += 1;
+            let line = buffer.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                parse_comment(line_no, comment, &mut meta)?;
+                continue;
+            }
+            let (width, height, rule) = parse_header(line_no, line)?;
+            if let Some(rule) = rule {
+                meta.rule = Some(RuleString::parse(rule).map_err(FormatError::InvalidRule)?);
+            }
+            break (width, height);
+        };
+        
+        Ok(RleReader {
+            reader,
+            meta,
+            width,
+            height,
+            line_no: line_no + 1,
+            column: 0,
+            row: 0,
+            col: 0,
+            count: None,
+            finished: false,
+        })
+    }
+    
+    /// Declared pattern width
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    
+    /// Declared pattern height
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    
+    /// Metadata from the comment lines and header
+    pub fn meta(&self) -> &PatternMeta {
+        &self.meta
+    }
+    
+    /// Consume the reader, keeping its metadata
+    pub fn into_meta(self) -> PatternMeta {
+        self.meta
+    }
+    
+    /// Decode the next run of live cells, or `None` once `!` or the end of input is reached
+    pub fn next_run(&mut self) -> Result<Option<CellRun>, FormatError> {
+        while !self.finished {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                self.finished = true;
+                break;
+            }
+            
+            let mut consumed = 0;
+            let mut found = None;
+            for &byte in buffer {
+                consumed += 1;
+                self.column += 1;
+                match byte {
+                    b'0'..=b'9' => {
+                        let digit = (byte - b'0') as usize;
+                        let count = self.count.unwrap_or(0).checked_mul(10).and_then(|n| n.checked_add(digit));
+                        self.count = Some(count.ok_or_else(|| FormatError::syntax_at(self.line_no, self.column, "Run count is too large"))?);
+                    }
+                    b'b' | b'.' => {
+                        let count = self.count.take().unwrap_or(1);
+                        self.col = self.col.checked_add(count).ok_or_else(|| FormatError::syntax_at(self.line_no, self.column, "Run of dead cells is too long"))?;
+                    }
+                    b'o' | b'A'..=b'X' => {
+                        let len = self.count.take().unwrap_or(1);
+                        let (row, col) = (self.row, self.col);
+                        // Compared as `len > width - col` so huge run counts cannot overflow
+                        if row >= self.height || col > self.width || len > self.width - col {
+                            return Err(FormatError::syntax_at(
+                                self.line_no,
+                                self.column,
+                                format!(
+                                    "{} live cells from row {}, column {} fall outside the declared {}x{} pattern",
+                                    len, row, col, self.width, self.height
+                                ),
+                            ));
+                        }
+                        self.col += len;
+                        found = Some(CellRun { row, col, len });
+                        break;
+                    }
+                    b'$' => {
+                        let count = self.count.take().unwrap_or(1);
+                        self.row = self.row.checked_add(count).ok_or_else(|| FormatError::syntax_at(self.line_no, self.column, "Run of blank rows is too long"))?;
+                        self.col = 0;
+                    }
+                    b'!' => {
+                        self.finished = true;
+                        break;
+                    }
+                    b'\n' => {
+                        self.line_no += 1;
+                        self.column = 0;
+                    }
+                    byte if byte.is_ascii_whitespace() => {}
+                    byte if byte.is_ascii() => {
+                        return Err(FormatError::syntax_at(self.line_no, self.column, format!("Unexpected character '{}'", byte as char)));
+                    }
+                    byte => return Err(FormatError::syntax_at(self.line_no, self.column, format!("Unexpected byte 0x{:02x}", byte))),
+                }
+            }
+            self.reader.consume(consumed);
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+        Ok(None)
+    }
+    
+    /// Decode the rest of the body into `grid` with the pattern's top-left at the origin
+    ///
+    /// Cells that fall outside `grid` are skipped.
+    pub fn read_into(&mut self, grid: &mut dyn Grid) -> Result<(), FormatError> {
+        while let Some(run) = self.next_run()? {
+            if run.row >= grid.height() {
+                continue;
+            }
+            let end = (run.col + run.len).min(grid.width());
+            grid.set_cells(&mut (run.col..end).map(|col| (run.row, col, true)));
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for RleReader<R> {
+    type Item = Result<CellRun, FormatError>;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_run() {
+            Ok(run) => run.map(Ok),
+            Err(err) => {
+                self.finished = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Parse an RLE pattern and load it into an engine, applying its rule first
+///
+/// The pattern replaces the engine's grid at its declared size, and the
+/// generation counter starts from the `#CXRLE Gen=` value when present. Fails
+/// without touching the grid if the engine cannot run the declared rule.
+pub fn load(engine: &mut dyn GameOfLifeEngine, text: &str) -> Result<PatternMeta, FormatError> {
+    let (grid, meta) = parse(text)?;
+    if let Some(rule) = &meta.rule {
+        apply_rule_header(engine, &rule.to_string())?;
+    }
+    engine.set_grid(&grid);
+    if let Some(generation) = meta.generation {
+        let mut snapshot = engine.snapshot();
+        snapshot.generation = generation as usize;
+        engine.restore(&snapshot).expect("an engine restores its own snapshot");
+    }
+    Ok(meta)
+}
+
+/// Write a grid as RLE, with comments, `#CXRLE` line and rule taken from `meta`
+///
+/// The rule defaults to B3/S23. Trailing dead cells and rows are omitted and
+/// body lines are wrapped at 70 characters.
+pub fn write(grid: &dyn Grid, meta: &PatternMeta) -> String {
+    let mut out = String::new();
+    if let Some(name) = &meta.name {
+        out.push_str(&format!("#N {}\n", name));
+    }
+    if let Some(author) = &meta.author {
+        out.push_str(&format!("#O {}\n", author));
+    }
+    for comment in &meta.comments {
+        out.push_str(&format!("#C {}\n", comment));
+    }
+    if meta.position.is_some() || meta.generation.is_some() {
+        out.push_str("#CXRLE");
+        if let Some((x, y)) = meta.position {
+            out.push_str(&format!(" Pos={},{}", x, y));
+        }
+        if let Some(generation) = meta.generation {
+            out.push_str(&format!(" Gen={}", generation));
+        }
+        out.push('\n');
+    }
+    let rule = meta.rule.as_ref().map_or_else(|| "B3/S23".to_string(), |rule| rule.to_string());
+    out.push_str(&format!("x = {}, y = {}, rule = {}\n", grid.width(), grid.height(), rule));
+    
+    let mut tokens = Vec::new();
+    let mut cursor_row = 0;
+    for row in 0..grid.height() {
+        let cells: Vec<bool> = grid.row(row).collect();
+        let Some(last_alive) = cells.iter().rposition(|&alive| alive) else {
+            continue;
+        };
+        if row > cursor_row {
+            tokens.push(run_token(row - cursor_row, '$'));
+        }
+        cursor_row = row;
+        for run in cells[..=last_alive].chunk_by(|a, b| a == b) {
+            tokens.push(run_token(run.len(), if run[0] { 'o' } else { 'b' }));
+        }
+    }
+    tokens.push("!".to_string());
+    
+    let mut line = String::new();
+    for token in tokens {
+        if line.len() + token.len() > MAX_LINE_LENGTH {
+            out.push_str(&line);
+            out.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    out.push_str(&line);
+    out.push('\n');
+    out
+}
+
+/// A tag preceded by its run count, which is omitted for runs of one
+fn run_token(count: usize, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", count, tag)
+    }
+}
+
+/// Record a `#` line's contents in the metadata
+fn parse_comment(line_no: usize, comment: &str, meta: &mut PatternMeta) -> Result<(), FormatError> {
+    let mut chars = comment.chars();
+    let kind = chars.next();
+    let rest = chars.as_str().trim();
+    match kind {
+        Some('N') => meta.name = Some(rest.to_string()),
+        Some('O') => meta.author = Some(rest.to_string()),
+        Some('C') if rest.starts_with("XRLE") => parse_xrle(line_no, &rest["XRLE".len()..], meta)?,
+        Some('C' | 'c') => meta.comments.push(rest.to_string()),
+        Some('r') => meta.rule = Some(RuleString::parse(rest).map_err(FormatError::InvalidRule)?),
+        // Offsets (#P, #R) and unknown lines carry nothing a bounded grid can use
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Parse the `Pos=x,y Gen=n` fields of a `#CXRLE` line
+fn parse_xrle(line_no: usize, fields: &str, meta: &mut PatternMeta) -> Result<(), FormatError> {
+    let invalid = |field: &str| FormatError::syntax(line_no, format!("Invalid #CXRLE field '{}'", field));
+    for field in fields.split_whitespace() {
+        match field.split_once('=') {
+            Some(("Pos", value)) => {
+                let (x, y) = value.split_once(',').ok_or_else(|| invalid(field))?;
+                let x = x.trim().parse().map_err(|_| invalid(field))?;
+                let y = y.trim().parse().map_err(|_| invalid(field))?;
+                meta.position = Some((x, y));
+            }
+            Some(("Gen", value)) => meta.generation = Some(value.parse().map_err(|_| invalid(field))?),
+            // Unknown keys from newer Golly versions are ignored
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Parse `x = m, y = n[, rule = r]`
+fn parse_header(line_no: usize, line: &str) -> Result<(usize, usize, Option<&str>), FormatError> {
+    let (mut width, mut height, mut rule) = (None, None, None);
+    for field in line.split(',') {
+        let Some((key, value)) = field.split_once('=') else {
+            return Err(FormatError::syntax(line_no, format!("Malformed header field '{}'", field.trim())));
+        };
+        let value = value.trim();
+        let dimension = || {
+            value.parse::<usize>().map_err(|_| FormatError::syntax(line_no, format!("Invalid dimension '{}'", value)))
+        };
+        match key.trim() {
+            "x" => width = Some(dimension()?),
+            "y" => height = Some(dimension()?),
+            "rule" => rule = Some(value),
+            // Unknown keys such as Golly's "h" are ignored
+            _ => {}
+        }
+    }
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height, rule)),
+        _ => Err(FormatError::syntax(line_no, "Header must declare both x and y")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NaiveEngine;
+    use crate::rules::Rule;
+    
+    const GOSPER_GUN: &str = "#N Gosper glider gun
+#O Bill Gosper
+#C A true period 30 glider gun.
+#C The first known gun and the first known finite pattern with unbounded growth.
+x = 36, y = 9, rule = B3/S23
+24bo11b$22bobo11b$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o14b$2o8bo3bob2o4b
+obo11b$10bo5bo7bo11b$11bo3bo20b$12b2o!
+";
+    
+    #[test]
+    fn test_parse_gosper_gun() {
+        let (grid, meta) = parse(GOSPER_GUN).unwrap();
+        assert_eq!((grid.width(), grid.height()), (36, 9));
+        assert_eq!(grid.count_live_cells(), 36);
+        assert!(grid.get_cell(0, 24));
+        assert!(grid.get_cell(5, 22) && grid.get_cell(5, 24));
+        assert_eq!(meta.name.as_deref(), Some("Gosper glider gun"));
+        assert_eq!(meta.author.as_deref(), Some("Bill Gosper"));
+        assert_eq!(meta.comments.len(), 2);
+        assert_eq!(meta.rule, Some(RuleString::Life(Rule::conway())));
+    }
+    
+    #[test]
+    fn test_wrapped_counts_and_blank_rows() {
+        let (grid, meta) = parse("x = 12, y = 4\n1\n1o$\n\n2$o!").unwrap();
+        assert_eq!(grid.count_live_cells(), 12);
+        assert!(grid.get_cell(3, 0) && !grid.get_cell(1, 0) && !grid.get_cell(2, 0));
+        assert_eq!(meta, PatternMeta::default());
+    }
+    
+    #[test]
+    fn test_errors_report_lines() {
+        assert!(matches!(parse(""), Err(FormatError::Syntax { .. })));
+        assert!(matches!(parse("x = 3\nooo!"), Err(FormatError::Syntax { line: 1, .. })));
+        assert!(matches!(parse("x = 2, y = 1\nooo!"), Err(FormatError::Syntax { line: 2, .. })));
+        assert!(matches!(parse("x = 2, y = 1\noz!"), Err(FormatError::Syntax { line: 2, column: 2, .. })));
+        // Huge run counts are rejected rather than overflowing the cursor
+        assert!(matches!(parse("x = 2, y = 2\n18446744073709551615b2o!"), Err(FormatError::Syntax { line: 2, .. })));
+        assert!(matches!(parse("x = 2, y = 2\no18446744073709551615o!"), Err(FormatError::Syntax { line: 2, .. })));
+        assert!(matches!(parse("x = 2, y = 2\n$18446744073709551615$o!"), Err(FormatError::Syntax { line: 2, .. })));
+        assert!(matches!(parse("x = 2, y = 1, rule = B9/S\no!"), Err(FormatError::InvalidRule(_))));
+    }
+    
+    #[test]
+    fn test_load_applies_rule() {
+        let mut engine = NaiveEngine::new(4, 4);
+        let meta = load(&mut engine, "#N Blinker\nx = 3, y = 1, rule = B36/S23\n3o!").unwrap();
+        assert_eq!(meta.name.as_deref(), Some("Blinker"));
+        assert_eq!(engine.rule().unwrap().to_string(), "B36/S23");
+        assert_eq!((engine.width(), engine.height()), (3, 1));
+        
+        let mut engine = NaiveEngine::new(4, 4);
+        let result = load(&mut engine, "x = 3, y = 1, rule = B2/S345/C4\n3o!");
+        assert!(matches!(result, Err(FormatError::RuleHeader(_))));
+        assert_eq!(engine.count_live_cells(), 0);
+    }
+    
+    #[test]
+    fn test_xrle_sets_position_and_generation() {
+        let text = "#CXRLE Pos=-18,-4 Gen=1200\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!";
+        let (_, meta) = parse(text).unwrap();
+        assert_eq!(meta.position, Some((-18, -4)));
+        assert_eq!(meta.generation, Some(1200));
+        assert!(meta.comments.is_empty());
+        assert!(matches!(parse("#CXRLE Pos=1\nx = 1, y = 1\no!"), Err(FormatError::Syntax { line: 1, .. })));
+        
+        let mut engine = NaiveEngine::new(8, 8);
+        load(&mut engine, text).unwrap();
+        assert_eq!(engine.generation(), 1200);
+        engine.step();
+        assert_eq!(engine.generation(), 1201);
+    }
+    
+    #[test]
+    fn test_reader_streams_runs() {
+        let mut reader = RleReader::new(GOSPER_GUN.as_bytes()).unwrap();
+        assert_eq!((reader.width(), reader.height()), (36, 9));
+        assert_eq!(reader.meta().name.as_deref(), Some("Gosper glider gun"));
+        assert_eq!(reader.next_run().unwrap(), Some(CellRun { row: 0, col: 24, len: 1 }));
+        
+        let runs: Vec<CellRun> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(runs.iter().map(|run| run.len).sum::<usize>(), 35);
+        assert_eq!(runs.last(), Some(&CellRun { row: 8, col: 12, len: 2 }));
+        assert_eq!(reader.next_run().unwrap(), None);
+        
+        // A smaller target grid clips instead of panicking
+        let mut small = StandardGrid::new(12, 6);
+        RleReader::new(GOSPER_GUN.as_bytes()).unwrap().read_into(&mut small).unwrap();
+        assert_eq!(small.count_live_cells(), 7);
+        
+        let mut failing = RleReader::new("x = 2, y = 1\nbz!".as_bytes()).unwrap();
+        assert!(matches!(failing.next(), Some(Err(FormatError::Syntax { line: 2, .. }))));
+        assert!(failing.next().is_none());
+    }
+    
+    #[test]
+    fn test_write_round_trips() {
+        let (grid, mut meta) = parse(GOSPER_GUN).unwrap();
+        meta.position = Some((-18, -4));
+        meta.generation = Some(30);
+        let text = write(&grid, &meta);
+        assert!(text.lines().filter(|line| !line.starts_with('#')).all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert!(text.contains("#CXRLE Pos=-18,-4 Gen=30\n"));
+        
+        let (parsed, parsed_meta) = parse(&text).unwrap();
+        assert!(crate::grid::grids_equal(&parsed, &grid));
+        assert_eq!(parsed_meta, meta);
+        
+        let sparse = StandardGrid::from_string_pattern(&["....", "##.#", "....", "...."], '#', '.').unwrap();
+        assert_eq!(write(&sparse, &PatternMeta::default()), "x = 4, y = 4, rule = B3/S23\n$2obo!\n");
+    }
+}
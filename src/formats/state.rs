@@ -0,0 +1,201 @@
+//! Compact binary snapshots of full engine state
+//!
+//! Layout, all integers little-endian:
+//!
+//! | bytes | field |
+//! |-------|-------|
+//! | 8 | magic `GOLSTATE` |
+//! | 2 | format version (1) |
+//! | 8, 8 | width, height |
+//! | 8 | generation |
+//! | 2 + n | rule string length and UTF-8 bytes, empty when the engine has no Life-like rule |
+//! | 1 | 1 if per-cell states follow the field, else 0 |
+//! | 8 per word | bit-packed field, `BitGrid` row layout |
+//! | width x height | per-cell states, if flagged |
+
+use super::FormatError;
+use crate::engines::EngineSnapshot;
+use crate::grid::BitGrid;
+use crate::rules::Rule;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// File signature
+pub const MAGIC: &[u8; 8] = b"GOLSTATE";
+
+/// Version written by this crate
+pub const VERSION: u16 = 1;
+
+/// Serialize a snapshot and the rule it was running under
+pub fn write_state(out: &mut impl Write, snapshot: &EngineSnapshot, rule: Option<Rule>) -> Result<(), FormatError> {
+    let rule = rule.map(|rule| rule.to_string()).unwrap_or_default();
+    out.write_all(MAGIC)?;
+    out.write_all(&VERSION.to_le_bytes())?;
+    out.write_all(&(snapshot.width() as u64).to_le_bytes())?;
+    out.write_all(&(snapshot.height() as u64).to_le_bytes())?;
+    out.write_all(&(snapshot.generation as u64).to_le_bytes())?;
+    out.write_all(&(rule.len() as u16).to_le_bytes())?;
+    out.write_all(rule.as_bytes())?;
+    out.write_all(&[snapshot.cell_states.is_some() as u8])?;
+    for word in snapshot.grid.words() {
+        out.write_all(&word.to_le_bytes())?;
+    }
+    if let Some(states) = &snapshot.cell_states {
+        out.write_all(states)?;
+    }
+    Ok(())
+}
+
+/// Deserialize a snapshot written by `write_state`
+pub fn read_state(input: &mut impl Read) -> Result<(EngineSnapshot, Option<Rule>), FormatError> {
+    let magic: [u8; 8] = read_array(input)?;
+    if &magic != MAGIC {
+        return Err(FormatError::Corrupt("not a state snapshot (bad magic)".to_string()));
+    }
+    let version = u16::from_le_bytes(read_array(input)?);
+    if version != VERSION {
+        return Err(FormatError::Corrupt(format!("unsupported snapshot version {}", version)));
+    }
+    
+    let width = read_usize(input)?;
+    let height = read_usize(input)?;
+    let generation = read_usize(input)?;
+    let rule_len = u16::from_le_bytes(read_array(input)?) as usize;
+    let rule = String::from_utf8(read_vec(input, rule_len)?)
+        .map_err(|_| FormatError::Corrupt("rule is not UTF-8".to_string()))?;
+    let rule = if rule.is_empty() {
+        None
+    } else {
+        Some(rule.parse::<Rule>().map_err(FormatError::rejected)?)
+    };
+    let has_states = match read_array::<1>(input)?[0] {
+        0 => false,
+        1 => true,
+        flag => return Err(FormatError::Corrupt(format!("invalid cell state flag {}", flag))),
+    };
+    
+    let overflow = || FormatError::Corrupt(format!("dimensions {}x{} overflow", width, height));
+    let word_bytes = width.div_ceil(64).checked_mul(height).and_then(|words| words.checked_mul(8)).ok_or_else(overflow)?;
+    let words = read_vec(input, word_bytes)?
+        .chunks_exact(8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .collect();
+    let grid = BitGrid::from_words(width, height, words).map_err(FormatError::rejected)?;
+    let cell_states = if has_states { Some(read_vec(input, width.checked_mul(height).ok_or_else(overflow)?)?) } else { None };
+    
+    Ok((EngineSnapshot { generation, grid, cell_states }, rule))
+}
+
+/// Write a snapshot file
+pub fn save(path: impl AsRef<Path>, snapshot: &EngineSnapshot, rule: Option<Rule>) -> Result<(), FormatError> {
+    let mut out = BufWriter::new(File::create(path)?);
+    write_state(&mut out, snapshot, rule)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Read a snapshot file
+pub fn load(path: impl AsRef<Path>) -> Result<(EngineSnapshot, Option<Rule>), FormatError> {
+    read_state(&mut BufReader::new(File::open(path)?))
+}
+
+fn read_array<const N: usize>(input: &mut impl Read) -> Result<[u8; N], FormatError> {
+    let mut bytes = [0; N];
+    input.read_exact(&mut bytes).map_err(truncated)?;
+    Ok(bytes)
+}
+
+fn read_usize(input: &mut impl Read) -> Result<usize, FormatError> {
+    let value = u64::from_le_bytes(read_array(input)?);
+    usize::try_from(value).map_err(|_| FormatError::Corrupt(format!("value {} does not fit in memory", value)))
+}
+
+/// Read exactly `len` bytes without trusting `len` for the initial allocation
+fn read_vec(input: &mut impl Read, len: usize) -> Result<Vec<u8>, FormatError> {
+    let mut bytes = Vec::new();
+    input.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(FormatError::Corrupt("unexpected end of data".to_string()));
+    }
+    Ok(bytes)
+}
+
+fn truncated(err: std::io::Error) -> FormatError {
+    if err.kind() == std::io::ErrorKind::UnexpectedEof {
+        FormatError::Corrupt("unexpected end of data".to_string())
+    } else {
+        FormatError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{GameOfLifeEngine, GenerationsEngine, NaiveEngine};
+    use crate::grid::StandardGrid;
+    use crate::rules::GenerationsRule;
+    
+    #[test]
+    fn test_engine_state_round_trip() {
+        let mut engine = NaiveEngine::from_grid(&StandardGrid::random(70, 20, 0.4, 3).unwrap());
+        engine.set_rule("B36/S23".parse().unwrap()).unwrap();
+        engine.run_steps(11);
+        
+        let path = std::env::temp_dir().join(format!("gol_state_{}.bin", std::process::id()));
+        engine.save_state(&path).unwrap();
+        let mut restored = NaiveEngine::new(5, 5);
+        let result = restored.load_state(&path);
+        std::fs::remove_file(&path).unwrap();
+        result.unwrap();
+        
+        assert_eq!((restored.width(), restored.height()), (70, 20));
+        assert_eq!(restored.generation(), 11);
+        assert_eq!(restored.rule(), engine.rule());
+        assert_eq!(restored.state_hash(), engine.state_hash());
+    }
+    
+    #[test]
+    fn test_cell_states_and_corruption() {
+        let mut engine = GenerationsEngine::new(10, 10, GenerationsRule::brians_brain());
+        engine.set_grid(&StandardGrid::random(10, 10, 0.5, 8).unwrap());
+        engine.run_steps(3);
+        
+        let mut bytes = Vec::new();
+        write_state(&mut bytes, &engine.snapshot(), engine.rule()).unwrap();
+        let (snapshot, _) = read_state(&mut bytes.as_slice()).unwrap();
+        assert_eq!(snapshot, engine.snapshot());
+        
+        assert!(matches!(read_state(&mut &bytes[..bytes.len() - 1]), Err(FormatError::Corrupt(_))));
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(matches!(read_state(&mut bad_magic.as_slice()), Err(FormatError::Corrupt(_))));
+        
+        // Dimensions whose byte counts overflow are corrupt, not a panic
+        let mut huge = bytes.clone();
+        huge[10..18].copy_from_slice(&64u64.to_le_bytes());
+        huge[18..26].copy_from_slice(&(1u64 << 61).to_le_bytes());
+        assert!(matches!(read_state(&mut huge.as_slice()), Err(FormatError::Corrupt(_))));
+    }
+    
+    #[test]
+    fn test_failed_load_leaves_engine_untouched() {
+        let mut engine = GenerationsEngine::new(12, 12, GenerationsRule::star_wars());
+        engine.set_grid(&StandardGrid::random(12, 12, 0.5, 4).unwrap());
+        engine.run_steps(3);
+        assert!(engine.snapshot().cell_states.unwrap().contains(&3));
+        
+        // Brian's Brain has no fourth state to restore into
+        let mut brain = GenerationsEngine::new(5, 5, GenerationsRule::brians_brain());
+        brain.set_grid(&StandardGrid::random(5, 5, 0.5, 9).unwrap());
+        brain.step();
+        let before = brain.snapshot();
+        let path = std::env::temp_dir().join(format!("gol_state_rejected_{}.bin", std::process::id()));
+        engine.save_state(&path).unwrap();
+        let result = brain.load_state(&path);
+        std::fs::remove_file(&path).unwrap();
+        
+        assert!(matches!(result, Err(FormatError::Rejected(_))));
+        assert_eq!(brain.snapshot(), before);
+    }
+}
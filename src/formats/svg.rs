@@ -0,0 +1,115 @@
+//! SVG rendering of a grid for figures and documentation
+//!
+//! Each horizontal run of live cells becomes one `<rect>`, so sparse and
+//! blocky patterns stay small.
+
+use crate::grid::Grid;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Appearance of a rendered SVG
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// Side length of a cell in SVG user units
+    pub cell_size: u32,
+    /// Fill of live cells, any SVG color
+    pub live_color: String,
+    /// Background fill behind dead cells
+    pub dead_color: String,
+    /// Stroke color of lines between cells, or `None` for no grid lines
+    pub grid_lines: Option<String>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 10,
+            live_color: "#000000".to_string(),
+            dead_color: "#ffffff".to_string(),
+            grid_lines: None,
+        }
+    }
+}
+
+/// Render a grid as an SVG document
+///
+/// For an engine, pass `&engine.snapshot().grid`, which works for every engine.
+pub fn render(grid: &dyn Grid, options: &SvgOptions) -> String {
+    let size = options.cell_size as usize;
+    let (width, height) = (grid.width() * size, grid.height() * size);
+    
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" shape-rendering="crispEdges">"#,
+        w = width,
+        h = height
+    );
+    let _ = writeln!(out, r#"<rect width="{}" height="{}" fill="{}"/>"#, width, height, options.dead_color);
+    
+    let _ = writeln!(out, r#"<g fill="{}">"#, options.live_color);
+    for row in 0..grid.height() {
+        let mut col = 0;
+        let cells: Vec<bool> = grid.row(row).collect();
+        for run in cells.chunk_by(|a, b| a == b) {
+            if run[0] {
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}"/>"#,
+                    col * size, row * size, run.len() * size, size
+                );
+            }
+            col += run.len();
+        }
+    }
+    out.push_str("</g>\n");
+    
+    if let Some(color) = &options.grid_lines {
+        let mut path = String::new();
+        for col in 0..=grid.width() {
+            let _ = write!(path, "M{} 0V{}", col * size, height);
+        }
+        for row in 0..=grid.height() {
+            let _ = write!(path, "M0 {}H{}", row * size, width);
+        }
+        let _ = writeln!(out, r#"<path d="{}" stroke="{}" stroke-width="1" fill="none"/>"#, path, color);
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Render a grid and write it to an SVG file
+pub fn write(grid: &dyn Grid, path: impl AsRef<Path>, options: &SvgOptions) -> std::io::Result<()> {
+    std::fs::write(path, render(grid, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_runs_become_rects() {
+        let grid = StandardGrid::from_string_pattern(&["###.", "...#"], '#', '.').unwrap();
+        let svg = render(&grid, &SvgOptions::default());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"40\" height=\"20\""));
+        assert!(svg.contains(r#"<rect x="0" y="0" width="30" height="10"/>"#));
+        assert!(svg.contains(r#"<rect x="30" y="10" width="10" height="10"/>"#));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(!svg.contains("<path"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+    
+    #[test]
+    fn test_colors_and_grid_lines() {
+        let options = SvgOptions {
+            cell_size: 4,
+            live_color: "teal".to_string(),
+            grid_lines: Some("#cccccc".to_string()),
+            ..SvgOptions::default()
+        };
+        let svg = render(&StandardGrid::new(2, 1), &options);
+        assert!(svg.contains(r#"<g fill="teal">"#));
+        assert!(svg.contains(r##"<path d="M0 0V4M4 0V4M8 0V4M0 0H8M0 4H8" stroke="#cccccc""##));
+    }
+}
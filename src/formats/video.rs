@@ -0,0 +1,214 @@
+//! Raw video frame output for piping simulations into ffmpeg
+//!
+//! Frames are streamed as they are produced, live cells black on white at an
+//! integer scale, so recordings of any length use constant memory.
+
+use crate::error::GameOfLifeError;
+use crate::grid::Grid;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+/// Pixel stream written by a `VideoSink`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VideoFormat {
+    /// YUV4MPEG2 with a single luma plane; self-describing, so ffmpeg needs no size flags
+    #[default]
+    Y4m,
+    /// Headerless RGBA, 4 bytes per pixel
+    Rgba,
+}
+
+/// Streams grids as raw video frames into any writer
+///
+/// Every frame must have the dimensions of the first.
+pub struct VideoSink<W: Write> {
+    out: W,
+    format: VideoFormat,
+    scale: u32,
+    fps: u32,
+    size: Option<(usize, usize)>,
+    frames: usize,
+    row_buffer: Vec<u8>,
+}
+
+impl<W: Write> VideoSink<W> {
+    /// Create a sink drawing each cell as a `scale` x `scale` block
+    ///
+    /// `fps` is only recorded in the Y4M header. Fails if `scale` or `fps` is zero.
+    pub fn new(out: W, format: VideoFormat, scale: u32, fps: u32) -> Result<Self, GameOfLifeError> {
+        check_settings(scale, fps)?;
+        Ok(Self { out, format, scale, fps, size: None, frames: 0, row_buffer: Vec::new() })
+    }
+    
+    /// Number of frames written so far
+    pub fn frames_written(&self) -> usize {
+        self.frames
+    }
+    
+    /// Write one frame
+    pub fn write_frame(&mut self, grid: &dyn Grid) -> io::Result<()> {
+        let size = (grid.width(), grid.height());
+        match self.size {
+            None => {
+                self.size = Some(size);
+                if self.format == VideoFormat::Y4m {
+                    let (width, height) = self.pixel_size(size);
+                    writeln!(self.out, "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 Cmono", width, height, self.fps)?;
+                }
+            }
+            Some(expected) if expected != size => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Frame is {}x{}, expected {}x{}", size.0, size.1, expected.0, expected.1),
+                ));
+            }
+            Some(_) => {}
+        }
+        
+        if self.format == VideoFormat::Y4m {
+            self.out.write_all(b"FRAME\n")?;
+        }
+        let scale = self.scale as usize;
+        for row in 0..grid.height() {
+            self.row_buffer.clear();
+            for alive in grid.row(row) {
+                let pixel: &[u8] = match (self.format, alive) {
+                    // Studio-range luma: 16 is black, 235 is white
+                    (VideoFormat::Y4m, true) => &[16],
+                    (VideoFormat::Y4m, false) => &[235],
+                    (VideoFormat::Rgba, true) => &[0, 0, 0, 255],
+                    (VideoFormat::Rgba, false) => &[255, 255, 255, 255],
+                };
+                for _ in 0..scale {
+                    self.row_buffer.extend_from_slice(pixel);
+                }
+            }
+            for _ in 0..scale {
+                self.out.write_all(&self.row_buffer)?;
+            }
+        }
+        self.frames += 1;
+        Ok(())
+    }
+    
+    /// Flush and return the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+    
+    fn pixel_size(&self, (width, height): (usize, usize)) -> (usize, usize) {
+        (width * self.scale as usize, height * self.scale as usize)
+    }
+}
+
+/// A `VideoSink` feeding a child `ffmpeg` process that encodes to a file
+pub struct FfmpegRecorder {
+    sink: VideoSink<ChildStdin>,
+    child: Child,
+}
+
+impl FfmpegRecorder {
+    /// Spawn `ffmpeg` writing `output`, whose extension picks the container
+    ///
+    /// `width` and `height` are the grid size in cells; RGBA input needs them
+    /// on the command line because the stream carries no header. Output is
+    /// yuv420p, which most encoders only accept at even pixel sizes. Fails
+    /// before starting ffmpeg if `scale` or `fps` is zero.
+    pub fn spawn(output: impl AsRef<Path>, width: usize, height: usize, format: VideoFormat, scale: u32, fps: u32) -> Result<Self, GameOfLifeError> {
+        check_settings(scale, fps)?;
+        let args = ffmpeg_args(output.as_ref(), width * scale as usize, height * scale as usize, format, fps);
+        let mut child = Command::new("ffmpeg")
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("ffmpeg stdin is piped");
+        Ok(Self { sink: VideoSink::new(stdin, format, scale, fps)?, child })
+    }
+    
+    /// Write one frame
+    pub fn write_frame(&mut self, grid: &dyn Grid) -> io::Result<()> {
+        self.sink.write_frame(grid)
+    }
+    
+    /// Close the stream and wait for ffmpeg to finish encoding
+    pub fn finish(mut self) -> io::Result<()> {
+        drop(self.sink.finish()?);
+        let status = self.child.wait()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("ffmpeg exited with {}", status)))
+        }
+    }
+}
+
+fn check_settings(scale: u32, fps: u32) -> Result<(), GameOfLifeError> {
+    if scale == 0 {
+        return Err(GameOfLifeError::invalid("Video scale must be at least 1"));
+    }
+    if fps == 0 {
+        return Err(GameOfLifeError::invalid("Video frame rate must be at least 1"));
+    }
+    Ok(())
+}
+
+/// Command-line arguments for encoding the sink's stream from stdin
+fn ffmpeg_args(output: &Path, pixel_width: usize, pixel_height: usize, format: VideoFormat, fps: u32) -> Vec<String> {
+    let mut args: Vec<String> = vec!["-y".into(), "-loglevel".into(), "error".into()];
+    match format {
+        VideoFormat::Y4m => args.extend(["-f", "yuv4mpegpipe"].map(String::from)),
+        VideoFormat::Rgba => {
+            args.extend(["-f", "rawvideo", "-pix_fmt", "rgba"].map(String::from));
+            args.extend(["-s".to_string(), format!("{}x{}", pixel_width, pixel_height)]);
+            args.extend(["-r".to_string(), fps.to_string()]);
+        }
+    }
+    args.extend(["-i", "-", "-pix_fmt", "yuv420p"].map(String::from));
+    args.push(output.to_string_lossy().into_owned());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_y4m_stream() {
+        let grid = StandardGrid::from_string_pattern(&["#.", ".#"], '#', '.').unwrap();
+        let mut sink = VideoSink::new(Vec::new(), VideoFormat::Y4m, 2, 30).unwrap();
+        sink.write_frame(&grid).unwrap();
+        sink.write_frame(&grid).unwrap();
+        assert_eq!(sink.frames_written(), 2);
+        assert!(sink.write_frame(&StandardGrid::new(3, 2)).is_err());
+        
+        let bytes = sink.finish().unwrap();
+        let header = b"YUV4MPEG2 W4 H4 F30:1 Ip A1:1 Cmono\n";
+        assert!(bytes.starts_with(header));
+        let frame = &bytes[header.len()..header.len() + 6 + 16];
+        assert_eq!(&frame[..6], b"FRAME\n");
+        assert_eq!(&frame[6..10], &[16, 16, 235, 235]);
+        assert_eq!(&frame[18..22], &[235, 235, 16, 16]);
+        assert_eq!(bytes.len(), header.len() + 2 * (6 + 16));
+    }
+    
+    #[test]
+    fn test_rgba_stream_and_ffmpeg_args() {
+        let grid = StandardGrid::from_string_pattern(&["#."], '#', '.').unwrap();
+        let mut sink = VideoSink::new(Vec::new(), VideoFormat::Rgba, 1, 30).unwrap();
+        sink.write_frame(&grid).unwrap();
+        assert_eq!(sink.finish().unwrap(), vec![0, 0, 0, 255, 255, 255, 255, 255]);
+        assert!(VideoSink::new(Vec::new(), VideoFormat::Rgba, 0, 30).is_err());
+        assert!(VideoSink::new(Vec::new(), VideoFormat::Rgba, 1, 0).is_err());
+        
+        let args = ffmpeg_args(Path::new("out.mp4"), 640, 480, VideoFormat::Rgba, 24);
+        assert_eq!(
+            args.join(" "),
+            "-y -loglevel error -f rawvideo -pix_fmt rgba -s 640x480 -r 24 -i - -pix_fmt yuv420p out.mp4"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Conversions between grids and `ndarray` arrays
+//!
+//! Arrays are indexed `[row, col]`, so their shape is `(height, width)`.
+
+use super::{Grid, StandardGrid};
+use ndarray::Array2;
+
+impl From<Array2<bool>> for StandardGrid {
+    fn from(array: Array2<bool>) -> Self {
+        Self::from(&array)
+    }
+}
+
+impl From<&Array2<bool>> for StandardGrid {
+    fn from(array: &Array2<bool>) -> Self {
+        let (height, width) = array.dim();
+        let mut grid = StandardGrid::new(width, height);
+        for ((row, col), &alive) in array.indexed_iter() {
+            if alive {
+                grid.set_cell(row, col, true);
+            }
+        }
+        grid
+    }
+}
+
+impl From<Array2<u8>> for StandardGrid {
+    /// Nonzero values are live cells
+    fn from(array: Array2<u8>) -> Self {
+        Self::from(&array)
+    }
+}
+
+impl From<&Array2<u8>> for StandardGrid {
+    /// Nonzero values are live cells
+    fn from(array: &Array2<u8>) -> Self {
+        Self::from(&array.mapv(|value| value != 0))
+    }
+}
+
+/// Copy a grid into a `(height, width)` array
+pub(crate) fn to_array2<G: Grid + ?Sized>(grid: &G) -> Array2<bool> {
+    Array2::from_shape_fn((grid.height(), grid.width()), |(row, col)| grid.get_cell(row, col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    
+    #[test]
+    fn test_array_round_trip() {
+        let glider = StandardGrid::from_string_pattern(&[".#.", "..#", "###"], '#', '.').unwrap();
+        let bools = glider.to_array2();
+        assert_eq!(bools.dim(), (3, 3));
+        assert!(bools[[1, 2]] && !bools[[1, 1]]);
+        assert_eq!(StandardGrid::from(bools), glider);
+        
+        let bytes = glider.to_array2_u8();
+        assert_eq!(bytes, array![[0, 1, 0], [0, 0, 1], [1, 1, 1]]);
+        assert_eq!(StandardGrid::from(bytes), glider);
+        
+        let wide = StandardGrid::from(array![[true, false, false, true]]);
+        assert_eq!((wide.width(), wide.height()), (4, 1));
+    }
+}
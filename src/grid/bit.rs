@@ -0,0 +1,352 @@
+use super::{Grid, Rect};
+use crate::error::GameOfLifeError;
+
+/// Bit-packed grid storing 64 cells per u64, most significant bit first
+///
+/// Each row occupies `words_per_row` words and the unused tail bits of the
+/// last word are always zero, matching the row layout of `UltimateEngine`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Create a new empty grid with the specified dimensions
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+    
+    /// Create a grid from packed rows laid out as returned by `words`
+    pub fn from_words(width: usize, height: usize, words: Vec<u64>) -> Result<Self, GameOfLifeError> {
+        let words_per_row = width.div_ceil(64);
+        if words.len() != words_per_row * height {
+            return Err(GameOfLifeError::invalid(format!(
+                "Expected {} words for a {}x{} grid, got {}",
+                words_per_row * height, width, height, words.len()
+            )));
+        }
+        let tail_bits = words_per_row * 64 - width;
+        if tail_bits > 0 {
+            let tail_mask = (1u64 << tail_bits) - 1;
+            if let Some(row) = words.chunks(words_per_row).position(|row| row[words_per_row - 1] & tail_mask != 0) {
+                return Err(GameOfLifeError::invalid(format!("Row {} has live cells beyond width {}", row, width)));
+            }
+        }
+        Ok(Self { width, height, words_per_row, words })
+    }
+    
+    /// Create a bit-packed copy of any grid
+    pub fn from_grid(grid: &dyn Grid) -> Self {
+        let mut bits = Self::new(grid.width(), grid.height());
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                if grid.get_cell(row, col) {
+                    bits.set_cell(row, col, true);
+                }
+            }
+        }
+        bits
+    }
+    
+    /// Number of u64 words used by each row
+    pub fn words_per_row(&self) -> usize {
+        self.words_per_row
+    }
+    
+    /// Get the packed words of a single row
+    pub fn row_words(&self, row: usize) -> &[u64] {
+        let start = row * self.words_per_row;
+        &self.words[start..start + self.words_per_row]
+    }
+    
+    /// Get the packed words of a single row mutably
+    ///
+    /// Callers must keep the tail bits beyond `width` cleared.
+    pub fn row_words_mut(&mut self, row: usize) -> &mut [u64] {
+        let start = row * self.words_per_row;
+        &mut self.words[start..start + self.words_per_row]
+    }
+    
+    /// Get a reference to all packed words, row by row
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+    
+    /// Get all packed words mutably, row by row
+    ///
+    /// Callers must keep the tail bits beyond `width` cleared.
+    pub fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.words
+    }
+    
+    /// Heap memory allocated for the packed words, in bytes
+    pub fn memory_bytes(&self) -> usize {
+        self.words.capacity() * size_of::<u64>()
+    }
+    
+    /// Cells alive in either grid
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+    
+    /// Cells alive in both grids
+    pub fn intersect(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+    
+    /// Cells alive in exactly one of the grids
+    pub fn xor(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.xor_with(other);
+        result
+    }
+    
+    /// Cells alive in this grid but not in `other`
+    pub fn subtract(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.subtract_with(other);
+        result
+    }
+    
+    /// In-place `union`, one word at a time
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a | b);
+    }
+    
+    /// In-place `intersect`, one word at a time
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a & b);
+    }
+    
+    /// In-place `xor`, one word at a time
+    pub fn xor_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a ^ b);
+    }
+    
+    /// In-place `subtract`, one word at a time
+    pub fn subtract_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a & !b);
+    }
+    
+    /// Combine each word with the matching word of a grid of the same size
+    ///
+    /// None of the operations can set a bit that is clear in both inputs, so
+    /// tail bits stay zero.
+    fn combine_with(&mut self, other: &BitGrid, op: fn(u64, u64) -> u64) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "Cannot combine a {}x{} grid with a {}x{} grid",
+            self.width, self.height, other.width, other.height
+        );
+        for (word, &other_word) in self.words.iter_mut().zip(&other.words) {
+            *word = op(*word, other_word);
+        }
+    }
+    
+    /// Get the word index and bit mask for row, col coordinates
+    fn locate(&self, row: usize, col: usize) -> (usize, u64) {
+        (row * self.words_per_row + col / 64, 0x8000_0000_0000_0000 >> (col % 64))
+    }
+}
+
+impl Grid for BitGrid {
+    fn width(&self) -> usize {
+        self.width
+    }
+    
+    fn height(&self) -> usize {
+        self.height
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        if row >= self.height || col >= self.width {
+            return false;
+        }
+        let (idx, bit) = self.locate(row, col);
+        self.words[idx] & bit != 0
+    }
+    
+    fn set_cell(&mut self, row: usize, col: usize, alive: bool) {
+        if row >= self.height || col >= self.width {
+            return;
+        }
+        let (idx, bit) = self.locate(row, col);
+        if alive {
+            self.words[idx] |= bit;
+        } else {
+            self.words[idx] &= !bit;
+        }
+    }
+    
+    fn clear(&mut self) {
+        self.words.fill(0);
+    }
+    
+    fn set_cells(&mut self, cells: &mut dyn Iterator<Item = (usize, usize, bool)>) {
+        // Group updates by word so each word is touched once; the stable sort
+        // keeps later updates to the same cell winning
+        let mut updates: Vec<(usize, u64, bool)> = cells
+            .filter(|&(row, col, _)| row < self.height && col < self.width)
+            .map(|(row, col, alive)| {
+                let (idx, bit) = self.locate(row, col);
+                (idx, bit, alive)
+            })
+            .collect();
+        updates.sort_by_key(|&(idx, _, _)| idx);
+        
+        for group in updates.chunk_by(|a, b| a.0 == b.0) {
+            let mut word = self.words[group[0].0];
+            for &(_, bit, alive) in group {
+                if alive {
+                    word |= bit;
+                } else {
+                    word &= !bit;
+                }
+            }
+            self.words[group[0].0] = word;
+        }
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+    
+    fn row_words(&self, row: usize) -> Option<&[u64]> {
+        Some(BitGrid::row_words(self, row))
+    }
+}
+
+/// Bounding box of the live cells in rows packed 64 cells per word, most significant bit first
+///
+/// Whole zero words are skipped and the edges within a word come from
+/// leading and trailing zero counts.
+pub(crate) fn packed_bounding_box<'a>(height: usize, row_words: impl Fn(usize) -> &'a [u64]) -> Option<Rect> {
+    let (mut top, mut bottom) = (None, 0);
+    let (mut left, mut right) = (usize::MAX, 0);
+    for row in 0..height {
+        for (index, &word) in row_words(row).iter().enumerate() {
+            if word == 0 {
+                continue;
+            }
+            top.get_or_insert(row);
+            bottom = row;
+            left = left.min(index * 64 + word.leading_zeros() as usize);
+            right = right.max(index * 64 + 63 - word.trailing_zeros() as usize);
+        }
+    }
+    top.map(|top| Rect::new(top, left, right - left + 1, bottom - top + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_set_get_cell() {
+        let mut grid = BitGrid::new(70, 3);
+        assert_eq!(grid.words_per_row(), 2);
+        
+        grid.set_cell(1, 0, true);
+        grid.set_cell(1, 69, true);
+        assert!(grid.get_cell(1, 0));
+        assert!(grid.get_cell(1, 69));
+        assert_eq!(grid.row_words(1), &[0x8000_0000_0000_0000, 0x0400_0000_0000_0000]);
+        assert_eq!(grid.count_live_cells(), 2);
+        
+        grid.set_cell(1, 0, false);
+        assert_eq!(grid.count_live_cells(), 1);
+    }
+    
+    #[test]
+    fn test_from_grid() {
+        let pattern = ["#..#", ".##.", "#..#"];
+        let standard = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let bits = BitGrid::from_grid(&standard);
+        
+        for row in 0..3 {
+            for col in 0..4 {
+                assert_eq!(bits.get_cell(row, col), standard.get_cell(row, col));
+            }
+        }
+        assert_eq!(bits.count_live_cells(), 6);
+    }
+    
+    #[test]
+    fn test_set_operations() {
+        let mut a = BitGrid::new(100, 2);
+        let mut b = BitGrid::new(100, 2);
+        a.set_cell(0, 3, true);
+        a.set_cell(1, 99, true);
+        b.set_cell(1, 99, true);
+        b.set_cell(1, 70, true);
+        
+        assert_eq!(a.union(&b).count_live_cells(), 3);
+        assert_eq!(a.intersect(&b).count_live_cells(), 1);
+        assert!(a.intersect(&b).get_cell(1, 99));
+        assert_eq!(a.xor(&b).count_live_cells(), 2);
+        
+        a.subtract_with(&b);
+        assert!(a.get_cell(0, 3));
+        assert_eq!(a.count_live_cells(), 1);
+    }
+    
+    #[test]
+    fn test_state_hash_matches_standard_grid() {
+        let standard = StandardGrid::random(130, 9, 0.4, 17).unwrap();
+        let bits = BitGrid::from_grid(&standard);
+        assert_eq!(bits.state_hash(), standard.state_hash());
+        
+        let mut shifted = bits.clone();
+        shifted.set_cell(8, 129, !shifted.get_cell(8, 129));
+        assert_ne!(shifted.state_hash(), bits.state_hash());
+        assert_ne!(BitGrid::new(10, 4).state_hash(), BitGrid::new(4, 10).state_hash());
+    }
+    
+    #[test]
+    fn test_row_accessors() {
+        let mut grid = BitGrid::new(70, 2);
+        grid.set_cell(1, 0, true);
+        grid.set_cell(1, 69, true);
+        
+        let dyn_grid: &dyn Grid = &grid;
+        assert_eq!(dyn_grid.row_words(1), Some(&[0x8000_0000_0000_0000, 0x0400_0000_0000_0000][..]));
+        let row: Vec<bool> = dyn_grid.row(1).collect();
+        assert_eq!(row.len(), 70);
+        assert_eq!(row.iter().filter(|&&alive| alive).count(), 2);
+        assert!(row[0] && row[69]);
+        assert_eq!(StandardGrid::new(3, 1).row_words(0), None);
+    }
+    
+    #[test]
+    fn test_set_cells_matches_set_cell() {
+        let updates: Vec<(usize, usize, bool)> = (0..500)
+            .map(|i| ((i * 7) % 13, (i * 31) % 150, i % 3 != 0))
+            .collect();
+        
+        let mut bits = BitGrid::new(150, 13);
+        bits.set_cells(&mut updates.iter().copied());
+        let mut standard = StandardGrid::new(150, 13);
+        for &(row, col, alive) in &updates {
+            standard.set_cell(row, col, alive);
+        }
+        assert_eq!(bits, BitGrid::from_grid(&standard));
+        
+        let grid: &mut dyn Grid = &mut bits;
+        grid.set_cells(&mut [(0, 0, true), (0, 0, false)].into_iter());
+        assert!(!grid.get_cell(0, 0));
+    }
+}
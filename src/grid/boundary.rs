@@ -0,0 +1,79 @@
+//! What lies beyond the edges of a finite grid
+
+use crate::error::GameOfLifeError;
+use std::fmt;
+use std::str::FromStr;
+
+/// How neighbors beyond the edges of a grid are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Boundary {
+    /// Cells beyond the edges are always dead
+    #[default]
+    Bounded,
+    /// Opposite edges are joined, so patterns leaving one side reenter on the other
+    Torus,
+    /// Cells beyond an edge mirror the cells just inside it
+    Mirror,
+}
+
+impl Boundary {
+    /// The index within `0..len` that `index` reads from, or `None` if it reads a dead cell
+    pub fn resolve(self, index: isize, len: usize) -> Option<usize> {
+        let len = len as isize;
+        if (0..len).contains(&index) {
+            return Some(index as usize);
+        }
+        match self {
+            Boundary::Bounded => None,
+            Boundary::Torus if len > 0 => Some(index.rem_euclid(len) as usize),
+            // -1 reads 0 and len reads len - 1: the edge row is repeated
+            Boundary::Mirror if len > 0 => {
+                let reflected = if index < 0 { -index - 1 } else { 2 * len - index - 1 };
+                Some(reflected.clamp(0, len - 1) as usize)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = GameOfLifeError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "bounded" | "dead" => Ok(Boundary::Bounded),
+            "torus" | "toroidal" | "wrap" => Ok(Boundary::Torus),
+            "mirror" | "reflect" => Ok(Boundary::Mirror),
+            other => Err(GameOfLifeError::parse(s, s, format!("Unknown boundary '{}', expected 'bounded', 'torus' or 'mirror'", other))),
+        }
+    }
+}
+
+impl fmt::Display for Boundary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Boundary::Bounded => write!(f, "bounded"),
+            Boundary::Torus => write!(f, "torus"),
+            Boundary::Mirror => write!(f, "mirror"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_resolve_beyond_edges() {
+        assert_eq!([-1, 0, 5].map(|index| Boundary::Bounded.resolve(index, 5)), [None, Some(0), None]);
+        assert_eq!([-2, -1, 5, 6].map(|index| Boundary::Torus.resolve(index, 5)), [Some(3), Some(4), Some(0), Some(1)]);
+        assert_eq!([-2, -1, 5, 6].map(|index| Boundary::Mirror.resolve(index, 5)), [Some(1), Some(0), Some(4), Some(3)]);
+        assert_eq!(Boundary::Mirror.resolve(-2, 1), Some(0));
+        assert_eq!(Boundary::Torus.resolve(0, 0), None);
+        
+        for boundary in [Boundary::Bounded, Boundary::Torus, Boundary::Mirror] {
+            assert_eq!(boundary.to_string().parse::<Boundary>().unwrap(), boundary);
+        }
+        assert!("klein".parse::<Boundary>().is_err());
+    }
+}
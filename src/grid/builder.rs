@@ -0,0 +1,114 @@
+//! Fluent composition of patterns into a larger grid
+
+use super::{Grid, PasteMode, Rect, StandardGrid};
+use crate::error::GameOfLifeError;
+
+/// Clockwise rotation applied to a pattern before it is placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rotation {
+    #[default]
+    None,
+    Cw90,
+    Cw180,
+    Cw270,
+}
+
+impl Rotation {
+    /// Rotate a pattern
+    pub fn apply(self, pattern: &dyn Grid) -> StandardGrid {
+        match self {
+            Rotation::None => StandardGrid::from_grid(pattern),
+            Rotation::Cw90 => pattern.rotate90(),
+            Rotation::Cw180 => pattern.rotate180(),
+            Rotation::Cw270 => pattern.rotate270(),
+        }
+    }
+}
+
+/// Builder that assembles a universe from patterns and random fills
+///
+/// Steps are applied in order, so later placements draw over earlier ones
+/// according to their paste mode. Anything placed past the edge is clipped.
+#[derive(Debug, Clone)]
+pub struct GridBuilder {
+    grid: StandardGrid,
+}
+
+impl GridBuilder {
+    /// Start from an empty `width` x `height` grid
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { grid: StandardGrid::new(width, height) }
+    }
+    
+    /// Start from a copy of an existing grid
+    pub fn from_grid(grid: &dyn Grid) -> Self {
+        Self { grid: StandardGrid::from_grid(grid) }
+    }
+    
+    /// Add a pattern's live cells with its top-left corner at (row, col)
+    pub fn place(self, pattern: &dyn Grid, row: usize, col: usize) -> Self {
+        self.place_with(pattern, row, col, PasteMode::Or)
+    }
+    
+    /// Add a rotated copy of a pattern with its top-left corner at (row, col)
+    pub fn place_rotated(self, pattern: &dyn Grid, row: usize, col: usize, rotation: Rotation) -> Self {
+        self.place_with(&rotation.apply(pattern), row, col, PasteMode::Or)
+    }
+    
+    /// Stamp a pattern at (row, col) using an explicit paste mode
+    pub fn place_with(mut self, pattern: &dyn Grid, row: usize, col: usize, mode: PasteMode) -> Self {
+        self.grid.paste(pattern, row, col, mode);
+        self
+    }
+    
+    /// Overwrite the whole grid with a seeded random soup
+    ///
+    /// Fails if `density` is outside 0..=1.
+    pub fn fill_random(self, density: f64, seed: u64) -> Result<Self, GameOfLifeError> {
+        let region = Rect::new(0, 0, self.grid.width(), self.grid.height());
+        self.fill_random_in(region, density, seed)
+    }
+    
+    /// Overwrite a rectangle with a seeded random soup
+    ///
+    /// Fails if `density` is outside 0..=1.
+    pub fn fill_random_in(self, region: Rect, density: f64, seed: u64) -> Result<Self, GameOfLifeError> {
+        let soup = StandardGrid::random(region.width, region.height, density, seed)?;
+        Ok(self.place_with(&soup, region.row, region.col, PasteMode::Copy))
+    }
+    
+    /// Kill every cell inside a rectangle
+    pub fn clear_region(self, region: Rect) -> Self {
+        let empty = StandardGrid::new(region.width, region.height);
+        self.place_with(&empty, region.row, region.col, PasteMode::Copy)
+    }
+    
+    /// Finish building
+    pub fn build(self) -> StandardGrid {
+        self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_builder_composes_in_order() {
+        let blinker = StandardGrid::from_string_pattern(&["###"], '#', '.').unwrap();
+        let grid = GridBuilder::new(10, 10)
+            .fill_random_in(Rect::new(5, 5, 5, 5), 1.0, 1)
+            .unwrap()
+            .clear_region(Rect::new(6, 6, 3, 3))
+            .place(&blinker, 0, 0)
+            .place_rotated(&blinker, 0, 8, Rotation::Cw90)
+            .place_with(&blinker, 0, 1, PasteMode::Xor)
+            .build();
+        
+        assert_eq!(grid.count_live_cells(), 16 + 3 + 2);
+        assert!(grid.get_cell(0, 0) && !grid.get_cell(0, 1) && !grid.get_cell(0, 2) && grid.get_cell(0, 3));
+        assert!(grid.get_cell(2, 8));
+        assert!(!grid.get_cell(7, 7));
+        assert!(GridBuilder::new(4, 4).fill_random(1.5, 1).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! Grouping live cells into connected clusters
+
+use super::Grid;
+
+/// Group the live cells into clusters, joining cells at most `reach` apart in both directions
+///
+/// A `reach` of 1 gives the usual 8-connected components. Each cluster lists
+/// its (row, col) cells; clusters appear in the order of their first cell in
+/// row-major order. The grid's edges do not wrap.
+pub(crate) fn components(grid: &dyn Grid, reach: usize) -> Vec<Vec<(usize, usize)>> {
+    let (width, height) = (grid.width(), grid.height());
+    let mut visited = vec![false; width * height];
+    let mut clusters = Vec::new();
+    
+    for start in 0..width * height {
+        if visited[start] || !grid.get_cell(start / width, start % width) {
+            continue;
+        }
+        visited[start] = true;
+        let mut stack = vec![start];
+        let mut cells = Vec::new();
+        while let Some(index) = stack.pop() {
+            let (row, col) = (index / width, index % width);
+            cells.push((row, col));
+            for r in row.saturating_sub(reach)..=(row + reach).min(height - 1) {
+                for c in col.saturating_sub(reach)..=(col + reach).min(width - 1) {
+                    let neighbor = r * width + c;
+                    if !visited[neighbor] && grid.get_cell(r, c) {
+                        visited[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+        clusters.push(cells);
+    }
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_reach_joins_nearby_cells() {
+        let grid = StandardGrid::from_string_pattern(&["#.#...#", ".#.....", "......#"], '#', '.').unwrap();
+        let sizes = |reach| components(&grid, reach).iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(sizes(1), vec![3, 1, 1]);
+        assert_eq!(sizes(2), vec![3, 2]);
+        assert_eq!(sizes(4), vec![5]);
+        assert!(components(&StandardGrid::new(3, 3), 1).is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+//! Run-length compressed grid for archiving sparse states
+
+use super::{BitGrid, Grid};
+use crate::engines::bitwise::step_packed_row;
+use crate::error::GameOfLifeError;
+use crate::rules::Rule;
+
+/// A run of `len` live cells starting at column `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Run {
+    pub start: u32,
+    pub len: u32,
+}
+
+impl Run {
+    /// One past the last column of the run
+    pub fn end(&self) -> u32 {
+        self.start + self.len
+    }
+}
+
+/// Grid that stores each row as a sorted list of live-cell runs
+///
+/// Memory is proportional to the number of runs rather than the board area,
+/// which makes it suited to keeping many generations of sparse states.
+/// Stepping expands only a three-row window of packed words at a time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RleGrid {
+    width: usize,
+    height: usize,
+    rows: Vec<Vec<Run>>,
+}
+
+impl RleGrid {
+    /// Create an empty grid
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width <= u32::MAX as usize, "RleGrid width is limited to {} columns", u32::MAX);
+        Self {
+            width,
+            height,
+            rows: vec![Vec::new(); height],
+        }
+    }
+    
+    /// Compress any grid, using packed rows when the source provides them
+    pub fn from_grid(grid: &dyn Grid) -> Self {
+        let mut compressed = Self::new(grid.width(), grid.height());
+        for (row, runs) in compressed.rows.iter_mut().enumerate() {
+            *runs = match grid.row_words(row) {
+                Some(words) => runs_from_words(words),
+                None => runs_from_cells(grid.row(row)),
+            };
+        }
+        compressed
+    }
+    
+    /// The live runs of one row, sorted and non-adjacent
+    pub fn row_runs(&self, row: usize) -> &[Run] {
+        &self.rows[row]
+    }
+    
+    /// Total number of runs stored
+    pub fn run_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).sum()
+    }
+    
+    /// Approximate heap memory used, in bytes
+    pub fn memory_bytes(&self) -> usize {
+        self.rows.iter().map(|runs| runs.capacity() * size_of::<Run>() + size_of::<Vec<Run>>()).sum()
+    }
+    
+    /// Expand one row into packed words in the `BitGrid` layout
+    pub fn expand_row(&self, row: usize) -> Vec<u64> {
+        let mut words = vec![0u64; self.width.div_ceil(64)];
+        for run in &self.rows[row] {
+            for col in run.start as usize..run.end() as usize {
+                words[col / 64] |= 0x8000_0000_0000_0000 >> (col % 64);
+            }
+        }
+        words
+    }
+    
+    /// Expand the whole grid into a bit-packed grid
+    pub fn to_bit_grid(&self) -> BitGrid {
+        let words = (0..self.height).flat_map(|row| self.expand_row(row)).collect();
+        BitGrid::from_words(self.width, self.height, words).expect("Expanded rows match the grid size")
+    }
+    
+    /// Compute the next generation under a Moore-neighborhood rule
+    ///
+    /// Only three expanded rows are held at a time, so the peak memory beyond
+    /// the two compressed grids is a few rows of packed words.
+    pub fn step(&self, rule: Rule) -> Result<RleGrid, GameOfLifeError> {
+        rule.require_moore("RleGrid stepping")?;
+        let mut next = RleGrid::new(self.width, self.height);
+        let mut out = vec![0u64; self.width.div_ceil(64)];
+        
+        let mut above: Option<Vec<u64>> = None;
+        let mut current = (self.height > 0).then(|| self.expand_row(0));
+        for row in 0..self.height {
+            let below = (row + 1 < self.height).then(|| self.expand_row(row + 1));
+            let words = current.as_deref().expect("Current row is expanded");
+            step_packed_row(above.as_deref(), words, below.as_deref(), &mut out, self.width, rule.birth_mask(), rule.survival_mask());
+            next.rows[row] = runs_from_words(&out);
+            above = current;
+            current = below;
+        }
+        Ok(next)
+    }
+}
+
+/// Collect live runs from one packed row
+fn runs_from_words(words: &[u64]) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for (i, &word) in words.iter().enumerate() {
+        let mut remaining = word;
+        while remaining != 0 {
+            let offset = remaining.leading_zeros();
+            let len = (!(remaining << offset)).leading_zeros().min(64 - offset);
+            let start = (i * 64) as u32 + offset;
+            match runs.last_mut() {
+                Some(last) if last.end() == start => last.len += len,
+                _ => runs.push(Run { start, len }),
+            }
+            remaining &= if offset + len >= 64 { 0 } else { !0u64 >> (offset + len) };
+        }
+    }
+    runs
+}
+
+/// Collect live runs from a row of cells
+fn runs_from_cells(cells: impl Iterator<Item = bool>) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for (col, alive) in cells.enumerate() {
+        if !alive {
+            continue;
+        }
+        let col = col as u32;
+        match runs.last_mut() {
+            Some(last) if last.end() == col => last.len += 1,
+            _ => runs.push(Run { start: col, len: 1 }),
+        }
+    }
+    runs
+}
+
+impl Grid for RleGrid {
+    fn width(&self) -> usize {
+        self.width
+    }
+    
+    fn height(&self) -> usize {
+        self.height
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        if row >= self.height || col >= self.width {
+            return false;
+        }
+        let col = col as u32;
+        let runs = &self.rows[row];
+        let idx = runs.partition_point(|run| run.end() <= col);
+        runs.get(idx).is_some_and(|run| run.start <= col)
+    }
+    
+    fn set_cell(&mut self, row: usize, col: usize, alive: bool) {
+        if row >= self.height || col >= self.width || self.get_cell(row, col) == alive {
+            return;
+        }
+        let col = col as u32;
+        let runs = &mut self.rows[row];
+        let idx = runs.partition_point(|run| run.end() <= col);
+        
+        if alive {
+            let joins_previous = idx > 0 && runs[idx - 1].end() == col;
+            let joins_next = idx < runs.len() && runs[idx].start == col + 1;
+            match (joins_previous, joins_next) {
+                (true, true) => {
+                    runs[idx - 1].len += 1 + runs[idx].len;
+                    runs.remove(idx);
+                }
+                (true, false) => runs[idx - 1].len += 1,
+                (false, true) => {
+                    runs[idx].start -= 1;
+                    runs[idx].len += 1;
+                }
+                (false, false) => runs.insert(idx, Run { start: col, len: 1 }),
+            }
+        } else {
+            let run = runs[idx];
+            let left = Run { start: run.start, len: col - run.start };
+            let right = Run { start: col + 1, len: run.end() - col - 1 };
+            runs.splice(idx..=idx, [left, right].into_iter().filter(|part| part.len > 0));
+        }
+    }
+    
+    fn clear(&mut self) {
+        self.rows.iter_mut().for_each(Vec::clear);
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.rows.iter().flatten().map(|run| run.len as usize).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{GameOfLifeEngine, NaiveEngine};
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_runs_and_edits() {
+        let grid = StandardGrid::from_string_pattern(&["##.###..", "........"], '#', '.').unwrap();
+        let mut compressed = RleGrid::from_grid(&grid);
+        assert_eq!(compressed.row_runs(0), &[Run { start: 0, len: 2 }, Run { start: 3, len: 3 }]);
+        assert!(compressed.row_runs(1).is_empty());
+        
+        compressed.set_cell(0, 2, true);
+        assert_eq!(compressed.row_runs(0), &[Run { start: 0, len: 6 }]);
+        compressed.set_cell(0, 4, false);
+        assert_eq!(compressed.row_runs(0), &[Run { start: 0, len: 4 }, Run { start: 5, len: 1 }]);
+        compressed.set_cell(0, 5, false);
+        compressed.set_cell(1, 7, true);
+        assert_eq!(compressed.count_live_cells(), 5);
+        assert!(compressed.get_cell(1, 7) && !compressed.get_cell(0, 4));
+    }
+    
+    #[test]
+    fn test_packed_round_trip_and_step() {
+        let soup = StandardGrid::random(150, 40, 0.3, 6).unwrap();
+        let bits = BitGrid::from_grid(&soup);
+        let compressed = RleGrid::from_grid(&bits);
+        assert_eq!(compressed, RleGrid::from_grid(&soup));
+        assert_eq!(compressed.to_bit_grid(), bits);
+        
+        let mut naive = NaiveEngine::from_grid(&soup);
+        let mut state = compressed;
+        for _ in 0..10 {
+            naive.step();
+            state = state.step(Rule::conway()).unwrap();
+            assert_eq!(state.state_hash(), naive.state_hash());
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Cell-level comparison of grids
+
+use super::Grid;
+use crate::error::GameOfLifeError;
+
+/// Cells that changed between two grids of the same size, as (row, col) pairs in row-major order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GridDiff {
+    /// Cells dead in the first grid and alive in the second
+    pub births: Vec<(usize, usize)>,
+    /// Cells alive in the first grid and dead in the second
+    pub deaths: Vec<(usize, usize)>,
+}
+
+impl GridDiff {
+    /// Whether the two grids were identical
+    pub fn is_empty(&self) -> bool {
+        self.births.is_empty() && self.deaths.is_empty()
+    }
+    
+    /// Total number of changed cells
+    pub fn len(&self) -> usize {
+        self.births.len() + self.deaths.len()
+    }
+    
+    /// Apply the changes to a grid, turning the first grid into the second
+    pub fn apply(&self, grid: &mut dyn Grid) {
+        for &(row, col) in &self.births {
+            grid.set_cell(row, col, true);
+        }
+        for &(row, col) in &self.deaths {
+            grid.set_cell(row, col, false);
+        }
+    }
+    
+    /// The diff that undoes this one
+    pub fn inverted(&self) -> GridDiff {
+        GridDiff {
+            births: self.deaths.clone(),
+            deaths: self.births.clone(),
+        }
+    }
+}
+
+/// List the births and deaths that turn grid `a` into grid `b`
+///
+/// Fails if the grids have different dimensions.
+pub fn diff(a: &dyn Grid, b: &dyn Grid) -> Result<GridDiff, GameOfLifeError> {
+    if a.width() != b.width() || a.height() != b.height() {
+        return Err(GameOfLifeError::dimensions((a.width(), a.height()), (b.width(), b.height())));
+    }
+    
+    let mut result = GridDiff::default();
+    for row in 0..a.height() {
+        for col in 0..a.width() {
+            match (a.get_cell(row, col), b.get_cell(row, col)) {
+                (false, true) => result.births.push((row, col)),
+                (true, false) => result.deaths.push((row, col)),
+                _ => {}
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Whether two grids have the same dimensions and cell contents, whatever their representation
+///
+/// Rows are compared word by word when both grids are bit-packed.
+pub fn grids_equal(a: &dyn Grid, b: &dyn Grid) -> bool {
+    if a.width() != b.width() || a.height() != b.height() {
+        return false;
+    }
+    (0..a.height()).all(|row| match (a.row_words(row), b.row_words(row)) {
+        (Some(a_words), Some(b_words)) => a_words == b_words,
+        _ => a.row(row).eq(b.row(row)),
+    })
+}
+
+impl PartialEq for dyn Grid + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        grids_equal(self, other)
+    }
+}
+
+impl Eq for dyn Grid + '_ {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{BitGrid, StandardGrid};
+    
+    #[test]
+    fn test_blinker_diff() {
+        let horizontal = StandardGrid::from_string_pattern(&["...", "###", "..."], '#', '.').unwrap();
+        let vertical = StandardGrid::from_string_pattern(&[".#.", ".#.", ".#."], '#', '.').unwrap();
+        
+        let changes = diff(&horizontal, &vertical).unwrap();
+        assert_eq!(changes.births, vec![(0, 1), (2, 1)]);
+        assert_eq!(changes.deaths, vec![(1, 0), (1, 2)]);
+        assert_eq!(changes.len(), 4);
+        
+        // Works across representations and round-trips through apply
+        let mut bits = BitGrid::from_grid(&horizontal);
+        changes.apply(&mut bits);
+        assert!(diff(&bits, &vertical).unwrap().is_empty());
+        changes.inverted().apply(&mut bits);
+        assert!(diff(&bits, &horizontal).unwrap().is_empty());
+        
+        let tall = StandardGrid::new(3, 4);
+        assert!(matches!(diff(&horizontal, &tall), Err(GameOfLifeError::DimensionMismatch { height: 4, .. })));
+    }
+    
+    #[test]
+    fn test_grids_equal_across_representations() {
+        let standard = StandardGrid::random(80, 5, 0.5, 21).unwrap();
+        let mut bits = BitGrid::from_grid(&standard);
+        assert!(grids_equal(&standard, &bits));
+        assert!(grids_equal(&bits, &bits.clone()));
+        assert!(&standard as &dyn Grid == &bits as &dyn Grid);
+        
+        bits.set_cell(4, 79, !bits.get_cell(4, 79));
+        assert!(!grids_equal(&standard, &bits));
+        assert!(!grids_equal(&bits, &BitGrid::from_grid(&standard)));
+        assert!(!grids_equal(&StandardGrid::new(4, 2), &StandardGrid::new(2, 4)));
+    }
+}
@@ -0,0 +1,11 @@
+//! Errors reported by grid operations
+
+use thiserror::Error;
+
+/// Error produced by checked grid accessors
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GridError {
+    /// The coordinates lie outside the grid
+    #[error("Cell ({row}, {col}) is outside the {width}x{height} grid")]
+    OutOfBounds { row: usize, col: usize, width: usize, height: usize },
+}
@@ -0,0 +1,62 @@
+//! Representation-independent hashing of grid contents
+
+/// Incremental hasher over bit-packed grid rows
+///
+/// Rows are fed as 64-cell words, most significant bit first, with the last
+/// word of each row zero-padded. Any grid or engine that packs its cells this
+/// way produces the same hash for the same contents.
+pub(crate) struct StateHasher {
+    state: u64,
+}
+
+impl StateHasher {
+    /// Start hashing a grid with the given dimensions
+    pub(crate) fn new(width: usize, height: usize) -> Self {
+        let mut hasher = Self { state: 0x9E37_79B9_7F4A_7C15 };
+        hasher.write_word(width as u64);
+        hasher.write_word(height as u64);
+        hasher
+    }
+
+    /// Mix one packed word into the hash
+    #[inline(always)]
+    pub(crate) fn write_word(&mut self, word: u64) {
+        self.state = fmix64(self.state.rotate_left(23) ^ word.wrapping_mul(0x87C3_7B91_1142_53D5));
+    }
+
+    /// Hash a row by packing the given cells into words
+    pub(crate) fn write_cells(&mut self, cells: impl Iterator<Item = bool>) {
+        let mut word = 0u64;
+        let mut bits = 0;
+        for alive in cells {
+            if alive {
+                word |= 0x8000_0000_0000_0000 >> bits;
+            }
+            bits += 1;
+            if bits == 64 {
+                self.write_word(word);
+                word = 0;
+                bits = 0;
+            }
+        }
+        if bits > 0 {
+            self.write_word(word);
+        }
+    }
+
+    /// Finish hashing and return the result
+    pub(crate) fn finish(&self) -> u64 {
+        fmix64(self.state)
+    }
+}
+
+/// MurmurHash3 64-bit finalizer
+#[inline(always)]
+pub(crate) fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+    k ^= k >> 33;
+    k
+}
@@ -0,0 +1,209 @@
+//! Disk-backed bit-packed grid for boards larger than memory
+//!
+//! The file holds a 32-byte header (magic, width, height) followed by the
+//! rows in `BitGrid` layout: 64 cells per native-endian u64, most significant
+//! bit first, each row padded to a whole word. The operating system pages
+//! rows in and out on demand, so only the bands being touched need to be resident.
+
+use super::Grid;
+use memmap2::MmapMut;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 8] = b"GOLBITS1";
+const HEADER_LEN: usize = 32;
+
+/// Bit-packed grid stored in a memory-mapped file
+pub struct MmapGrid {
+    path: PathBuf,
+    file: File,
+    map: MmapMut,
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+}
+
+impl MmapGrid {
+    /// Create (or truncate) a file holding an empty `width` x `height` grid
+    pub fn create(path: impl AsRef<Path>, width: usize, height: usize) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path)?;
+        Self::initialize(path, file, width, height)
+    }
+    
+    /// Open a grid file previously written by `create`
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+        // SAFETY: the mapping is only valid while no other process resizes the
+        // file; grid files are owned by the process that opened them
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        if map.len() < HEADER_LEN || &map[..8] != MAGIC {
+            return Err(invalid_data(format!("{} is not a grid file", path.display())));
+        }
+        let width = read_u64(&map, 8) as usize;
+        let height = read_u64(&map, 16) as usize;
+        let words_per_row = width.div_ceil(64);
+        if map.len() != HEADER_LEN + words_per_row * height * 8 {
+            return Err(invalid_data(format!(
+                "{} has {} bytes, expected {} for a {}x{} grid",
+                path.display(), map.len(), HEADER_LEN + words_per_row * height * 8, width, height
+            )));
+        }
+        Ok(Self { path, file, map, width, height, words_per_row })
+    }
+    
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// Number of u64 words per row
+    pub fn words_per_row(&self) -> usize {
+        self.words_per_row
+    }
+    
+    /// All packed words, row-major
+    pub fn words(&self) -> &[u64] {
+        // SAFETY: u64 has no invalid bit patterns and the data starts at a
+        // page-aligned address plus the 32-byte header
+        let (prefix, words, _) = unsafe { self.map[HEADER_LEN..].align_to::<u64>() };
+        debug_assert!(prefix.is_empty());
+        words
+    }
+    
+    /// All packed words, row-major, mutably
+    ///
+    /// Callers must keep the tail bits beyond `width` cleared.
+    pub fn words_mut(&mut self) -> &mut [u64] {
+        // SAFETY: as for `words`
+        let (prefix, words, _) = unsafe { self.map[HEADER_LEN..].align_to_mut::<u64>() };
+        debug_assert!(prefix.is_empty());
+        words
+    }
+    
+    /// Resize the backing file to new dimensions and clear every cell
+    pub fn resize(&mut self, width: usize, height: usize) -> io::Result<()> {
+        self.map.flush()?;
+        // Unmap before truncating; some platforms refuse to resize mapped files
+        drop(std::mem::replace(&mut self.map, MmapMut::map_anon(1)?));
+        let file = self.file.try_clone()?;
+        *self = Self::initialize(self.path.clone(), file, width, height)?;
+        Ok(())
+    }
+    
+    /// Size of the mapping, header included, in bytes
+    ///
+    /// The pages are backed by the file, so the kernel may evict them; this is
+    /// an upper bound on the resident memory rather than heap allocation.
+    pub fn memory_bytes(&self) -> usize {
+        self.map.len()
+    }
+    
+    /// Write dirty pages back to the file
+    pub fn flush(&self) -> io::Result<()> {
+        self.map.flush()
+    }
+    
+    /// Size the file for the given dimensions, zero it and write the header
+    fn initialize(path: PathBuf, file: File, width: usize, height: usize) -> io::Result<Self> {
+        let words_per_row = width.div_ceil(64);
+        file.set_len(0)?;
+        file.set_len((HEADER_LEN + words_per_row * height * 8) as u64)?;
+        // SAFETY: see `open`
+        let mut map = unsafe { MmapMut::map_mut(&file)? };
+        map[..8].copy_from_slice(MAGIC);
+        map[8..16].copy_from_slice(&(width as u64).to_le_bytes());
+        map[16..24].copy_from_slice(&(height as u64).to_le_bytes());
+        Ok(Self { path, file, map, width, height, words_per_row })
+    }
+    
+    /// Get the word index and bit mask for row, col coordinates
+    fn locate(&self, row: usize, col: usize) -> (usize, u64) {
+        (row * self.words_per_row + col / 64, 0x8000_0000_0000_0000 >> (col % 64))
+    }
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl Grid for MmapGrid {
+    fn width(&self) -> usize {
+        self.width
+    }
+    
+    fn height(&self) -> usize {
+        self.height
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        if row >= self.height || col >= self.width {
+            return false;
+        }
+        let (idx, bit) = self.locate(row, col);
+        self.words()[idx] & bit != 0
+    }
+    
+    fn set_cell(&mut self, row: usize, col: usize, alive: bool) {
+        if row >= self.height || col >= self.width {
+            return;
+        }
+        let (idx, bit) = self.locate(row, col);
+        let word = &mut self.words_mut()[idx];
+        if alive {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+    
+    fn clear(&mut self) {
+        self.words_mut().fill(0);
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.words().iter().map(|word| word.count_ones() as usize).sum()
+    }
+    
+    fn row_words(&self, row: usize) -> Option<&[u64]> {
+        let start = row * self.words_per_row;
+        Some(&self.words()[start..start + self.words_per_row])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{BitGrid, StandardGrid};
+    
+    #[test]
+    fn test_create_reopen_and_resize() {
+        let path = std::env::temp_dir().join(format!("gol_mmap_grid_{}.bits", std::process::id()));
+        let soup = StandardGrid::random(100, 7, 0.5, 8).unwrap();
+        {
+            let mut grid = MmapGrid::create(&path, 100, 7).unwrap();
+            grid.paste(&soup, 0, 0, crate::grid::PasteMode::Copy);
+            grid.flush().unwrap();
+        }
+        
+        let mut grid = MmapGrid::open(&path).unwrap();
+        assert_eq!((grid.width(), grid.height()), (100, 7));
+        assert_eq!(grid.words(), BitGrid::from_grid(&soup).words());
+        assert_eq!(grid.state_hash(), soup.state_hash());
+        
+        grid.resize(3, 2).unwrap();
+        assert_eq!(grid.count_live_cells(), 0);
+        drop(grid);
+        assert_eq!(MmapGrid::open(&path).unwrap().width(), 3);
+        
+        std::fs::write(&path, b"not a grid").unwrap();
+        assert!(MmapGrid::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,6 +1,49 @@
+#[cfg(feature = "ndarray")]
+mod array;
+pub mod bit;
+pub mod boundary;
+pub mod builder;
+pub mod compressed;
+pub mod diff;
+pub mod error;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod noise;
+#[cfg(feature = "serde")]
+mod packed;
+pub mod paste;
+#[cfg(feature = "image")]
+mod raster;
+pub mod rect;
 pub mod standard;
+pub mod symmetry;
+pub mod topology;
+pub mod transform;
+pub mod value;
+pub mod volume;
+pub(crate) mod components;
+pub(crate) mod hash;
+pub(crate) mod random;
 
+pub use bit::BitGrid;
+pub use boundary::Boundary;
+pub use builder::{GridBuilder, Rotation};
+pub use compressed::{RleGrid, Run};
+pub use diff::{diff, grids_equal, GridDiff};
+pub use error::GridError;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapGrid;
+pub use paste::PasteMode;
+pub use rect::{Anchor, Rect};
 pub use standard::StandardGrid;
+pub use symmetry::{Symmetry, SymmetryGroup};
+pub use topology::Topology;
+pub use transform::Transform;
+pub use value::{StateGrid, ValueGrid};
+pub use volume::{Grid3, StandardGrid3};
+
+use crate::error::GameOfLifeError;
+use crate::rules::NeighborhoodKind;
 
 /// Trait for Game of Life grid representations
 pub trait Grid {
@@ -11,11 +54,61 @@ pub trait Grid {
     fn height(&self) -> usize;
     
     /// Get the state of a cell (true = alive, false = dead)
+    ///
+    /// Out-of-bounds coordinates read as dead; use `try_get_cell` to tell
+    /// them apart from dead cells.
     fn get_cell(&self, row: usize, col: usize) -> bool;
     
     /// Set the state of a cell
+    ///
+    /// Out-of-bounds coordinates are ignored; use `try_set_cell` to detect them.
     fn set_cell(&mut self, row: usize, col: usize, alive: bool);
     
+    /// Iterate over the cells of one row, left to right
+    ///
+    /// Panics if `row` is out of bounds.
+    fn row(&self, row: usize) -> Box<dyn Iterator<Item = bool> + '_> {
+        assert!(row < self.height(), "Row {} out of bounds for height {}", row, self.height());
+        Box::new((0..self.width()).map(move |col| self.get_cell(row, col)))
+    }
+    
+    /// The packed words of one row, for grids stored 64 cells per word, most significant bit first
+    ///
+    /// Unused tail bits of the last word are zero. Returns `None` for grids
+    /// that are not bit-packed.
+    fn row_words(&self, _row: usize) -> Option<&[u64]> {
+        None
+    }
+    
+    /// Set many cells at once from (row, col, alive) triples, applied in order
+    ///
+    /// Takes a `dyn Iterator` so it can be called through `&mut dyn Grid`.
+    /// Out-of-bounds cells are skipped, as with `set_cell`.
+    fn set_cells(&mut self, cells: &mut dyn Iterator<Item = (usize, usize, bool)>) {
+        for (row, col, alive) in cells {
+            self.set_cell(row, col, alive);
+        }
+    }
+    
+    /// Whether (row, col) lies inside the grid
+    fn in_bounds(&self, row: usize, col: usize) -> bool {
+        row < self.height() && col < self.width()
+    }
+    
+    /// Get the state of a cell, or `None` if the coordinates are out of bounds
+    fn try_get_cell(&self, row: usize, col: usize) -> Option<bool> {
+        self.in_bounds(row, col).then(|| self.get_cell(row, col))
+    }
+    
+    /// Set the state of a cell, failing instead of ignoring out-of-bounds coordinates
+    fn try_set_cell(&mut self, row: usize, col: usize, alive: bool) -> Result<(), GridError> {
+        if !self.in_bounds(row, col) {
+            return Err(GridError::OutOfBounds { row, col, width: self.width(), height: self.height() });
+        }
+        self.set_cell(row, col, alive);
+        Ok(())
+    }
+    
     /// Clear all cells (set to dead)
     fn clear(&mut self);
     
@@ -26,37 +119,245 @@ pub trait Grid {
     
     /// Count live neighbors for a cell at the given position
     fn count_neighbors(&self, row: usize, col: usize) -> u8 {
+        self.count_neighbors_in(row, col, Topology::Square)
+    }
+    
+    /// Count live neighbors for a cell using the given neighborhood topology
+    fn count_neighbors_in(&self, row: usize, col: usize, topology: Topology) -> u8 {
+        self.count_neighbors_at(row, col, topology.neighbor_offsets(row))
+    }
+    
+    /// Count live neighbors for a cell over a rule neighborhood on a square grid
+    fn count_neighbors_with(&self, row: usize, col: usize, neighborhood: NeighborhoodKind) -> u8 {
+        self.count_neighbors_at(row, col, neighborhood.offsets())
+    }
+    
+    /// Count live cells at the given (row, col) offsets; cells outside the grid are dead
+    fn count_neighbors_at(&self, row: usize, col: usize, offsets: &[(isize, isize)]) -> u8 {
         let mut count = 0;
         let height = self.height();
         let width = self.width();
         
-        for dr in [-1, 0, 1].iter() {
-            for dc in [-1, 0, 1].iter() {
-                if *dr == 0 && *dc == 0 {
-                    continue;
-                }
-                let r = row as isize + dr;
-                let c = col as isize + dc;
-                if r >= 0 && r < height as isize && c >= 0 && c < width as isize {
-                    if self.get_cell(r as usize, c as usize) {
-                        count += 1;
-                    }
+        for (dr, dc) in offsets {
+            let r = row as isize + dr;
+            let c = col as isize + dc;
+            if r >= 0 && r < height as isize && c >= 0 && c < width as isize {
+                if self.get_cell(r as usize, c as usize) {
+                    count += 1;
                 }
             }
         }
         count
     }
     
+    /// Count live cells at the given (row, col) offsets, reading cells beyond the edges as `boundary` says
+    fn count_neighbors_across(&self, row: usize, col: usize, offsets: &[(isize, isize)], boundary: Boundary) -> u8 {
+        let (height, width) = (self.height(), self.width());
+        let mut count = 0;
+        for (dr, dc) in offsets {
+            let r = boundary.resolve(row as isize + dr, height);
+            let c = boundary.resolve(col as isize + dc, width);
+            if let (Some(r), Some(c)) = (r, c) {
+                count += self.get_cell(r, c) as u8;
+            }
+        }
+        count
+    }
+    
     /// Count total live cells in the grid
     fn count_live_cells(&self) -> usize {
-        let mut count = 0;
+        (0..self.height()).map(|row| self.row(row).filter(|&alive| alive).count()).sum()
+    }
+    
+    /// Copy a rectangular window into a new grid
+    ///
+    /// The result always has the size of `rect`; parts of the window that fall
+    /// outside this grid are dead.
+    fn crop(&self, rect: Rect) -> StandardGrid {
+        let mut cropped = StandardGrid::new(rect.width, rect.height);
+        let visible = rect.intersection(&Rect::new(0, 0, self.width(), self.height()));
+        for row in visible.row..visible.row + visible.height {
+            for col in visible.col..visible.col + visible.width {
+                if self.get_cell(row, col) {
+                    cropped.set_cell(row - rect.row, col - rect.col, true);
+                }
+            }
+        }
+        cropped
+    }
+    
+    /// Stamp `pattern` with its top-left corner at (row, col)
+    ///
+    /// Parts of the pattern that fall outside this grid are dropped.
+    fn paste(&mut self, pattern: &dyn Grid, row: usize, col: usize, mode: PasteMode) {
+        let height = pattern.height().min(self.height().saturating_sub(row));
+        let width = pattern.width().min(self.width().saturating_sub(col));
+        for r in 0..height {
+            for c in 0..width {
+                let current = self.get_cell(row + r, col + c);
+                let next = mode.combine(current, pattern.get_cell(r, c));
+                if next != current {
+                    self.set_cell(row + r, col + c, next);
+                }
+            }
+        }
+    }
+    
+    /// Copy the grid rotated a quarter turn clockwise
+    fn rotate90(&self) -> StandardGrid {
+        let height = self.height();
+        transformed(self, height, self.width(), &|row, col| (height - 1 - col, row))
+    }
+    
+    /// Copy the grid rotated a half turn
+    fn rotate180(&self) -> StandardGrid {
+        let (width, height) = (self.width(), self.height());
+        transformed(self, width, height, &|row, col| (height - 1 - row, width - 1 - col))
+    }
+    
+    /// Copy the grid rotated a quarter turn counter-clockwise
+    fn rotate270(&self) -> StandardGrid {
+        let width = self.width();
+        transformed(self, self.height(), width, &|row, col| (col, width - 1 - row))
+    }
+    
+    /// Copy the grid mirrored left to right
+    fn flip_horizontal(&self) -> StandardGrid {
+        let width = self.width();
+        transformed(self, width, self.height(), &|row, col| (row, width - 1 - col))
+    }
+    
+    /// Copy the grid mirrored top to bottom
+    fn flip_vertical(&self) -> StandardGrid {
+        let height = self.height();
+        transformed(self, self.width(), height, &|row, col| (height - 1 - row, col))
+    }
+    
+    /// Copy the grid mirrored across its main diagonal
+    fn transpose(&self) -> StandardGrid {
+        transformed(self, self.height(), self.width(), &|row, col| (col, row))
+    }
+    
+    /// Smallest rectangle containing every live cell, or `None` if the grid is empty
+    fn bounding_box(&self) -> Option<Rect> {
+        if self.height() > 0 && self.row_words(0).is_some() {
+            return bit::packed_bounding_box(self.height(), |row| self.row_words(row).unwrap_or(&[]));
+        }
+        let (mut top, mut left) = (usize::MAX, usize::MAX);
+        let (mut bottom, mut right) = (0, 0);
         for row in 0..self.height() {
             for col in 0..self.width() {
                 if self.get_cell(row, col) {
-                    count += 1;
+                    top = top.min(row);
+                    bottom = bottom.max(row);
+                    left = left.min(col);
+                    right = right.max(col);
                 }
             }
         }
-        count
+        if top == usize::MAX {
+            return None;
+        }
+        Some(Rect::new(top, left, right - left + 1, bottom - top + 1))
+    }
+    
+    /// Copy of the live cells cropped to their bounding box; an empty grid trims to 0x0
+    fn trimmed(&self) -> StandardGrid {
+        match self.bounding_box() {
+            Some(rect) => self.crop(rect),
+            None => StandardGrid::new(0, 0),
+        }
+    }
+    
+    /// Detect which symmetries of the square the whole grid has
+    ///
+    /// Quarter turns and diagonal reflections are only checked on square grids.
+    /// Trim the grid first to test a pattern independently of where it sits.
+    fn symmetries(&self) -> SymmetryGroup {
+        let (width, height) = (self.width(), self.height());
+        let square = width == height;
+        let invariant = |image: &dyn Fn(usize, usize) -> (usize, usize)| {
+            (0..height).all(|row| {
+                (0..width).all(|col| {
+                    let (r, c) = image(row, col);
+                    self.get_cell(row, col) == self.get_cell(r, c)
+                })
+            })
+        };
+        let (last_row, last_col) = (height.saturating_sub(1), width.saturating_sub(1));
+        SymmetryGroup {
+            rotate90: square && invariant(&|row, col| (col, last_col - row)),
+            rotate180: invariant(&|row, col| (last_row - row, last_col - col)),
+            flip_horizontal: invariant(&|row, col| (row, last_col - col)),
+            flip_vertical: invariant(&|row, col| (last_row - row, col)),
+            diagonal: square && invariant(&|row, col| (col, row)),
+            anti_diagonal: square && invariant(&|row, col| (last_col - col, last_row - row)),
+        }
+    }
+    
+    /// Make the grid invariant under a symmetry group
+    ///
+    /// Each orbit of cells copies the state of its top-most, then left-most
+    /// member, so the top-left fundamental region is reflected into the rest.
+    fn symmetrize(&mut self, symmetry: Symmetry) -> Result<(), GameOfLifeError> {
+        let (width, height) = (self.width(), self.height());
+        symmetry.check_dimensions(width, height)?;
+        for row in 0..height {
+            for col in 0..width {
+                let (r, c) = symmetry.orbit(row, col, width, height).into_iter().min().unwrap();
+                let alive = self.get_cell(r, c);
+                if self.get_cell(row, col) != alive {
+                    self.set_cell(row, col, alive);
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// Stable 64-bit hash of the dimensions and cell contents
+    ///
+    /// Equal for any two grids or engines with the same contents, whatever
+    /// their representation, and stable across runs and platforms.
+    fn state_hash(&self) -> u64 {
+        let mut hasher = hash::StateHasher::new(self.width(), self.height());
+        for row in 0..self.height() {
+            match self.row_words(row) {
+                Some(words) => words.iter().for_each(|&word| hasher.write_word(word)),
+                None => hasher.write_cells(self.row(row)),
+            }
+        }
+        hasher.finish()
+    }
+    
+    /// Copy the grid into a `(height, width)` boolean array
+    #[cfg(feature = "ndarray")]
+    fn to_array2(&self) -> ndarray::Array2<bool> {
+        array::to_array2(self)
+    }
+    
+    /// Copy the grid into a `(height, width)` array of 0 and 1
+    #[cfg(feature = "ndarray")]
+    fn to_array2_u8(&self) -> ndarray::Array2<u8> {
+        array::to_array2(self).mapv(u8::from)
+    }
+    
+    /// Render the grid as a grayscale image, one pixel per cell, live cells black on white
+    #[cfg(feature = "image")]
+    fn to_image(&self) -> image::GrayImage {
+        raster::to_image(self)
+    }
+}
+
+/// Build a `width` x `height` grid whose cell (row, col) is read from `source(row, col)` in `grid`
+fn transformed<G: Grid + ?Sized>(grid: &G, width: usize, height: usize, source: &dyn Fn(usize, usize) -> (usize, usize)) -> StandardGrid {
+    let mut result = StandardGrid::new(width, height);
+    for row in 0..height {
+        for col in 0..width {
+            let (src_row, src_col) = source(row, col);
+            if grid.get_cell(src_row, src_col) {
+                result.set_cell(row, col, true);
+            }
+        }
     }
-}
\ No newline at end of file
+    result
+}
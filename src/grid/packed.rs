@@ -0,0 +1,80 @@
+//! Serde support for grids using a compact bit-packed encoding
+//!
+//! Grids serialize as `{ width, height, words }`, where `words` holds each
+//! row as 64-cell words, most significant bit first, exactly as in `BitGrid`.
+//! `StandardGrid` and `BitGrid` share the encoding and can read each other's output.
+
+use super::{BitGrid, Grid, StandardGrid};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize)]
+struct PackedRef<'a> {
+    width: usize,
+    height: usize,
+    words: &'a [u64],
+}
+
+#[derive(Deserialize)]
+struct Packed {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+}
+
+impl Serialize for BitGrid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PackedRef { width: self.width(), height: self.height(), words: self.words() }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BitGrid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let packed = Packed::deserialize(deserializer)?;
+        BitGrid::from_words(packed.width, packed.height, packed.words).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for StandardGrid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BitGrid::from_grid(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StandardGrid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = BitGrid::deserialize(deserializer)?;
+        let mut grid = StandardGrid::new(bits.width(), bits.height());
+        for row in 0..bits.height() {
+            for col in 0..bits.width() {
+                if bits.get_cell(row, col) {
+                    grid.set_cell(row, col, true);
+                }
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_round_trip_is_compact() {
+        let grid = StandardGrid::random(100, 3, 0.5, 9).unwrap();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert!(!json.contains("true"));
+        assert_eq!(serde_json::from_str::<StandardGrid>(&json).unwrap(), grid);
+        
+        let bits: BitGrid = serde_json::from_str(&json).unwrap();
+        assert_eq!(bits, BitGrid::from_grid(&grid));
+    }
+    
+    #[test]
+    fn test_rejects_malformed_words() {
+        assert!(serde_json::from_str::<BitGrid>(r#"{"width":70,"height":1,"words":[0]}"#).is_err());
+        assert!(serde_json::from_str::<BitGrid>(r#"{"width":4,"height":1,"words":[1]}"#).is_err());
+        assert!(serde_json::from_str::<BitGrid>(r#"{"width":4,"height":1,"words":[9223372036854775808]}"#).is_ok());
+    }
+}
@@ -0,0 +1,24 @@
+//! How a pattern is combined with the cells it is pasted over
+
+/// Combination mode for `Grid::paste`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PasteMode {
+    /// Live pattern cells are added; existing live cells are kept
+    #[default]
+    Or,
+    /// Live pattern cells toggle the cells underneath
+    Xor,
+    /// The pattern replaces everything underneath it, dead cells included
+    Copy,
+}
+
+impl PasteMode {
+    /// The new state of a cell given its current state and the pattern cell over it
+    pub fn combine(self, current: bool, pattern: bool) -> bool {
+        match self {
+            PasteMode::Or => current | pattern,
+            PasteMode::Xor => current ^ pattern,
+            PasteMode::Copy => pattern,
+        }
+    }
+}
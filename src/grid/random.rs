@@ -0,0 +1,38 @@
+//! Small seeded random number generator for reproducible initial states
+
+use super::hash::fmix64;
+
+/// SplitMix64 generator: fast, seedable and stable across platforms and releases
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    
+    /// Uniform sample in [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+    
+    /// Bernoulli trial that succeeds with probability `p`
+    pub(crate) fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+}
+
+/// Uniform sample in [0, 1) addressed by `index`, for layouts that are not filled in order
+pub(crate) fn indexed_sample(seed: u64, index: usize) -> f64 {
+    (fmix64(seed ^ fmix64(index as u64)) >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,63 @@
+//! Conversions between grids and grayscale images
+//!
+//! Live cells are dark pixels, so black-on-white artwork seeds live cells
+//! where the ink is and exported frames read naturally.
+
+use super::{Grid, StandardGrid};
+use crate::formats::FormatError;
+use image::{GrayImage, Luma};
+use std::path::Path;
+
+impl StandardGrid {
+    /// Load an image and make every pixel darker than `threshold` a live cell
+    ///
+    /// Color images are converted to grayscale first; one pixel becomes one cell.
+    pub fn from_image(path: impl AsRef<Path>, threshold: u8) -> Result<Self, FormatError> {
+        let image = image::open(path)?.into_luma8();
+        Ok(Self::from_gray_image(&image, threshold))
+    }
+    
+    /// Make every pixel darker than `threshold` a live cell
+    pub fn from_gray_image(image: &GrayImage, threshold: u8) -> Self {
+        let mut grid = StandardGrid::new(image.width() as usize, image.height() as usize);
+        for (x, y, pixel) in image.enumerate_pixels() {
+            if pixel.0[0] < threshold {
+                grid.set_cell(y as usize, x as usize, true);
+            }
+        }
+        grid
+    }
+}
+
+/// Render a grid as one pixel per cell, live cells black on white
+pub(crate) fn to_image<G: Grid + ?Sized>(grid: &G) -> GrayImage {
+    GrayImage::from_fn(grid.width() as u32, grid.height() as u32, |x, y| {
+        if grid.get_cell(y as usize, x as usize) {
+            Luma([0])
+        } else {
+            Luma([255])
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_image_round_trip() {
+        let glider = StandardGrid::from_string_pattern(&[".#..", "..#.", "###."], '#', '.').unwrap();
+        let image = glider.to_image();
+        assert_eq!(image.dimensions(), (4, 3));
+        assert_eq!(image.get_pixel(1, 0).0, [0]);
+        assert_eq!(image.get_pixel(0, 0).0, [255]);
+        assert_eq!(StandardGrid::from_gray_image(&image, 128), glider);
+        
+        let path = std::env::temp_dir().join(format!("gol_image_{}.png", std::process::id()));
+        image.save(&path).unwrap();
+        let loaded = StandardGrid::from_image(&path, 128);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), glider);
+        assert!(matches!(StandardGrid::from_image(&path, 128), Err(FormatError::Image(_))));
+    }
+}
@@ -0,0 +1,90 @@
+//! Axis-aligned rectangles of cells
+
+/// A rectangle of cells with its top-left corner at (`row`, `col`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+    pub row: usize,
+    pub col: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    /// Create a rectangle from its top-left corner and size
+    pub fn new(row: usize, col: usize, width: usize, height: usize) -> Self {
+        Self { row, col, width, height }
+    }
+    
+    /// Whether the rectangle covers no cells
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+    
+    /// Number of cells covered
+    pub fn area(&self) -> usize {
+        self.width * self.height
+    }
+    
+    /// Whether the cell at (row, col) lies inside the rectangle
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row >= self.row && row < self.row + self.height && col >= self.col && col < self.col + self.width
+    }
+    
+    /// The overlap of two rectangles, or an empty rectangle if they do not overlap
+    pub fn intersection(&self, other: &Rect) -> Rect {
+        let row = self.row.max(other.row);
+        let col = self.col.max(other.col);
+        let bottom = (self.row + self.height).min(other.row + other.height);
+        let right = (self.col + self.width).min(other.col + other.width);
+        if bottom <= row || right <= col {
+            return Rect::new(row, col, 0, 0);
+        }
+        Rect::new(row, col, right - col, bottom - row)
+    }
+}
+
+/// Where a smaller pattern is placed inside a larger grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Anchor {
+    #[default]
+    Center,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Top-left (row, col) of an `inner_width` x `inner_height` block placed in an outer area
+    ///
+    /// Blocks larger than the outer area are placed at the origin along that axis.
+    pub fn origin(self, inner_width: usize, inner_height: usize, outer_width: usize, outer_height: usize) -> (usize, usize) {
+        let spare_rows = outer_height.saturating_sub(inner_height);
+        let spare_cols = outer_width.saturating_sub(inner_width);
+        match self {
+            Anchor::Center => (spare_rows / 2, spare_cols / 2),
+            Anchor::TopLeft => (0, 0),
+            Anchor::TopRight => (0, spare_cols),
+            Anchor::BottomLeft => (spare_rows, 0),
+            Anchor::BottomRight => (spare_rows, spare_cols),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_contains_and_intersection() {
+        let a = Rect::new(2, 3, 4, 5);
+        assert!(a.contains(2, 3));
+        assert!(a.contains(6, 6));
+        assert!(!a.contains(7, 6));
+        assert!(!a.contains(2, 7));
+        
+        let b = Rect::new(4, 0, 5, 10);
+        assert_eq!(a.intersection(&b), Rect::new(4, 3, 2, 3));
+        assert!(a.intersection(&Rect::new(0, 0, 3, 2)).is_empty());
+    }
+}
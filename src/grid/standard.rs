@@ -1,4 +1,10 @@
-use super::Grid;
+use super::{Anchor, Grid, PasteMode, Symmetry};
+use super::noise::perlin;
+use super::random::{indexed_sample, SplitMix64};
+use crate::error::GameOfLifeError;
+use crate::formats::{self, FormatError};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 /// Standard grid implementation that stores each cell as a boolean
 #[derive(Debug, Clone, PartialEq)]
@@ -18,22 +24,81 @@ impl StandardGrid {
         }
     }
     
+    /// Create a random soup where each cell is alive with probability `density`
+    ///
+    /// The same seed always produces the same grid.
+    pub fn random(width: usize, height: usize, density: f64, seed: u64) -> Result<Self, GameOfLifeError> {
+        if !(0.0..=1.0).contains(&density) {
+            return Err(GameOfLifeError::invalid(format!("Density must be between 0 and 1, got {}", density)));
+        }
+        let mut rng = SplitMix64::new(seed);
+        Ok(Self {
+            width,
+            height,
+            cells: (0..width * height).map(|_| rng.chance(density)).collect(),
+        })
+    }
+    
+    /// Create a random soup that is invariant under the given symmetry group
+    ///
+    /// Every orbit of cells shares one random sample, so `density` is still the
+    /// chance of any single cell being alive.
+    pub fn random_symmetric(width: usize, height: usize, density: f64, seed: u64, symmetry: Symmetry) -> Result<Self, GameOfLifeError> {
+        if !(0.0..=1.0).contains(&density) {
+            return Err(GameOfLifeError::invalid(format!("Density must be between 0 and 1, got {}", density)));
+        }
+        symmetry.check_dimensions(width, height)?;
+        
+        let mut cells = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                let (r, c) = symmetry.orbit(row, col, width, height).into_iter().min().unwrap();
+                cells.push(indexed_sample(seed, r * width + c) < density);
+            }
+        }
+        Ok(Self { width, height, cells })
+    }
+    
+    /// Create a grid alive wherever Perlin noise exceeds `threshold`
+    ///
+    /// `scale` is the feature size in cells; a threshold of 0 gives roughly half
+    /// the grid alive, in smooth blobs rather than uniform static.
+    pub fn from_noise(width: usize, height: usize, scale: f64, threshold: f64, seed: u64) -> Result<Self, GameOfLifeError> {
+        if scale.is_nan() || scale <= 0.0 {
+            return Err(GameOfLifeError::invalid(format!("Noise scale must be positive, got {}", scale)));
+        }
+        let mut cells = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                let value = perlin((col as f64 + 0.5) / scale, (row as f64 + 0.5) / scale, seed);
+                cells.push(value > threshold);
+            }
+        }
+        Ok(Self { width, height, cells })
+    }
+    
+    /// Copy any grid into one cell per boolean
+    pub fn from_grid(grid: &dyn Grid) -> Self {
+        let cells = (0..grid.height()).flat_map(|row| grid.row(row)).collect();
+        Self { width: grid.width(), height: grid.height(), cells }
+    }
+    
     /// Create a grid from a 2D boolean array
-    pub fn from_cells(cells: Vec<Vec<bool>>) -> Result<Self, String> {
+    pub fn from_cells(cells: Vec<Vec<bool>>) -> Result<Self, GameOfLifeError> {
         if cells.is_empty() {
-            return Err("Grid cannot be empty".to_string());
+            return Err(GameOfLifeError::invalid("Grid cannot be empty"));
         }
         
         let height = cells.len();
         let width = cells[0].len();
         
         if width == 0 {
-            return Err("Grid width cannot be zero".to_string());
+            return Err(GameOfLifeError::invalid("Grid width cannot be zero"));
         }
         
         for (i, row) in cells.iter().enumerate() {
             if row.len() != width {
-                return Err(format!("Row {} has length {}, expected {}", i, row.len(), width));
+                return Err(GameOfLifeError::invalid(format!("Row {} has length {}, expected {}", i, row.len(), width)));
             }
         }
         
@@ -47,16 +112,18 @@ impl StandardGrid {
     }
     
     /// Create a grid from a string representation
-    pub fn from_string_pattern(pattern: &[&str], alive_char: char, dead_char: char) -> Result<Self, String> {
+    ///
+    /// Errors report the 1-based row as the line and the character position as the column.
+    pub fn from_string_pattern(pattern: &[&str], alive_char: char, dead_char: char) -> Result<Self, GameOfLifeError> {
         if pattern.is_empty() {
-            return Err("Pattern cannot be empty".to_string());
+            return Err(GameOfLifeError::invalid("Pattern cannot be empty"));
         }
         
         let height = pattern.len();
         let width = pattern[0].chars().count();
         
         if width == 0 {
-            return Err("Pattern width cannot be zero".to_string());
+            return Err(GameOfLifeError::invalid("Pattern width cannot be zero"));
         }
         
         let mut cells = Vec::with_capacity(width * height);
@@ -64,14 +131,24 @@ impl StandardGrid {
         for (row_idx, row) in pattern.iter().enumerate() {
             let row_chars: Vec<char> = row.chars().collect();
             if row_chars.len() != width {
-                return Err(format!("Row {} has length {}, expected {}", row_idx, row_chars.len(), width));
+                return Err(GameOfLifeError::Parse {
+                    line: row_idx + 1,
+                    column: row_chars.len().min(width) + 1,
+                    message: format!("Row {} has length {}, expected {}", row_idx, row_chars.len(), width),
+                });
             }
             
-            for ch in row_chars {
+            for (col_idx, ch) in row_chars.into_iter().enumerate() {
                 let cell = match ch {
                     c if c == alive_char => true,
                     c if c == dead_char => false,
-                    _ => return Err(format!("Invalid character '{}' in pattern", ch)),
+                    _ => {
+                        return Err(GameOfLifeError::Parse {
+                            line: row_idx + 1,
+                            column: col_idx + 1,
+                            message: format!("Invalid character '{}' in pattern", ch),
+                        })
+                    }
                 };
                 cells.push(cell);
             }
@@ -84,44 +161,13 @@ impl StandardGrid {
         })
     }
     
-    /// Create a grid from a file containing █ and ·
-    pub fn from_file(file_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    /// Load a pattern file, detecting its format from the content
+    ///
+    /// Accepts RLE, Life 1.06, plaintext `.cells`, rows of `0`/`1` and rows of
+    /// Unicode blocks. Use `formats::parse_any` to also get the metadata.
+    pub fn from_file(file_path: &str) -> Result<Self, FormatError> {
         let content = std::fs::read_to_string(file_path)?;
-        let lines: Vec<&str> = content.lines().collect();
-        
-        if lines.is_empty() {
-            return Err("File is empty".into());
-        }
-        
-        let height = lines.len();
-        let width = lines[0].len();
-        
-        if width == 0 {
-            return Err("Grid width cannot be zero".into());
-        }
-        
-        let mut cells = Vec::with_capacity(width * height);
-        
-        for (row_idx, line) in lines.iter().enumerate() {
-            if line.len() != width {
-                return Err(format!("Row {} has length {}, expected {}", row_idx, line.len(), width).into());
-            }
-            
-            for ch in line.chars() {
-                let cell = match ch {
-                    '1' => true,
-                    '0' => false,
-                    _ => return Err(format!("Invalid character '{}' in file. Only '0' and '1' are allowed.", ch).into()),
-                };
-                cells.push(cell);
-            }
-        }
-        
-        Ok(Self {
-            width,
-            height,
-            cells,
-        })
+        formats::parse_any(&content).map(|(grid, _, _)| grid)
     }
     
     /// Get the internal cell index for row, col coordinates
@@ -138,6 +184,84 @@ impl StandardGrid {
     pub fn cells_mut(&mut self) -> &mut [bool] {
         &mut self.cells
     }
+    
+    /// Heap memory allocated for the cells, in bytes
+    pub fn memory_bytes(&self) -> usize {
+        self.cells.capacity() * size_of::<bool>()
+    }
+    
+    /// Copy this pattern into an empty `width` x `height` grid at the given anchor
+    ///
+    /// Parts of the pattern that do not fit are clipped.
+    pub fn embedded_in(&self, width: usize, height: usize, anchor: Anchor) -> StandardGrid {
+        let (row, col) = anchor.origin(self.width, self.height, width, height);
+        let mut grid = StandardGrid::new(width, height);
+        grid.paste(self, row, col, PasteMode::Or);
+        grid
+    }
+    
+    /// Cells alive in either grid
+    pub fn union(&self, other: &dyn Grid) -> StandardGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+    
+    /// Cells alive in both grids
+    pub fn intersect(&self, other: &dyn Grid) -> StandardGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+    
+    /// Cells alive in exactly one of the grids
+    pub fn xor(&self, other: &dyn Grid) -> StandardGrid {
+        let mut result = self.clone();
+        result.xor_with(other);
+        result
+    }
+    
+    /// Cells alive in this grid but not in `other`
+    pub fn subtract(&self, other: &dyn Grid) -> StandardGrid {
+        let mut result = self.clone();
+        result.subtract_with(other);
+        result
+    }
+    
+    /// In-place `union`
+    pub fn union_with(&mut self, other: &dyn Grid) {
+        self.combine_with(other, |a, b| a | b);
+    }
+    
+    /// In-place `intersect`
+    pub fn intersect_with(&mut self, other: &dyn Grid) {
+        self.combine_with(other, |a, b| a & b);
+    }
+    
+    /// In-place `xor`
+    pub fn xor_with(&mut self, other: &dyn Grid) {
+        self.combine_with(other, |a, b| a ^ b);
+    }
+    
+    /// In-place `subtract`
+    pub fn subtract_with(&mut self, other: &dyn Grid) {
+        self.combine_with(other, |a, b| a & !b);
+    }
+    
+    /// Combine each cell with the matching cell of a grid of the same size
+    fn combine_with(&mut self, other: &dyn Grid, op: fn(bool, bool) -> bool) {
+        assert!(
+            self.width == other.width() && self.height == other.height(),
+            "Cannot combine a {}x{} grid with a {}x{} grid",
+            self.width, self.height, other.width(), other.height()
+        );
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.index(row, col);
+                self.cells[idx] = op(self.cells[idx], other.get_cell(row, col));
+            }
+        }
+    }
 }
 
 impl Grid for StandardGrid {
@@ -150,12 +274,16 @@ impl Grid for StandardGrid {
     }
     
     fn get_cell(&self, row: usize, col: usize) -> bool {
-        assert!(row < self.height && col < self.width, "Cell coordinates out of bounds");
+        if row >= self.height || col >= self.width {
+            return false;
+        }
         self.cells[self.index(row, col)]
     }
     
     fn set_cell(&mut self, row: usize, col: usize, alive: bool) {
-        assert!(row < self.height && col < self.width, "Cell coordinates out of bounds");
+        if row >= self.height || col >= self.width {
+            return;
+        }
         let idx = self.index(row, col);
         self.cells[idx] = alive;
     }
@@ -163,11 +291,59 @@ impl Grid for StandardGrid {
     fn clear(&mut self) {
         self.cells.fill(false);
     }
+    
+    fn row(&self, row: usize) -> Box<dyn Iterator<Item = bool> + '_> {
+        assert!(row < self.height, "Row {} out of bounds for height {}", row, self.height);
+        let start = row * self.width;
+        Box::new(self.cells[start..start + self.width].iter().copied())
+    }
+}
+
+impl Display for StandardGrid {
+    /// One line per row with '#' for live cells and '.' for dead ones
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            let line: String = row.iter().map(|&alive| if alive { '#' } else { '.' }).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for StandardGrid {
+    type Err = GameOfLifeError;
+    
+    /// Parse rows of '#', 'O', '*' or '█' for live cells and '.' or '·' for dead ones
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.trim_end().lines().collect();
+        if lines.is_empty() {
+            return Err(GameOfLifeError::invalid("Grid cannot be empty"));
+        }
+        
+        let cells = lines
+            .iter()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(i, ch)| match ch {
+                        '#' | 'O' | '*' | '█' => Ok(true),
+                        '.' | '·' => Ok(false),
+                        _ => Err(GameOfLifeError::parse(s, &line[i..], format!("Invalid character '{}' in grid", ch))),
+                    })
+                    .collect::<Result<Vec<bool>, _>>()
+            })
+            .collect::<Result<Vec<Vec<bool>>, _>>()?;
+        if let Some(line) = lines.iter().find(|line| line.chars().count() != cells[0].len()) {
+            let message = format!("Row has length {}, expected {}", line.chars().count(), cells[0].len());
+            return Err(GameOfLifeError::parse(s, line, message));
+        }
+        Self::from_cells(cells)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid::{BitGrid, GridError, Rect, Topology};
     
     #[test]
     fn test_new_grid() {
@@ -217,4 +393,199 @@ mod tests {
         assert_eq!(grid.count_neighbors(0, 0), 1);
         assert_eq!(grid.count_neighbors(0, 1), 3);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_count_neighbors_hex() {
+        let pattern = [
+            "###",
+            "###",
+            "###",
+        ];
+        
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        assert_eq!(grid.count_neighbors_in(1, 1, Topology::Hex), 6);
+        assert_eq!(grid.count_neighbors_in(0, 0, Topology::Hex), 2);
+        assert_eq!(grid.count_neighbors_in(1, 2, Topology::Hex), 3);
+    }
+    
+    #[test]
+    fn test_set_operations() {
+        let a = StandardGrid::from_string_pattern(&["##.", "..."], '#', '.').unwrap();
+        let b = StandardGrid::from_string_pattern(&[".##", "..."], '#', '.').unwrap();
+        
+        assert_eq!(a.union(&b), StandardGrid::from_string_pattern(&["###", "..."], '#', '.').unwrap());
+        assert_eq!(a.intersect(&b), StandardGrid::from_string_pattern(&[".#.", "..."], '#', '.').unwrap());
+        assert_eq!(a.xor(&b), StandardGrid::from_string_pattern(&["#.#", "..."], '#', '.').unwrap());
+        assert_eq!(a.subtract(&b), StandardGrid::from_string_pattern(&["#..", "..."], '#', '.').unwrap());
+        
+        let mut c = a.clone();
+        c.xor_with(&a);
+        assert_eq!(c.count_live_cells(), 0);
+    }
+    
+    #[test]
+    fn test_crop() {
+        let grid = StandardGrid::from_string_pattern(&["#...", ".#..", "..##"], '#', '.').unwrap();
+        let cropped = grid.crop(Rect::new(1, 1, 3, 2));
+        assert_eq!(cropped, StandardGrid::from_string_pattern(&["#..", ".##"], '#', '.').unwrap());
+        
+        // Windows hanging off the edge are padded with dead cells
+        let padded = grid.crop(Rect::new(2, 2, 3, 3));
+        assert_eq!((padded.width(), padded.height()), (3, 3));
+        assert_eq!(padded.count_live_cells(), 2);
+        assert!(padded.get_cell(0, 0) && padded.get_cell(0, 1));
+    }
+    
+    #[test]
+    fn test_from_grid() {
+        let grid = StandardGrid::from_string_pattern(&["#...", ".#..", "..##"], '#', '.').unwrap();
+        assert_eq!(StandardGrid::from_grid(&BitGrid::from_grid(&grid)), grid);
+        assert_eq!(StandardGrid::from_grid(&StandardGrid::new(0, 3)), StandardGrid::new(0, 3));
+    }
+    
+    #[test]
+    fn test_paste_modes() {
+        let block = StandardGrid::from_string_pattern(&["##", "##"], '#', '.').unwrap();
+        let mut grid = StandardGrid::from_string_pattern(&["....", ".#..", "...#"], '#', '.').unwrap();
+        
+        let mut or = grid.clone();
+        or.paste(&block, 0, 0, PasteMode::Or);
+        assert_eq!(or, StandardGrid::from_string_pattern(&["##..", "##..", "...#"], '#', '.').unwrap());
+        
+        let mut xor = grid.clone();
+        xor.paste(&block, 0, 0, PasteMode::Xor);
+        assert_eq!(xor, StandardGrid::from_string_pattern(&["##..", "#...", "...#"], '#', '.').unwrap());
+        
+        // Copy clears dead pattern cells and clips at the edge
+        let hollow = StandardGrid::from_string_pattern(&["#.", ".."], '#', '.').unwrap();
+        grid.paste(&hollow, 2, 3, PasteMode::Copy);
+        assert_eq!(grid, StandardGrid::from_string_pattern(&["....", ".#..", "...#"], '#', '.').unwrap());
+        grid.paste(&hollow, 1, 1, PasteMode::Copy);
+        assert_eq!(grid, StandardGrid::from_string_pattern(&["....", ".#..", "...#"], '#', '.').unwrap());
+        grid.paste(&hollow, 0, 0, PasteMode::Copy);
+        assert_eq!(grid, StandardGrid::from_string_pattern(&["#...", "....", "...#"], '#', '.').unwrap());
+    }
+    
+    #[test]
+    fn test_transforms() {
+        let glider = StandardGrid::from_string_pattern(&[".#.", "..#", "###"], '#', '.').unwrap();
+        let l = StandardGrid::from_string_pattern(&["#..", "###"], '#', '.').unwrap();
+        
+        assert_eq!(l.rotate90(), StandardGrid::from_string_pattern(&["##", "#.", "#."], '#', '.').unwrap());
+        assert_eq!(l.rotate180(), StandardGrid::from_string_pattern(&["###", "..#"], '#', '.').unwrap());
+        assert_eq!(l.rotate270(), StandardGrid::from_string_pattern(&[".#", ".#", "##"], '#', '.').unwrap());
+        assert_eq!(l.flip_horizontal(), StandardGrid::from_string_pattern(&["..#", "###"], '#', '.').unwrap());
+        assert_eq!(l.flip_vertical(), StandardGrid::from_string_pattern(&["###", "#.."], '#', '.').unwrap());
+        assert_eq!(l.transpose(), StandardGrid::from_string_pattern(&["##", ".#", ".#"], '#', '.').unwrap());
+        
+        assert_eq!(glider.rotate90().rotate270(), glider);
+        assert_eq!(glider.rotate90().rotate90(), glider.rotate180());
+        assert_eq!(glider.transpose(), glider.rotate90().flip_horizontal());
+    }
+    
+    #[test]
+    fn test_bounding_box_and_trim() {
+        let grid = StandardGrid::from_string_pattern(&[".....", "..#..", "...#.", ".###.", "....."], '#', '.').unwrap();
+        assert_eq!(grid.bounding_box(), Some(Rect::new(1, 1, 3, 3)));
+        assert_eq!(grid.trimmed(), StandardGrid::from_string_pattern(&[".#.", "..#", "###"], '#', '.').unwrap());
+        
+        let empty = StandardGrid::new(4, 4);
+        assert_eq!(empty.bounding_box(), None);
+        assert_eq!(empty.trimmed().total_cells(), 0);
+    }
+    
+    #[test]
+    fn test_embedded_in() {
+        let blinker = StandardGrid::from_string_pattern(&["###"], '#', '.').unwrap();
+        
+        let centered = blinker.embedded_in(5, 3, Anchor::Center);
+        assert_eq!(centered, StandardGrid::from_string_pattern(&[".....", ".###.", "....."], '#', '.').unwrap());
+        
+        let corner = blinker.embedded_in(4, 2, Anchor::BottomRight);
+        assert_eq!(corner, StandardGrid::from_string_pattern(&["....", ".###"], '#', '.').unwrap());
+        
+        let clipped = blinker.embedded_in(2, 1, Anchor::Center);
+        assert_eq!(clipped.count_live_cells(), 2);
+    }
+    
+    #[test]
+    fn test_random_soup() {
+        let soup = StandardGrid::random(64, 64, 0.3, 42).unwrap();
+        assert_eq!(soup, StandardGrid::random(64, 64, 0.3, 42).unwrap());
+        assert_ne!(soup, StandardGrid::random(64, 64, 0.3, 43).unwrap());
+        
+        let density = soup.count_live_cells() as f64 / soup.total_cells() as f64;
+        assert!((density - 0.3).abs() < 0.05);
+        assert_eq!(StandardGrid::random(8, 8, 0.0, 1).unwrap().count_live_cells(), 0);
+        assert_eq!(StandardGrid::random(8, 8, 1.0, 1).unwrap().count_live_cells(), 64);
+        assert!(StandardGrid::random(8, 8, -0.1, 1).is_err());
+    }
+    
+    #[test]
+    fn test_random_symmetric() {
+        let soup = StandardGrid::random_symmetric(16, 16, 0.5, 7, Symmetry::D8).unwrap();
+        assert!(soup.count_live_cells() > 0);
+        assert_eq!(soup.rotate90(), soup);
+        assert_eq!(soup.flip_horizontal(), soup);
+        assert_eq!(soup.transpose(), soup);
+        
+        let c2 = StandardGrid::random_symmetric(15, 10, 0.5, 7, Symmetry::C2).unwrap();
+        assert_eq!(c2.rotate180(), c2);
+        assert_ne!(c2.flip_horizontal(), c2);
+        
+        assert!(StandardGrid::random_symmetric(15, 10, 0.5, 7, Symmetry::C4).is_err());
+        assert!(StandardGrid::random_symmetric(8, 8, 1.5, 7, Symmetry::C1).is_err());
+    }
+    
+    #[test]
+    fn test_from_noise() {
+        let grid = StandardGrid::from_noise(64, 64, 8.0, 0.0, 3).unwrap();
+        assert_eq!(grid, StandardGrid::from_noise(64, 64, 8.0, 0.0, 3).unwrap());
+        let live = grid.count_live_cells();
+        assert!(live > 0 && live < grid.total_cells());
+        assert!(StandardGrid::from_noise(64, 64, 8.0, 0.3, 3).unwrap().count_live_cells() < live);
+        
+        // Smooth noise leaves most cells agreeing with their right-hand neighbor
+        let agreeing = (0..64)
+            .flat_map(|row| (0..63).map(move |col| (row, col)))
+            .filter(|&(row, col)| grid.get_cell(row, col) == grid.get_cell(row, col + 1))
+            .count();
+        assert!(agreeing > 64 * 63 * 3 / 4);
+        assert!(StandardGrid::from_noise(8, 8, 0.0, 0.0, 3).is_err());
+    }
+    
+    #[test]
+    fn test_checked_accessors() {
+        let mut grid = StandardGrid::new(4, 3);
+        assert_eq!(grid.try_set_cell(2, 3, true), Ok(()));
+        assert_eq!(grid.try_get_cell(2, 3), Some(true));
+        assert_eq!(grid.try_get_cell(3, 0), None);
+        assert_eq!(
+            grid.try_set_cell(0, 4, true),
+            Err(GridError::OutOfBounds { row: 0, col: 4, width: 4, height: 3 })
+        );
+        assert_eq!(grid.count_live_cells(), 1);
+        
+        // The unchecked accessors treat the outside as dead and ignore writes to it
+        grid.set_cell(5, 5, true);
+        assert!(!grid.get_cell(5, 5));
+        assert_eq!(grid.count_live_cells(), 1);
+    }
+    
+    #[test]
+    fn test_display_from_str_round_trip() {
+        let glider: StandardGrid = ".#.\n..#\n###\n".parse().unwrap();
+        assert_eq!(glider.to_string(), ".#.\n..#\n###\n");
+        assert_eq!(glider.count_live_cells(), 5);
+        
+        let soup = StandardGrid::random(17, 9, 0.5, 4).unwrap();
+        assert_eq!(soup.to_string().parse::<StandardGrid>().unwrap(), soup);
+        assert_eq!("█·\r\n·█".parse::<StandardGrid>().unwrap(), "#.\n.#".parse().unwrap());
+        
+        assert!("".parse::<StandardGrid>().is_err());
+        assert!("#.\n#".parse::<StandardGrid>().is_err());
+        assert!("#x".parse::<StandardGrid>().is_err());
+        assert!(matches!("#.\n#".parse::<StandardGrid>(), Err(GameOfLifeError::Parse { line: 2, column: 1, .. })));
+        assert!(matches!("##\n#x".parse::<StandardGrid>(), Err(GameOfLifeError::Parse { line: 2, column: 2, .. })));
+    }
+}
@@ -0,0 +1,196 @@
+//! Symmetry groups of the square grid
+
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Symmetry groups used for symmetric soups, named as in apgsearch
+///
+/// The rotational groups C4 and D8 only make sense on square grids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Symmetry {
+    /// No symmetry
+    #[default]
+    C1,
+    /// Half-turn rotation
+    C2,
+    /// Quarter-turn rotation
+    C4,
+    /// Mirror across the vertical axis
+    D2,
+    /// Mirrors across both axes
+    D4,
+    /// All rotations and reflections of the square
+    D8,
+}
+
+impl Symmetry {
+    /// Whether the group contains quarter turns or diagonal reflections, which need a square grid
+    pub fn requires_square(self) -> bool {
+        matches!(self, Symmetry::C4 | Symmetry::D8)
+    }
+    
+    /// Number of elements in the group
+    pub fn order(self) -> usize {
+        match self {
+            Symmetry::C1 => 1,
+            Symmetry::C2 | Symmetry::D2 => 2,
+            Symmetry::C4 | Symmetry::D4 => 4,
+            Symmetry::D8 => 8,
+        }
+    }
+    
+    /// Images of (row, col) under every element of the group, including the identity
+    pub fn orbit(self, row: usize, col: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+        let (last_row, last_col) = (height - 1, width - 1);
+        let half_turn = (last_row - row, last_col - col);
+        let mirror_h = (row, last_col - col);
+        let mirror_v = (last_row - row, col);
+        match self {
+            Symmetry::C1 => vec![(row, col)],
+            Symmetry::C2 => vec![(row, col), half_turn],
+            Symmetry::C4 => vec![(row, col), (col, last_col - row), half_turn, (last_row - col, row)],
+            Symmetry::D2 => vec![(row, col), mirror_h],
+            Symmetry::D4 => vec![(row, col), mirror_h, mirror_v, half_turn],
+            Symmetry::D8 => vec![
+                (row, col), (col, last_col - row), half_turn, (last_row - col, row),
+                mirror_h, mirror_v, (col, row), (last_col - col, last_row - row),
+            ],
+        }
+    }
+    
+    /// Check that the group can act on a grid of the given size
+    pub fn check_dimensions(self, width: usize, height: usize) -> Result<(), GameOfLifeError> {
+        if self.requires_square() && width != height {
+            return Err(GameOfLifeError::unsupported(format!("{} symmetry", self), format!("the non-square {}x{} grid", width, height)));
+        }
+        Ok(())
+    }
+}
+
+/// The symmetries a grid actually has, relative to its own frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SymmetryGroup {
+    /// Unchanged by a quarter turn
+    pub rotate90: bool,
+    /// Unchanged by a half turn
+    pub rotate180: bool,
+    /// Unchanged by mirroring left to right
+    pub flip_horizontal: bool,
+    /// Unchanged by mirroring top to bottom
+    pub flip_vertical: bool,
+    /// Unchanged by reflection across the main diagonal
+    pub diagonal: bool,
+    /// Unchanged by reflection across the anti-diagonal
+    pub anti_diagonal: bool,
+}
+
+impl SymmetryGroup {
+    /// Whether the grid has any symmetry besides the identity
+    pub fn is_symmetric(&self) -> bool {
+        *self != SymmetryGroup::default()
+    }
+    
+    /// Whether every element of the named group is present
+    pub fn contains(&self, symmetry: Symmetry) -> bool {
+        match symmetry {
+            Symmetry::C1 => true,
+            Symmetry::C2 => self.rotate180,
+            Symmetry::C4 => self.rotate90 && self.rotate180,
+            Symmetry::D2 => self.flip_horizontal,
+            Symmetry::D4 => self.flip_horizontal && self.flip_vertical && self.rotate180,
+            Symmetry::D8 => self.contains(Symmetry::C4) && self.contains(Symmetry::D4) && self.diagonal && self.anti_diagonal,
+        }
+    }
+    
+    /// The largest named group contained in this one
+    pub fn classify(&self) -> Symmetry {
+        [Symmetry::D8, Symmetry::C4, Symmetry::D4, Symmetry::C2, Symmetry::D2]
+            .into_iter()
+            .find(|&symmetry| self.contains(symmetry))
+            .unwrap_or(Symmetry::C1)
+    }
+    
+    /// The named group this one matches with its axes in any orientation
+    ///
+    /// Unlike `classify`, a single mirror across a diagonal or the horizontal
+    /// axis counts as D2, and two diagonal mirrors count as D4, so a pattern
+    /// classifies the same however it is rotated or reflected.
+    pub fn classify_any_orientation(&self) -> Symmetry {
+        let mirrored = self.flip_horizontal || self.flip_vertical || self.diagonal || self.anti_diagonal;
+        match (self.rotate90, self.rotate180, mirrored) {
+            (true, _, true) => Symmetry::D8,
+            (true, _, false) => Symmetry::C4,
+            (false, true, true) => Symmetry::D4,
+            (false, true, false) => Symmetry::C2,
+            (false, false, true) => Symmetry::D2,
+            (false, false, false) => Symmetry::C1,
+        }
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = GameOfLifeError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "C1" => Ok(Symmetry::C1),
+            "C2" => Ok(Symmetry::C2),
+            "C4" => Ok(Symmetry::C4),
+            "D2" => Ok(Symmetry::D2),
+            "D4" => Ok(Symmetry::D4),
+            "D8" => Ok(Symmetry::D8),
+            _ => Err(GameOfLifeError::parse(s, s, format!("Unknown symmetry '{}', expected C1, C2, C4, D2, D4 or D8", s))),
+        }
+    }
+}
+
+impl Display for Symmetry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{BitGrid, Grid, StandardGrid};
+    
+    #[test]
+    fn test_detect_symmetries() {
+        let block = StandardGrid::from_string_pattern(&["....", ".##.", ".##.", "...."], '#', '.').unwrap();
+        assert_eq!(block.symmetries().classify(), Symmetry::D8);
+        
+        let glider = StandardGrid::from_string_pattern(&[".#.", "..#", "###"], '#', '.').unwrap();
+        assert!(!glider.symmetries().is_symmetric());
+        
+        let corner = StandardGrid::from_string_pattern(&["##.", "#..", "..."], '#', '.').unwrap();
+        let group = corner.symmetries();
+        assert!(group.diagonal && !group.anti_diagonal && !group.rotate180);
+        assert_eq!(group.classify(), Symmetry::C1);
+        assert_eq!(group.classify_any_orientation(), Symmetry::D2);
+        
+        // The ship is symmetric across both diagonals
+        let ship = StandardGrid::from_string_pattern(&["##.", "#.#", ".##"], '#', '.').unwrap();
+        assert_eq!(ship.symmetries().classify(), Symmetry::C2);
+        assert_eq!(ship.symmetries().classify_any_orientation(), Symmetry::D4);
+        assert_eq!(Symmetry::D4.order(), 4);
+        
+        let s_tetromino = StandardGrid::from_string_pattern(&[".##", "##."], '#', '.').unwrap();
+        assert_eq!(s_tetromino.symmetries().classify(), Symmetry::C2);
+    }
+    
+    #[test]
+    fn test_symmetrize() {
+        let mut grid = BitGrid::from_grid(&StandardGrid::random(20, 20, 0.5, 11).unwrap());
+        grid.symmetrize(Symmetry::D8).unwrap();
+        assert_eq!(grid.symmetries().classify(), Symmetry::D8);
+        
+        let mut wide = StandardGrid::random(9, 4, 0.5, 3).unwrap();
+        let left = wide.get_cell(1, 2);
+        wide.symmetrize(Symmetry::D2).unwrap();
+        assert!(wide.symmetries().contains(Symmetry::D2));
+        assert_eq!(wide.get_cell(1, 6), left);
+        assert!(wide.symmetrize(Symmetry::C4).is_err());
+    }
+}
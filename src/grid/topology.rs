@@ -0,0 +1,102 @@
+//! Neighborhood topologies for two-dimensional grids
+
+use crate::error::GameOfLifeError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Neighbor offsets for the square (Moore) topology
+const SQUARE_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1),           (0, 1),
+    (1, -1),  (1, 0),  (1, 1),
+];
+
+/// Hex neighbor offsets for even rows
+const HEX_EVEN_OFFSETS: [(isize, isize); 6] = [
+    (-1, -1), (-1, 0),
+    (0, -1),  (0, 1),
+    (1, -1),  (1, 0),
+];
+
+/// Hex neighbor offsets for odd rows, which sit half a cell to the right
+const HEX_ODD_OFFSETS: [(isize, isize); 6] = [
+    (-1, 0), (-1, 1),
+    (0, -1), (0, 1),
+    (1, 0),  (1, 1),
+];
+
+/// How cells of a rectangular grid are connected to their neighbors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Topology {
+    /// Square cells with 8 neighbors
+    #[default]
+    Square,
+    /// Hexagonal cells with 6 neighbors, stored as an offset grid where odd rows
+    /// are shifted half a cell to the right
+    Hex,
+}
+
+impl Topology {
+    /// Relative (row, col) offsets of the neighbors of a cell in the given row
+    pub fn neighbor_offsets(self, row: usize) -> &'static [(isize, isize)] {
+        match self {
+            Topology::Square => &SQUARE_OFFSETS,
+            Topology::Hex if row.is_multiple_of(2) => &HEX_EVEN_OFFSETS,
+            Topology::Hex => &HEX_ODD_OFFSETS,
+        }
+    }
+    
+    /// Number of neighbors each interior cell has
+    pub fn max_neighbors(self) -> u8 {
+        match self {
+            Topology::Square => 8,
+            Topology::Hex => 6,
+        }
+    }
+}
+
+impl FromStr for Topology {
+    type Err = GameOfLifeError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "square" | "moore" => Ok(Topology::Square),
+            "hex" | "hexagonal" => Ok(Topology::Hex),
+            other => Err(GameOfLifeError::parse(s, s, format!("Unknown topology '{}', expected 'square' or 'hex'", other))),
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topology::Square => write!(f, "square"),
+            Topology::Hex => write!(f, "hex"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_hex_offsets_are_symmetric() {
+        // If B is a neighbor of A then A must be a neighbor of B
+        for row in 2..4usize {
+            for &(dr, dc) in Topology::Hex.neighbor_offsets(row) {
+                let other = (row as isize + dr) as usize;
+                let back = Topology::Hex.neighbor_offsets(other);
+                assert!(back.contains(&(-dr, -dc)), "row {} offset ({}, {})", row, dr, dc);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_parse_round_trip() {
+        for topology in [Topology::Square, Topology::Hex] {
+            assert_eq!(topology.to_string().parse::<Topology>().unwrap(), topology);
+        }
+        assert!("triangle".parse::<Topology>().is_err());
+    }
+}
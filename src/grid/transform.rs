@@ -0,0 +1,70 @@
+//! The eight rotations and reflections of the square
+
+use super::{Grid, StandardGrid};
+
+/// A rotation or reflection of a pattern; rotations are clockwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Transform {
+    #[default]
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirror left to right
+    FlipHorizontal,
+    /// Mirror top to bottom
+    FlipVertical,
+    /// Mirror across the main diagonal
+    Transpose,
+    /// Mirror across the anti-diagonal
+    AntiTranspose,
+}
+
+impl Transform {
+    /// Every transform, identity first
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+    
+    /// Copy a pattern with the transform applied
+    pub fn apply(self, pattern: &dyn Grid) -> StandardGrid {
+        match self {
+            Transform::Identity => StandardGrid::from_grid(pattern),
+            Transform::Rotate90 => pattern.rotate90(),
+            Transform::Rotate180 => pattern.rotate180(),
+            Transform::Rotate270 => pattern.rotate270(),
+            Transform::FlipHorizontal => pattern.flip_horizontal(),
+            Transform::FlipVertical => pattern.flip_vertical(),
+            Transform::Transpose => pattern.transpose(),
+            Transform::AntiTranspose => pattern.transpose().rotate180(),
+        }
+    }
+    
+    /// Whether the transform swaps width and height
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(self, Transform::Rotate90 | Transform::Rotate270 | Transform::Transpose | Transform::AntiTranspose)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_transforms_are_distinct() {
+        let l = StandardGrid::from_string_pattern(&["#.", "#.", "##"], '#', '.').unwrap();
+        let images: Vec<_> = Transform::ALL.iter().map(|transform| transform.apply(&l)).collect();
+        for (i, image) in images.iter().enumerate() {
+            assert_eq!(image.width() == 3, Transform::ALL[i].swaps_dimensions());
+            assert!(images[..i].iter().all(|earlier| earlier != image));
+        }
+        assert!(Transform::AntiTranspose.apply(&l).get_cell(0, 0));
+    }
+}
@@ -0,0 +1,210 @@
+//! Grids of arbitrary per-cell values for multi-state engines and cell metadata
+
+use crate::error::GameOfLifeError;
+
+/// Grid whose cells hold a value of type `T` rather than a single bit
+pub trait StateGrid<T: Copy> {
+    /// Get the width of the grid
+    fn width(&self) -> usize;
+    
+    /// Get the height of the grid
+    fn height(&self) -> usize;
+    
+    /// Get the value of a cell
+    fn get_state(&self, row: usize, col: usize) -> T;
+    
+    /// Set the value of a cell
+    fn set_state(&mut self, row: usize, col: usize, value: T);
+    
+    /// Count cells at the given (row, col) offsets whose value satisfies `pred`; cells outside the grid never match
+    fn count_neighbors_matching(&self, row: usize, col: usize, offsets: &[(isize, isize)], pred: &dyn Fn(T) -> bool) -> u8 {
+        let (height, width) = (self.height() as isize, self.width() as isize);
+        let mut count = 0;
+        for (dr, dc) in offsets {
+            let r = row as isize + dr;
+            let c = col as isize + dc;
+            if r >= 0 && r < height && c >= 0 && c < width && pred(self.get_state(r as usize, c as usize)) {
+                count += 1;
+            }
+        }
+        count
+    }
+    
+    /// Count all cells whose value satisfies `pred`
+    fn count_matching(&self, pred: &dyn Fn(T) -> bool) -> usize {
+        (0..self.height())
+            .map(|row| (0..self.width()).filter(|&col| pred(self.get_state(row, col))).count())
+            .sum()
+    }
+}
+
+/// Row-major grid storing one `T` per cell
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValueGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Copy + Default> ValueGrid<T> {
+    /// Create a grid with every cell set to `T::default()`
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, T::default())
+    }
+    
+    /// Create a grid with every cell set to `value`
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+    
+    /// Create a grid by evaluating `f(row, col)` for every cell
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..width * height).map(|idx| f(idx / width, idx % width)).collect();
+        Self { width, height, cells }
+    }
+    
+    /// Create a grid from row-major values
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Result<Self, GameOfLifeError> {
+        if cells.len() != width * height {
+            return Err(GameOfLifeError::invalid(format!(
+                "Expected {} values for a {}x{} grid, got {}",
+                width * height, width, height, cells.len()
+            )));
+        }
+        Ok(Self { width, height, cells })
+    }
+    
+    /// Get the width of the grid
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    
+    /// Get the height of the grid
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    
+    /// Get the value of a cell
+    pub fn get(&self, row: usize, col: usize) -> T {
+        assert!(row < self.height && col < self.width, "Cell coordinates out of bounds");
+        self.cells[row * self.width + col]
+    }
+    
+    /// Set the value of a cell
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        assert!(row < self.height && col < self.width, "Cell coordinates out of bounds");
+        self.cells[row * self.width + col] = value;
+    }
+    
+    /// Get a mutable reference to a cell
+    pub fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert!(row < self.height && col < self.width, "Cell coordinates out of bounds");
+        &mut self.cells[row * self.width + col]
+    }
+    
+    /// Set every cell to `value`
+    pub fn fill(&mut self, value: T) {
+        self.cells.fill(value);
+    }
+    
+    /// Resize to new dimensions, resetting every cell to `T::default()`
+    pub fn reset(&mut self, width: usize, height: usize) {
+        *self = Self::new(width, height);
+    }
+    
+    /// All values in row-major order
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+    
+    /// All values in row-major order, mutably
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+    
+    /// Heap memory allocated for the values, in bytes
+    pub fn memory_bytes(&self) -> usize {
+        self.cells.capacity() * size_of::<T>()
+    }
+    
+    /// The values of one row
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+    
+    /// Iterate over rows as slices; an empty-width grid yields no rows
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+    
+    /// Iterate over rows as mutable slices, e.g. for `rayon`'s `par_chunks_mut` style processing
+    pub fn rows_mut(&mut self) -> std::slice::ChunksMut<'_, T> {
+        self.cells.chunks_mut(self.width.max(1))
+    }
+    
+    /// Apply `f` to every value, producing a grid of the same size
+    pub fn map<U: Copy + Default>(&self, f: impl Fn(T) -> U) -> ValueGrid<U> {
+        ValueGrid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(|&value| f(value)).collect(),
+        }
+    }
+}
+
+impl<T: Copy + Default> StateGrid<T> for ValueGrid<T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+    
+    fn height(&self) -> usize {
+        self.height
+    }
+    
+    fn get_state(&self, row: usize, col: usize) -> T {
+        self.get(row, col)
+    }
+    
+    fn set_state(&mut self, row: usize, col: usize, value: T) {
+        self.set(row, col, value);
+    }
+    
+    fn count_matching(&self, pred: &dyn Fn(T) -> bool) -> usize {
+        self.cells.iter().filter(|&&value| pred(value)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::NeighborhoodKind;
+    
+    #[test]
+    fn test_value_grid_access() {
+        let mut grid = ValueGrid::from_fn(4, 3, |row, col| (row * 10 + col) as u8);
+        assert_eq!(grid.get(2, 3), 23);
+        assert_eq!(grid.row(1), &[10, 11, 12, 13]);
+        assert_eq!(grid.rows().count(), 3);
+        
+        grid.set(0, 0, 99);
+        *grid.get_mut(0, 1) += 1;
+        assert_eq!(grid.row(0), &[99, 2, 2, 3]);
+        
+        let even = grid.map(|value| value % 2 == 0);
+        assert!(even.get(0, 1) && !even.get(0, 0));
+        assert!(ValueGrid::from_vec(2, 2, vec![0u32; 3]).is_err());
+    }
+    
+    #[test]
+    fn test_state_grid_counts() {
+        let grid = ValueGrid::from_vec(3, 3, vec![1u8, 2, 0, 1, 1, 0, 0, 0, 2]).unwrap();
+        let offsets = NeighborhoodKind::Moore.offsets();
+        assert_eq!(grid.count_neighbors_matching(1, 1, offsets, &|state| state == 1), 2);
+        assert_eq!(grid.count_neighbors_matching(0, 0, offsets, &|state| state != 0), 3);
+        assert_eq!(grid.count_matching(&|state| state == 2), 2);
+    }
+}
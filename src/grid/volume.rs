@@ -0,0 +1,203 @@
+//! Three-dimensional grids, stored as a stack of two-dimensional layers
+
+use super::Grid;
+
+/// Trait for three-dimensional Life grids indexed by (layer, row, col)
+pub trait Grid3 {
+    /// Get the width of each layer
+    fn width(&self) -> usize;
+    
+    /// Get the height of each layer
+    fn height(&self) -> usize;
+    
+    /// Get the number of layers
+    fn depth(&self) -> usize;
+    
+    /// Get the state of a cell (true = alive, false = dead)
+    fn get_cell(&self, layer: usize, row: usize, col: usize) -> bool;
+    
+    /// Set the state of a cell
+    fn set_cell(&mut self, layer: usize, row: usize, col: usize, alive: bool);
+    
+    /// Clear all cells (set to dead)
+    fn clear(&mut self);
+    
+    /// Get the total number of cells
+    fn total_cells(&self) -> usize {
+        self.width() * self.height() * self.depth()
+    }
+    
+    /// Count live cells among the 26 neighbors of a cell; cells outside the volume are dead
+    fn count_neighbors(&self, layer: usize, row: usize, col: usize) -> u8 {
+        let mut count = 0;
+        for dl in -1..=1isize {
+            for dr in -1..=1isize {
+                for dc in -1..=1isize {
+                    if dl == 0 && dr == 0 && dc == 0 {
+                        continue;
+                    }
+                    let l = layer as isize + dl;
+                    let r = row as isize + dr;
+                    let c = col as isize + dc;
+                    if l >= 0 && l < self.depth() as isize
+                        && r >= 0 && r < self.height() as isize
+                        && c >= 0 && c < self.width() as isize
+                        && self.get_cell(l as usize, r as usize, c as usize)
+                    {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+    
+    /// Count total live cells in the volume
+    fn count_live_cells(&self) -> usize {
+        let mut count = 0;
+        for layer in 0..self.depth() {
+            for row in 0..self.height() {
+                for col in 0..self.width() {
+                    if self.get_cell(layer, row, col) {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+}
+
+/// Three-dimensional grid that stores each cell as a boolean
+///
+/// It also implements the two-dimensional `Grid` trait as a view of its
+/// layers stacked vertically: 2D row `layer * height + row`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardGrid3 {
+    width: usize,
+    height: usize,
+    depth: usize,
+    cells: Vec<bool>,
+}
+
+impl StandardGrid3 {
+    /// Create a new empty grid with the specified dimensions
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        Self {
+            width,
+            height,
+            depth,
+            cells: vec![false; width * height * depth],
+        }
+    }
+    
+    /// Get the internal cell index for layer, row, col coordinates
+    fn index(&self, layer: usize, row: usize, col: usize) -> usize {
+        (layer * self.height + row) * self.width + col
+    }
+    
+    /// Get a reference to the internal cells vector, layer by layer
+    pub fn cells(&self) -> &[bool] {
+        &self.cells
+    }
+    
+    /// Get a mutable reference to the internal cells vector
+    pub fn cells_mut(&mut self) -> &mut [bool] {
+        &mut self.cells
+    }
+    
+    /// Heap memory allocated for the cells, in bytes
+    pub fn memory_bytes(&self) -> usize {
+        self.cells.capacity() * size_of::<bool>()
+    }
+}
+
+impl Grid3 for StandardGrid3 {
+    fn width(&self) -> usize {
+        self.width
+    }
+    
+    fn height(&self) -> usize {
+        self.height
+    }
+    
+    fn depth(&self) -> usize {
+        self.depth
+    }
+    
+    fn get_cell(&self, layer: usize, row: usize, col: usize) -> bool {
+        assert!(layer < self.depth && row < self.height && col < self.width, "Cell coordinates out of bounds");
+        self.cells[self.index(layer, row, col)]
+    }
+    
+    fn set_cell(&mut self, layer: usize, row: usize, col: usize, alive: bool) {
+        assert!(layer < self.depth && row < self.height && col < self.width, "Cell coordinates out of bounds");
+        let idx = self.index(layer, row, col);
+        self.cells[idx] = alive;
+    }
+    
+    fn clear(&mut self) {
+        self.cells.fill(false);
+    }
+}
+
+impl Grid for StandardGrid3 {
+    fn width(&self) -> usize {
+        self.width
+    }
+    
+    fn height(&self) -> usize {
+        self.height * self.depth
+    }
+    
+    fn get_cell(&self, row: usize, col: usize) -> bool {
+        if row >= self.height * self.depth || col >= self.width {
+            return false;
+        }
+        self.cells[row * self.width + col]
+    }
+    
+    fn set_cell(&mut self, row: usize, col: usize, alive: bool) {
+        if row >= self.height * self.depth || col >= self.width {
+            return;
+        }
+        self.cells[row * self.width + col] = alive;
+    }
+    
+    fn clear(&mut self) {
+        self.cells.fill(false);
+    }
+    
+    fn count_live_cells(&self) -> usize {
+        self.cells.iter().filter(|&&alive| alive).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_count_neighbors_3d() {
+        let mut grid = StandardGrid3::new(3, 3, 3);
+        for layer in 0..3 {
+            for row in 0..3 {
+                for col in 0..3 {
+                    Grid3::set_cell(&mut grid, layer, row, col, true);
+                }
+            }
+        }
+        assert_eq!(Grid3::count_neighbors(&grid, 1, 1, 1), 26);
+        assert_eq!(Grid3::count_neighbors(&grid, 0, 0, 0), 7);
+        assert_eq!(Grid3::count_live_cells(&grid), 27);
+    }
+    
+    #[test]
+    fn test_stacked_view() {
+        let mut grid = StandardGrid3::new(4, 2, 3);
+        Grid3::set_cell(&mut grid, 2, 1, 3, true);
+        assert!(Grid::get_cell(&grid, 5, 3));
+        assert_eq!(Grid::height(&grid), 6);
+        assert_eq!(Grid::total_cells(&grid), Grid3::total_cells(&grid));
+    }
+}
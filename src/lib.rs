@@ -1,16 +1,43 @@
 #![feature(portable_simd)]
 #![feature(array_windows)]
 
+pub mod analysis;
 pub mod engines;
+pub mod error;
+pub mod experiments;
 pub mod grid;
 pub mod benchmark;
+pub mod formats;
+pub mod patterns;
+pub mod replay;
+pub mod rules;
+pub mod search;
+pub mod stats;
 
-pub use engines::{GameOfLifeEngine, EngineInfo};
+pub use error::GameOfLifeError;
+pub use engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationEvent, StabilizationReport};
+pub use formats::{FormatError, Pattern, PatternFormat, PatternMeta};
 pub use grid::Grid;
+pub use rules::{BaysRule, ElementaryRule, GenerationsRule, LtlRule, MapRule, NeighborhoodKind, Rule, RuleString, StochasticRule};
 
 pub mod prelude {
-    pub use crate::engines::{GameOfLifeEngine, EngineInfo};
-    pub use crate::grid::{Grid, StandardGrid};
+    pub use crate::error::GameOfLifeError;
+    pub use crate::engines::{GameOfLifeEngine, CancellationToken, EngineInfo, EngineSnapshot, GenerationEvent, StabilizationReport};
+    pub use crate::grid::{Anchor, BitGrid, Boundary, Grid, Grid3, GridBuilder, GridError, PasteMode, Rect, RleGrid, Rotation, StandardGrid, StandardGrid3, StateGrid, Symmetry, SymmetryGroup, Topology, Transform, ValueGrid};
+    pub use crate::rules::{BaysRule, ElementaryRule, GenerationsRule, LtlRule, MapRule, NeighborhoodKind, Rule, RuleString, StochasticRule};
+    pub use crate::engines::age::{AgeTrackingEngine, CellAge};
+    pub use crate::engines::callback::CallbackEngine;
+    pub use crate::engines::elementary::ElementaryAutomaton;
+    pub use crate::engines::generations::GenerationsEngine;
+    pub use crate::engines::life3d::Life3DEngine;
+    pub use crate::engines::lookup::LookupEngine;
+    pub use crate::engines::ltl::LtlEngine;
+    #[cfg(feature = "mmap")]
+    pub use crate::engines::mmap::MmapEngine;
+    #[cfg(feature = "mmap")]
+    pub use crate::grid::MmapGrid;
     pub use crate::engines::naive::NaiveEngine;
-    pub use crate::engines::ultimate::{UltimateEngine, auto_new_ultimate_engine, auto_from_grid_ultimate_engine, safe_auto_new_ultimate_engine, create_optimal_engine};
+    pub use crate::engines::registry::{EngineBuilder, EngineKind};
+    pub use crate::engines::stochastic::StochasticEngine;
+    pub use crate::engines::ultimate::{UltimateEngine, auto_new_ultimate_engine, auto_from_grid_ultimate_engine, safe_auto_new_ultimate_engine, create_optimal_engine, ultimate_engine_from_grid};
 }
\ No newline at end of file
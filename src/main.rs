@@ -1,18 +1,44 @@
 use game_of_life::prelude::*;
+use game_of_life::analysis::{analyze_pattern, ObjectDatabase, StabilityTracker, StateClass};
 use game_of_life::grid::StandardGrid;
+use game_of_life::{FormatError, Pattern, PatternFormat, PatternMeta};
 use std::io::{self, Write};
+use std::path::PathBuf;
 use std::{thread, time};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(name = "game_of_life")]
 #[command(about = "A high-performance Conway's Game of Life simulator")]
 #[command(version)]
 struct Args {
-    /// Input file containing the initial grid state (1s and 0s)
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Input file with the initial state: RLE, plaintext (.cells), Life 1.06, macrocell or rows of 0s and 1s
     #[arg(short, long, default_value = "default.txt")]
     input: String,
 
+    /// Save the final state here; the extension picks the format: .rle, .cells, .lif or .txt
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Dead cells to surround a loaded pattern with, so it has room to grow; 0/1 grids are used as they are
+    #[arg(long, default_value = "16")]
+    margin: usize,
+
+    /// Start from a random soup of this size instead of the input file, e.g. 80x40
+    #[arg(long, value_name = "WIDTHxHEIGHT", value_parser = parse_size)]
+    random: Option<(usize, usize)>,
+
+    /// Fraction of live cells in a random soup
+    #[arg(long, default_value = "0.35", value_parser = parse_density)]
+    density: f64,
+
+    /// Seed for the random soup; the seed used is printed so a run can be repeated
+    #[arg(long)]
+    seed: Option<u64>,
+
     /// Number of generations to simulate
     #[arg(short, long, default_value = "8")]
     generations: usize,
@@ -20,19 +46,121 @@ struct Args {
     /// Frame duration in milliseconds for visual simulation
     #[arg(short, long, default_value = "400")]
     frame_duration: u64,
+
+    /// Simulation engine: naive, ultimate (bit-packed SIMD) or auto (ultimate where supported)
+    #[arg(long, default_value = "auto")]
+    engine: EngineKind,
+
+    /// Rule to simulate instead of Conway's Life, e.g. B36/S23, /2/3 or R5,C0,M1,S34..58,B34..45,NM
+    #[arg(short, long)]
+    rule: Option<RuleString>,
+
+    /// Worker threads to simulate on; defaults to every core
+    #[arg(long)]
+    threads: Option<usize>,
+
+    /// u64 lanes per SIMD vector for the ultimate engine: 1, 2, 4, 8 or 16
+    #[arg(long)]
+    simd_width: Option<usize>,
+
+    /// Cell lattice: square (8 neighbors) or hex (6 neighbors)
+    #[arg(short, long, default_value = "square")]
+    lattice: Topology,
+
+    /// What lies beyond the edges: bounded (dead cells), torus (wrap around) or mirror
+    #[arg(short = 't', long = "topology", alias = "boundary", short_alias = 'b', default_value = "bounded")]
+    boundary: Boundary,
+
+    /// Run a 1D Wolfram rule (e.g. W30) from a single cell instead, scrolling one row per generation
+    #[arg(short, long)]
+    elementary: Option<ElementaryRule>,
+
+    /// Run 3D Life with this many layers instead, seeding the middle layer from the input
+    #[arg(short, long, value_parser = parse_depth)]
+    depth: Option<usize>,
+
+    /// Bays rule for 3D Life, e.g. 5766 or 4555
+    #[arg(long, default_value = "5766")]
+    rule3d: BaysRule,
+
+    /// Stop early once the grid empties, becomes a still life or starts oscillating
+    #[arg(long)]
+    until_stable: bool,
+
+    /// Skip drawing the grid and run as fast as possible, showing a progress bar instead
+    #[arg(long)]
+    headless: bool,
+}
+
+/// How often a headless run redraws its progress bar
+const PROGRESS_INTERVAL: time::Duration = time::Duration::from_millis(100);
+
+/// Characters in a headless run's progress bar
+const PROGRESS_WIDTH: usize = 20;
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a pattern without drawing it and report how it stabilizes, what it leaves and how it grows
+    Analyze(AnalyzeArgs),
+}
+
+#[derive(clap::Args)]
+struct AnalyzeArgs {
+    /// Pattern file in any supported format
+    pattern: String,
+    
+    /// Give up looking for a repeating state after this many generations
+    #[arg(long, default_value = "100000")]
+    max_gens: usize,
+    
+    /// Dead cells to surround the pattern with; objects that reach the edge are distorted by it
+    #[arg(long, default_value = "64")]
+    margin: usize,
+    
+    /// Simulation engine: naive, ultimate (bit-packed SIMD) or auto (ultimate where supported)
+    #[arg(long, default_value = "auto")]
+    engine: EngineKind,
+    
+    /// Rule to run instead of the one the file declares
+    #[arg(short, long)]
+    rule: Option<RuleString>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
+    let output_format = match &args.output {
+        Some(path) => Some(PatternFormat::from_path(path).ok_or_else(|| {
+            format!("Cannot tell the format of '{}' from its extension, expected .rle, .cells, .lif or .txt", path.display())
+        })?),
+        None => None,
+    };
+    if let Some(threads) = args.threads {
+        rayon::ThreadPoolBuilder::new().num_threads(threads.max(1)).build_global()?;
+    }
+    if let Some(Command::Analyze(analyze)) = &args.command {
+        return run_analysis(analyze);
+    }
 
     println!("Game of Life Optimization Demo");
     println!("==============================");
     
-    let grid = match StandardGrid::from_file(&args.input) {
-        Ok(grid) => {
-            println!("Loaded initial state from: {}", args.input);
-            grid
+    let loaded = match args.random {
+        Some((width, height)) => {
+            let seed = args.seed.unwrap_or_else(|| {
+                time::SystemTime::now().duration_since(time::UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64)
+            });
+            println!("Random {}x{} soup at density {} with seed {}", width, height, args.density, seed);
+            Ok(Pattern::new(StandardGrid::random(width, height, args.density, seed)?))
         }
+        None => load_input(&args.input, args.margin).inspect(|pattern| match &pattern.meta.name {
+            Some(name) => println!("Loaded {} from: {}", name, args.input),
+            None => println!("Loaded initial state from: {}", args.input),
+        }),
+    };
+    // A rule given on the command line overrides the one the file declares
+    let rule = args.rule.clone().or_else(|| loaded.as_ref().ok().and_then(|pattern| pattern.meta.rule.clone()));
+    let (grid, meta) = match loaded {
+        Ok(pattern) => (pattern.grid, pattern.meta),
         Err(e) => {
             println!("Could not load file '{}', using default pattern: {}", args.input, e);
             let initial_state = [
@@ -44,57 +172,290 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "⬜⬜███⬜⬜⬜█⬜███⬜██⬜██",
                 "⬜⬜⬜⬜⬜⬜██⬜⬜█⬜███⬜██⬜",
             ];
-            StandardGrid::from_string_pattern(&initial_state, '█', '⬜')?
+            (StandardGrid::from_string_pattern(&initial_state, '█', '⬜')?, PatternMeta::default())
         }
     };
 
-    let mut engine = auto_from_grid_ultimate_engine(&grid as &dyn Grid);
+    let mut engine: Box<dyn GameOfLifeEngine> = match (args.elementary, args.depth, args.lattice) {
+        (Some(rule), _, _) => {
+            let mut engine = ElementaryAutomaton::<4>::new(grid.width(), grid.height(), rule);
+            engine.seed_center();
+            Box::new(engine)
+        }
+        (None, Some(depth), _) => {
+            let mut engine = Life3DEngine::new(grid.width(), grid.height(), depth, args.rule3d);
+            engine.set_grid(&grid);
+            Box::new(engine)
+        }
+        (None, None, lattice) => {
+            let mut builder = EngineBuilder::new(args.engine).topology(lattice).boundary(args.boundary);
+            if let Some(rule) = rule.clone() {
+                builder = builder.rule(rule);
+            }
+            if let Some(threads) = args.threads {
+                builder = builder.threads(threads);
+            }
+            if let Some(simd_width) = args.simd_width {
+                builder = builder.simd_width(simd_width);
+            }
+            builder.build(&grid)?
+        }
+    };
 
-    println!("\nRunning visual simulation with Ultimate Engine...");
-    println!("Grid size: {}x{}", engine.width(), engine.height());
+    if args.simd_width.is_some() && engine.benchmark_info().name != "Ultimate" {
+        eprintln!("Warning: --simd-width only applies to the ultimate engine, ignoring it for {}", engine.benchmark_info().name);
+    }
+    
+    let mode = if args.headless { "headless" } else { "visual" };
+    println!("\nRunning {} simulation with {} Engine...", mode, engine.benchmark_info().name);
+    if let (Some(rule), None, None) = (&rule, args.elementary, args.depth) {
+        println!("Rule: {}", rule);
+    }
+    println!("Topology: {}, {} edges", engine.topology(), engine.boundary());
+    let layer_height = if args.depth.is_some() { grid.height() } else { engine.height() };
+    if let Some(depth) = args.depth {
+        println!("Grid size: {}x{}x{} ({} layers)", engine.width(), layer_height, depth, depth);
+    } else {
+        println!("Grid size: {}x{}", engine.width(), engine.height());
+    }
     println!("Initial live cells: {}", engine.count_live_cells());
     println!("Generations to simulate: {}", args.generations);
     
-    print!("\x1b[?1049h"); // Enter alternate screen
-    io::stdout().flush().unwrap();
-    
-    let frame_duration = time::Duration::from_millis(args.frame_duration);
-    
-    for step in 0..=args.generations {
-        print!("\x1b[H"); // Move cursor to top
-        print!("\x1b[2J"); // Clear screen
+    let mut settled = None;
+    if args.headless {
+        settled = run_headless(engine.as_mut(), args.generations, args.until_stable);
+    } else {
+        let mut tracker = StabilityTracker::new();
+        print!("\x1b[?1049h"); // Enter alternate screen
+        io::stdout().flush().unwrap();
         
-        println!("Step: {} | Live cells: {}", step, engine.count_live_cells());
-        print_grid_from_engine(&engine);
+        let frame_duration = time::Duration::from_millis(args.frame_duration);
+        for step in 0..=args.generations {
+            print!("\x1b[H"); // Move cursor to top
+            print!("\x1b[2J"); // Clear screen
+            
+            println!("Step: {} | Live cells: {}", step, engine.count_live_cells());
+            print_grid_from_engine(&engine, layer_height);
+            
+            io::stdout().flush().unwrap();
+            thread::sleep(frame_duration);
+            
+            if args.until_stable {
+                if let Some(class) = tracker.observe(engine.as_ref()) {
+                    settled = Some((step, class));
+                    break;
+                }
+            }
+            
+            if step < args.generations {
+                engine.step();
+            }
+        }
         
+        thread::sleep(time::Duration::from_millis(2000));
+        print!("\x1b[?1049l"); // Exit alternate screen
         io::stdout().flush().unwrap();
-        thread::sleep(frame_duration);
-        
-        if step < args.generations {
-            engine.step();
+    }
+    
+    println!("\nSimulation complete!");
+    println!("Live cells at generation {}: {}", engine.generation(), engine.count_live_cells());
+    if let (Some(path), Some(format)) = (&args.output, output_format) {
+        let meta = PatternMeta {
+            rule: rule.clone().or_else(|| engine.rule().map(RuleString::Life)),
+            position: None,
+            generation: Some(engine.generation() as u64),
+            ..meta
+        };
+        let grid = StandardGrid::from_grid(&engine.snapshot().grid);
+        Pattern { grid, meta }.save(path, format)?;
+        println!("Saved generation {} as {} to: {}", engine.generation(), format, path.display());
+    }
+    match settled {
+        Some((step, StateClass::Empty)) => println!("Grid emptied at step {}", step),
+        Some((step, class)) => println!("Settled into a {} by step {}", class, step),
+        None if args.until_stable => println!("Still evolving after {} generations", args.generations),
+        None => {}
+    }
+    if engine.benchmark_info().name == "Ultimate" {
+        println!("Ultimate Engine features demonstrated:");
+        println!("- Bit-packed representation (64 cells per u64)");
+        println!("- SIMD parallelism for massive speedup");
+        println!("- Advanced bit manipulation algorithms");
+        println!("- Multi-threading with Rayon");
+    }
+    
+    Ok(())
+}
+
+/// Step `engine` `generations` times without drawing it, with a progress bar on stderr
+///
+/// Generations run in batches that grow or shrink to redraw the bar about
+/// every `PROGRESS_INTERVAL`. With `until_stable` every state is still
+/// checked, so the run stops on the generation it settles.
+fn run_headless(engine: &mut dyn GameOfLifeEngine, generations: usize, until_stable: bool) -> Option<(usize, StateClass)> {
+    let mut tracker = StabilityTracker::new();
+    if until_stable {
+        if let Some(class) = tracker.observe(engine) {
+            return Some((0, class));
         }
     }
     
-    thread::sleep(time::Duration::from_millis(2000));
-    print!("\x1b[?1049l"); // Exit alternate screen
-    io::stdout().flush().unwrap();
+    let start = time::Instant::now();
+    let mut done = 0;
+    let mut batch = 1;
+    let mut settled = None;
+    while done < generations && settled.is_none() {
+        let steps = batch.min(generations - done);
+        let batch_start = time::Instant::now();
+        if until_stable {
+            for _ in 0..steps {
+                engine.step();
+                done += 1;
+                if let Some(class) = tracker.observe(engine) {
+                    settled = Some((done, class));
+                    break;
+                }
+            }
+        } else {
+            engine.run_steps(steps);
+            done += steps;
+        }
+        
+        let batch_time = batch_start.elapsed();
+        if batch_time < PROGRESS_INTERVAL / 2 {
+            batch = (batch * 2).min(generations);
+        } else if batch_time > PROGRESS_INTERVAL * 2 {
+            batch = (batch / 2).max(1);
+        }
+        eprint!("\r{}", progress_bar(done, generations, start.elapsed()));
+        io::stderr().flush().unwrap();
+    }
+    eprintln!();
+    settled
+}
+
+/// A progress bar with generations done, generations per second and time remaining
+fn progress_bar(done: usize, total: usize, elapsed: time::Duration) -> String {
+    let filled = (done * PROGRESS_WIDTH).checked_div(total).unwrap_or(PROGRESS_WIDTH).min(PROGRESS_WIDTH);
+    let rate = if elapsed.is_zero() { 0.0 } else { done as f64 / elapsed.as_secs_f64() };
+    let eta = if rate > 0.0 {
+        let seconds = (total.saturating_sub(done) as f64 / rate).round() as u64;
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    } else {
+        "-:--".to_string()
+    };
+    format!(
+        "[{}{}] {}/{} gens, {:.0} gens/s, ETA {}",
+        "#".repeat(filled),
+        "-".repeat(PROGRESS_WIDTH - filled),
+        done,
+        total,
+        rate,
+        eta
+    )
+}
+
+/// Run the `analyze` subcommand: load a pattern, run it to stability and print the report
+fn run_analysis(args: &AnalyzeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let pattern = load_input(&args.pattern, args.margin)?;
+    let rule = args.rule.clone().or_else(|| pattern.meta.rule.clone());
+    let mut builder = EngineBuilder::new(args.engine);
+    if let Some(rule) = rule.clone() {
+        builder = builder.rule(rule);
+    }
+    let mut engine = builder.build(&pattern.grid)?;
     
-    println!("\nSimulation complete!");
-    println!("Ultimate Engine features demonstrated:");
-    println!("- Bit-packed representation (64 cells per u64)");
-    println!("- SIMD parallelism for massive speedup");
-    println!("- Advanced bit manipulation algorithms");
-    println!("- Multi-threading with Rayon");
+    println!("Pattern: {} from {}", pattern.meta.name.as_deref().unwrap_or("unnamed"), args.pattern);
+    if let Some(rule) = &rule {
+        println!("Rule: {}", rule);
+    }
+    println!("Grid size: {}x{}, {} Engine", engine.width(), engine.height(), engine.benchmark_info().name);
+    println!("Initial live cells: {}", engine.count_live_cells());
     
+    let analysis = analyze_pattern(engine.as_mut(), args.max_gens);
+    match (analysis.stabilization.stabilized_at, analysis.stabilization.period) {
+        (Some(generation), Some(period)) => println!("Stabilized at generation {} with period {}", generation, period),
+        _ => println!("Did not stabilize within {} generations", args.max_gens),
+    }
+    println!("Final population: {}", analysis.final_population);
+    let mut objects: Vec<String> = analysis
+        .census
+        .named(&ObjectDatabase::common())
+        .into_iter()
+        .map(|(name, count)| format!("{} {}", count, name))
+        .collect();
+    if analysis.census.unrecognized > 0 {
+        objects.push(format!("{} unrecognized", analysis.census.unrecognized));
+    }
+    println!("Final census: {}", if objects.is_empty() { "nothing".to_string() } else { objects.join(", ") });
+    println!("Growth: {}", analysis.growth_class());
     Ok(())
 }
 
-fn print_grid_from_engine(engine: &Box<dyn GameOfLifeEngine>) {
+/// Load a pattern file in any supported format
+///
+/// Formats that describe a pattern rather than a whole grid, such as RLE, are
+/// centered in a grid `margin` cells larger on every side; grids of 0s and 1s
+/// and of block characters keep their size.
+fn load_input(path: &str, margin: usize) -> Result<Pattern, FormatError> {
+    let text = std::fs::read_to_string(path)?;
+    let mut pattern = Pattern::parse(&text)?;
+    if !matches!(PatternFormat::detect(&text), Some(PatternFormat::Binary | PatternFormat::Blocks)) {
+        let (width, height) = (pattern.grid.width() + 2 * margin, pattern.grid.height() + 2 * margin);
+        pattern.grid = pattern.grid.embedded_in(width, height, Anchor::Center);
+    }
+    Ok(pattern)
+}
+
+/// Parse a grid size written as WIDTHxHEIGHT, e.g. 80x40
+fn parse_size(s: &str) -> Result<(usize, usize), String> {
+    let (width, height) = s.split_once(['x', 'X']).ok_or_else(|| format!("expected WIDTHxHEIGHT, got '{}'", s))?;
+    let parse = |value: &str| value.trim().parse::<usize>().ok().filter(|&value| value > 0);
+    match (parse(width), parse(height)) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => Err(format!("expected two positive numbers in WIDTHxHEIGHT, got '{}'", s)),
+    }
+}
+
+/// Parse a live-cell density between 0 and 1
+fn parse_density(s: &str) -> Result<f64, String> {
+    match s.trim().parse::<f64>() {
+        Ok(density) if (0.0..=1.0).contains(&density) => Ok(density),
+        _ => Err(format!("expected a density between 0 and 1, got '{}'", s)),
+    }
+}
+
+/// Parse a 3D layer count, which must be at least 1
+fn parse_depth(s: &str) -> Result<usize, String> {
+    match s.trim().parse::<usize>() {
+        Ok(depth) if depth > 0 => Ok(depth),
+        _ => Err(format!("expected a positive number of layers, got '{}'", s)),
+    }
+}
+
+/// Colored squares for decaying cells, from just-died to nearly dead
+const DECAY_SQUARES: [&str; 6] = ["🟥", "🟧", "🟨", "🟩", "🟦", "🟪"];
+
+/// Print the engine's grid; rows are split into labeled slices when `layer_height` is smaller than the grid
+fn print_grid_from_engine(engine: &Box<dyn GameOfLifeEngine>, layer_height: usize) {
     let mut output = String::new();
+    let decay_states = engine.num_states().saturating_sub(2) as usize;
+    let hex = engine.topology() == Topology::Hex;
+    let sliced = layer_height > 0 && layer_height < engine.height();
     for row in 0..engine.height() {
+        if sliced && row % layer_height == 0 {
+            output.push_str(&format!("z = {}\n", row / layer_height));
+        }
+        // Odd hex rows sit half a cell to the right; each square is two columns wide
+        if hex && row % 2 == 1 {
+            output.push(' ');
+        }
         for col in 0..engine.width() {
-            let cell = engine.get_cell(row, col);
-            let square = if cell { "⬛" } else { "⬜" };
+            let square = match engine.cell_state(row, col) {
+                0 => "⬜",
+                1 => "⬛",
+                state => DECAY_SQUARES[decay_square_index(state as usize - 2, decay_states)],
+            };
             output.push_str(square);
         }
         output.push('\n');
@@ -102,6 +463,11 @@ fn print_grid_from_engine(engine: &Box<dyn GameOfLifeEngine>) {
     print!("{}", output);
 }
 
+/// Spread decay states evenly over the available colors
+fn decay_square_index(decay: usize, decay_states: usize) -> usize {
+    decay * DECAY_SQUARES.len() / decay_states.max(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,4 +531,247 @@ mod tests {
             ultimate_engine.step();
         }
     }
+    
+    #[test]
+    fn test_state_hash_matches_across_engines() {
+        let mut grid = StandardGrid::new(70, 6);
+        for &(row, col) in &[(1, 62), (2, 63), (3, 61), (3, 62), (3, 63), (4, 69)] {
+            grid.set_cell(row, col, true);
+        }
+        
+        let naive_engine = NaiveEngine::from_grid(&grid as &dyn Grid);
+        let ultimate_engine = UltimateEngine::<4>::from_grid(&grid as &dyn Grid);
+        assert_eq!(naive_engine.state_hash(), ultimate_engine.state_hash());
+        
+        assert_eq!(grid.state_hash(), ultimate_engine.state_hash());
+        
+        let empty = UltimateEngine::<4>::new(70, 6);
+        assert_ne!(empty.state_hash(), ultimate_engine.state_hash());
+    }
+    
+    #[test]
+    fn test_ultimate_run_until_stable() {
+        let pattern = [".....", ".....", ".###.", ".....", "....."];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut engine = UltimateEngine::<4>::from_grid(&grid);
+        
+        let report = engine.run_until_stable(100);
+        assert_eq!(report.period, Some(2));
+        assert_eq!(report.steps_run, 2);
+    }
+    
+    #[test]
+    fn test_ultimate_generation_observer() {
+        use std::sync::{Arc, Mutex};
+        
+        let pattern = [".....", ".###.", ".....", ".###.", "....."];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut naive_engine = NaiveEngine::from_grid(&grid as &dyn Grid);
+        let mut ultimate_engine = UltimateEngine::<4>::from_grid(&grid as &dyn Grid);
+        
+        let populations = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&populations);
+        ultimate_engine.on_generation(Box::new(move |event| sink.lock().unwrap().push(event.population)));
+        ultimate_engine.run_steps(4);
+        
+        let mut expected = Vec::new();
+        for _ in 0..4 {
+            naive_engine.step();
+            expected.push(naive_engine.count_live_cells());
+        }
+        assert_eq!(*populations.lock().unwrap(), expected);
+        assert_eq!(ultimate_engine.generation(), 4);
+    }
+    
+    #[test]
+    fn test_ultimate_snapshot_restore() {
+        let mut grid = StandardGrid::new(100, 20);
+        for &(row, col) in &[(1, 61), (2, 62), (3, 60), (3, 61), (3, 62)] {
+            grid.set_cell(row, col, true);
+        }
+        let mut ultimate_engine = UltimateEngine::<4>::from_grid(&grid as &dyn Grid);
+        let mut naive_engine = NaiveEngine::from_grid(&grid as &dyn Grid);
+        
+        ultimate_engine.run_steps(6);
+        let snapshot = ultimate_engine.snapshot();
+        ultimate_engine.run_steps(10);
+        ultimate_engine.restore(&snapshot).unwrap();
+        assert_eq!(ultimate_engine.generation(), 6);
+        
+        // Snapshots are interchangeable between engines of the same size
+        naive_engine.restore(&snapshot).unwrap();
+        ultimate_engine.run_steps(8);
+        naive_engine.run_steps(8);
+        assert_eq!(naive_engine.snapshot(), ultimate_engine.snapshot());
+    }
+    
+    #[test]
+    fn test_ultimate_concurrent_reader() {
+        let pattern = [".....", ".....", ".###.", ".....", "....."];
+        let grid = StandardGrid::from_string_pattern(&pattern, '#', '.').unwrap();
+        let mut engine = UltimateEngine::<4>::from_grid(&grid);
+        let reader = engine.reader();
+        
+        let render = std::thread::spawn(move || {
+            let mut last_generation = 0;
+            while last_generation < 200 {
+                let frame = reader.read();
+                assert!(frame.generation() >= last_generation);
+                assert_eq!(frame.count_live_cells(), 3);
+                assert!(frame.get_cell(2, 2));
+                last_generation = frame.generation();
+            }
+        });
+        
+        engine.run_steps(200);
+        render.join().unwrap();
+        assert_eq!(engine.reader().read().generation(), 200);
+    }
+    
+    #[test]
+    fn test_ultimate_cancellation() {
+        let mut engine = UltimateEngine::<4>::new(128, 128);
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert_eq!(engine.run_steps_cancellable(1_000_000, &cancel), 0);
+        
+        cancel.reset();
+        assert_eq!(engine.run_steps_cancellable(5, &cancel), 5);
+        assert_eq!(engine.generation(), 5);
+    }
+    
+    #[test]
+    fn test_engine_equivalence_custom_rules() {
+        let grid = StandardGrid::random(90, 24, 0.33, 0x2545_F491_4F6C_DD1D).unwrap();
+        
+        for rule in ["B36/S23", "B3678/S34678", "B2/S", "B1357/S1357", "B0/S8"] {
+            let rule: Rule = rule.parse().unwrap();
+            let mut naive_engine = NaiveEngine::from_grid(&grid as &dyn Grid);
+            let mut ultimate_engine = UltimateEngine::<4>::from_grid(&grid as &dyn Grid);
+            naive_engine.set_rule(rule).unwrap();
+            ultimate_engine.set_rule(rule).unwrap();
+            
+            for step in 0..6 {
+                assert_eq!(
+                    naive_engine.snapshot().grid,
+                    ultimate_engine.snapshot().grid,
+                    "Engines diverged under {} at step {}", rule, step
+                );
+                naive_engine.step();
+                ultimate_engine.step();
+            }
+        }
+    }
+    
+    #[test]
+    fn test_ultimate_step_stats_match_naive() {
+        let grid = StandardGrid::random(150, 40, 0.35, 8).unwrap();
+        let mut naive_engine = NaiveEngine::from_grid(&grid as &dyn Grid);
+        let mut ultimate_engine = UltimateEngine::<4>::from_grid(&grid as &dyn Grid);
+        
+        for _ in 0..12 {
+            assert_eq!(ultimate_engine.step_with_stats(), naive_engine.step_with_stats());
+        }
+        assert_eq!(naive_engine.snapshot(), ultimate_engine.snapshot());
+    }
+    
+    #[test]
+    fn test_ultimate_bounding_box_matches_grid() {
+        let mut grid = StandardGrid::new(150, 40);
+        assert_eq!(UltimateEngine::<4>::from_grid(&grid as &dyn Grid).bounding_box(), None);
+        
+        grid.set_cell(3, 70, true);
+        grid.set_cell(31, 129, true);
+        grid.set_cell(12, 64, true);
+        let ultimate_engine = UltimateEngine::<4>::from_grid(&grid as &dyn Grid);
+        assert_eq!(ultimate_engine.bounding_box(), grid.bounding_box());
+        assert_eq!(ultimate_engine.bounding_box(), Some(Rect::new(3, 64, 66, 29)));
+    }
+    
+    #[test]
+    fn test_load_input_any_format() {
+        let dir = std::env::temp_dir().join(format!("gol_cli_input_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let rle = dir.join("glider.rle");
+        std::fs::write(&rle, "#N Glider\nx = 3, y = 3, rule = B36/S23\nbo$2bo$3o!\n").unwrap();
+        let cells = dir.join("blinker.cells");
+        std::fs::write(&cells, "!Name: Blinker\nOOO\n").unwrap();
+        let binary = dir.join("grid.txt");
+        std::fs::write(&binary, "010\n010\n").unwrap();
+        
+        let glider = load_input(rle.to_str().unwrap(), 4).unwrap();
+        assert_eq!((glider.grid.width(), glider.grid.height(), glider.grid.count_live_cells()), (11, 11, 5));
+        assert_eq!(glider.meta.name.as_deref(), Some("Glider"));
+        assert_eq!(glider.meta.rule.map(|rule| rule.to_string()).as_deref(), Some("B36/S23"));
+        let blinker = load_input(cells.to_str().unwrap(), 1).unwrap();
+        assert_eq!((blinker.grid.width(), blinker.grid.height()), (5, 3));
+        let grid = load_input(binary.to_str().unwrap(), 4).unwrap();
+        assert_eq!((grid.grid.width(), grid.grid.height()), (3, 2));
+        
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(load_input(rle.to_str().unwrap(), 4).is_err());
+    }
+    
+    #[test]
+    fn test_parse_random_soup_args() {
+        assert_eq!(parse_size("80x40"), Ok((80, 40)));
+        assert_eq!(parse_size("16X9"), Ok((16, 9)));
+        assert!(parse_size("80").is_err());
+        assert!(parse_size("0x40").is_err());
+        assert!(parse_size("80x-1").is_err());
+        
+        assert_eq!(parse_density("0.35"), Ok(0.35));
+        assert!(parse_density("1.5").is_err());
+        assert!(parse_density("lots").is_err());
+        
+        assert_eq!(parse_depth("3"), Ok(3));
+        assert!(parse_depth("0").is_err());
+        assert!(Args::try_parse_from(["game_of_life", "--depth", "0"]).is_err());
+    }
+    
+    #[test]
+    fn test_parse_topology_args() {
+        let args = Args::try_parse_from(["game_of_life", "--topology", "torus", "--lattice", "hex"]).unwrap();
+        assert_eq!((args.boundary, args.lattice), (Boundary::Torus, Topology::Hex));
+        let args = Args::try_parse_from(["game_of_life", "-t", "mirror"]).unwrap();
+        assert_eq!((args.boundary, args.lattice), (Boundary::Mirror, Topology::Square));
+        assert_eq!(Args::try_parse_from(["game_of_life", "--boundary", "torus"]).unwrap().boundary, Boundary::Torus);
+        assert!(Args::try_parse_from(["game_of_life", "--topology", "hex"]).is_err());
+    }
+    
+    #[test]
+    fn test_parse_analyze_command() {
+        let args = Args::try_parse_from(["game_of_life", "analyze", "pattern.rle", "--max-gens", "500", "-r", "B36/S23"]).unwrap();
+        let Some(Command::Analyze(analyze)) = args.command else { panic!("expected the analyze subcommand") };
+        assert_eq!((analyze.pattern.as_str(), analyze.max_gens, analyze.margin), ("pattern.rle", 500, 64));
+        assert_eq!(analyze.rule, Some(RuleString::parse("B36/S23").unwrap()));
+        
+        // Without a subcommand the simulator runs as before
+        let args = Args::try_parse_from(["game_of_life", "-g", "3"]).unwrap();
+        assert!(args.command.is_none());
+        assert_eq!(args.generations, 3);
+    }
+    
+    #[test]
+    fn test_progress_bar() {
+        let second = time::Duration::from_secs(1);
+        assert_eq!(progress_bar(250, 1000, second), "[#####---------------] 250/1000 gens, 250 gens/s, ETA 0:03");
+        assert_eq!(progress_bar(0, 1000, time::Duration::ZERO), "[--------------------] 0/1000 gens, 0 gens/s, ETA -:--");
+        assert!(progress_bar(1000, 1000, second).starts_with("[####################] 1000/1000"));
+        
+        let blinker = game_of_life::patterns::blinker().grid.embedded_in(8, 8, Anchor::Center);
+        let mut engine = NaiveEngine::from_grid(&blinker);
+        assert_eq!(run_headless(&mut engine, 1000, false), None);
+        assert_eq!(engine.generation(), 1000);
+        let mut engine = NaiveEngine::from_grid(&blinker);
+        assert_eq!(run_headless(&mut engine, 1000, true), Some((2, StateClass::Oscillator { period: 2 })));
+    }
+    
+    #[test]
+    fn test_decay_square_index() {
+        assert_eq!(decay_square_index(0, 1), 0);
+        assert_eq!(decay_square_index(0, 2), 0);
+        assert_eq!(decay_square_index(1, 2), 3);
+        assert_eq!(decay_square_index(253, 254), DECAY_SQUARES.len() - 1);
+    }
 }
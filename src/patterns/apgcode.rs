@@ -0,0 +1,156 @@
+//! Encoding and decoding Catagolue apgcodes such as `xs4_33` or `xq4_153`
+//!
+//! The part after the underscore is in extended Wechsler format: the pattern
+//! is cut into strips five rows tall, each character encodes one column of a
+//! strip as a 5-bit number (top row in the lowest bit), `z` starts the next
+//! strip, and `w`, `x` and `yN` abbreviate runs of 2, 3 and 4 + N empty
+//! columns.
+
+use crate::formats::{FormatError, Pattern, PatternMeta};
+use crate::grid::{Grid, StandardGrid};
+
+/// Whether `code` looks like a still life, oscillator or spaceship apgcode
+pub fn is_apgcode(code: &str) -> bool {
+    code.split_once('_').is_some_and(|(prefix, _)| {
+        let kind = prefix.get(..2).unwrap_or("");
+        matches!(kind, "xs" | "xp" | "xq") && prefix.len() > 2 && prefix[2..].bytes().all(|byte| byte.is_ascii_digit())
+    })
+}
+
+/// Decode an apgcode into the pattern it names, trimmed to its bounding box
+pub fn from_apgcode(code: &str) -> Result<Pattern, FormatError> {
+    let invalid = |message: String| FormatError::syntax(1, message);
+    if !is_apgcode(code) {
+        return Err(invalid(format!("'{}' is not an xs, xp or xq apgcode", code)));
+    }
+    let (_, wechsler) = code.split_once('_').unwrap();
+    
+    let mut strips: Vec<Vec<u8>> = vec![Vec::new()];
+    let mut chars = wechsler.chars();
+    while let Some(ch) = chars.next() {
+        let strip = strips.last_mut().unwrap();
+        match ch {
+            'w' => strip.extend([0, 0]),
+            'x' => strip.extend([0, 0, 0]),
+            'y' => {
+                let count = chars.next().and_then(|next| next.to_digit(36)).ok_or_else(|| invalid("'y' must be followed by a run length".to_string()))?;
+                strip.extend(std::iter::repeat_n(0, 4 + count as usize));
+            }
+            'z' => strips.push(Vec::new()),
+            _ => match ch.to_digit(32) {
+                Some(column) => strip.push(column as u8),
+                None => return Err(invalid(format!("Invalid character '{}' in apgcode", ch))),
+            },
+        }
+    }
+    
+    let width = strips.iter().map(Vec::len).max().unwrap_or(0);
+    let mut grid = StandardGrid::new(width, strips.len() * 5);
+    grid.set_cells(&mut strips.iter().enumerate().flat_map(|(strip_index, strip)| {
+        strip.iter().enumerate().flat_map(move |(col, &column)| {
+            (0..5).filter(move |bit| column >> bit & 1 == 1).map(move |bit| (strip_index * 5 + bit, col, true))
+        })
+    }));
+    
+    let meta = PatternMeta {
+        name: Some(code.to_string()),
+        ..PatternMeta::default()
+    };
+    Ok(Pattern { grid: grid.trimmed(), meta })
+}
+
+/// Encode the live cells of `grid` in extended Wechsler format, the part of an apgcode after the underscore
+///
+/// The pattern is trimmed first; an empty grid encodes as an empty string.
+/// Only this one orientation is encoded, so the result is not canonical.
+pub fn to_wechsler(grid: &dyn Grid) -> String {
+    let pattern = grid.trimmed();
+    if pattern.count_live_cells() == 0 {
+        return String::new();
+    }
+    
+    let mut strips = Vec::new();
+    for top in (0..pattern.height()).step_by(5) {
+        let mut columns: Vec<u32> = (0..pattern.width())
+            .map(|col| {
+                (0..5)
+                    .filter(|bit| top + bit < pattern.height() && pattern.get_cell(top + bit, col))
+                    .fold(0, |column, bit| column | 1 << bit)
+            })
+            .collect();
+        while columns.last() == Some(&0) {
+            columns.pop();
+        }
+        
+        let mut strip = String::new();
+        let mut blanks = 0;
+        for column in columns {
+            if column == 0 {
+                blanks += 1;
+                continue;
+            }
+            push_blanks(&mut strip, blanks);
+            blanks = 0;
+            strip.push(char::from_digit(column, 32).unwrap());
+        }
+        strips.push(strip);
+    }
+    strips.join("z")
+}
+
+/// Append the shortest abbreviation for a run of `count` empty columns
+fn push_blanks(strip: &mut String, mut count: u32) {
+    while count > 0 {
+        let used = match count {
+            1 => { strip.push('0'); 1 }
+            2 => { strip.push('w'); 2 }
+            3 => { strip.push('x'); 3 }
+            _ => {
+                let run = (count - 4).min(35);
+                strip.push('y');
+                strip.push(char::from_digit(run, 36).unwrap());
+                run + 4
+            }
+        };
+        count -= used;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns;
+    
+    #[test]
+    fn test_decodes_known_objects() {
+        assert_eq!(from_apgcode("xs4_33").unwrap().grid, patterns::block().grid);
+        assert_eq!(from_apgcode("xp2_7").unwrap().grid, patterns::blinker().grid.transpose());
+        
+        // Catagolue's canonical glider phase is the library glider mirrored
+        assert_eq!(from_apgcode("xq4_153").unwrap().grid, patterns::glider().grid.flip_vertical());
+        
+        // Strips, blank-column runs and a second strip
+        let wide = from_apgcode("xs8_1y011z1").unwrap().grid;
+        assert_eq!((wide.width(), wide.height(), wide.count_live_cells()), (7, 6, 4));
+    }
+    
+    #[test]
+    fn test_wechsler_round_trip() {
+        assert_eq!(to_wechsler(&patterns::block().grid), "33");
+        assert_eq!(to_wechsler(&patterns::glider().grid.flip_vertical()), "153");
+        assert_eq!(to_wechsler(&StandardGrid::new(4, 4)), "");
+        
+        for code in ["xs8_1y011z1", "xs4_1y911", "xp2_1x1w1zx1", "xq4_6frc"] {
+            let grid = from_apgcode(code).unwrap().grid;
+            assert_eq!(to_wechsler(&grid), code.split_once('_').unwrap().1);
+        }
+    }
+    
+    #[test]
+    fn test_rejects_bad_codes() {
+        assert!(!is_apgcode("gosperglidergun"));
+        assert!(!is_apgcode("xs_33"));
+        assert!(from_apgcode("xs4_3!").is_err());
+        assert!(from_apgcode("xs4_y").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! Downloading patterns from LifeWiki, with a local cache
+//!
+//! Requires the `net` feature.
+
+use super::apgcode::{from_apgcode, is_apgcode};
+use crate::formats::{FormatError, Pattern};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where LifeWiki serves raw pattern files
+pub const LIFEWIKI_URL: &str = "https://conwaylife.com/patterns";
+
+/// Fetch a pattern by LifeWiki file name (e.g. `gosperglidergun`) or apgcode, using the default cache
+pub fn fetch(name: &str) -> Result<Pattern, FormatError> {
+    Fetcher::new(default_cache_dir()).fetch(name)
+}
+
+/// Default cache directory: `$XDG_CACHE_HOME/game_of_life/patterns`, then `~/.cache/...`, then the temp dir
+pub fn default_cache_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("game_of_life").join("patterns")
+}
+
+/// Downloads RLE files and keeps a copy of each so later lookups work offline
+///
+/// Apgcodes (`xs4_33`, `xq4_153`, ...) name Catagolue objects and are decoded
+/// locally without touching the network.
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    cache_dir: PathBuf,
+    base_url: String,
+}
+
+impl Fetcher {
+    /// Fetch from LifeWiki, caching in `cache_dir`
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self { cache_dir: cache_dir.into(), base_url: LIFEWIKI_URL.to_string() }
+    }
+    
+    /// Fetch `<base_url>/<name>.rle` instead, e.g. from a mirror
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+    
+    /// Directory holding cached RLE files
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+    
+    /// Fetch a pattern, reading the cache first and downloading on a miss
+    pub fn fetch(&self, name: &str) -> Result<Pattern, FormatError> {
+        if is_apgcode(name) {
+            return from_apgcode(name);
+        }
+        let name = name.to_ascii_lowercase();
+        if name.is_empty() || !name.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-') {
+            return Err(FormatError::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is not a LifeWiki pattern file name", name),
+            )));
+        }
+        
+        let cached = self.cache_dir.join(format!("{}.rle", name));
+        if let Ok(text) = std::fs::read_to_string(&cached) {
+            return Pattern::parse(&text);
+        }
+        
+        let url = format!("{}/{}.rle", self.base_url, name);
+        let text = ureq::get(&url)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|err| FormatError::Network(Box::new(err)))?;
+        // Only cache what parses, so a bad download is retried next time
+        let pattern = Pattern::parse(&text)?;
+        
+        std::fs::create_dir_all(&self.cache_dir)?;
+        let temp = cached.with_extension("rle.tmp");
+        std::fs::write(&temp, &text)?;
+        std::fs::rename(&temp, &cached)?;
+        Ok(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns;
+    
+    #[test]
+    fn test_cache_hits_and_apgcodes_stay_offline() {
+        let dir = std::env::temp_dir().join(format!("gol_fetch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("glider.rle"), "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n").unwrap();
+        
+        // An unroutable base URL proves nothing is downloaded
+        let fetcher = Fetcher::new(&dir).with_base_url("http://127.0.0.1:9/");
+        let glider = fetcher.fetch("Glider");
+        let block = fetcher.fetch("xs4_33");
+        let missing = fetcher.fetch("notcached");
+        let invalid = fetcher.fetch("../etc/passwd");
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        assert_eq!(glider.unwrap().grid, patterns::glider().grid);
+        assert_eq!(block.unwrap().grid, patterns::block().grid);
+        assert!(matches!(missing, Err(FormatError::Network(_))));
+        assert!(matches!(invalid, Err(FormatError::Io(_))));
+    }
+}
@@ -0,0 +1,185 @@
+//! Library of well-known patterns
+//!
+//! Each constructor returns the pattern trimmed to its bounding box, with its
+//! name, discoverer and a short description in the metadata. All run under
+//! B3/S23. Catagolue apgcodes can be decoded with `from_apgcode`, and with the
+//! `net` feature anything on LifeWiki can be downloaded with `fetch`.
+
+pub mod apgcode;
+#[cfg(feature = "net")]
+pub mod fetch;
+
+pub use apgcode::{from_apgcode, to_wechsler};
+#[cfg(feature = "net")]
+pub use fetch::{fetch, Fetcher};
+
+use crate::formats::{rle, Pattern};
+
+/// Parse one of the built-in RLE strings
+fn builtin(name: &str, author: &str, comment: &str, body: &str, width: usize, height: usize) -> Pattern {
+    let text = format!("x = {}, y = {}, rule = B3/S23\n{}", width, height, body);
+    let (grid, mut meta) = rle::parse(&text).expect("built-in patterns are valid RLE");
+    meta.name = Some(name.to_string());
+    meta.author = Some(author.to_string());
+    meta.comments = vec![comment.to_string()];
+    Pattern { grid, meta }
+}
+
+/// Period-2 oscillator of three cells in a row
+pub fn blinker() -> Pattern {
+    builtin("Blinker", "John Conway", "The smallest oscillator.", "3o!", 3, 1)
+}
+
+/// The 2x2 still life
+pub fn block() -> Pattern {
+    builtin("Block", "John Conway", "The most common still life.", "2o$2o!", 2, 2)
+}
+
+/// The smallest spaceship, moving diagonally at c/4
+pub fn glider() -> Pattern {
+    builtin("Glider", "Richard K. Guy", "The smallest spaceship, travelling diagonally at c/4.", "bo$2bo$3o!", 3, 3)
+}
+
+/// Lightweight spaceship, moving orthogonally at c/2
+pub fn lwss() -> Pattern {
+    builtin("Lightweight spaceship", "John Conway", "The smallest orthogonal spaceship, travelling at c/2.", "bo2bo$o4b$o3bo$4o!", 5, 4)
+}
+
+/// Middleweight spaceship, moving orthogonally at c/2
+pub fn mwss() -> Pattern {
+    builtin("Middleweight spaceship", "John Conway", "Orthogonal c/2 spaceship one cell longer than the LWSS.", "3bo2b$bo3bo$o5b$o4bo$5o!", 6, 5)
+}
+
+/// Heavyweight spaceship, moving orthogonally at c/2
+pub fn hwss() -> Pattern {
+    builtin("Heavyweight spaceship", "John Conway", "The largest of the three standard orthogonal c/2 spaceships.", "3b2o2b$bo4bo$o6b$o5bo$6o!", 7, 5)
+}
+
+/// Period-30 gun emitting a glider every cycle
+pub fn gosper_glider_gun() -> Pattern {
+    builtin(
+        "Gosper glider gun",
+        "Bill Gosper",
+        "The first known gun and the first known finite pattern with unbounded growth.",
+        "24bo11b$22bobo11b$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o14b$2o8bo3bob2o4b\nobo11b$10bo5bo7bo11b$11bo3bo20b$12b2o!",
+        36,
+        9,
+    )
+}
+
+/// Five-cell methuselah that stabilizes after 1103 generations
+pub fn r_pentomino() -> Pattern {
+    builtin("R-pentomino", "John Conway", "A methuselah that stabilizes at generation 1103.", "b2o$2ob$bo!", 3, 3)
+}
+
+/// Seven-cell methuselah that stabilizes after 5206 generations
+pub fn acorn() -> Pattern {
+    builtin("Acorn", "Charles Corderman", "A methuselah that stabilizes at generation 5206.", "bo5b$3bo3b$2o2b3o!", 7, 3)
+}
+
+/// The most common period-3 oscillator
+pub fn pulsar() -> Pattern {
+    builtin(
+        "Pulsar",
+        "John Conway",
+        "The most common period-3 oscillator.",
+        "2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$2b3o3b3o2b$o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+        13,
+        13,
+    )
+}
+
+/// Period-15 oscillator
+pub fn pentadecathlon() -> Pattern {
+    builtin("Pentadecathlon", "John Conway", "A period-15 oscillator.", "2bo4bo2b$2ob4ob2o$2bo4bo!", 10, 3)
+}
+
+/// Eater 1, a still life that consumes gliders
+pub fn eater() -> Pattern {
+    builtin("Eater 1", "Bill Gosper", "A still life that can eat gliders and other patterns.", "2o2b$obob$2bob$2b2o!", 4, 4)
+}
+
+/// Every pattern in the library
+pub fn all() -> Vec<Pattern> {
+    vec![
+        blinker(),
+        block(),
+        glider(),
+        lwss(),
+        mwss(),
+        hwss(),
+        gosper_glider_gun(),
+        r_pentomino(),
+        acorn(),
+        pulsar(),
+        pentadecathlon(),
+        eater(),
+    ]
+}
+
+/// Look a pattern up by name, ignoring case, spaces and dashes ("R-pentomino", "lwss", "gosper glider gun")
+pub fn by_name(name: &str) -> Option<Pattern> {
+    let key = |name: &str| name.chars().filter(|ch| ch.is_alphanumeric()).collect::<String>().to_lowercase();
+    let wanted = key(name);
+    let aliases = [("lwss", "lightweightspaceship"), ("mwss", "middleweightspaceship"), ("hwss", "heavyweightspaceship"), ("eater", "eater1")];
+    let wanted = aliases.iter().find(|(alias, _)| *alias == wanted).map_or(wanted.clone(), |(_, full)| full.to_string());
+    all().into_iter().find(|pattern| pattern.meta.name.as_deref().map(key).as_deref() == Some(wanted.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{GameOfLifeEngine, NaiveEngine};
+    use crate::grid::{Anchor, Grid, Rect};
+    
+    /// Run a pattern centered in a padded field and return the engine
+    fn run(pattern: &Pattern, padding: usize, steps: usize) -> NaiveEngine {
+        let grid = &pattern.grid;
+        let field = grid.embedded_in(grid.width() + 2 * padding, grid.height() + 2 * padding, Anchor::Center);
+        let mut engine = NaiveEngine::from_grid(&field);
+        engine.run_steps(steps);
+        engine
+    }
+    
+    #[test]
+    fn test_oscillators_and_still_lifes() {
+        for (pattern, period) in [(blinker(), 2), (block(), 1), (pulsar(), 3), (pentadecathlon(), 15), (eater(), 1)] {
+            let mut engine = run(&pattern, 8, 0);
+            let report = engine.run_until_stable(period);
+            assert_eq!(report.period, Some(period), "{:?}", pattern.meta.name);
+            assert_eq!(report.stabilized_at, Some(0), "{:?}", pattern.meta.name);
+        }
+    }
+    
+    #[test]
+    fn test_spaceships_translate() {
+        for (pattern, shift) in [(glider(), (1, 1)), (lwss(), (0, 2)), (mwss(), (0, 2)), (hwss(), (0, 2))] {
+            let engine = run(&pattern, 10, 4);
+            let bounds = engine.get_grid().bounding_box().unwrap();
+            let moved = engine.get_grid().crop(bounds);
+            assert_eq!(moved, pattern.grid, "{:?}", pattern.meta.name);
+            
+            let start = Rect::new(10, 10, pattern.grid.width(), pattern.grid.height());
+            let (d_row, d_col) = (bounds.row.abs_diff(start.row), bounds.col.abs_diff(start.col));
+            assert_eq!((d_row, d_col), shift, "{:?}", pattern.meta.name);
+        }
+    }
+    
+    #[test]
+    fn test_gun_and_methuselahs() {
+        let gun = run(&gosper_glider_gun(), 30, 30);
+        assert_eq!(gun.count_live_cells(), 36 + 5);
+        assert_eq!(run(&r_pentomino(), 0, 0).count_live_cells(), 5);
+        assert_eq!(run(&acorn(), 0, 0).count_live_cells(), 7);
+        assert_eq!(all().len(), 12);
+    }
+    
+    #[test]
+    fn test_by_name() {
+        assert_eq!(by_name("LWSS"), Some(lwss()));
+        assert_eq!(by_name("r-pentomino"), Some(r_pentomino()));
+        assert_eq!(by_name("Gosper glider gun"), Some(gosper_glider_gun()));
+        assert_eq!(by_name("eater"), Some(eater()));
+        assert_eq!(by_name("unknown"), None);
+    }
+}
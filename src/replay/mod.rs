@@ -0,0 +1,95 @@
+//! Recording simulations to disk and playing them back with random access
+//!
+//! A replay file is a header followed by one record per recorded generation.
+//! Records are either keyframes holding the full bit-packed field or deltas
+//! listing the cells that toggled since the previous record. Because a toggle
+//! list is its own inverse, the player can step backwards as cheaply as
+//! forwards, and keyframes bound the cost of seeking anywhere.
+//!
+//! Layout, integers little-endian unless noted:
+//!
+//! | bytes | field |
+//! |-------|-------|
+//! | 8 | magic `GOLREPLY` |
+//! | 2 | format version (1) |
+//! | 8, 8 | width, height |
+//! | 4 | keyframe interval |
+//! | ... | records |
+//!
+//! A keyframe record is tag 0, the generation as a LEB128 varint, then the
+//! field in `BitGrid` word layout. A delta record is tag 1, the generation,
+//! the toggle count, then the toggled cell indices (`row * width + col`) in
+//! increasing order as varint gaps from the previous index.
+
+pub mod player;
+pub mod recorder;
+
+pub use player::ReplayPlayer;
+pub use recorder::ReplayRecorder;
+
+use crate::formats::FormatError;
+use std::io::{Read, Write};
+
+/// File signature
+pub const MAGIC: &[u8; 8] = b"GOLREPLY";
+
+/// Version written by this crate
+pub const VERSION: u16 = 1;
+
+const TAG_KEYFRAME: u8 = 0;
+const TAG_DELTA: u8 = 1;
+
+/// Write an unsigned LEB128 varint
+fn write_varint(out: &mut impl Write, mut value: u64) -> std::io::Result<()> {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            return out.write_all(&[byte]);
+        }
+        out.write_all(&[byte | 0x80])?;
+    }
+}
+
+/// Read an unsigned LEB128 varint
+fn read_varint(input: &mut impl Read) -> Result<u64, FormatError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8];
+        input.read_exact(&mut byte).map_err(truncated)?;
+        value |= ((byte[0] & 0x7F) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(FormatError::Corrupt("varint is too long".to_string()))
+}
+
+fn truncated(err: std::io::Error) -> FormatError {
+    if err.kind() == std::io::ErrorKind::UnexpectedEof {
+        FormatError::Corrupt("unexpected end of replay".to_string())
+    } else {
+        FormatError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_varint_round_trip() {
+        let values = [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX];
+        let mut bytes = Vec::new();
+        for &value in &values {
+            write_varint(&mut bytes, value).unwrap();
+        }
+        assert_eq!(&bytes[..4], &[0, 1, 127, 0x80]);
+        
+        let mut input = bytes.as_slice();
+        for &value in &values {
+            assert_eq!(read_varint(&mut input).unwrap(), value);
+        }
+        assert!(matches!(read_varint(&mut input), Err(FormatError::Corrupt(_))));
+    }
+}
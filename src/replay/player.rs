@@ -0,0 +1,284 @@
+//! Random-access playback of replay files
+
+use super::{read_varint, truncated, MAGIC, TAG_DELTA, TAG_KEYFRAME, VERSION};
+use crate::formats::{FormatError, MAX_DENSE_CELLS};
+use crate::grid::{BitGrid, Grid};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Location of one record in the replay stream
+#[derive(Debug, Clone, Copy)]
+struct Record {
+    generation: usize,
+    /// Offset of the record's payload, just past its generation
+    offset: u64,
+    keyframe: bool,
+}
+
+/// Reconstructs any recorded generation of a replay
+///
+/// Opening scans the stream once to index its records. Seeking then loads the
+/// nearest keyframe at or before the target and applies toggle lists, or walks
+/// from the current position when that is closer, in either direction.
+pub struct ReplayPlayer<R: Read + Seek> {
+    input: R,
+    records: Vec<Record>,
+    keyframe_interval: u32,
+    grid: BitGrid,
+    position: usize,
+}
+
+impl ReplayPlayer<BufReader<File>> {
+    /// Open a replay file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FormatError> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> ReplayPlayer<R> {
+    /// Index a replay stream and position the player at its first generation
+    pub fn new(mut input: R) -> Result<Self, FormatError> {
+        let mut header = [0u8; 30];
+        input.read_exact(&mut header).map_err(truncated)?;
+        if &header[..8] != MAGIC {
+            return Err(FormatError::Corrupt("not a replay (bad magic)".to_string()));
+        }
+        let version = u16::from_le_bytes([header[8], header[9]]);
+        if version != VERSION {
+            return Err(FormatError::Corrupt(format!("unsupported replay version {}", version)));
+        }
+        let width = u64::from_le_bytes(header[10..18].try_into().unwrap());
+        let height = u64::from_le_bytes(header[18..26].try_into().unwrap());
+        if width.checked_mul(height).is_none_or(|cells| cells > MAX_DENSE_CELLS) {
+            return Err(FormatError::Corrupt(format!("dimensions {}x{} are too large", width, height)));
+        }
+        let keyframe_interval = u32::from_le_bytes(header[26..30].try_into().unwrap());
+        let grid = BitGrid::new(width as usize, height as usize);
+        let keyframe_bytes = (grid.words().len() * 8) as i64;
+        
+        let mut records: Vec<Record> = Vec::new();
+        loop {
+            let mut tag = [0u8];
+            if input.read(&mut tag)? == 0 {
+                break;
+            }
+            let generation = read_varint(&mut input)? as usize;
+            if records.last().is_some_and(|last| generation <= last.generation) {
+                return Err(FormatError::Corrupt(format!("generation {} is out of order", generation)));
+            }
+            let offset = input.stream_position()?;
+            match tag[0] {
+                TAG_KEYFRAME => {
+                    input.seek(SeekFrom::Current(keyframe_bytes))?;
+                }
+                TAG_DELTA => {
+                    for _ in 0..read_varint(&mut input)? {
+                        read_varint(&mut input)?;
+                    }
+                }
+                tag => return Err(FormatError::Corrupt(format!("unknown record tag {}", tag))),
+            }
+            records.push(Record { generation, offset, keyframe: tag[0] == TAG_KEYFRAME });
+        }
+        if !records.first().is_some_and(|first| first.keyframe) {
+            return Err(FormatError::Corrupt("replay does not start with a keyframe".to_string()));
+        }
+        // The scan may have seeked past a truncated final keyframe
+        if input.stream_position()? > input.seek(SeekFrom::End(0))? {
+            return Err(FormatError::Corrupt("unexpected end of replay".to_string()));
+        }
+        
+        let mut player = Self { input, records, keyframe_interval, grid, position: 0 };
+        player.load_keyframe(0)?;
+        Ok(player)
+    }
+    
+    /// Keyframe interval the replay was recorded with
+    pub fn keyframe_interval(&self) -> u32 {
+        self.keyframe_interval
+    }
+    
+    /// Number of recorded generations
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+    
+    /// Whether the replay has no records; never true for a successfully opened replay
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+    
+    /// First and last recorded generations
+    pub fn generation_range(&self) -> (usize, usize) {
+        (self.records[0].generation, self.records[self.records.len() - 1].generation)
+    }
+    
+    /// Every recorded generation, in order
+    pub fn generations(&self) -> impl Iterator<Item = usize> + '_ {
+        self.records.iter().map(|record| record.generation)
+    }
+    
+    /// Generation currently loaded
+    pub fn generation(&self) -> usize {
+        self.records[self.position].generation
+    }
+    
+    /// State at the current generation
+    pub fn grid(&self) -> &BitGrid {
+        &self.grid
+    }
+    
+    /// Load a recorded generation and return its state
+    pub fn seek(&mut self, generation: usize) -> Result<&BitGrid, FormatError> {
+        let target = self
+            .records
+            .binary_search_by_key(&generation, |record| record.generation)
+            .map_err(|_| FormatError::Corrupt(format!("generation {} was not recorded", generation)))?;
+        let keyframe = (0..=target).rev().find(|&index| self.records[index].keyframe).unwrap_or(0);
+        
+        // Keyframes cannot be undone, so only walk back over deltas
+        let walk_back = self.position > target
+            && self.position - target < target - keyframe
+            && !self.records[target + 1..=self.position].iter().any(|record| record.keyframe);
+        if walk_back {
+            // Undo each delta from the current record down to the target
+            for index in (target + 1..=self.position).rev() {
+                self.apply_delta(index)?;
+            }
+        } else {
+            let start = if (keyframe..=target).contains(&self.position) {
+                self.position
+            } else {
+                self.load_keyframe(keyframe)?;
+                keyframe
+            };
+            for index in start + 1..=target {
+                if self.records[index].keyframe {
+                    self.load_keyframe(index)?;
+                } else {
+                    self.apply_delta(index)?;
+                }
+            }
+        }
+        self.position = target;
+        Ok(&self.grid)
+    }
+    
+    /// Advance to the next recorded generation, returning `false` at the end
+    pub fn step_forward(&mut self) -> Result<bool, FormatError> {
+        match self.records.get(self.position + 1) {
+            Some(record) => self.seek(record.generation).map(|_| true),
+            None => Ok(false),
+        }
+    }
+    
+    /// Go back to the previous recorded generation, returning `false` at the start
+    pub fn step_back(&mut self) -> Result<bool, FormatError> {
+        match self.position.checked_sub(1) {
+            Some(previous) => self.seek(self.records[previous].generation).map(|_| true),
+            None => Ok(false),
+        }
+    }
+    
+    fn load_keyframe(&mut self, index: usize) -> Result<(), FormatError> {
+        self.input.seek(SeekFrom::Start(self.records[index].offset))?;
+        let mut bytes = vec![0u8; self.grid.words().len() * 8];
+        self.input.read_exact(&mut bytes).map_err(truncated)?;
+        let words = bytes.chunks_exact(8).map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap())).collect();
+        self.grid = BitGrid::from_words(self.grid.width(), self.grid.height(), words).map_err(FormatError::rejected)?;
+        self.position = index;
+        Ok(())
+    }
+    
+    /// Toggle the cells listed by a delta record
+    fn apply_delta(&mut self, index: usize) -> Result<(), FormatError> {
+        self.input.seek(SeekFrom::Start(self.records[index].offset))?;
+        let width = self.grid.width() as u64;
+        let cells = width * self.grid.height() as u64;
+        let mut cell = 0;
+        for _ in 0..read_varint(&mut self.input)? {
+            cell += read_varint(&mut self.input)?;
+            if cell >= cells {
+                return Err(FormatError::Corrupt(format!("cell index {} is outside the grid", cell)));
+            }
+            let (row, col) = ((cell / width) as usize, (cell % width) as usize);
+            let alive = self.grid.get_cell(row, col);
+            self.grid.set_cell(row, col, !alive);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{GameOfLifeEngine, NaiveEngine, UltimateEngine};
+    use crate::error::GameOfLifeError;
+    use crate::grid::StandardGrid;
+    use crate::replay::ReplayRecorder;
+    use std::io::Cursor;
+    
+    #[test]
+    fn test_seek_matches_simulation() {
+        let mut engine = NaiveEngine::from_grid(&StandardGrid::random(70, 40, 0.35, 12).unwrap());
+        let mut recorder = ReplayRecorder::new(Vec::new(), engine.get_grid(), 0, 16).unwrap();
+        let mut hashes = vec![engine.state_hash()];
+        for _ in 0..100 {
+            engine.step();
+            recorder.record_engine(&engine).unwrap();
+            hashes.push(engine.state_hash());
+        }
+        
+        let mut player = ReplayPlayer::new(Cursor::new(recorder.finish().unwrap())).unwrap();
+        assert_eq!(player.len(), 101);
+        assert_eq!(player.generation_range(), (0, 100));
+        for generation in [73, 100, 74, 5, 99, 0, 40, 39] {
+            assert_eq!(player.seek(generation).unwrap().state_hash(), hashes[generation], "generation {}", generation);
+        }
+        assert!(player.step_back().unwrap());
+        assert_eq!((player.generation(), player.grid().state_hash()), (38, hashes[38]));
+        assert!(player.step_forward().unwrap());
+        assert_eq!(player.grid().state_hash(), hashes[39]);
+        assert!(player.seek(101).is_err());
+        
+        // Stepping back over a keyframe has to reload from the one before it
+        for generation in [33, 31, 48, 47, 32] {
+            assert_eq!(player.seek(generation).unwrap().state_hash(), hashes[generation], "generation {}", generation);
+        }
+    }
+    
+    #[test]
+    fn test_records_ultimate_engine() {
+        let grid = StandardGrid::random(100, 30, 0.35, 5).unwrap();
+        let mut engine = UltimateEngine::<4>::from_grid(&grid);
+        let mut recorder = ReplayRecorder::new(Vec::new(), &grid, 0, 8).unwrap();
+        for _ in 0..20 {
+            engine.step();
+            recorder.record_engine(&engine).unwrap();
+        }
+        
+        let mut player = ReplayPlayer::new(Cursor::new(recorder.finish().unwrap())).unwrap();
+        assert_eq!(player.seek(20).unwrap().state_hash(), engine.state_hash());
+    }
+    
+    #[test]
+    fn test_rejects_damaged_streams() {
+        let grid = StandardGrid::random(10, 10, 0.5, 1).unwrap();
+        let mut recorder = ReplayRecorder::new(Vec::new(), &grid, 0, 4).unwrap();
+        recorder.record(&StandardGrid::new(10, 10), 1).unwrap();
+        assert!(matches!(recorder.record(&StandardGrid::new(10, 10), 1), Err(FormatError::Rejected(GameOfLifeError::InvalidArgument(_)))));
+        assert!(matches!(recorder.record(&StandardGrid::new(10, 9), 2), Err(FormatError::Rejected(GameOfLifeError::DimensionMismatch { .. }))));
+        assert!(ReplayRecorder::new(Vec::new(), &grid, 0, 0).is_err());
+        let bytes = recorder.finish().unwrap();
+        
+        assert!(ReplayPlayer::new(Cursor::new(bytes.clone())).is_ok());
+        assert!(matches!(ReplayPlayer::new(Cursor::new(bytes[..40].to_vec())), Err(FormatError::Corrupt(_))));
+        assert!(matches!(ReplayPlayer::new(Cursor::new(b"GOLSTATE".to_vec())), Err(FormatError::Corrupt(_))));
+        
+        // A damaged header must not size the grid
+        let mut huge = bytes.clone();
+        huge[18..26].copy_from_slice(&(1u64 << 61).to_le_bytes());
+        assert!(matches!(ReplayPlayer::new(Cursor::new(huge)), Err(FormatError::Corrupt(_))));
+    }
+}
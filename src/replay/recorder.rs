@@ -0,0 +1,131 @@
+//! Writing replay files
+
+use super::{write_varint, MAGIC, TAG_DELTA, TAG_KEYFRAME, VERSION};
+use crate::engines::GameOfLifeEngine;
+use crate::error::GameOfLifeError;
+use crate::formats::FormatError;
+use crate::grid::{BitGrid, Grid};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Appends generations to a replay stream as keyframes and toggle lists
+pub struct ReplayRecorder<W: Write> {
+    out: W,
+    keyframe_interval: u32,
+    since_keyframe: u32,
+    previous: BitGrid,
+    generation: usize,
+    toggled: Vec<u64>,
+}
+
+impl ReplayRecorder<BufWriter<File>> {
+    /// Start a replay file with `initial` as the first keyframe
+    pub fn create(path: impl AsRef<Path>, initial: &dyn Grid, generation: usize, keyframe_interval: u32) -> Result<Self, FormatError> {
+        Self::new(BufWriter::new(File::create(path)?), initial, generation, keyframe_interval)
+    }
+}
+
+impl<W: Write> ReplayRecorder<W> {
+    /// Start a replay stream, writing a keyframe every `keyframe_interval` records
+    ///
+    /// Fails if `keyframe_interval` is zero.
+    pub fn new(mut out: W, initial: &dyn Grid, generation: usize, keyframe_interval: u32) -> Result<Self, FormatError> {
+        if keyframe_interval == 0 {
+            return Err(FormatError::rejected(GameOfLifeError::invalid("Keyframe interval must be at least 1")));
+        }
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        out.write_all(&(initial.width() as u64).to_le_bytes())?;
+        out.write_all(&(initial.height() as u64).to_le_bytes())?;
+        out.write_all(&keyframe_interval.to_le_bytes())?;
+        
+        let mut recorder = Self {
+            out,
+            keyframe_interval,
+            since_keyframe: 0,
+            previous: BitGrid::from_grid(initial),
+            generation,
+            toggled: Vec::new(),
+        };
+        recorder.write_keyframe()?;
+        Ok(recorder)
+    }
+    
+    /// Generation of the most recent record
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+    
+    /// Record `grid` as the state at `generation`
+    ///
+    /// Fails without writing anything if the grid size changed or `generation`
+    /// does not increase.
+    pub fn record(&mut self, grid: &dyn Grid, generation: usize) -> Result<(), FormatError> {
+        if grid.width() != self.previous.width() || grid.height() != self.previous.height() {
+            let expected = (self.previous.width(), self.previous.height());
+            return Err(FormatError::rejected(GameOfLifeError::dimensions(expected, (grid.width(), grid.height()))));
+        }
+        if generation <= self.generation {
+            let message = format!("Replay generations must increase ({} after {})", generation, self.generation);
+            return Err(FormatError::rejected(GameOfLifeError::invalid(message)));
+        }
+        
+        let current = BitGrid::from_grid(grid);
+        self.generation = generation;
+        self.since_keyframe += 1;
+        if self.since_keyframe >= self.keyframe_interval {
+            self.previous = current;
+            return self.write_keyframe();
+        }
+        
+        self.toggled.clear();
+        let width = current.width() as u64;
+        let words_per_row = current.words_per_row();
+        for (index, (&old, &new)) in self.previous.words().iter().zip(current.words()).enumerate() {
+            let mut changed = old ^ new;
+            let (row, word) = ((index / words_per_row) as u64, (index % words_per_row) as u64);
+            // Columns are stored most significant bit first
+            while changed != 0 {
+                let offset = changed.leading_zeros();
+                self.toggled.push(row * width + word * 64 + offset as u64);
+                changed &= !(1 << (63 - offset));
+            }
+        }
+        
+        self.out.write_all(&[TAG_DELTA])?;
+        write_varint(&mut self.out, generation as u64)?;
+        write_varint(&mut self.out, self.toggled.len() as u64)?;
+        let mut last = 0;
+        for &index in &self.toggled {
+            write_varint(&mut self.out, index - last)?;
+            last = index;
+        }
+        self.previous = current;
+        Ok(())
+    }
+    
+    /// Record the engine's current grid at its current generation
+    ///
+    /// The grid is read through a snapshot, so engines such as `UltimateEngine`
+    /// that do not expose a grid can be recorded too.
+    pub fn record_engine(&mut self, engine: &dyn GameOfLifeEngine) -> Result<(), FormatError> {
+        self.record(&engine.snapshot().grid, engine.generation())
+    }
+    
+    /// Flush and return the underlying writer
+    pub fn finish(mut self) -> Result<W, FormatError> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+    
+    fn write_keyframe(&mut self) -> Result<(), FormatError> {
+        self.since_keyframe = 0;
+        self.out.write_all(&[TAG_KEYFRAME])?;
+        write_varint(&mut self.out, self.generation as u64)?;
+        for word in self.previous.words() {
+            self.out.write_all(&word.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
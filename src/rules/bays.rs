@@ -0,0 +1,126 @@
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Largest neighbor count in the 26-cell 3D Moore neighborhood
+pub const MAX_BAYS_NEIGHBORS: u8 = 26;
+
+/// Three-dimensional Life rule in Carter Bays' "EEFF" notation, e.g. 5766
+///
+/// A live cell survives with `survival_min..=survival_max` live neighbors and
+/// a dead cell is born with `birth_min..=birth_max`, out of its 26 neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BaysRule {
+    survival_min: u8,
+    survival_max: u8,
+    birth_min: u8,
+    birth_max: u8,
+}
+
+impl BaysRule {
+    /// Create a rule from its survival and birth neighbor count bounds
+    pub fn new(survival_min: u8, survival_max: u8, birth_min: u8, birth_max: u8) -> Result<Self, GameOfLifeError> {
+        for count in [survival_min, survival_max, birth_min, birth_max] {
+            if count > MAX_BAYS_NEIGHBORS {
+                return Err(GameOfLifeError::invalid(format!("Neighbor count {} out of range 0-{}", count, MAX_BAYS_NEIGHBORS)));
+            }
+        }
+        if survival_min > survival_max || birth_min > birth_max {
+            return Err(GameOfLifeError::invalid(format!(
+                "Empty range in rule {},{},{},{}: lower bounds must not exceed upper bounds",
+                survival_min, survival_max, birth_min, birth_max
+            )));
+        }
+        Ok(Self { survival_min, survival_max, birth_min, birth_max })
+    }
+    
+    /// Bays' Life 5766
+    pub const fn life_5766() -> Self {
+        Self { survival_min: 5, survival_max: 7, birth_min: 6, birth_max: 6 }
+    }
+    
+    /// Bays' Life 4555
+    pub const fn life_4555() -> Self {
+        Self { survival_min: 4, survival_max: 5, birth_min: 5, birth_max: 5 }
+    }
+    
+    /// Whether a dead cell with `neighbors` live neighbors is born
+    #[inline(always)]
+    pub fn births_on(&self, neighbors: u8) -> bool {
+        (self.birth_min..=self.birth_max).contains(&neighbors)
+    }
+    
+    /// Whether a live cell with `neighbors` live neighbors survives
+    #[inline(always)]
+    pub fn survives_on(&self, neighbors: u8) -> bool {
+        (self.survival_min..=self.survival_max).contains(&neighbors)
+    }
+    
+    /// Compute the next state of a cell
+    #[inline(always)]
+    pub fn next_state(&self, alive: bool, neighbors: u8) -> bool {
+        if alive {
+            self.survives_on(neighbors)
+        } else {
+            self.births_on(neighbors)
+        }
+    }
+}
+
+impl Default for BaysRule {
+    fn default() -> Self {
+        Self::life_5766()
+    }
+}
+
+impl FromStr for BaysRule {
+    type Err = GameOfLifeError;
+    
+    /// Parse "5766", or "5,7,6,6" when a bound needs two digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let counts: Option<Vec<u8>> = if trimmed.contains(',') {
+            trimmed.split(',').map(|count| count.trim().parse().ok()).collect()
+        } else {
+            trimmed.chars().map(|ch| ch.to_digit(10).map(|n| n as u8)).collect()
+        };
+        match counts.as_deref() {
+            Some(&[survival_min, survival_max, birth_min, birth_max]) => {
+                Self::new(survival_min, survival_max, birth_min, birth_max)
+            }
+            _ => Err(GameOfLifeError::parse(s, trimmed, format!("Invalid 3D rule '{}': expected four counts, e.g. 5766", trimmed))),
+        }
+    }
+}
+
+impl Display for BaysRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let counts = [self.survival_min, self.survival_max, self.birth_min, self.birth_max];
+        if counts.iter().all(|&count| count < 10) {
+            write!(f, "{}{}{}{}", counts[0], counts[1], counts[2], counts[3])
+        } else {
+            write!(f, "{},{},{},{}", counts[0], counts[1], counts[2], counts[3])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_and_format() {
+        let rule: BaysRule = "5766".parse().unwrap();
+        assert_eq!(rule, BaysRule::life_5766());
+        assert_eq!(rule.to_string(), "5766");
+        assert!(rule.survives_on(7) && !rule.survives_on(8));
+        assert!(rule.births_on(6) && !rule.births_on(5));
+        
+        let wide: BaysRule = "10,26,9,12".parse().unwrap();
+        assert_eq!(wide.to_string(), "10,26,9,12");
+        
+        assert!("576".parse::<BaysRule>().is_err());
+        assert!("7566".parse::<BaysRule>().is_err());
+        assert!("5,7,6,27".parse::<BaysRule>().is_err());
+    }
+}
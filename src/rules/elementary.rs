@@ -0,0 +1,93 @@
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Wolfram code for a one-dimensional, two-state, radius-1 cellular automaton
+///
+/// Bit `(left << 2) | (center << 1) | right` of the rule number gives the next
+/// state of a cell with that neighborhood, so rule 30 maps 100, 011, 010 and
+/// 001 to alive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ElementaryRule(u8);
+
+impl ElementaryRule {
+    /// Create a rule from its Wolfram number (0-255)
+    pub const fn new(number: u8) -> Self {
+        Self(number)
+    }
+    
+    /// Wolfram's rule 30, the classic chaotic rule
+    pub const fn rule_30() -> Self {
+        Self(30)
+    }
+    
+    /// Rule 110, which is Turing complete
+    pub const fn rule_110() -> Self {
+        Self(110)
+    }
+    
+    /// The Wolfram rule number
+    pub fn number(&self) -> u8 {
+        self.0
+    }
+    
+    /// Whether a cell with the given left/center/right neighborhood is alive next generation
+    #[inline(always)]
+    pub fn next_state(&self, left: bool, center: bool, right: bool) -> bool {
+        let pattern = ((left as u8) << 2) | ((center as u8) << 1) | right as u8;
+        self.0 & (1 << pattern) != 0
+    }
+}
+
+impl From<u8> for ElementaryRule {
+    fn from(number: u8) -> Self {
+        Self(number)
+    }
+}
+
+impl FromStr for ElementaryRule {
+    type Err = GameOfLifeError;
+    
+    /// Parse "30", "W30" (Golly) or "Rule 30"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let lower = trimmed.to_ascii_lowercase();
+        let digits = lower
+            .strip_prefix("rule")
+            .or_else(|| lower.strip_prefix('w'))
+            .unwrap_or(&lower)
+            .trim();
+        digits
+            .parse::<u8>()
+            .map(Self)
+            .map_err(|_| {
+                GameOfLifeError::parse(s, trimmed, format!("Invalid elementary rule '{}': expected a number 0-255, e.g. W30", trimmed))
+            })
+    }
+}
+
+impl Display for ElementaryRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "W{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_and_next_state() {
+        let rule: ElementaryRule = "W30".parse().unwrap();
+        assert_eq!(rule, ElementaryRule::rule_30());
+        assert_eq!("Rule 110".parse::<ElementaryRule>().unwrap(), ElementaryRule::rule_110());
+        assert_eq!(rule.to_string(), "W30");
+        assert!("W256".parse::<ElementaryRule>().is_err());
+        
+        // 30 = 0b00011110
+        assert!(!rule.next_state(true, true, true));
+        assert!(rule.next_state(true, false, false));
+        assert!(rule.next_state(false, true, true));
+        assert!(!rule.next_state(false, false, false));
+    }
+}
@@ -0,0 +1,152 @@
+use super::life::parse_counts;
+use super::{NeighborhoodKind, Rule};
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Generations rule: Life-like birth/survival plus decay states
+///
+/// State 0 is dead and state 1 is alive. A live cell that fails to survive
+/// enters state 2 and then counts up through the remaining decay states
+/// before dying; decaying cells neither count as neighbors nor can be reborn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenerationsRule {
+    life: Rule,
+    states: u8,
+}
+
+impl GenerationsRule {
+    /// Create a rule from a Life-like birth/survival rule and a state count (2-255)
+    pub fn new(life: Rule, states: u8) -> Result<Self, GameOfLifeError> {
+        if states < 2 {
+            return Err(GameOfLifeError::invalid(format!("Generations rules need at least 2 states, got {}", states)));
+        }
+        Ok(Self { life, states })
+    }
+    
+    /// Brian's Brain (/2/3)
+    pub fn brians_brain() -> Self {
+        Self {
+            life: Rule::from_masks(1 << 2, 0),
+            states: 3,
+        }
+    }
+    
+    /// Star Wars (345/2/4)
+    pub fn star_wars() -> Self {
+        Self {
+            life: Rule::from_masks(1 << 2, (1 << 3) | (1 << 4) | (1 << 5)),
+            states: 4,
+        }
+    }
+    
+    /// Birth/survival part of the rule
+    pub fn life_rule(&self) -> Rule {
+        self.life
+    }
+    
+    /// Total number of states, including dead and alive
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+    
+    /// Compute the next state of a cell from its state and its number of live neighbors
+    #[inline(always)]
+    pub fn next_state(&self, state: u8, live_neighbors: u8) -> u8 {
+        match state {
+            0 => self.life.births_on(live_neighbors) as u8,
+            1 if self.life.survives_on(live_neighbors) => 1,
+            _ => (state + 1) % self.states,
+        }
+    }
+}
+
+impl From<Rule> for GenerationsRule {
+    fn from(life: Rule) -> Self {
+        Self { life, states: 2 }
+    }
+}
+
+impl FromStr for GenerationsRule {
+    type Err = GameOfLifeError;
+    
+    /// Parse "S/B/C" notation such as "345/2/4" or "/2/3", or "B2/S345/C4"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let parts: Vec<&str> = s.split('/').collect();
+        if parts.len() != 3 && parts.len() != 4 {
+            return Err(GameOfLifeError::parse(s, s, format!("Invalid Generations rule '{}': expected three sections, e.g. 345/2/4", s)));
+        }
+        
+        let parse_states = |digits: &str| -> Result<u8, GameOfLifeError> {
+            digits
+                .parse::<u8>()
+                .map_err(|_| GameOfLifeError::parse(s, digits, format!("Invalid state count '{}' in rule '{}'", digits, s)))
+        };
+        
+        let numeric = parts.iter().all(|part| part.chars().all(|ch| ch.is_ascii_digit()));
+        if numeric && parts.len() == 3 {
+            let life = Rule::new(&parse_counts(parts[1], s)?, &parse_counts(parts[0], s)?)?;
+            return Self::new(life, parse_states(parts[2])?);
+        }
+        
+        let (mut birth, mut survival, mut states, mut neighborhood) = (None, None, None, None);
+        for part in parts {
+            let mut chars = part.chars();
+            match chars.next().map(|ch| ch.to_ascii_uppercase()) {
+                Some('B') if birth.is_none() => birth = Some(parse_counts(chars.as_str(), s)?),
+                Some('S') if survival.is_none() => survival = Some(parse_counts(chars.as_str(), s)?),
+                Some('C') | Some('G') if states.is_none() => states = Some(parse_states(chars.as_str())?),
+                _ => match part.parse::<NeighborhoodKind>() {
+                    Ok(kind) if neighborhood.is_none() => neighborhood = Some(kind),
+                    _ => return Err(GameOfLifeError::parse(s, part, format!("Invalid section '{}' in Generations rule '{}'", part, s))),
+                },
+            }
+        }
+        match (birth, survival, states) {
+            (Some(birth), Some(survival), Some(states)) => {
+                let life = Rule::with_neighborhood(&birth, &survival, neighborhood.unwrap_or_default())?;
+                Self::new(life, states)
+            }
+            _ => Err(GameOfLifeError::parse(s, s, format!("Invalid Generations rule '{}': expected B, S and C sections", s))),
+        }
+    }
+}
+
+impl Display for GenerationsRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/C{}", self.life, self.states)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_notations() {
+        let brain: GenerationsRule = "/2/3".parse().unwrap();
+        assert_eq!(brain, GenerationsRule::brians_brain());
+        assert_eq!(brain.to_string(), "B2/S/C3");
+        
+        let star_wars: GenerationsRule = "B2/S345/C4".parse().unwrap();
+        assert_eq!(star_wars, GenerationsRule::star_wars());
+        assert_eq!("345/2/4".parse::<GenerationsRule>().unwrap(), star_wars);
+        
+        assert!("345/2/1".parse::<GenerationsRule>().is_err());
+        assert!("B3/S23".parse::<GenerationsRule>().is_err());
+        
+        let von_neumann: GenerationsRule = "B2/S/V/C3".parse().unwrap();
+        assert_eq!(von_neumann.to_string(), "B2/S/V/C3");
+    }
+    
+    #[test]
+    fn test_decay() {
+        let rule = GenerationsRule::star_wars();
+        assert_eq!(rule.next_state(0, 2), 1);
+        assert_eq!(rule.next_state(1, 3), 1);
+        assert_eq!(rule.next_state(1, 1), 2);
+        assert_eq!(rule.next_state(2, 2), 3);
+        assert_eq!(rule.next_state(3, 2), 0);
+    }
+}
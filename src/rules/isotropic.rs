@@ -0,0 +1,306 @@
+use super::{NeighborhoodKind, Rule};
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Hensel letters for each neighbor count, with one representative neighborhood each
+///
+/// Neighborhoods are 8-bit masks with NW as the most significant bit, then
+/// N, NE, W, E, SW, S and SE. Every other neighborhood sharing a letter is a
+/// rotation or reflection of the representative.
+const HENSEL_LETTERS: [&[(char, u8)]; 9] = [
+    &[],
+    &[('c', 0x01), ('e', 0x02)],
+    &[('c', 0x05), ('e', 0x0a), ('k', 0x0c), ('a', 0x03), ('i', 0x18), ('n', 0x24)],
+    &[
+        ('c', 0x25), ('e', 0x1a), ('k', 0x32), ('a', 0x0b), ('i', 0x07),
+        ('n', 0x0d), ('y', 0x31), ('q', 0x26), ('j', 0x0e), ('r', 0x19),
+    ],
+    &[
+        ('c', 0xa5), ('e', 0x5a), ('k', 0x33), ('a', 0x0f), ('i', 0x1d), ('n', 0x27), ('y', 0x35),
+        ('q', 0x36), ('j', 0x3a), ('r', 0x1b), ('t', 0x39), ('w', 0x2e), ('z', 0x3c),
+    ],
+    &[
+        ('c', 0x5b), ('e', 0xa7), ('k', 0x75), ('a', 0x2f), ('i', 0x1f),
+        ('n', 0x3b), ('y', 0x5d), ('q', 0x3e), ('j', 0x37), ('r', 0x3d),
+    ],
+    &[('c', 0x5f), ('e', 0xaf), ('k', 0x77), ('a', 0x3f), ('i', 0xbd), ('n', 0x7e)],
+    &[('c', 0x7f), ('e', 0xbf)],
+    &[],
+];
+
+/// Neighbor offsets (row, col) for bits 7 down to 0 of a neighborhood mask
+const NEIGHBOR_OFFSETS: [(i8, i8); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Apply one of the 8 symmetries of the square to a neighborhood mask
+fn transform_neighborhood(mask: u8, symmetry: u8) -> u8 {
+    let mut result = 0;
+    for (i, &(row, col)) in NEIGHBOR_OFFSETS.iter().enumerate() {
+        if mask & (0x80 >> i) == 0 {
+            continue;
+        }
+        let (mut r, mut c) = (row, col);
+        for _ in 0..symmetry % 4 {
+            (r, c) = (c, -r);
+        }
+        if symmetry >= 4 {
+            c = -c;
+        }
+        let j = NEIGHBOR_OFFSETS.iter().position(|&offset| offset == (r, c)).unwrap();
+        result |= 0x80 >> j;
+    }
+    result
+}
+
+/// All neighborhoods described by a Hensel letter for the given count
+fn letter_neighborhoods(count: usize, letter: char) -> Option<Vec<u8>> {
+    let &(_, representative) = HENSEL_LETTERS[count].iter().find(|&&(l, _)| l == letter)?;
+    let mut masks: Vec<u8> = (0..8).map(|symmetry| transform_neighborhood(representative, symmetry)).collect();
+    masks.sort_unstable();
+    masks.dedup();
+    Some(masks)
+}
+
+/// Arbitrary 2-state rule on the 3x3 Moore neighborhood, as a 512-entry lookup table
+///
+/// The table index weights the cells NW=256, N=128, NE=64, W=32, C=16, E=8,
+/// SW=4, S=2, SE=1, matching Golly's MAP rule strings. Parsing accepts MAP
+/// strings, isotropic non-totalistic (Hensel) rulestrings such as "B2-a/S12",
+/// and plain B/S rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MapRule {
+    table: [u64; 8],
+}
+
+impl MapRule {
+    /// Build a rule from a function of the 9-bit neighborhood index
+    pub fn from_fn(mut f: impl FnMut(u16) -> bool) -> Self {
+        let mut table = [0u64; 8];
+        for index in 0..512u16 {
+            if f(index) {
+                table[index as usize / 64] |= 1 << (index % 64);
+            }
+        }
+        Self { table }
+    }
+    
+    /// Build a rule from birth and survival sets of 8-bit neighbor masks (NW most significant)
+    fn from_neighborhoods(birth: &[bool; 256], survival: &[bool; 256]) -> Self {
+        Self::from_fn(|index| {
+            let (center, neighbors) = split_index(index);
+            if center { survival[neighbors as usize] } else { birth[neighbors as usize] }
+        })
+    }
+    
+    /// Next state of the center cell for a 9-bit neighborhood index
+    #[inline(always)]
+    pub fn next_state(&self, index: u16) -> bool {
+        self.table[index as usize / 64] & (1 << (index % 64)) != 0
+    }
+    
+    /// Equivalent Life-like rule, if the table only depends on neighbor counts
+    pub fn as_life_rule(&self) -> Option<Rule> {
+        let mut birth = 0u32;
+        let mut survival = 0u32;
+        for index in 0..512u16 {
+            let (center, neighbors) = split_index(index);
+            if self.next_state(index) {
+                let count = neighbors.count_ones();
+                if center { survival |= 1 << count } else { birth |= 1 << count }
+            }
+        }
+        let rule = Rule::from_masks(birth, survival);
+        Self::try_from(rule).is_ok_and(|map| map == *self).then_some(rule)
+    }
+    
+    /// Parse a Golly MAP string ("MAP" followed by 512 bits in base64)
+    fn parse_map(s: &str, data: &str) -> Result<Self, GameOfLifeError> {
+        let data = data.trim_end_matches('=');
+        let mut bits = Vec::with_capacity(data.len() * 6);
+        for (i, ch) in data.char_indices() {
+            let value = BASE64.iter().position(|&b| b as char == ch).ok_or_else(|| {
+                let fragment = &data[i..i + ch.len_utf8()];
+                GameOfLifeError::parse(s, fragment, format!("Invalid base64 character '{}' in MAP rule '{}'", ch, s))
+            })?;
+            for shift in (0..6).rev() {
+                bits.push((value >> shift) & 1 == 1);
+            }
+        }
+        if bits.len() < 512 {
+            return Err(GameOfLifeError::parse(s, s, format!("MAP rule '{}' is too short: expected 512 bits, got {}", s, bits.len())));
+        }
+        Ok(Self::from_fn(|index| bits[index as usize]))
+    }
+    
+    /// Parse one B or S section of a Hensel rulestring into a set of neighborhoods
+    fn parse_hensel_section(s: &str, section: &str) -> Result<[bool; 256], GameOfLifeError> {
+        let mut set = [false; 256];
+        let mut chars = section.char_indices().peekable();
+        let at = |i: usize, ch: char| &section[i..i + ch.len_utf8()];
+        
+        while let Some((start, ch)) = chars.next() {
+            let count = ch
+                .to_digit(10)
+                .filter(|&n| n <= 8)
+                .ok_or_else(|| GameOfLifeError::parse(s, at(start, ch), format!("Invalid neighbor count '{}' in rule '{}'", ch, s)))?
+                as usize;
+            
+            let negate = chars.next_if(|&(_, c)| c == '-').is_some();
+            let mut letters = Vec::new();
+            while let Some(letter) = chars.next_if(|(_, c)| c.is_ascii_lowercase()) {
+                letters.push(letter);
+            }
+            if negate && letters.is_empty() {
+                return Err(GameOfLifeError::parse(s, at(start, ch), format!("Expected letters after '{}-' in rule '{}'", count, s)));
+            }
+            
+            let mut chosen = [false; 256];
+            for &(i, letter) in &letters {
+                for mask in letter_neighborhoods(count, letter).ok_or_else(|| {
+                    GameOfLifeError::parse(s, at(i, letter), format!("Invalid letter '{}' for count {} in rule '{}'", letter, count, s))
+                })? {
+                    chosen[mask as usize] = true;
+                }
+            }
+            for mask in 0..256usize {
+                if mask.count_ones() as usize == count && (letters.is_empty() || chosen[mask] != negate) {
+                    set[mask] = true;
+                }
+            }
+        }
+        Ok(set)
+    }
+    
+    /// Parse an isotropic non-totalistic rulestring such as "B2-a/S12"
+    fn parse_hensel(s: &str) -> Result<Self, GameOfLifeError> {
+        let (mut birth, mut survival) = (None, None);
+        for part in s.split('/') {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some('B') | Some('b') if birth.is_none() => birth = Some(Self::parse_hensel_section(s, chars.as_str())?),
+                Some('S') | Some('s') if survival.is_none() => survival = Some(Self::parse_hensel_section(s, chars.as_str())?),
+                _ => return Err(GameOfLifeError::parse(s, part, format!("Invalid rule '{}': expected B and S sections, e.g. B2-a/S12", s))),
+            }
+        }
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(Self::from_neighborhoods(&birth, &survival)),
+            _ => Err(GameOfLifeError::parse(s, s, format!("Invalid rule '{}': expected B and S sections, e.g. B2-a/S12", s))),
+        }
+    }
+}
+
+/// Split a 9-bit index into the center state and an 8-bit neighbor mask (NW most significant)
+fn split_index(index: u16) -> (bool, u8) {
+    let center = index & 0x10 != 0;
+    let neighbors = ((index >> 5) << 4) as u8 | (index & 0x0f) as u8;
+    (center, neighbors)
+}
+
+/// Neighbor mask bits of the orthogonal neighbors N, W, E and S
+const ORTHOGONAL_NEIGHBORS: u8 = 0b0101_1010;
+
+impl TryFrom<Rule> for MapRule {
+    type Error = GameOfLifeError;
+    
+    /// Tabulate a Life-like rule; only neighborhoods within the 3x3 square fit in a MAP
+    fn try_from(rule: Rule) -> Result<Self, Self::Error> {
+        let mask = match rule.neighborhood() {
+            NeighborhoodKind::Moore => 0xff,
+            NeighborhoodKind::VonNeumann => ORTHOGONAL_NEIGHBORS,
+            NeighborhoodKind::MooreRadius2 => {
+                return Err(GameOfLifeError::unsupported("MAP rules", format!("rule {}, which does not fit in a 3x3 neighborhood", rule)));
+            }
+        };
+        Ok(Self::from_fn(|index| {
+            let (center, neighbors) = split_index(index);
+            rule.next_state(center, (neighbors & mask).count_ones() as u8)
+        }))
+    }
+}
+
+impl FromStr for MapRule {
+    type Err = GameOfLifeError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.strip_prefix("MAP") {
+            Some(data) => Self::parse_map(s, data),
+            None => Self::parse_hensel(s),
+        }
+    }
+}
+
+impl Display for MapRule {
+    /// Format as a Golly MAP string
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut encoded = String::from("MAP");
+        for chunk in 0..86 {
+            let mut value = 0;
+            for bit in 0..6 {
+                let index = chunk * 6 + bit;
+                value <<= 1;
+                if index < 512 && self.next_state(index as u16) {
+                    value |= 1;
+                }
+            }
+            encoded.push(BASE64[value] as char);
+        }
+        write!(f, "{}", encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const CONWAY_MAP: &str = "MAPARYXfhZofugWaH7oaIDogBZofuhogOiAaIDogIAAgAAWaH7oaIDogGiA6ICAAIAAaIDogIAAgACAAIAAAAAAAA";
+    
+    #[test]
+    fn test_letters_partition_neighborhoods() {
+        let mut seen = [0u8; 256];
+        for (count, letters) in HENSEL_LETTERS.iter().enumerate() {
+            for &(letter, _) in letters.iter() {
+                for mask in letter_neighborhoods(count, letter).unwrap() {
+                    assert_eq!(mask.count_ones() as usize, count);
+                    seen[mask as usize] += 1;
+                }
+            }
+        }
+        // Counts 0 and 8 have a single neighborhood and no letters
+        assert_eq!(seen[0x00], 0);
+        assert_eq!(seen[0xff], 0);
+        assert!(seen[1..255].iter().all(|&n| n == 1));
+    }
+    
+    #[test]
+    fn test_totalistic_equivalents() {
+        let conway = MapRule::try_from(Rule::conway()).unwrap();
+        assert_eq!("B3/S23".parse::<MapRule>().unwrap(), conway);
+        assert_eq!(CONWAY_MAP.parse::<MapRule>().unwrap(), conway);
+        assert_eq!(conway.to_string(), CONWAY_MAP);
+        assert_eq!(conway.as_life_rule(), Some(Rule::conway()));
+    }
+    
+    #[test]
+    fn test_non_totalistic() {
+        let rule: MapRule = "B2-a/S12".parse().unwrap();
+        assert_eq!(rule.as_life_rule(), None);
+        
+        // Dead center, neighbors N and NE (2a): not born
+        assert!(!rule.next_state(0b011_000_000));
+        // Dead center, neighbors N and S (2i): born
+        assert!(rule.next_state(0b010_000_010));
+        // Live center with one neighbor survives
+        assert!(rule.next_state(0b000_011_000));
+        
+        let rule: MapRule = "B3ai/S23-k".parse().unwrap();
+        assert!(rule.next_state(0b111_000_000)); // 3i
+        assert!(!rule.next_state(0b101_000_100)); // 3c
+        
+        assert!("B2z/S23".parse::<MapRule>().is_err());
+        assert!("B2-/S23".parse::<MapRule>().is_err());
+        assert!("MAPAB".parse::<MapRule>().is_err());
+    }
+}
@@ -0,0 +1,246 @@
+use super::NeighborhoodKind;
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Outer-totalistic Life-like rule in B/S notation
+///
+/// Bit `n` of the birth mask is set when a dead cell with `n` live neighbors
+/// is born; bit `n` of the survival mask is set when a live cell with `n` live
+/// neighbors survives. Neighbors are counted over the rule's neighborhood.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: u32,
+    survival: u32,
+    neighborhood: NeighborhoodKind,
+}
+
+impl Rule {
+    /// Conway's Game of Life (B3/S23)
+    pub const fn conway() -> Self {
+        Self {
+            birth: 1 << 3,
+            survival: (1 << 2) | (1 << 3),
+            neighborhood: NeighborhoodKind::Moore,
+        }
+    }
+    
+    /// Create a Moore-neighborhood rule from birth and survival neighbor counts
+    pub fn new(birth: &[u8], survival: &[u8]) -> Result<Self, GameOfLifeError> {
+        Self::with_neighborhood(birth, survival, NeighborhoodKind::Moore)
+    }
+    
+    /// Create a rule from birth and survival neighbor counts over the given neighborhood
+    pub fn with_neighborhood(birth: &[u8], survival: &[u8], neighborhood: NeighborhoodKind) -> Result<Self, GameOfLifeError> {
+        let max = neighborhood.size();
+        let mut rule = Self { birth: 0, survival: 0, neighborhood };
+        for &count in birth {
+            if count > max {
+                return Err(GameOfLifeError::invalid(format!("Birth count {} out of range 0-{}", count, max)));
+            }
+            rule.birth |= 1 << count;
+        }
+        for &count in survival {
+            if count > max {
+                return Err(GameOfLifeError::invalid(format!("Survival count {} out of range 0-{}", count, max)));
+            }
+            rule.survival |= 1 << count;
+        }
+        Ok(rule)
+    }
+    
+    /// Create a Moore-neighborhood rule from raw 9-bit birth and survival masks
+    pub const fn from_masks(birth: u32, survival: u32) -> Self {
+        Self {
+            birth: birth & 0x1FF,
+            survival: survival & 0x1FF,
+            neighborhood: NeighborhoodKind::Moore,
+        }
+    }
+    
+    /// Bit mask of neighbor counts that cause a birth
+    pub fn birth_mask(&self) -> u32 {
+        self.birth
+    }
+    
+    /// Bit mask of neighbor counts that allow survival
+    pub fn survival_mask(&self) -> u32 {
+        self.survival
+    }
+    
+    /// Neighborhood over which live neighbors are counted
+    pub fn neighborhood(&self) -> NeighborhoodKind {
+        self.neighborhood
+    }
+    
+    /// Whether a dead cell with `neighbors` live neighbors is born
+    #[inline(always)]
+    pub fn births_on(&self, neighbors: u8) -> bool {
+        self.birth & (1 << neighbors) != 0
+    }
+    
+    /// Whether a live cell with `neighbors` live neighbors survives
+    #[inline(always)]
+    pub fn survives_on(&self, neighbors: u8) -> bool {
+        self.survival & (1 << neighbors) != 0
+    }
+    
+    /// Compute the next state of a cell
+    #[inline(always)]
+    pub fn next_state(&self, alive: bool, neighbors: u8) -> bool {
+        if alive {
+            self.survives_on(neighbors)
+        } else {
+            self.births_on(neighbors)
+        }
+    }
+    
+    /// Whether this is standard B3/S23 Life
+    pub fn is_conway(&self) -> bool {
+        *self == Self::conway()
+    }
+    
+    /// Error unless the rule uses the Moore neighborhood, for code paths hard-wired to it
+    pub(crate) fn require_moore(&self, context: &str) -> Result<(), GameOfLifeError> {
+        if self.neighborhood == NeighborhoodKind::Moore {
+            Ok(())
+        } else {
+            Err(GameOfLifeError::unsupported(context, format!("the non-Moore rule {}", self)))
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+/// Parse the neighbor counts of a B or S section
+///
+/// Counts are single digits ("23") unless the section contains commas, which
+/// allows counts of 10 or more for larger neighborhoods ("3,12,13").
+pub(crate) fn parse_counts(section: &str, rule: &str) -> Result<Vec<u8>, GameOfLifeError> {
+    let invalid = |count: &str| {
+        GameOfLifeError::parse(rule, count, format!("Invalid neighbor count '{}' in rule '{}'", count, rule))
+    };
+    if section.contains(',') {
+        section
+            .split(',')
+            .map(|count| count.trim().parse::<u8>().map_err(|_| invalid(count)))
+            .collect()
+    } else {
+        section
+            .char_indices()
+            .map(|(i, ch)| ch.to_digit(10).map(|n| n as u8).ok_or_else(|| invalid(&section[i..i + ch.len_utf8()])))
+            .collect()
+    }
+}
+
+impl FromStr for Rule {
+    type Err = GameOfLifeError;
+    
+    /// Parse a rulestring such as "B3/S23", "b36/s23", "S23/B3" or "B2/S/V"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut birth = None;
+        let mut survival = None;
+        let mut neighborhood = None;
+        
+        for part in s.trim().split('/') {
+            let mut chars = part.chars();
+            let target = match chars.next() {
+                Some('B') | Some('b') => &mut birth,
+                Some('S') | Some('s') => &mut survival,
+                _ if neighborhood.is_none() && birth.is_some() && survival.is_some() => {
+                    neighborhood = Some(part.parse::<NeighborhoodKind>()?);
+                    continue;
+                }
+                _ => {
+                    return Err(GameOfLifeError::parse(s, part, format!("Invalid rule '{}': expected B and S sections, e.g. B3/S23", s)))
+                }
+            };
+            if target.is_some() {
+                return Err(GameOfLifeError::parse(s, part, format!("Invalid rule '{}': duplicate section '{}'", s, part)));
+            }
+            *target = Some(parse_counts(chars.as_str(), s)?);
+        }
+        
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Self::with_neighborhood(&birth, &survival, neighborhood.unwrap_or_default()),
+            _ => Err(GameOfLifeError::parse(s, s, format!("Invalid rule '{}': expected B and S sections, e.g. B3/S23", s))),
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let max = self.neighborhood.size();
+        let separator = if (self.birth | self.survival) >> 10 != 0 { "," } else { "" };
+        let write_counts = |f: &mut Formatter<'_>, mask: u32| -> std::fmt::Result {
+            let counts: Vec<String> = (0..=max).filter(|&n| mask & (1 << n) != 0).map(|n| n.to_string()).collect();
+            write!(f, "{}", counts.join(separator))
+        };
+        
+        write!(f, "B")?;
+        write_counts(f, self.birth)?;
+        write!(f, "/S")?;
+        write_counts(f, self.survival)?;
+        if self.neighborhood != NeighborhoodKind::Moore {
+            write!(f, "/{}", self.neighborhood)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_and_format() {
+        let rule: Rule = "B3/S23".parse().unwrap();
+        assert!(rule.is_conway());
+        assert_eq!(rule.to_string(), "B3/S23");
+        
+        let highlife: Rule = "s23/b36".parse().unwrap();
+        assert_eq!(highlife.to_string(), "B36/S23");
+        
+        let seeds: Rule = "B2/S".parse().unwrap();
+        assert_eq!(seeds.survival_mask(), 0);
+        assert_eq!("B3678/S34678".parse::<Rule>().unwrap().to_string(), "B3678/S34678");
+    }
+    
+    #[test]
+    fn test_parse_errors() {
+        assert!("B9/S23".parse::<Rule>().is_err());
+        assert!("B3".parse::<Rule>().is_err());
+        assert!("B3/B4".parse::<Rule>().is_err());
+        assert!("X3/S23".parse::<Rule>().is_err());
+        assert!(matches!("B3/S2x".parse::<Rule>(), Err(GameOfLifeError::Parse { line: 1, column: 6, .. })));
+        assert!(matches!("B3/B4".parse::<Rule>(), Err(GameOfLifeError::Parse { column: 4, .. })));
+    }
+    
+    #[test]
+    fn test_next_state() {
+        let rule = Rule::conway();
+        assert!(rule.next_state(true, 2));
+        assert!(rule.next_state(false, 3));
+        assert!(!rule.next_state(false, 2));
+        assert!(!rule.next_state(true, 4));
+    }
+    
+    #[test]
+    fn test_neighborhoods() {
+        let rule: Rule = "B2/S/V".parse().unwrap();
+        assert_eq!(rule.neighborhood(), NeighborhoodKind::VonNeumann);
+        assert_eq!(rule.to_string(), "B2/S/V");
+        assert!("B5/S4/V".parse::<Rule>().is_err());
+        
+        let wide: Rule = "B3,12/S2,3,24/M2".parse().unwrap();
+        assert!(wide.births_on(12));
+        assert!(wide.survives_on(24));
+        assert_eq!(wide.to_string(), "B3,12/S2,3,24/M2");
+        assert_eq!("B3/S23/M2".parse::<Rule>().unwrap().to_string(), "B3/S23/M2");
+        assert!(!"B3/S23/M2".parse::<Rule>().unwrap().is_conway());
+    }
+}
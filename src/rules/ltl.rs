@@ -0,0 +1,187 @@
+use super::{NeighborhoodKind, Rule};
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Largest neighborhood radius accepted by `LtlRule`
+pub const MAX_LTL_RANGE: usize = 500;
+
+/// Neighborhood shape for Larger than Life rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LtlNeighborhood {
+    /// Square of side 2R+1 (NM)
+    Moore,
+    /// Diamond of cells within Manhattan distance R (NN)
+    VonNeumann,
+}
+
+/// Larger than Life rule in Golly notation, e.g. "R5,C0,M1,S34..58,B34..45,NM" (Bosco's Rule)
+///
+/// Counts include the center cell when `include_center` is set (M1). With
+/// more than two states, cells that fail to survive decay like Generations rules.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LtlRule {
+    pub range: usize,
+    pub states: u8,
+    pub include_center: bool,
+    pub survival: RangeInclusive<u32>,
+    pub birth: RangeInclusive<u32>,
+    pub neighborhood: LtlNeighborhood,
+}
+
+impl LtlRule {
+    /// Bosco's Rule (R5,C0,M1,S34..58,B34..45,NM)
+    pub fn bosco() -> Self {
+        Self {
+            range: 5,
+            states: 2,
+            include_center: true,
+            survival: 34..=58,
+            birth: 34..=45,
+            neighborhood: LtlNeighborhood::Moore,
+        }
+    }
+    
+    /// Number of cells in the neighborhood, including the center if counted
+    pub fn neighborhood_size(&self) -> u32 {
+        let r = self.range as u32;
+        let cells = match self.neighborhood {
+            LtlNeighborhood::Moore => (2 * r + 1) * (2 * r + 1),
+            LtlNeighborhood::VonNeumann => 2 * r * (r + 1) + 1,
+        };
+        if self.include_center { cells } else { cells - 1 }
+    }
+    
+    /// Equivalent Life-like rule, for two-state range-1 rules and range-2 Moore rules
+    pub fn as_life_rule(&self) -> Option<Rule> {
+        let neighborhood = match (self.range, self.neighborhood) {
+            (1, LtlNeighborhood::Moore) => NeighborhoodKind::Moore,
+            (1, LtlNeighborhood::VonNeumann) => NeighborhoodKind::VonNeumann,
+            (2, LtlNeighborhood::Moore) => NeighborhoodKind::MooreRadius2,
+            _ => return None,
+        };
+        if self.states != 2 {
+            return None;
+        }
+        // With M1 a live cell counts itself, so survival counts shift by one
+        let offset = self.include_center as u32;
+        let max = neighborhood.size() as u32;
+        let birth: Vec<u8> = (0..=max).filter(|&n| self.birth.contains(&n)).map(|n| n as u8).collect();
+        let survival: Vec<u8> = (0..=max).filter(|&n| self.survival.contains(&(n + offset))).map(|n| n as u8).collect();
+        Rule::with_neighborhood(&birth, &survival, neighborhood).ok()
+    }
+    
+    /// Compute the next state of a cell from its state and live neighborhood count
+    #[inline(always)]
+    pub fn next_state(&self, state: u8, count: u32) -> u8 {
+        match state {
+            0 => self.birth.contains(&count) as u8,
+            1 if self.survival.contains(&count) => 1,
+            _ => (state + 1) % self.states,
+        }
+    }
+}
+
+impl FromStr for LtlRule {
+    type Err = GameOfLifeError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (mut range, mut states, mut middle) = (None, 0u8, false);
+        let (mut survival, mut birth, mut neighborhood) = (None, None, LtlNeighborhood::Moore);
+        
+        let error = |fragment: &str, message: String| GameOfLifeError::parse(s, fragment, message);
+        let parse_number = |text: &str| -> Result<u32, GameOfLifeError> {
+            text.parse::<u32>()
+                .map_err(|_| error(text, format!("Invalid number '{}' in Larger than Life rule '{}'", text, s)))
+        };
+        let parse_range = |text: &str| -> Result<RangeInclusive<u32>, GameOfLifeError> {
+            let (low, high) = text
+                .split_once("..")
+                .ok_or_else(|| error(text, format!("Invalid count range '{}' in rule '{}', expected e.g. 34..58", text, s)))?;
+            Ok(parse_number(low)?..=parse_number(high)?)
+        };
+        
+        for token in s.split(',') {
+            let token = token.trim();
+            let (key, value) = token.split_at(token.chars().next().map_or(0, char::len_utf8));
+            match key.to_ascii_uppercase().as_str() {
+                "R" => range = Some(parse_number(value)? as usize),
+                "C" => {
+                    states = u8::try_from(parse_number(value)?)
+                        .map_err(|_| error(value, format!("State count '{}' too large in rule '{}'", value, s)))?
+                }
+                "M" => middle = parse_number(value)? == 1,
+                "S" => survival = Some(parse_range(value)?),
+                "B" => birth = Some(parse_range(value)?),
+                "N" => {
+                    neighborhood = match value.to_ascii_uppercase().as_str() {
+                        "M" => LtlNeighborhood::Moore,
+                        "N" => LtlNeighborhood::VonNeumann,
+                        _ => return Err(error(token, format!("Unsupported neighborhood 'N{}' in rule '{}'", value, s))),
+                    }
+                }
+                _ => return Err(error(token, format!("Invalid token '{}' in Larger than Life rule '{}'", token, s))),
+            }
+        }
+        
+        let range = range.ok_or_else(|| error(s, format!("Larger than Life rule '{}' is missing R", s)))?;
+        if range == 0 || range > MAX_LTL_RANGE {
+            return Err(GameOfLifeError::invalid(format!("Range {} out of range 1-{}", range, MAX_LTL_RANGE)));
+        }
+        
+        Ok(Self {
+            range,
+            // C0 and C1 both mean a plain two-state rule
+            states: states.max(2),
+            include_center: middle,
+            survival: survival.ok_or_else(|| error(s, format!("Larger than Life rule '{}' is missing S", s)))?,
+            birth: birth.ok_or_else(|| error(s, format!("Larger than Life rule '{}' is missing B", s)))?,
+            neighborhood,
+        })
+    }
+}
+
+impl Display for LtlRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let states = if self.states == 2 { 0 } else { self.states };
+        let neighborhood = match self.neighborhood {
+            LtlNeighborhood::Moore => "NM",
+            LtlNeighborhood::VonNeumann => "NN",
+        };
+        write!(
+            f,
+            "R{},C{},M{},S{}..{},B{}..{},{}",
+            self.range, states, self.include_center as u8,
+            self.survival.start(), self.survival.end(),
+            self.birth.start(), self.birth.end(),
+            neighborhood
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_bosco() {
+        let rule: LtlRule = "R5,C0,M1,S34..58,B34..45,NM".parse().unwrap();
+        assert_eq!(rule, LtlRule::bosco());
+        assert_eq!(rule.to_string(), "R5,C0,M1,S34..58,B34..45,NM");
+        assert_eq!(rule.neighborhood_size(), 121);
+        assert_eq!(rule.as_life_rule(), None);
+        
+        let life: LtlRule = "R1,C0,M1,S3..4,B3..3,NM".parse().unwrap();
+        assert_eq!(life.as_life_rule(), Some(Rule::conway()));
+    }
+    
+    #[test]
+    fn test_parse_errors() {
+        assert!("C0,M1,S34..58,B34..45,NM".parse::<LtlRule>().is_err());
+        assert!("R5,C0,M1,S34-58,B34..45,NM".parse::<LtlRule>().is_err());
+        assert!("R5,C0,M1,S34..58,B34..45,NC".parse::<LtlRule>().is_err());
+        assert!("R0,C0,M1,S1..2,B1..2".parse::<LtlRule>().is_err());
+    }
+}
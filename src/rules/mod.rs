@@ -0,0 +1,21 @@
+//! Cellular automaton rule definitions
+
+pub mod bays;
+pub mod elementary;
+pub mod generations;
+pub mod isotropic;
+pub mod life;
+pub mod ltl;
+pub mod neighborhood;
+pub mod rulestring;
+pub mod stochastic;
+
+pub use bays::BaysRule;
+pub use elementary::ElementaryRule;
+pub use generations::GenerationsRule;
+pub use isotropic::MapRule;
+pub use life::Rule;
+pub use ltl::{LtlNeighborhood, LtlRule};
+pub use neighborhood::NeighborhoodKind;
+pub use rulestring::{RuleNotation, RuleString, RuleStringError};
+pub use stochastic::StochasticRule;
@@ -0,0 +1,80 @@
+//! Neighborhood shapes for Life-like rules
+
+use crate::error::GameOfLifeError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Offsets of the 8 cells touching the center
+const MOORE_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1),           (0, 1),
+    (1, -1),  (1, 0),  (1, 1),
+];
+
+/// Offsets of the 4 orthogonally adjacent cells
+const VON_NEUMANN_OFFSETS: [(isize, isize); 4] = [
+    (-1, 0),
+    (0, -1), (0, 1),
+    (1, 0),
+];
+
+/// Offsets of the 24 cells in the 5x5 square around the center
+const MOORE_RADIUS_2_OFFSETS: [(isize, isize); 24] = [
+    (-2, -2), (-2, -1), (-2, 0), (-2, 1), (-2, 2),
+    (-1, -2), (-1, -1), (-1, 0), (-1, 1), (-1, 2),
+    (0, -2),  (0, -1),           (0, 1),  (0, 2),
+    (1, -2),  (1, -1),  (1, 0),  (1, 1),  (1, 2),
+    (2, -2),  (2, -1),  (2, 0),  (2, 1),  (2, 2),
+];
+
+/// Which cells around the center count as neighbors for a Life-like rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NeighborhoodKind {
+    /// The 8 surrounding cells
+    #[default]
+    Moore,
+    /// The 4 orthogonally adjacent cells (V)
+    VonNeumann,
+    /// The 24 cells within Chebyshev distance 2 (M2)
+    MooreRadius2,
+}
+
+impl NeighborhoodKind {
+    /// Relative (row, col) offsets of the neighbors of a cell
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            NeighborhoodKind::Moore => &MOORE_OFFSETS,
+            NeighborhoodKind::VonNeumann => &VON_NEUMANN_OFFSETS,
+            NeighborhoodKind::MooreRadius2 => &MOORE_RADIUS_2_OFFSETS,
+        }
+    }
+    
+    /// Number of neighbors, which is also the largest possible neighbor count
+    pub fn size(self) -> u8 {
+        self.offsets().len() as u8
+    }
+}
+
+impl FromStr for NeighborhoodKind {
+    type Err = GameOfLifeError;
+    
+    /// Parse a rulestring neighborhood suffix: "M", "V" or "M2"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "M" => Ok(NeighborhoodKind::Moore),
+            "V" => Ok(NeighborhoodKind::VonNeumann),
+            "M2" => Ok(NeighborhoodKind::MooreRadius2),
+            _ => Err(GameOfLifeError::parse(s, s, format!("Unknown neighborhood '{}', expected M, V or M2", s))),
+        }
+    }
+}
+
+impl Display for NeighborhoodKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NeighborhoodKind::Moore => write!(f, "M"),
+            NeighborhoodKind::VonNeumann => write!(f, "V"),
+            NeighborhoodKind::MooreRadius2 => write!(f, "M2"),
+        }
+    }
+}
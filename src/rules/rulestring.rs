@@ -0,0 +1,253 @@
+//! Rulestring parsing, canonical formatting and notation conversion shared by all rule families
+
+use super::{GenerationsRule, LtlRule, MapRule, NeighborhoodKind, Rule};
+use crate::error::GameOfLifeError;
+use thiserror::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Notations a rulestring can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleNotation {
+    /// Birth first with section letters: "B3/S23", "B2/S345/C4"
+    BirthSurvival,
+    /// Classic survival-first digits: "23/3", "345/2/4"
+    SurvivalBirth,
+    /// Golly's canonical forms: "B3/S23", "B2/S3V", "345/2/4", "R5,C0,...", "MAP..."
+    Golly,
+}
+
+/// Error produced when a rulestring cannot be parsed or converted
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RuleStringError {
+    /// The rulestring was empty
+    #[error("Rulestring is empty")]
+    Empty,
+    /// The rulestring does not look like any supported rule family
+    #[error("Unrecognized rulestring '{0}'")]
+    UnrecognizedFormat(String),
+    /// The rulestring belongs to a known family but is malformed or out of range
+    #[error("{reason}")]
+    Invalid { rule: String, reason: String },
+    /// The rule is valid but uses a feature this crate does not simulate
+    #[error("Rule '{rule}' uses unsupported {feature}")]
+    Unsupported { rule: String, feature: &'static str },
+    /// The rule cannot be written in the requested notation
+    #[error("Rule '{rule}' cannot be written in {notation:?} notation")]
+    NotRepresentable { rule: String, notation: RuleNotation },
+}
+
+/// A parsed rulestring of any supported rule family, in canonical form
+///
+/// Parsing accepts B/S, S/B and Golly notations and normalizes the result:
+/// Generations rules with two states, isotropic rules that only depend on
+/// neighbor counts and Larger than Life rules with a Life-like equivalent all
+/// become `Life`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleString {
+    Life(Rule),
+    Generations(GenerationsRule),
+    LargerThanLife(LtlRule),
+    Isotropic(MapRule),
+}
+
+impl RuleString {
+    /// Parse and validate a rulestring in any supported notation
+    pub fn parse(s: &str) -> Result<Self, RuleStringError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(RuleStringError::Empty);
+        }
+        let invalid = |reason: GameOfLifeError| RuleStringError::Invalid { rule: s.to_string(), reason: reason.to_string() };
+        
+        if s.starts_with("MAP") {
+            return s.parse::<MapRule>().map(Self::from).map_err(invalid);
+        }
+        if s.starts_with(['R', 'r']) && s.contains(',') {
+            return s.parse::<LtlRule>().map(Self::from).map_err(invalid);
+        }
+        
+        // Golly appends the neighborhood to the last section: "B2/S34H", "B1/S012V"
+        let (body, golly_suffix) = match s.char_indices().last() {
+            Some((i, 'H' | 'h')) if s.contains('/') => (&s[..i], Some('H')),
+            Some((i, 'V' | 'v')) if s.contains('/') && !s.ends_with("/V") && !s.ends_with("/v") => (&s[..i], Some('V')),
+            _ => (s, None),
+        };
+        if golly_suffix == Some('H') {
+            return Err(RuleStringError::Unsupported { rule: s.to_string(), feature: "hexagonal neighborhoods in rulestrings" });
+        }
+        let normalized = match golly_suffix {
+            Some(suffix) => format!("{}/{}", body, suffix),
+            None => body.to_string(),
+        };
+        
+        let sections: Vec<&str> = normalized.split('/').collect();
+        let numeric = sections.iter().all(|section| section.chars().all(|ch| ch.is_ascii_digit()));
+        let lettered = |section: &str| section.starts_with(['B', 'b', 'S', 's']);
+        
+        if numeric && sections.len() == 2 {
+            // Classic S/B notation
+            let survival = super::life::parse_counts(sections[0], s).map_err(invalid)?;
+            let birth = super::life::parse_counts(sections[1], s).map_err(invalid)?;
+            return Rule::new(&birth, &survival).map(Self::Life).map_err(invalid);
+        }
+        if (numeric && sections.len() == 3) || sections.iter().any(|section| section.starts_with(['C', 'c', 'G', 'g'])) {
+            return normalized.parse::<GenerationsRule>().map(Self::from).map_err(invalid);
+        }
+        if !sections.iter().take(2).all(|section| lettered(section)) {
+            return Err(RuleStringError::UnrecognizedFormat(s.to_string()));
+        }
+        
+        // Letters after the counts are Hensel notation for isotropic non-totalistic rules
+        let hensel = sections
+            .iter()
+            .filter(|section| lettered(section))
+            .any(|section| section[1..].chars().any(|ch| ch.is_ascii_alphabetic() || ch == '-'));
+        if hensel {
+            return normalized.parse::<MapRule>().map(Self::from).map_err(invalid);
+        }
+        normalized.parse::<Rule>().map(Self::Life).map_err(invalid)
+    }
+    
+    /// The Life-like rule, if this rulestring describes one
+    pub fn life_rule(&self) -> Option<Rule> {
+        match self {
+            RuleString::Life(rule) => Some(*rule),
+            _ => None,
+        }
+    }
+    
+    /// Format the rule in the requested notation
+    pub fn format(&self, notation: RuleNotation) -> Result<String, RuleStringError> {
+        let not_representable = || RuleStringError::NotRepresentable { rule: self.to_string(), notation };
+        match (self, notation) {
+            (_, RuleNotation::BirthSurvival) => Ok(self.to_string()),
+            (RuleString::Life(rule), RuleNotation::SurvivalBirth) => {
+                if rule.neighborhood() != NeighborhoodKind::Moore {
+                    return Err(not_representable());
+                }
+                Ok(format!("{}/{}", counts(rule.survival_mask()), counts(rule.birth_mask())))
+            }
+            (RuleString::Life(rule), RuleNotation::Golly) => match rule.neighborhood() {
+                NeighborhoodKind::Moore => Ok(rule.to_string()),
+                NeighborhoodKind::VonNeumann => Ok(format!("B{}/S{}V", counts(rule.birth_mask()), counts(rule.survival_mask()))),
+                NeighborhoodKind::MooreRadius2 => Err(not_representable()),
+            },
+            (RuleString::Generations(rule), RuleNotation::SurvivalBirth | RuleNotation::Golly) => {
+                let life = rule.life_rule();
+                if life.neighborhood() != NeighborhoodKind::Moore {
+                    return Err(not_representable());
+                }
+                Ok(format!("{}/{}/{}", counts(life.survival_mask()), counts(life.birth_mask()), rule.states()))
+            }
+            (RuleString::LargerThanLife(_) | RuleString::Isotropic(_), RuleNotation::Golly) => Ok(self.to_string()),
+            (RuleString::LargerThanLife(_) | RuleString::Isotropic(_), RuleNotation::SurvivalBirth) => Err(not_representable()),
+        }
+    }
+}
+
+/// Digits of the neighbor counts set in a single-digit mask
+fn counts(mask: u32) -> String {
+    (0..=8).filter(|n| mask & (1 << n) != 0).map(|n| n.to_string()).collect()
+}
+
+impl From<Rule> for RuleString {
+    fn from(rule: Rule) -> Self {
+        RuleString::Life(rule)
+    }
+}
+
+impl From<GenerationsRule> for RuleString {
+    fn from(rule: GenerationsRule) -> Self {
+        if rule.states() == 2 {
+            RuleString::Life(rule.life_rule())
+        } else {
+            RuleString::Generations(rule)
+        }
+    }
+}
+
+impl From<LtlRule> for RuleString {
+    fn from(rule: LtlRule) -> Self {
+        match rule.as_life_rule() {
+            Some(life) => RuleString::Life(life),
+            None => RuleString::LargerThanLife(rule),
+        }
+    }
+}
+
+impl From<MapRule> for RuleString {
+    fn from(rule: MapRule) -> Self {
+        match rule.as_life_rule() {
+            Some(life) => RuleString::Life(life),
+            None => RuleString::Isotropic(rule),
+        }
+    }
+}
+
+impl FromStr for RuleString {
+    type Err = RuleStringError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for RuleString {
+    /// Canonical B/S-style formatting
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleString::Life(rule) => write!(f, "{}", rule),
+            RuleString::Generations(rule) => write!(f, "{}", rule),
+            RuleString::LargerThanLife(rule) => write!(f, "{}", rule),
+            RuleString::Isotropic(rule) => write!(f, "{}", rule),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn canonical(s: &str) -> String {
+        RuleString::parse(s).unwrap().to_string()
+    }
+    
+    #[test]
+    fn test_notations_normalize() {
+        assert_eq!(canonical("B3/S23"), "B3/S23");
+        assert_eq!(canonical("23/3"), "B3/S23");
+        assert_eq!(canonical("s23/b36"), "B36/S23");
+        assert_eq!(canonical("B1/S012V"), "B1/S012/V");
+        assert_eq!(canonical("345/2/4"), "B2/S345/C4");
+        assert_eq!(canonical("B3/S23/C2"), "B3/S23");
+        assert_eq!(canonical("R1,C0,M0,S2..3,B3..3,NM"), "B3/S23");
+        assert!(matches!(RuleString::parse("B2-a/S12").unwrap(), RuleString::Isotropic(_)));
+        assert_eq!(RuleString::parse("B3/S23").unwrap().life_rule(), Some(Rule::conway()));
+    }
+    
+    #[test]
+    fn test_convert_notations() {
+        let highlife = RuleString::parse("B36/S23").unwrap();
+        assert_eq!(highlife.format(RuleNotation::SurvivalBirth).unwrap(), "23/36");
+        assert_eq!(highlife.format(RuleNotation::Golly).unwrap(), "B36/S23");
+        
+        let star_wars = RuleString::parse("B2/S345/C4").unwrap();
+        assert_eq!(star_wars.format(RuleNotation::Golly).unwrap(), "345/2/4");
+        
+        let von_neumann = RuleString::parse("B1/S012/V").unwrap();
+        assert_eq!(von_neumann.format(RuleNotation::Golly).unwrap(), "B1/S012V");
+        assert!(matches!(
+            von_neumann.format(RuleNotation::SurvivalBirth),
+            Err(RuleStringError::NotRepresentable { .. })
+        ));
+    }
+    
+    #[test]
+    fn test_typed_errors() {
+        assert_eq!(RuleString::parse("  "), Err(RuleStringError::Empty));
+        assert!(matches!(RuleString::parse("Life"), Err(RuleStringError::UnrecognizedFormat(_))));
+        assert!(matches!(RuleString::parse("B9/S23"), Err(RuleStringError::Invalid { .. })));
+        assert!(matches!(RuleString::parse("B2/S34H"), Err(RuleStringError::Unsupported { .. })));
+    }
+}
@@ -0,0 +1,141 @@
+use super::Rule;
+use crate::error::GameOfLifeError;
+
+/// Life-like rule where births and survivals happen with a probability per neighbor count
+///
+/// A dead cell with `n` live neighbors is born with probability
+/// `birth_probability(n)` and a live cell survives with probability
+/// `survival_probability(n)`. Probabilities of exactly 0 and 1 reproduce an
+/// ordinary deterministic rule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StochasticRule {
+    birth: [f64; 9],
+    survival: [f64; 9],
+}
+
+impl StochasticRule {
+    /// Perturb a Life-like rule: its births happen with `birth_probability` and its survivals
+    /// with `survival_probability`; transitions the rule forbids never happen
+    pub fn new(rule: Rule, birth_probability: f64, survival_probability: f64) -> Result<Self, GameOfLifeError> {
+        rule.require_moore("Stochastic rules")?;
+        check_probability(birth_probability)?;
+        check_probability(survival_probability)?;
+        
+        let mut stochastic = Self { birth: [0.0; 9], survival: [0.0; 9] };
+        for count in 0..=8u8 {
+            if rule.births_on(count) {
+                stochastic.birth[count as usize] = birth_probability;
+            }
+            if rule.survives_on(count) {
+                stochastic.survival[count as usize] = survival_probability;
+            }
+        }
+        Ok(stochastic)
+    }
+    
+    /// Probability that a dead cell with `neighbors` live neighbors is born
+    pub fn birth_probability(&self, neighbors: u8) -> f64 {
+        self.birth[neighbors as usize]
+    }
+    
+    /// Probability that a live cell with `neighbors` live neighbors survives
+    pub fn survival_probability(&self, neighbors: u8) -> f64 {
+        self.survival[neighbors as usize]
+    }
+    
+    /// Set the birth probability for one neighbor count
+    pub fn set_birth_probability(&mut self, neighbors: u8, probability: f64) -> Result<(), GameOfLifeError> {
+        check_count(neighbors)?;
+        check_probability(probability)?;
+        self.birth[neighbors as usize] = probability;
+        Ok(())
+    }
+    
+    /// Set the survival probability for one neighbor count
+    pub fn set_survival_probability(&mut self, neighbors: u8, probability: f64) -> Result<(), GameOfLifeError> {
+        check_count(neighbors)?;
+        check_probability(probability)?;
+        self.survival[neighbors as usize] = probability;
+        Ok(())
+    }
+    
+    /// Equivalent Life-like rule, if every probability is exactly 0 or 1
+    pub fn as_life_rule(&self) -> Option<Rule> {
+        let mut birth = 0;
+        let mut survival = 0;
+        for count in 0..9 {
+            for (probability, mask) in [(self.birth[count], &mut birth), (self.survival[count], &mut survival)] {
+                if probability == 1.0 {
+                    *mask |= 1 << count;
+                } else if probability != 0.0 {
+                    return None;
+                }
+            }
+        }
+        Some(Rule::from_masks(birth, survival))
+    }
+    
+    /// Compute the next state of a cell given a uniform sample in [0, 1)
+    #[inline(always)]
+    pub fn next_state(&self, alive: bool, neighbors: u8, sample: f64) -> bool {
+        let probability = if alive {
+            self.survival[neighbors as usize]
+        } else {
+            self.birth[neighbors as usize]
+        };
+        sample < probability
+    }
+}
+
+impl TryFrom<Rule> for StochasticRule {
+    type Error = GameOfLifeError;
+    
+    /// The deterministic equivalent of a Moore-neighborhood rule
+    fn try_from(rule: Rule) -> Result<Self, Self::Error> {
+        Self::new(rule, 1.0, 1.0)
+    }
+}
+
+fn check_probability(probability: f64) -> Result<(), GameOfLifeError> {
+    if (0.0..=1.0).contains(&probability) {
+        Ok(())
+    } else {
+        Err(GameOfLifeError::invalid(format!("Probability {} out of range 0-1", probability)))
+    }
+}
+
+fn check_count(neighbors: u8) -> Result<(), GameOfLifeError> {
+    if neighbors <= 8 {
+        Ok(())
+    } else {
+        Err(GameOfLifeError::invalid(format!("Neighbor count {} out of range 0-8", neighbors)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_deterministic_round_trip() {
+        let rule = StochasticRule::try_from(Rule::conway()).unwrap();
+        assert_eq!(rule.as_life_rule(), Some(Rule::conway()));
+        assert!(rule.next_state(false, 3, 0.999));
+        assert!(!rule.next_state(true, 4, 0.0));
+    }
+    
+    #[test]
+    fn test_probabilities() {
+        let mut rule = StochasticRule::new(Rule::conway(), 0.5, 0.9).unwrap();
+        assert_eq!(rule.birth_probability(3), 0.5);
+        assert_eq!(rule.survival_probability(2), 0.9);
+        assert_eq!(rule.birth_probability(2), 0.0);
+        assert_eq!(rule.as_life_rule(), None);
+        assert!(rule.next_state(false, 3, 0.25));
+        assert!(!rule.next_state(false, 3, 0.75));
+        
+        assert!(rule.set_birth_probability(9, 0.5).is_err());
+        assert!(rule.set_survival_probability(2, 1.5).is_err());
+        assert!(StochasticRule::new(Rule::conway(), -0.1, 1.0).is_err());
+    }
+}
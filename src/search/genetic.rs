@@ -0,0 +1,323 @@
+//! Evolutionary search: breed small seed patterns towards a fitness goal
+
+use crate::analysis::find_isolated_pattern;
+use crate::analysis::streams::glider_shapes;
+use crate::engines::{auto_from_grid_ultimate_engine, CancellationToken};
+use crate::error::GameOfLifeError;
+use crate::grid::random::SplitMix64;
+use crate::grid::{Grid, PasteMode, StandardGrid};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+
+/// Fitness function scoring a seed pattern; higher is better
+pub type FitnessFn = Box<dyn Fn(&StandardGrid) -> f64 + Send + Sync>;
+
+/// Callback invoked with the summary of each finished generation
+pub type GenerationCallback = Box<dyn Fn(&GenerationSummary) + Send + Sync>;
+
+/// A seed pattern and its fitness
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub pattern: StandardGrid,
+    pub fitness: f64,
+}
+
+/// Fitness of one generation of the population
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationSummary {
+    /// Generation of the search, starting from 0 for the random initial population
+    pub generation: usize,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+}
+
+/// Outcome of an evolutionary search
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneticReport {
+    /// Fittest pattern seen in any generation
+    pub best: Candidate,
+    /// The final population, fittest first
+    pub population: Vec<Candidate>,
+    /// Summary of every generation that was evaluated
+    pub history: Vec<GenerationSummary>,
+}
+
+/// Evolves a population of fixed-size seed patterns against a fitness function
+///
+/// Each generation keeps the fittest few patterns unchanged and breeds the
+/// rest from parents chosen by tournament. A child is a copy of one parent
+/// with a random rectangle swapped in from the other (crossover), then has
+/// each cell flipped with a small probability (mutation). Candidates are
+/// scored in parallel; breeding is driven by one seeded generator, so a search
+/// with the same settings and a deterministic fitness function always gives
+/// the same result.
+pub struct GeneticSearch {
+    width: usize,
+    height: usize,
+    seed: u64,
+    fitness: FitnessFn,
+    population_size: usize,
+    density: f64,
+    mutation_rate: f64,
+    crossover_rate: f64,
+    elites: usize,
+    tournament_size: usize,
+    on_generation: Option<GenerationCallback>,
+    cancel: CancellationToken,
+}
+
+impl GeneticSearch {
+    /// Evolve `width`x`height` patterns scored by `fitness`, with a population of 32
+    pub fn new(width: usize, height: usize, seed: u64, fitness: impl Fn(&StandardGrid) -> f64 + Send + Sync + 'static) -> Self {
+        Self {
+            width,
+            height,
+            seed,
+            fitness: Box::new(fitness),
+            population_size: 32,
+            density: 0.5,
+            mutation_rate: 0.02,
+            crossover_rate: 0.7,
+            elites: 2,
+            tournament_size: 3,
+            on_generation: None,
+            cancel: CancellationToken::new(),
+        }
+    }
+    
+    /// Change the number of patterns in each generation
+    pub fn with_population_size(mut self, population_size: usize) -> Self {
+        self.population_size = population_size;
+        self
+    }
+    
+    /// Change the probability that a cell of the random initial population is alive
+    pub fn with_density(mut self, density: f64) -> Self {
+        self.density = density;
+        self
+    }
+    
+    /// Change the probability that mutation flips each cell of a child
+    pub fn with_mutation_rate(mut self, mutation_rate: f64) -> Self {
+        self.mutation_rate = mutation_rate;
+        self
+    }
+    
+    /// Change the probability that a child is bred by crossover rather than copied from one parent
+    pub fn with_crossover_rate(mut self, crossover_rate: f64) -> Self {
+        self.crossover_rate = crossover_rate;
+        self
+    }
+    
+    /// Change how many of the fittest patterns pass to the next generation unchanged
+    pub fn with_elites(mut self, elites: usize) -> Self {
+        self.elites = elites;
+        self
+    }
+    
+    /// Change how many candidates compete to become each parent
+    pub fn with_tournament_size(mut self, tournament_size: usize) -> Self {
+        self.tournament_size = tournament_size.max(1);
+        self
+    }
+    
+    /// Call `callback` after each generation is evaluated
+    pub fn on_generation(mut self, callback: impl Fn(&GenerationSummary) + Send + Sync + 'static) -> Self {
+        self.on_generation = Some(Box::new(callback));
+        self
+    }
+    
+    /// Stop after the current generation, keeping the results so far, once `cancel` is triggered
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+    
+    /// Evaluate a random population, then breed and evaluate `generations` more
+    ///
+    /// Fails before running anything if the settings are invalid.
+    pub fn run(&self, generations: usize) -> Result<GeneticReport, GameOfLifeError> {
+        self.validate()?;
+        let mut rng = SplitMix64::new(self.seed);
+        let mut patterns: Vec<StandardGrid> = (0..self.population_size)
+            .map(|_| StandardGrid::random(self.width, self.height, self.density, rng.next_u64()))
+            .collect::<Result<_, _>>()?;
+        let mut scored: Vec<Candidate> = Vec::new();
+        let mut history = Vec::new();
+        let mut best: Option<Candidate> = None;
+        
+        for generation in 0..=generations {
+            // Elites carried over from the previous generation keep their scores
+            let fresh: Vec<Candidate> = patterns
+                .par_iter()
+                .map(|pattern| Candidate { pattern: pattern.clone(), fitness: (self.fitness)(pattern) })
+                .collect();
+            scored.truncate(self.elites);
+            scored.extend(fresh);
+            scored.sort_by(|a, b| by_fitness(b, a));
+            
+            if best.as_ref().is_none_or(|best| scored[0].fitness > best.fitness) {
+                best = Some(scored[0].clone());
+            }
+            let summary = GenerationSummary {
+                generation,
+                best_fitness: scored[0].fitness,
+                mean_fitness: scored.iter().map(|candidate| candidate.fitness).sum::<f64>() / scored.len() as f64,
+            };
+            if let Some(callback) = &self.on_generation {
+                callback(&summary);
+            }
+            history.push(summary);
+            if generation == generations || self.cancel.is_cancelled() {
+                break;
+            }
+            
+            patterns = (self.elites..self.population_size).map(|_| self.breed(&scored, &mut rng)).collect();
+        }
+        
+        Ok(GeneticReport { best: best.expect("the population is never empty"), population: scored, history })
+    }
+    
+    fn validate(&self) -> Result<(), GameOfLifeError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(GameOfLifeError::invalid("Patterns must be at least 1x1"));
+        }
+        if self.population_size == 0 || self.elites >= self.population_size {
+            return Err(GameOfLifeError::invalid(format!(
+                "Population of {} must be larger than the {} elites",
+                self.population_size, self.elites
+            )));
+        }
+        for (name, value) in [("Density", self.density), ("Mutation rate", self.mutation_rate), ("Crossover rate", self.crossover_rate)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(GameOfLifeError::invalid(format!("{} must be between 0 and 1, got {}", name, value)));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Breed one child from two tournament winners
+    fn breed(&self, population: &[Candidate], rng: &mut SplitMix64) -> StandardGrid {
+        let mut child = self.select(population, rng).pattern.clone();
+        if rng.chance(self.crossover_rate) {
+            let other = &self.select(population, rng).pattern;
+            let (top, bottom) = sorted_pair(rng, self.height);
+            let (left, right) = sorted_pair(rng, self.width);
+            for row in top..=bottom {
+                for col in left..=right {
+                    child.set_cell(row, col, other.get_cell(row, col));
+                }
+            }
+        }
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if rng.chance(self.mutation_rate) {
+                    child.set_cell(row, col, !child.get_cell(row, col));
+                }
+            }
+        }
+        child
+    }
+    
+    /// The fittest of a few candidates picked at random
+    fn select<'a>(&self, population: &'a [Candidate], rng: &mut SplitMix64) -> &'a Candidate {
+        (0..self.tournament_size)
+            .map(|_| &population[rng.next_u64() as usize % population.len()])
+            .max_by(|a, b| by_fitness(a, b))
+            .unwrap()
+    }
+}
+
+/// Order candidates by fitness, treating NaN as the worst possible score
+fn by_fitness(a: &Candidate, b: &Candidate) -> Ordering {
+    let key = |candidate: &Candidate| if candidate.fitness.is_nan() { f64::NEG_INFINITY } else { candidate.fitness };
+    key(a).total_cmp(&key(b))
+}
+
+/// Two random indices below `len`, smallest first
+fn sorted_pair(rng: &mut SplitMix64, len: usize) -> (usize, usize) {
+    let a = rng.next_u64() as usize % len;
+    let b = rng.next_u64() as usize % len;
+    (a.min(b), a.max(b))
+}
+
+/// Place a seed pattern in the middle of an empty arena with `margin` cells on each side
+fn arena(pattern: &StandardGrid, margin: usize) -> StandardGrid {
+    let mut arena = StandardGrid::new(pattern.width() + 2 * margin, pattern.height() + 2 * margin);
+    arena.paste(pattern, margin, margin, PasteMode::Copy);
+    arena
+}
+
+/// Fitness: generations a pattern stays active before settling into a cycle, up to `max_generations`
+///
+/// The pattern runs in an arena with a 32-cell margin on the fast engine.
+pub fn lifespan(max_generations: usize) -> impl Fn(&StandardGrid) -> f64 + Send + Sync {
+    move |pattern| {
+        let mut engine = auto_from_grid_ultimate_engine(&arena(pattern, 32));
+        let report = engine.run_until_stable(max_generations);
+        report.stabilized_at.unwrap_or(report.steps_run) as f64
+    }
+}
+
+/// Fitness: isolated gliders present after running a pattern for `generations` steps
+///
+/// The arena is wide enough that no glider can reach its edge in that time.
+pub fn glider_count(generations: usize) -> impl Fn(&StandardGrid) -> f64 + Send + Sync {
+    move |pattern| {
+        let mut engine = auto_from_grid_ultimate_engine(&arena(pattern, generations / 4 + 8));
+        engine.run_steps(generations);
+        let grid = engine.snapshot().grid;
+        glider_shapes().iter().map(|shape| find_isolated_pattern(&grid, shape).len()).sum::<usize>() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    
+    #[test]
+    fn test_evolves_towards_fitness() {
+        // Reward live cells: the population should fill up quickly
+        let population = |pattern: &StandardGrid| pattern.count_live_cells() as f64;
+        let search = GeneticSearch::new(6, 6, 11, population).with_population_size(16);
+        let report = search.run(25).unwrap();
+        
+        assert_eq!(report.history.len(), 26);
+        assert!(report.best.fitness >= 34.0, "{:?}", report.history);
+        assert!(report.history[25].mean_fitness > report.history[0].mean_fitness);
+        assert!(report.history.windows(2).all(|pair| pair[1].best_fitness >= pair[0].best_fitness));
+        assert_eq!(report.population.len(), 16);
+        
+        // Same seed, same result
+        assert_eq!(GeneticSearch::new(6, 6, 11, population).with_population_size(16).run(25).unwrap(), report);
+        assert!(GeneticSearch::new(6, 6, 11, population).with_elites(32).run(1).is_err());
+    }
+    
+    #[test]
+    fn test_builtin_fitness_and_callbacks() {
+        let glider = crate::patterns::glider().grid;
+        assert_eq!(glider_count(40)(&glider), 1.0);
+        assert_eq!(lifespan(100)(&crate::patterns::block().grid), 0.0);
+        
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = calls.clone();
+        let cancel = CancellationToken::new();
+        let stop = cancel.clone();
+        let report = GeneticSearch::new(5, 5, 3, lifespan(200))
+            .with_population_size(8)
+            .with_cancellation(cancel)
+            .on_generation(move |summary| {
+                seen.fetch_add(1, Ordering::Relaxed);
+                if summary.generation == 2 {
+                    stop.cancel();
+                }
+            })
+            .run(10)
+            .unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(report.history.len(), 3);
+        assert!(report.best.fitness > 0.0);
+    }
+}
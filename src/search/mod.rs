@@ -0,0 +1,11 @@
+//! Searching for patterns: random soups, evolution, and with the `predecessor` feature, reverse steps
+
+pub mod genetic;
+#[cfg(feature = "predecessor")]
+pub mod predecessor;
+pub mod soup;
+
+pub use genetic::{Candidate, FitnessFn, GenerationCallback, GenerationSummary, GeneticReport, GeneticSearch};
+#[cfg(feature = "predecessor")]
+pub use predecessor::{find_predecessor, PredecessorOutcome, PredecessorSearch};
+pub use soup::{SoupProgress, SoupProgressCallback, SoupSearch, SoupSearchReport};
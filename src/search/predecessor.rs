@@ -0,0 +1,275 @@
+//! Reverse-step search: predecessors and Garden-of-Eden proofs
+//!
+//! A predecessor of a w×h target is a (w+2)×(h+2) pattern whose next
+//! generation matches the target inside the central w×h window; cells outside
+//! the window are unconstrained. A target with no predecessor is a Garden of
+//! Eden (an orphan). The solver assigns predecessor cells in row-major order,
+//! rejects an assignment as soon as some target cell can no longer be produced
+//! from the cells around it, and remembers which pairs of completed rows led to
+//! a dead end so the same sub-search is never repeated.
+
+use crate::engines::CancellationToken;
+use crate::error::GameOfLifeError;
+use crate::grid::{Grid, StandardGrid};
+use crate::rules::{NeighborhoodKind, Rule};
+use std::collections::HashSet;
+
+/// Widest target the solver accepts, so a padded predecessor row fits in 64 bits
+pub const MAX_TARGET_WIDTH: usize = 62;
+
+/// Result of a predecessor search
+#[derive(Debug, Clone, PartialEq)]
+pub enum PredecessorOutcome {
+    /// A predecessor, two cells wider and taller than the target
+    Found(StandardGrid),
+    /// The search was exhaustive and no predecessor exists
+    GardenOfEden,
+    /// The node limit was reached or the search was cancelled
+    GaveUp,
+}
+
+impl PredecessorOutcome {
+    /// The predecessor, if one was found
+    pub fn predecessor(&self) -> Option<&StandardGrid> {
+        match self {
+            PredecessorOutcome::Found(grid) => Some(grid),
+            _ => None,
+        }
+    }
+    
+    /// Whether the target was proven to be a Garden of Eden
+    pub fn is_garden_of_eden(&self) -> bool {
+        matches!(self, PredecessorOutcome::GardenOfEden)
+    }
+}
+
+/// Configurable search for predecessors of small patterns
+#[derive(Debug, Clone)]
+pub struct PredecessorSearch {
+    rule: Rule,
+    node_limit: Option<u64>,
+    cancel: CancellationToken,
+}
+
+impl Default for PredecessorSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PredecessorSearch {
+    /// Search under Conway's Life with no node limit
+    pub fn new() -> Self {
+        Self { rule: Rule::conway(), node_limit: None, cancel: CancellationToken::new() }
+    }
+    
+    /// Search under another Life-like rule with the Moore neighborhood
+    pub fn with_rule(mut self, rule: Rule) -> Result<Self, GameOfLifeError> {
+        if rule.neighborhood() != NeighborhoodKind::Moore {
+            return Err(GameOfLifeError::unsupported("Predecessor search", format!("the non-Moore rule {}", rule)));
+        }
+        self.rule = rule;
+        Ok(self)
+    }
+    
+    /// Give up after assigning this many cells
+    pub fn with_node_limit(mut self, nodes: u64) -> Self {
+        self.node_limit = Some(nodes);
+        self
+    }
+    
+    /// Give up once `cancel` is triggered
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+    
+    /// Find a predecessor of `target` or prove that none exists
+    ///
+    /// Fails if the target is wider than `MAX_TARGET_WIDTH`.
+    pub fn run(&self, target: &dyn Grid) -> Result<PredecessorOutcome, GameOfLifeError> {
+        if target.width() > MAX_TARGET_WIDTH {
+            return Err(GameOfLifeError::invalid(format!(
+                "Predecessor search supports targets up to {} cells wide, got {}",
+                MAX_TARGET_WIDTH,
+                target.width()
+            )));
+        }
+        
+        let mut solver = Solver {
+            rule: self.rule,
+            target,
+            width: target.width() + 2,
+            height: target.height() + 2,
+            rows: vec![0; target.height() + 2],
+            dead_ends: HashSet::new(),
+            nodes: 0,
+            node_limit: self.node_limit.unwrap_or(u64::MAX),
+            cancel: &self.cancel,
+        };
+        Ok(match solver.solve_row(0) {
+            Some(true) => {
+                let mut predecessor = StandardGrid::new(solver.width, solver.height);
+                for (row, &bits) in solver.rows.iter().enumerate() {
+                    for col in 0..solver.width {
+                        predecessor.set_cell(row, col, bits >> col & 1 == 1);
+                    }
+                }
+                PredecessorOutcome::Found(predecessor)
+            }
+            Some(false) => PredecessorOutcome::GardenOfEden,
+            None => PredecessorOutcome::GaveUp,
+        })
+    }
+}
+
+/// Find a predecessor of `target` under Conway's Life, with no node limit
+pub fn find_predecessor(target: &dyn Grid) -> Result<PredecessorOutcome, GameOfLifeError> {
+    PredecessorSearch::new().run(target)
+}
+
+/// Depth-first search state; `Some(false)` means proven impossible and `None` means gave up
+struct Solver<'a> {
+    rule: Rule,
+    target: &'a dyn Grid,
+    width: usize,
+    height: usize,
+    /// Predecessor rows as bit masks, column `c` in bit `c`
+    rows: Vec<u64>,
+    /// (row, previous two rows) that are known not to lead to a solution
+    dead_ends: HashSet<(usize, u64, u64)>,
+    nodes: u64,
+    node_limit: u64,
+    cancel: &'a CancellationToken,
+}
+
+impl Solver<'_> {
+    fn solve_row(&mut self, row: usize) -> Option<bool> {
+        if row == self.height {
+            return Some(true);
+        }
+        // Only the last two rows can still affect target cells from here on
+        let key = (row, row.checked_sub(2).map_or(0, |r| self.rows[r]), row.checked_sub(1).map_or(0, |r| self.rows[r]));
+        if row >= 2 && self.dead_ends.contains(&key) {
+            return Some(false);
+        }
+        let result = self.assign(row, 0);
+        if row >= 2 && result == Some(false) {
+            self.dead_ends.insert(key);
+        }
+        result
+    }
+    
+    fn assign(&mut self, row: usize, col: usize) -> Option<bool> {
+        if col == self.width {
+            return self.solve_row(row + 1);
+        }
+        self.nodes += 1;
+        if self.nodes > self.node_limit || (self.nodes.is_multiple_of(4096) && self.cancel.is_cancelled()) {
+            return None;
+        }
+        
+        for alive in [false, true] {
+            if alive {
+                self.rows[row] |= 1 << col;
+            }
+            if self.consistent(row, col) {
+                match self.assign(row, col + 1) {
+                    Some(false) => {}
+                    other => return other,
+                }
+            }
+        }
+        self.rows[row] &= !(1 << col);
+        Some(false)
+    }
+    
+    /// Whether every target cell whose neighborhood contains (row, col) can still be produced
+    fn consistent(&self, row: usize, col: usize) -> bool {
+        let (target_width, target_height) = (self.target.width(), self.target.height());
+        (row.saturating_sub(2)..=row.min(target_height.saturating_sub(1)))
+            .filter(|&target_row| target_row < target_height)
+            .all(|target_row| {
+                (col.saturating_sub(2)..=col.min(target_width.saturating_sub(1)))
+                    .filter(|&target_col| target_col < target_width)
+                    .all(|target_col| self.feasible(target_row, target_col, row, col))
+            })
+    }
+    
+    /// Whether target cell (target_row, target_col) can be produced, given that
+    /// predecessor cells up to (row, col) in row-major order are assigned
+    fn feasible(&self, target_row: usize, target_col: usize, row: usize, col: usize) -> bool {
+        let assigned = |r: usize, c: usize| r < row || (r == row && c <= col);
+        let (mut alive, mut unknown) = (0u8, 0u8);
+        for r in target_row..target_row + 3 {
+            for c in target_col..target_col + 3 {
+                if (r, c) == (target_row + 1, target_col + 1) {
+                    continue;
+                }
+                if !assigned(r, c) {
+                    unknown += 1;
+                } else if self.rows[r] >> c & 1 == 1 {
+                    alive += 1;
+                }
+            }
+        }
+        
+        let (center_row, center_col) = (target_row + 1, target_col + 1);
+        let centers: &[bool] = if assigned(center_row, center_col) {
+            if self.rows[center_row] >> center_col & 1 == 1 { &[true] } else { &[false] }
+        } else {
+            &[false, true]
+        };
+        let wanted = self.target.get_cell(target_row, target_col);
+        centers.iter().any(|&center| (alive..=alive + unknown).any(|neighbors| self.rule.next_state(center, neighbors) == wanted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{GameOfLifeEngine, NaiveEngine};
+    use crate::grid::Rect;
+    use crate::patterns;
+    
+    /// Step a predecessor once and cut out the window the target covers
+    fn successor_window(predecessor: &StandardGrid, rule: Rule) -> StandardGrid {
+        let mut engine = NaiveEngine::from_grid(predecessor);
+        engine.set_rule(rule).unwrap();
+        engine.step();
+        engine.get_grid().crop(Rect { row: 1, col: 1, width: predecessor.width() - 2, height: predecessor.height() - 2 })
+    }
+    
+    #[test]
+    fn test_finds_predecessors() {
+        for target in [patterns::block().grid, patterns::glider().grid, StandardGrid::new(3, 3)] {
+            let outcome = find_predecessor(&target).unwrap();
+            let predecessor = outcome.predecessor().expect("pattern has a predecessor");
+            assert_eq!(successor_window(predecessor, Rule::conway()), target);
+        }
+    }
+    
+    #[test]
+    fn test_proves_garden_of_eden() {
+        // Under Seeds (B2/S) a live cell must have been dead, so the centre of a
+        // solid 3x3 square was dead with no live neighbours and cannot be born
+        let seeds = PredecessorSearch::new().with_rule("B2/S".parse().unwrap()).unwrap();
+        let mut square = StandardGrid::new(3, 3);
+        for row in 0..3 {
+            for col in 0..3 {
+                square.set_cell(row, col, true);
+            }
+        }
+        assert!(seeds.run(&square).unwrap().is_garden_of_eden());
+        
+        // Two separated cells can each be born from a pair of neighbours
+        let pair = StandardGrid::from_string_pattern(&["#..", "...", "..#"], '#', '.').unwrap();
+        let outcome = seeds.run(&pair).unwrap();
+        assert_eq!(successor_window(outcome.predecessor().unwrap(), "B2/S".parse().unwrap()), pair);
+        
+        // A budget too small to decide anything gives up rather than guessing
+        let limited = PredecessorSearch::new().with_node_limit(10).run(&patterns::glider().grid).unwrap();
+        assert_eq!(limited, PredecessorOutcome::GaveUp);
+        assert!(PredecessorSearch::new().run(&StandardGrid::new(63, 2)).is_err());
+    }
+}
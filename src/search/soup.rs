@@ -0,0 +1,321 @@
+//! Random-soup search: run many seeded soups to stability and census the ash
+
+use crate::analysis::census::{object_apgcode, object_grid};
+use crate::analysis::{census_with_period, CensusReport};
+use crate::engines::{CancellationToken, GameOfLifeEngine, NaiveEngine};
+use crate::error::GameOfLifeError;
+use crate::grid::components::components;
+use crate::grid::{Grid, PasteMode, StandardGrid, Symmetry};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Longest period looked for when classifying ash objects and escaping ships
+const MAX_OBJECT_PERIOD: usize = 30;
+
+/// Generations between checks for stability and escaping spaceships
+const CHECK_INTERVAL: usize = 30;
+
+/// Cells from the edge of the arena at which spaceships are removed before they hit it
+///
+/// No Life spaceship is faster than c/2, so none can cross this zone between checks.
+const EDGE_ZONE: usize = CHECK_INTERVAL / 2 + 1;
+
+/// Progress of a running search, passed to the progress callback
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoupProgress {
+    /// Soups finished so far
+    pub completed: usize,
+    /// Soups the search was asked to run
+    pub total: usize,
+    /// Objects counted so far
+    pub objects: usize,
+    /// Time since the search started
+    pub elapsed: Duration,
+}
+
+impl SoupProgress {
+    /// Soups finished per second
+    pub fn soups_per_second(&self) -> f64 {
+        self.completed as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Callback invoked with the search's progress
+pub type SoupProgressCallback = Box<dyn Fn(&SoupProgress) + Send + Sync>;
+
+/// Aggregated outcome of a soup search
+#[derive(Debug, Clone, Default)]
+pub struct SoupSearchReport {
+    /// Soups that were run to completion
+    pub soups: usize,
+    /// Object counts over the ash of every soup, including spaceships that escaped
+    pub census: CensusReport,
+    /// Seeds of soups still active when the generation limit ran out
+    pub unstabilized: Vec<u64>,
+    /// Wall-clock time of the search
+    pub elapsed: Duration,
+}
+
+/// Runs seeded random soups on worker threads and tallies the objects they leave behind
+///
+/// Soup `i` uses seed `seed + i`, so any soup of interest can be regenerated
+/// with `soup`. Each soup is placed in the middle of an empty arena and run
+/// under Conway's rule until its population becomes periodic. Spaceships
+/// approaching the edge of the arena are counted and removed so they do not
+/// crash into it.
+pub struct SoupSearch {
+    seed: u64,
+    width: usize,
+    height: usize,
+    density: f64,
+    symmetry: Symmetry,
+    margin: usize,
+    max_generations: usize,
+    progress_interval: usize,
+    progress: Option<SoupProgressCallback>,
+    cancel: CancellationToken,
+}
+
+impl SoupSearch {
+    /// Search 16x16 soups at density 0.5, starting from `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            width: 16,
+            height: 16,
+            density: 0.5,
+            symmetry: Symmetry::C1,
+            margin: 48,
+            max_generations: 20_000,
+            progress_interval: 100,
+            progress: None,
+            cancel: CancellationToken::new(),
+        }
+    }
+    
+    /// Change the size of each soup
+    pub fn with_soup_size(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+    
+    /// Change the probability that a soup cell starts alive
+    pub fn with_density(mut self, density: f64) -> Self {
+        self.density = density;
+        self
+    }
+    
+    /// Generate soups invariant under a symmetry group
+    pub fn with_symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+    
+    /// Change the empty border around the soup that the arena provides
+    ///
+    /// Spaceships are removed once they come within 16 cells of the arena's
+    /// edge, so the margin should be wider than that.
+    pub fn with_margin(mut self, margin: usize) -> Self {
+        self.margin = margin;
+        self
+    }
+    
+    /// Give up on soups that are still active after this many generations
+    pub fn with_max_generations(mut self, max_generations: usize) -> Self {
+        self.max_generations = max_generations;
+        self
+    }
+    
+    /// Report progress through `callback` every `interval` soups
+    pub fn on_progress(mut self, interval: usize, callback: impl Fn(&SoupProgress) + Send + Sync + 'static) -> Self {
+        self.progress_interval = interval.max(1);
+        self.progress = Some(Box::new(callback));
+        self
+    }
+    
+    /// Stop the search early, keeping the results of finished soups, once `cancel` is triggered
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+    
+    /// Generate soup number `index` of this search
+    pub fn soup(&self, index: u64) -> Result<StandardGrid, GameOfLifeError> {
+        StandardGrid::random_symmetric(self.width, self.height, self.density, self.seed.wrapping_add(index), self.symmetry)
+    }
+    
+    /// Run `count` soups in parallel and aggregate their censuses
+    ///
+    /// Fails before running anything if the soup settings are invalid.
+    pub fn run(&self, count: usize) -> Result<SoupSearchReport, GameOfLifeError> {
+        self.soup(0)?;
+        let start = Instant::now();
+        let completed = AtomicUsize::new(0);
+        let report = Mutex::new(SoupSearchReport::default());
+        
+        (0..count as u64).into_par_iter().for_each(|index| {
+            if self.cancel.is_cancelled() {
+                return;
+            }
+            let soup = self.soup(index).expect("soup settings were validated");
+            let (census, stabilized) = self.run_soup(&soup);
+            
+            let mut report = report.lock().unwrap();
+            report.soups += 1;
+            report.census.merge(&census);
+            if !stabilized {
+                report.unstabilized.push(self.seed.wrapping_add(index));
+            }
+            let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+            if let Some(callback) = &self.progress {
+                if done.is_multiple_of(self.progress_interval) || done == count {
+                    callback(&SoupProgress {
+                        completed: done,
+                        total: count,
+                        objects: report.census.total(),
+                        elapsed: start.elapsed(),
+                    });
+                }
+            }
+        });
+        
+        let mut report = report.into_inner().unwrap();
+        report.unstabilized.sort_unstable();
+        report.elapsed = start.elapsed();
+        Ok(report)
+    }
+    
+    /// Run one soup until its population is periodic and census the ash
+    ///
+    /// Returns the census and whether the soup stabilized within the generation limit.
+    pub fn run_soup(&self, soup: &dyn Grid) -> (CensusReport, bool) {
+        let mut arena = StandardGrid::new(soup.width() + 2 * self.margin, soup.height() + 2 * self.margin);
+        arena.paste(soup, self.margin, self.margin, PasteMode::Copy);
+        let mut engine = NaiveEngine::from_grid(&arena);
+        let mut escaped = CensusReport::default();
+        let mut populations = vec![engine.count_live_cells()];
+        
+        let mut generations = 0;
+        let stabilized = loop {
+            if populations_periodic(&populations) {
+                break true;
+            }
+            if generations >= self.max_generations {
+                break false;
+            }
+            for _ in 0..CHECK_INTERVAL {
+                engine.step();
+                populations.push(engine.count_live_cells());
+            }
+            generations += CHECK_INTERVAL;
+            
+            if remove_escaping_ships(&mut engine, &mut escaped) {
+                populations.clear();
+                populations.push(engine.count_live_cells());
+            }
+        };
+        
+        let mut census = census_with_period(engine.get_grid(), MAX_OBJECT_PERIOD);
+        census.merge(&escaped);
+        (census, stabilized)
+    }
+}
+
+/// Whether the last stretch of the population record repeats with some period up to `MAX_OBJECT_PERIOD`
+fn populations_periodic(populations: &[usize]) -> bool {
+    let window = 2 * MAX_OBJECT_PERIOD;
+    let len = populations.len();
+    (1..=MAX_OBJECT_PERIOD).any(|period| {
+        len >= window + period && (len - window..len).all(|i| populations[i] == populations[i - period])
+    })
+}
+
+/// Count and delete spaceships near the arena's edge, returning whether any were removed
+fn remove_escaping_ships(engine: &mut NaiveEngine, escaped: &mut CensusReport) -> bool {
+    let grid = engine.get_grid();
+    let (width, height) = (grid.width(), grid.height());
+    let near_edge = |&(row, col): &(usize, usize)| {
+        row < EDGE_ZONE || col < EDGE_ZONE || row + EDGE_ZONE >= height || col + EDGE_ZONE >= width
+    };
+    
+    let mut removed = Vec::new();
+    for cells in components(grid, 2) {
+        if !cells.iter().any(near_edge) {
+            continue;
+        }
+        let object = object_grid(&cells);
+        if let Some(code) = object_apgcode(&object, MAX_OBJECT_PERIOD).filter(|code| code.starts_with("xq")) {
+            *escaped.counts.entry(code).or_insert(0) += 1;
+            removed.extend(cells);
+        }
+    }
+    if removed.is_empty() {
+        return false;
+    }
+    
+    let mut cleared = engine.snapshot().grid;
+    for (row, col) in removed {
+        cleared.set_cell(row, col, false);
+    }
+    engine.set_grid(&cleared);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns;
+    use std::sync::Arc;
+    
+    #[test]
+    fn test_soups_are_reproducible() {
+        let search = SoupSearch::new(42).with_soup_size(8, 8);
+        assert_eq!(search.soup(3).unwrap(), search.soup(3).unwrap());
+        assert_ne!(search.soup(3).unwrap(), search.soup(4).unwrap());
+        assert!(SoupSearch::new(1).with_density(2.0).run(1).is_err());
+    }
+    
+    #[test]
+    fn test_escaping_glider_is_counted_once() {
+        // A blinker stays put while a glider flies off towards the corner
+        let mut soup = StandardGrid::new(12, 12);
+        soup.paste(&patterns::blinker().grid, 1, 1, PasteMode::Copy);
+        soup.paste(&patterns::glider().grid, 6, 6, PasteMode::Copy);
+        
+        let search = SoupSearch::new(0).with_margin(24);
+        let (census, stabilized) = search.run_soup(&soup);
+        assert!(stabilized);
+        assert_eq!(census.count("xp2_7"), 1);
+        assert_eq!(census.count("xq4_153"), 1);
+        assert_eq!(census.total(), 2);
+    }
+    
+    #[test]
+    fn test_parallel_search_reports_progress() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = calls.clone();
+        let search = SoupSearch::new(7)
+            .with_soup_size(8, 8)
+            .with_margin(24)
+            .with_max_generations(600)
+            .on_progress(2, move |progress| {
+                assert!(progress.completed <= progress.total);
+                seen.fetch_add(1, Ordering::Relaxed);
+            });
+        
+        let report = search.run(4).unwrap();
+        assert_eq!(report.soups, 4);
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+        assert!(report.census.total() > 0);
+        
+        // The aggregate matches running the same soups one by one
+        let mut serial = CensusReport::default();
+        for index in 0..4 {
+            serial.merge(&search.run_soup(&search.soup(index).unwrap()).0);
+        }
+        assert_eq!(report.census, serial);
+    }
+}
@@ -0,0 +1,96 @@
+//! Summary statistics of a set of samples
+
+/// Two-sided 95% quantile of the standard normal distribution
+const Z_95: f64 = 1.959_963_984_540_054;
+
+/// Mean, spread and a 95% confidence interval of a set of samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    /// The samples, sorted ascending
+    pub samples: Vec<f64>,
+    pub mean: f64,
+    /// Sample standard deviation (n - 1 denominator); 0 for fewer than two samples
+    pub std_dev: f64,
+}
+
+impl Distribution {
+    /// Summarize a set of samples in any order
+    pub fn from_samples(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len() as f64;
+        let mean = if samples.is_empty() { 0.0 } else { samples.iter().sum::<f64>() / n };
+        let std_dev = if samples.len() < 2 {
+            0.0
+        } else {
+            (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        };
+        Self { samples, mean, std_dev }
+    }
+    
+    /// Number of samples
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+    
+    /// Whether there are no samples
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+    
+    /// Smallest sample
+    pub fn min(&self) -> Option<f64> {
+        self.samples.first().copied()
+    }
+    
+    /// Largest sample
+    pub fn max(&self) -> Option<f64> {
+        self.samples.last().copied()
+    }
+    
+    /// Value below which a fraction `q` of the samples lie, interpolating between neighbors
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let last = self.samples.len().checked_sub(1)?;
+        let position = q.clamp(0.0, 1.0) * last as f64;
+        let (below, above) = (position.floor() as usize, position.ceil() as usize);
+        let weight = position - below as f64;
+        Some(self.samples[below] * (1.0 - weight) + self.samples[above] * weight)
+    }
+    
+    /// Middle sample, or the mean of the two middle samples
+    pub fn median(&self) -> Option<f64> {
+        self.quantile(0.5)
+    }
+    
+    /// Standard error of the mean
+    pub fn std_error(&self) -> f64 {
+        if self.samples.is_empty() { 0.0 } else { self.std_dev / (self.samples.len() as f64).sqrt() }
+    }
+    
+    /// 95% confidence interval for the mean, using the normal approximation
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let margin = Z_95 * self.std_error();
+        (self.mean - margin, self.mean + margin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_distribution_statistics() {
+        let dist = Distribution::from_samples(vec![4.0, 2.0, 8.0, 6.0]);
+        assert_eq!(dist.samples, vec![2.0, 4.0, 6.0, 8.0]);
+        assert_eq!(dist.mean, 5.0);
+        assert!((dist.std_dev - (20.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(dist.median(), Some(5.0));
+        assert_eq!(dist.quantile(1.0), dist.max());
+        
+        let (low, high) = dist.confidence_interval();
+        assert!((high - dist.mean - Z_95 * dist.std_dev / 2.0).abs() < 1e-12);
+        assert!((dist.mean - low - (high - dist.mean)).abs() < 1e-12);
+        
+        let empty = Distribution::from_samples(Vec::new());
+        assert_eq!((empty.mean, empty.median(), empty.confidence_interval()), (0.0, None, (0.0, 0.0)));
+    }
+}
@@ -0,0 +1,11 @@
+//! Statistics collected while a simulation runs or measured on a single grid
+
+pub mod distribution;
+pub mod population;
+pub mod step;
+pub mod summary;
+
+pub use distribution::Distribution;
+pub use population::{PopulationHistory, SharedPopulationHistory};
+pub use step::StepStats;
+pub use summary::{block_entropy, summary, summary_with_blocks, BlockEntropy, GridSummary};
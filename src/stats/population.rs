@@ -0,0 +1,147 @@
+//! Population per generation
+
+use crate::engines::GameOfLifeEngine;
+use std::sync::{Arc, Mutex};
+
+/// Population history shared with an engine's generation observer
+pub type SharedPopulationHistory = Arc<Mutex<PopulationHistory>>;
+
+/// Population of every sampled generation, with running extremes and total
+///
+/// Samples come from `GenerationEvent`s, whose population engines compute
+/// by popcount, so recording costs O(1) per generation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PopulationHistory {
+    samples: Vec<(usize, usize)>,
+    min: Option<(usize, usize)>,
+    max: Option<(usize, usize)>,
+    total: u128,
+}
+
+impl PopulationHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Record the current population of `engine` and every later generation it reaches
+    ///
+    /// The history is shared with an observer registered through
+    /// `GameOfLifeEngine::on_generation`; lock it to read the series.
+    pub fn attach(engine: &mut dyn GameOfLifeEngine) -> SharedPopulationHistory {
+        let mut history = Self::new();
+        history.sample(engine);
+        let shared = Arc::new(Mutex::new(history));
+        let sink = Arc::clone(&shared);
+        engine.on_generation(Box::new(move |event| {
+            sink.lock().unwrap().record(event.generation, event.population);
+        }));
+        shared
+    }
+    
+    /// Record the population of one generation
+    pub fn record(&mut self, generation: usize, population: usize) {
+        self.samples.push((generation, population));
+        self.total += population as u128;
+        if self.min.is_none_or(|(_, min)| population < min) {
+            self.min = Some((generation, population));
+        }
+        if self.max.is_none_or(|(_, max)| population > max) {
+            self.max = Some((generation, population));
+        }
+    }
+    
+    /// Record the engine's current generation and population
+    pub fn sample(&mut self, engine: &dyn GameOfLifeEngine) {
+        self.record(engine.generation(), engine.count_live_cells());
+    }
+    
+    /// Number of recorded generations
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+    
+    /// Whether nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+    
+    /// Recorded (generation, population) pairs in recording order
+    pub fn samples(&self) -> &[(usize, usize)] {
+        &self.samples
+    }
+    
+    /// Recorded populations in recording order
+    pub fn populations(&self) -> impl Iterator<Item = usize> + '_ {
+        self.samples.iter().map(|&(_, population)| population)
+    }
+    
+    /// Most recent (generation, population) pair
+    pub fn latest(&self) -> Option<(usize, usize)> {
+        self.samples.last().copied()
+    }
+    
+    /// Smallest population and the first generation that reached it
+    pub fn min(&self) -> Option<(usize, usize)> {
+        self.min
+    }
+    
+    /// Largest population and the first generation that reached it
+    pub fn max(&self) -> Option<(usize, usize)> {
+        self.max
+    }
+    
+    /// Mean population over the recorded generations
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| self.total as f64 / self.samples.len() as f64)
+    }
+    
+    /// Forget every sample
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{auto_from_grid_ultimate_engine, NaiveEngine};
+    use crate::grid::{Grid, StandardGrid};
+    
+    #[test]
+    fn test_record_tracks_extremes_and_mean() {
+        let mut history = PopulationHistory::new();
+        assert_eq!(history.mean(), None);
+        assert_eq!(history.min(), None);
+        
+        for (generation, population) in [(0, 5), (1, 3), (2, 8), (3, 3), (4, 8)] {
+            history.record(generation, population);
+        }
+        assert_eq!(history.len(), 5);
+        assert_eq!(history.min(), Some((1, 3)));
+        assert_eq!(history.max(), Some((2, 8)));
+        assert_eq!(history.mean(), Some(5.4));
+        assert_eq!(history.latest(), Some((4, 8)));
+        assert_eq!(history.populations().collect::<Vec<_>>(), vec![5, 3, 8, 3, 8]);
+        
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.max(), None);
+    }
+    
+    #[test]
+    fn test_attached_history_matches_stepping() {
+        let soup = StandardGrid::random(48, 40, 0.35, 11).unwrap();
+        let mut engine = auto_from_grid_ultimate_engine(&soup);
+        let history = PopulationHistory::attach(engine.as_mut());
+        engine.run_steps(20);
+        
+        let mut reference = NaiveEngine::from_grid(&soup);
+        let mut expected = vec![(0, soup.count_live_cells())];
+        for _ in 0..20 {
+            reference.step();
+            expected.push((reference.generation(), reference.count_live_cells()));
+        }
+        assert_eq!(history.lock().unwrap().samples(), expected.as_slice());
+    }
+}
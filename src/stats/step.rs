@@ -0,0 +1,88 @@
+//! Births and deaths between consecutive generations
+
+use crate::grid::BitGrid;
+
+/// Cell changes made by one step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StepStats {
+    /// Generation the step reached
+    pub generation: usize,
+    /// Live cells after the step
+    pub population: usize,
+    /// Cells that were dead before the step and alive after it
+    pub births: usize,
+    /// Cells that were alive before the step and dead after it
+    pub deaths: usize,
+}
+
+impl StepStats {
+    /// Compare two bit-packed fields of the same layout word by word
+    ///
+    /// Bits that toggled are `before ^ after`; births are the toggled bits set
+    /// in `after` and deaths the ones set in `before`.
+    pub fn from_words(generation: usize, before: &[u64], after: &[u64]) -> Self {
+        debug_assert_eq!(before.len(), after.len());
+        let mut stats = Self { generation, ..Self::default() };
+        for (&old, &new) in before.iter().zip(after) {
+            let toggled = old ^ new;
+            stats.population += new.count_ones() as usize;
+            stats.births += (toggled & new).count_ones() as usize;
+            stats.deaths += (toggled & old).count_ones() as usize;
+        }
+        stats
+    }
+    
+    /// Compare the live cells of two grids of the same size
+    pub fn between(generation: usize, before: &BitGrid, after: &BitGrid) -> Self {
+        Self::from_words(generation, before.words(), after.words())
+    }
+    
+    /// Number of cells that changed state
+    pub fn activity(&self) -> usize {
+        self.births + self.deaths
+    }
+    
+    /// Whether nothing changed, so the pattern is a still life (or empty)
+    pub fn is_static(&self) -> bool {
+        self.activity() == 0
+    }
+    
+    /// Net change in population
+    pub fn population_change(&self) -> isize {
+        self.births as isize - self.deaths as isize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::{GameOfLifeEngine, NaiveEngine};
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_blinker_and_block_stats() {
+        let blinker = StandardGrid::from_string_pattern(&[".....", ".###.", "....."], '#', '.').unwrap();
+        let mut engine = NaiveEngine::from_grid(&blinker);
+        let stats = engine.step_with_stats();
+        assert_eq!(stats, StepStats { generation: 1, population: 3, births: 2, deaths: 2 });
+        assert_eq!(stats.activity(), 4);
+        assert_eq!(stats.population_change(), 0);
+        
+        let block = StandardGrid::from_string_pattern(&["....", ".##.", ".##.", "...."], '#', '.').unwrap();
+        let mut engine = NaiveEngine::from_grid(&block);
+        assert!(engine.step_with_stats().is_static());
+    }
+    
+    #[test]
+    fn test_stats_match_population_changes() {
+        let soup = StandardGrid::random(70, 30, 0.4, 3).unwrap();
+        let mut engine = NaiveEngine::from_grid(&soup);
+        for _ in 0..10 {
+            let before = engine.count_live_cells();
+            let stats = engine.step_with_stats();
+            assert_eq!(stats.population, engine.count_live_cells());
+            assert_eq!(before as isize + stats.population_change(), stats.population as isize);
+            assert!(stats.activity() > 0);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Whole-grid statistics: density, block entropy and cluster sizes
+
+use crate::grid::components::components;
+use crate::grid::Grid;
+use std::collections::{BTreeMap, HashMap};
+
+/// Block sizes `summary` measures entropy over
+pub const DEFAULT_BLOCK_SIZES: &[usize] = &[1, 2, 3, 4];
+
+/// Shannon entropy of the k×k blocks tiling a grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockEntropy {
+    /// Side length of the blocks
+    pub block_size: usize,
+    /// Number of whole blocks that fit in the grid
+    pub blocks: usize,
+    /// Entropy of the block contents in bits
+    pub bits: f64,
+}
+
+impl BlockEntropy {
+    /// Entropy normalized by the number of cells in a block, between 0 and 1
+    pub fn bits_per_cell(&self) -> f64 {
+        self.bits / (self.block_size * self.block_size) as f64
+    }
+}
+
+/// Summary statistics of a single grid state
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridSummary {
+    pub width: usize,
+    pub height: usize,
+    /// Number of live cells
+    pub population: usize,
+    /// Fraction of cells that are alive
+    pub density: f64,
+    /// Entropy for each requested block size, in the order requested
+    pub entropy: Vec<BlockEntropy>,
+    /// Number of 8-connected clusters of each size
+    pub cluster_sizes: BTreeMap<usize, usize>,
+}
+
+impl GridSummary {
+    /// Entropy measured over blocks of this size, if it was requested
+    pub fn entropy_for(&self, block_size: usize) -> Option<&BlockEntropy> {
+        self.entropy.iter().find(|entropy| entropy.block_size == block_size)
+    }
+    
+    /// Number of clusters
+    pub fn cluster_count(&self) -> usize {
+        self.cluster_sizes.values().sum()
+    }
+    
+    /// Size of the largest cluster, or 0 for an empty grid
+    pub fn largest_cluster(&self) -> usize {
+        self.cluster_sizes.keys().next_back().copied().unwrap_or(0)
+    }
+    
+    /// Mean cluster size, or 0 for an empty grid
+    pub fn mean_cluster_size(&self) -> f64 {
+        match self.cluster_count() {
+            0 => 0.0,
+            clusters => self.population as f64 / clusters as f64,
+        }
+    }
+}
+
+/// Summarize a grid using the default block sizes
+pub fn summary(grid: &dyn Grid) -> GridSummary {
+    summary_with_blocks(grid, DEFAULT_BLOCK_SIZES)
+}
+
+/// Summarize a grid, measuring entropy over each of `block_sizes`
+///
+/// Block sizes must be between 1 and 8 so a block fits in 64 bits.
+pub fn summary_with_blocks(grid: &dyn Grid, block_sizes: &[usize]) -> GridSummary {
+    let (width, height) = (grid.width(), grid.height());
+    let population = grid.count_live_cells();
+    let cells = width * height;
+    
+    let mut cluster_sizes = BTreeMap::new();
+    for cluster in components(grid, 1) {
+        *cluster_sizes.entry(cluster.len()).or_insert(0) += 1;
+    }
+    
+    GridSummary {
+        width,
+        height,
+        population,
+        density: if cells == 0 { 0.0 } else { population as f64 / cells as f64 },
+        entropy: block_sizes.iter().map(|&size| block_entropy(grid, size)).collect(),
+        cluster_sizes,
+    }
+}
+
+/// Shannon entropy of the non-overlapping k×k blocks, ignoring partial blocks at the edges
+pub fn block_entropy(grid: &dyn Grid, block_size: usize) -> BlockEntropy {
+    assert!((1..=8).contains(&block_size), "Block size must be between 1 and 8, got {}", block_size);
+    let mut frequencies: HashMap<u64, usize> = HashMap::new();
+    for top in (0..grid.height() / block_size).map(|i| i * block_size) {
+        for left in (0..grid.width() / block_size).map(|j| j * block_size) {
+            let mut block = 0u64;
+            for row in top..top + block_size {
+                for col in left..left + block_size {
+                    block = block << 1 | grid.get_cell(row, col) as u64;
+                }
+            }
+            *frequencies.entry(block).or_insert(0) += 1;
+        }
+    }
+    
+    let blocks: usize = frequencies.values().sum();
+    let bits = frequencies
+        .values()
+        .map(|&count| {
+            let p = count as f64 / blocks as f64;
+            -p * p.log2()
+        })
+        .sum::<f64>()
+        .max(0.0);
+    BlockEntropy { block_size, blocks, bits }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::StandardGrid;
+    
+    #[test]
+    fn test_summary_of_small_pattern() {
+        let grid = StandardGrid::from_string_pattern(&["##.#", "##..", "...#", "...."], '#', '.').unwrap();
+        let stats = summary(&grid);
+        assert_eq!(stats.population, 6);
+        assert_eq!(stats.density, 6.0 / 16.0);
+        assert_eq!(stats.cluster_sizes, BTreeMap::from([(1, 2), (4, 1)]));
+        assert_eq!(stats.cluster_count(), 3);
+        assert_eq!(stats.largest_cluster(), 4);
+        assert_eq!(stats.mean_cluster_size(), 2.0);
+        
+        // Four 2x2 blocks: one full, one blank and two holding the same single cell
+        let two = stats.entropy_for(2).unwrap();
+        assert_eq!(two.blocks, 4);
+        assert!((two.bits - 1.5).abs() < 1e-12);
+        assert!(stats.entropy_for(5).is_none());
+    }
+    
+    #[test]
+    fn test_entropy_extremes() {
+        let empty = StandardGrid::new(12, 12);
+        assert_eq!(block_entropy(&empty, 3).bits, 0.0);
+        assert_eq!(summary(&empty).mean_cluster_size(), 0.0);
+        
+        let mut checkerboard = StandardGrid::new(12, 12);
+        for row in 0..12 {
+            for col in 0..12 {
+                checkerboard.set_cell(row, col, (row + col).is_multiple_of(2));
+            }
+        }
+        assert!((block_entropy(&checkerboard, 1).bits - 1.0).abs() < 1e-12);
+        // Every 2x2 block is the same, every 3x3 block alternates between two
+        assert_eq!(block_entropy(&checkerboard, 2).bits, 0.0);
+        assert!((block_entropy(&checkerboard, 3).bits_per_cell() - 1.0 / 9.0).abs() < 1e-12);
+        
+        let random = StandardGrid::random(64, 64, 0.5, 3).unwrap();
+        assert!(block_entropy(&random, 1).bits > 0.99);
+    }
+}